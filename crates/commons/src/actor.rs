@@ -12,9 +12,33 @@ pub trait ActorTrait: Debug + Send + Sync {
     fn actor_type(&self) -> ActorType {
         (&self.actor()).into()
     }
+    /// Role granted to user actors. Meaningless for the system actor.
+    fn role(&self) -> Role {
+        Role::User
+    }
     fn actor(&self) -> Actor;
 }
 
+#[derive(Debug, Clone, Copy, Default, Type, PartialEq, Eq, Serialize, Deserialize)]
+#[sqlx(type_name = "user_role", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    #[default]
+    User,
+    Moderator,
+    Admin,
+}
+
+impl Role {
+    pub const fn is_moderator(&self) -> bool {
+        matches!(self, Self::Moderator | Self::Admin)
+    }
+
+    pub const fn is_admin(&self) -> bool {
+        matches!(self, Self::Admin)
+    }
+}
+
 #[derive(Debug, Clone, Type, PartialEq, Eq)]
 #[sqlx(type_name = "actor_type", rename_all = "snake_case")]
 pub enum ActorType {
@@ -37,6 +61,12 @@ impl From<&Actor> for ActorType {
     }
 }
 
+impl ActorTrait for Actor {
+    fn actor(&self) -> Actor {
+        *self
+    }
+}
+
 impl Actor {
     pub const fn is_user(&self) -> bool {
         matches!(self, Self::User(_))
@@ -0,0 +1,166 @@
+use argon2::{
+    password_hash::{rand_core::OsRng, rand_core::RngCore, SaltString},
+    Argon2, PasswordHasher, PasswordVerifier,
+};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::Type;
+use std::fmt::Debug;
+
+const TOKEN_BYTES: usize = 32;
+
+fn random_token() -> String {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+#[derive(Clone, PartialEq, Eq, Type, Serialize, Deserialize)]
+#[sqlx(transparent)]
+#[serde(transparent)]
+pub struct PasswordHash(String);
+
+impl Debug for PasswordHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PasswordHash(***)")
+    }
+}
+
+impl PasswordHash {
+    pub fn generate(password: &str) -> Result<Self, anyhow::Error> {
+        let salt = SaltString::generate(&mut OsRng);
+        Ok(Self(
+            Argon2::default()
+                .hash_password(password.as_bytes(), &salt)
+                .map_err(|e| anyhow::anyhow!("Failed to hash password: {e}"))?
+                .to_string(),
+        ))
+    }
+
+    pub fn verify(&self, password: &str) -> bool {
+        argon2::PasswordHash::new(&self.0)
+            .map(|hash| {
+                Argon2::default()
+                    .verify_password(password.as_bytes(), &hash)
+                    .is_ok()
+            })
+            .unwrap_or(false)
+    }
+}
+
+/// Opaque bearer token handed to the client. Only its hash is persisted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SessionToken(String);
+
+impl SessionToken {
+    pub fn generate() -> Self {
+        Self(random_token())
+    }
+
+    pub fn hash(&self) -> TokenHash {
+        TokenHash(hex::encode(Sha256::digest(self.0.as_bytes())))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for SessionToken {
+    fn from(value: &str) -> Self {
+        Self(String::from(value))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Type)]
+#[sqlx(transparent)]
+pub struct TokenHash(String);
+
+/// Token of the unsubscribe link sent with email digests. It only allows turning digests
+/// off, so it is stored as is to be put in every digest.
+#[derive(Clone, Debug, PartialEq, Eq, Type, Serialize, Deserialize)]
+#[sqlx(transparent)]
+#[serde(transparent)]
+pub struct UnsubscribeToken(String);
+
+impl UnsubscribeToken {
+    pub fn generate() -> Self {
+        Self(random_token())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for UnsubscribeToken {
+    fn from(value: &str) -> Self {
+        Self(String::from(value))
+    }
+}
+
+/// Key shared with a webhook endpoint, which checks the signature of every delivery.
+#[derive(Clone, PartialEq, Eq, Type, Serialize, Deserialize)]
+#[sqlx(transparent)]
+#[serde(transparent)]
+pub struct WebhookSecret(String);
+
+impl Debug for WebhookSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("WebhookSecret(***)")
+    }
+}
+
+impl WebhookSecret {
+    pub fn len(&self) -> usize {
+        self.0.chars().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Hex encoded HMAC-SHA256 of the payload.
+    pub fn sign(&self, payload: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.0.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(payload);
+        hex::encode(mac.finalize().into_bytes())
+    }
+}
+
+impl From<&str> for WebhookSecret {
+    fn from(value: &str) -> Self {
+        Self(String::from(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password_hash() {
+        let hash = PasswordHash::generate("secret").unwrap();
+        assert!(hash.verify("secret"));
+        assert!(!hash.verify("other"));
+    }
+
+    #[test]
+    fn test_session_token_hash() {
+        let token = SessionToken::generate();
+        assert_eq!(token.hash(), SessionToken::from(token.as_str()).hash());
+        assert_ne!(token.hash(), SessionToken::generate().hash());
+    }
+
+    #[test]
+    fn test_webhook_signature() {
+        let secret = WebhookSecret::from("key");
+        assert_eq!(
+            secret.sign(b"The quick brown fox jumps over the lazy dog"),
+            "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+        assert_eq!(format!("{secret:?}"), "WebhookSecret(***)");
+    }
+}
@@ -13,4 +13,46 @@ pub enum CommandType {
     UpdateFragment,
     ReviewFork,
     SubmitFork,
+    RegisterUser,
+    UpdateProfile,
+    AssignRole,
+    SetForkPolicy,
+    DeleteFragment,
+    RestoreFragment,
+    PurgeFragments,
+    RevertFragment,
+    AcceptSuggestion,
+    RejectSuggestion,
+    CreateComment,
+    EditComment,
+    DeleteComment,
+    ModerateComment,
+    WithdrawFork,
+    ResubmitFork,
+    UnpublishFragment,
+    CancelPublication,
+    CreateStory,
+    UpdateStory,
+    AddTag,
+    RemoveTag,
+    SetCanonicalBranch,
+    OpenPoll,
+    CastVote,
+    ClosePoll,
+    InviteMaintainer,
+    AcceptMaintainerInvitation,
+    DeclineMaintainerInvitation,
+    SetReviewQuorum,
+    SetReviewSla,
+    ExpireFork,
+    TrustContributor,
+    UntrustContributor,
+    MarkNotificationsRead,
+    SetNotificationPreferences,
+    RecordDigest,
+    UnsubscribeDigest,
+    CreateWebhook,
+    DeleteWebhook,
+    EnableWebhook,
+    DisableWebhook,
 }
@@ -16,6 +16,13 @@ pub struct Settings {
     pub application_name: String,
     pub server: ServerSettings,
     pub database: DatabaseSettings,
+    pub auth: AuthSettings,
+    pub retention: RetentionSettings,
+    pub review: ReviewSettings,
+    pub tasks: TaskSettings,
+    pub mail: MailSettings,
+    pub webhooks: WebhookSettings,
+    pub live: LiveSettings,
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -44,6 +51,90 @@ pub struct DatabaseSettings {
     pub migration: MigrationSettings,
 }
 
+#[derive(Deserialize, Clone, Debug)]
+pub struct AuthSettings {
+    /// Session lifetime in seconds.
+    pub session_ttl: u64,
+    /// Accepts the raw `user-id` header as authentication. Development only.
+    pub allow_user_id_header: bool,
+    /// User promoted to admin when the server starts, so the first admin can be appointed.
+    #[serde(default)]
+    pub admin_username: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct RetentionSettings {
+    /// Seconds a soft deleted fragment is kept before being purged.
+    pub deleted_fragments: u64,
+    /// Seconds between two runs of the purge job.
+    pub purge_interval: u64,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ReviewSettings {
+    /// Times a fork author can resubmit a fork after it has been rejected.
+    pub max_resubmissions: u32,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct TaskSettings {
+    /// Seconds between two runs of the scheduled tasks job.
+    pub poll_interval: u64,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct MailSettings {
+    /// Sender of every email, e.g. `Tales <no-reply@example.com>`.
+    pub from: String,
+    /// Public URL of the server, used to build the links put in emails.
+    pub public_url: String,
+    /// Seconds between two runs of the digest job.
+    pub digest_interval: u64,
+    pub transport: MailTransportSettings,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MailTransportSettings {
+    Smtp {
+        host: String,
+        port: u16,
+        username: Option<String>,
+        password: Option<Secret<String>>,
+        starttls: bool,
+    },
+    /// Writes emails to files instead of sending them.
+    File { dir: String },
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct WebhookSettings {
+    /// Seconds between two runs of the delivery job.
+    pub poll_interval: u64,
+    /// Seconds to wait for an endpoint to answer.
+    pub timeout: u64,
+    /// Attempts made for a delivery before giving up on it.
+    pub max_attempts: u32,
+    /// Seconds before the first retry, doubled after every failed attempt.
+    pub retry_delay: u64,
+    /// Consecutive failed attempts after which the endpoint is disabled.
+    pub failure_threshold: u32,
+    /// Hosts endpoints may use even though they resolve to internal addresses.
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct LiveSettings {
+    /// Seconds between two keep-alive messages on an idle stream.
+    pub heartbeat_interval: u64,
+    /// Events buffered for a stream before it falls back to reading them from the database.
+    pub buffer: usize,
+    /// Events a stream may replay at most when it resumes or catches up, older positions
+    /// are rejected.
+    pub max_replay: i64,
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct MigrationSettings {
     pub enabled: bool,
@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+
+/// A run of consecutive words sharing the same diff operation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", content = "text", rename_all = "snake_case")]
+pub enum Change {
+    Equal(String),
+    Insert(String),
+    Delete(String),
+}
+
+impl Change {
+    fn push(&mut self, word: &str) {
+        let (Self::Equal(text) | Self::Insert(text) | Self::Delete(text)) = self;
+        text.push(' ');
+        text.push_str(word);
+    }
+
+    const fn same_kind(&self, other: &Self) -> bool {
+        matches!(
+            (self, other),
+            (Self::Equal(_), Self::Equal(_))
+                | (Self::Insert(_), Self::Insert(_))
+                | (Self::Delete(_), Self::Delete(_))
+        )
+    }
+}
+
+/// Words each text may have besides their common prefix and suffix. Bounds the table of
+/// the diff to a few megabytes.
+pub const MAX_DIFF_WORDS: usize = 2_000;
+
+#[derive(Debug, Clone, Copy, thiserror::Error, PartialEq, Eq)]
+#[error("Texts differ by more than {MAX_DIFF_WORDS} words")]
+pub struct DiffTooLarge;
+
+/// Word level diff based on the longest common subsequence. Whitespace is not preserved.
+pub fn diff_words(old: &str, new: &str) -> Result<Vec<Change>, DiffTooLarge> {
+    let old: Vec<&str> = old.split_whitespace().collect();
+    let new: Vec<&str> = new.split_whitespace().collect();
+
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (old_middle, new_middle) = (
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    );
+    if old_middle.len() > MAX_DIFF_WORDS || new_middle.len() > MAX_DIFF_WORDS {
+        return Err(DiffTooLarge);
+    }
+
+    let mut changes: Vec<Change> = Vec::new();
+    let mut add = |change: Change, word: &str| match changes.last_mut() {
+        Some(last) if last.same_kind(&change) => last.push(word),
+        _ => changes.push(change),
+    };
+
+    for word in &old[..prefix] {
+        add(Change::Equal((*word).to_owned()), word);
+    }
+    let common_suffix = &new[new.len() - suffix..];
+    let (old, new) = (old_middle, new_middle);
+
+    // lcs[i][j] is the LCS length of old[i..] and new[j..].
+    let mut lcs = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            add(Change::Equal(old[i].to_owned()), old[i]);
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            add(Change::Delete(old[i].to_owned()), old[i]);
+            i += 1;
+        } else {
+            add(Change::Insert(new[j].to_owned()), new[j]);
+            j += 1;
+        }
+    }
+    for word in common_suffix {
+        add(Change::Equal((*word).to_owned()), word);
+    }
+
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_words() {
+        assert_eq!(
+            diff_words("the quick brown fox", "the slow brown dog jumps").unwrap(),
+            vec![
+                Change::Equal("the".to_owned()),
+                Change::Delete("quick".to_owned()),
+                Change::Insert("slow".to_owned()),
+                Change::Equal("brown".to_owned()),
+                Change::Delete("fox".to_owned()),
+                Change::Insert("dog jumps".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_identical_and_empty() {
+        assert_eq!(
+            diff_words("once upon", "once  upon").unwrap(),
+            vec![Change::Equal("once upon".to_owned())]
+        );
+        assert_eq!(
+            diff_words("", "a tale").unwrap(),
+            vec![Change::Insert("a tale".to_owned())]
+        );
+        assert!(diff_words("", "").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_diff_too_large() {
+        let long = "word ".repeat(MAX_DIFF_WORDS + 1);
+        assert_eq!(diff_words("", &long), Err(DiffTooLarge));
+        assert_eq!(diff_words(&long, "other"), Err(DiffTooLarge));
+
+        // Common words around the change do not count.
+        let edited = format!("{long} edit {long}");
+        assert_eq!(
+            diff_words(&format!("{long}{long}"), &edited).unwrap(),
+            vec![
+                Change::Equal(long.split_whitespace().collect::<Vec<_>>().join(" ")),
+                Change::Insert("edit".to_owned()),
+                Change::Equal(long.split_whitespace().collect::<Vec<_>>().join(" ")),
+            ]
+        );
+    }
+}
@@ -1,7 +1,12 @@
-use sqlx::Type;
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    postgres::{PgHasArrayType, PgTypeInfo},
+    Type,
+};
 
-#[derive(Debug, Clone, Type, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Type, PartialEq, Eq, Serialize, Deserialize)]
 #[sqlx(type_name = "event_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
 pub enum EventType {
     FragmentCreated,
     FragmentForked,
@@ -13,4 +18,54 @@ pub enum EventType {
     UserFollowed,
     UserUnfollowed,
     ForkSubmitted,
+    UserRegistered,
+    ProfileUpdated,
+    RoleAssigned,
+    ForkPolicyChanged,
+    FragmentDeleted,
+    FragmentRestored,
+    FragmentsPurged,
+    FragmentReverted,
+    SuggestionAccepted,
+    SuggestionRejected,
+    CommentCreated,
+    CommentEdited,
+    CommentDeleted,
+    CommentModerated,
+    ForkWithdrawn,
+    ForkResubmitted,
+    FragmentUnpublished,
+    PublicationCancelled,
+    StoryCreated,
+    StoryUpdated,
+    TagAdded,
+    TagRemoved,
+    CanonicalBranchSet,
+    PollOpened,
+    VoteCast,
+    PollClosed,
+    MaintainerInvited,
+    MaintainerInvitationAccepted,
+    MaintainerInvitationDeclined,
+    ReviewQuorumChanged,
+    ReviewSlaChanged,
+    ForkAutoRejected,
+    ForkReturnedToDraft,
+    ForkEscalated,
+    ContributorTrusted,
+    ContributorUntrusted,
+    NotificationsRead,
+    NotificationPreferencesChanged,
+    DigestSent,
+    DigestUnsubscribed,
+    WebhookCreated,
+    WebhookDeleted,
+    WebhookEnabled,
+    WebhookDisabled,
+}
+
+impl PgHasArrayType for EventType {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_event_type")
+    }
 }
@@ -17,3 +17,9 @@ impl From<&str> for Content {
         Self(String::from(value))
     }
 }
+
+impl AsRef<str> for Content {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
@@ -1,9 +1,13 @@
 pub mod actor;
+pub mod auth;
 pub mod commands;
 pub mod configuration;
+pub mod diff;
 pub mod events;
 pub mod fragment;
 pub mod id;
+pub mod mail;
 pub mod review;
+pub mod tag;
 pub mod time;
 pub mod tracing;
@@ -0,0 +1,261 @@
+use crate::configuration::settings::{MailSettings, MailTransportSettings};
+use lettre::{
+    message::{header::ContentType, Mailbox, MultiPart, SinglePart},
+    transport::smtp::authentication::Credentials,
+    Address, AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use secrecy::ExposeSecret;
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+use std::{
+    path::PathBuf,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum MailError {
+    #[error("Invalid email address: {0}")]
+    InvalidAddress(String),
+
+    #[error("Failed to build email: {0}")]
+    Build(#[from] lettre::error::Error),
+
+    #[error("Failed to send email: {0}")]
+    Smtp(#[from] lettre::transport::smtp::Error),
+
+    #[error("Failed to write email: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Type, Serialize, Deserialize)]
+#[sqlx(transparent)]
+#[serde(try_from = "String", into = "String")]
+pub struct EmailAddress(String);
+
+impl EmailAddress {
+    pub fn parse(value: &str) -> Result<Self, MailError> {
+        Address::from_str(value)
+            .map(|_| Self(String::from(value)))
+            .map_err(|_| MailError::InvalidAddress(String::from(value)))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for EmailAddress {
+    type Error = MailError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl From<EmailAddress> for String {
+    fn from(value: EmailAddress) -> Self {
+        value.0
+    }
+}
+
+/// Email with a plain text body and an optional HTML alternative.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Email {
+    pub to: EmailAddress,
+    pub subject: String,
+    pub text: String,
+    pub html: Option<String>,
+    /// Sent as the `List-Unsubscribe` header, unsubscribing with a one-click POST (RFC 8058).
+    pub unsubscribe_url: Option<String>,
+}
+
+impl Email {
+    fn into_message(self, from: &Mailbox) -> Result<Message, MailError> {
+        let to = Address::from_str(self.to.as_str())
+            .map_err(|_| MailError::InvalidAddress(self.to.0.clone()))?;
+        let mut builder = Message::builder()
+            .from(from.clone())
+            .to(Mailbox::new(None, to))
+            .subject(self.subject);
+        if let Some(url) = self.unsubscribe_url {
+            builder = builder
+                .header(ListUnsubscribe(format!("<{url}>")))
+                .header(ListUnsubscribePost);
+        }
+
+        let text = SinglePart::builder()
+            .header(ContentType::TEXT_PLAIN)
+            .body(self.text);
+        Ok(match self.html {
+            Some(html) => builder.multipart(
+                MultiPart::alternative().singlepart(text).singlepart(
+                    SinglePart::builder()
+                        .header(ContentType::TEXT_HTML)
+                        .body(html),
+                ),
+            )?,
+            None => builder.singlepart(text)?,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ListUnsubscribe(String);
+
+impl lettre::message::header::Header for ListUnsubscribe {
+    fn name() -> lettre::message::header::HeaderName {
+        lettre::message::header::HeaderName::new_from_ascii_str("List-Unsubscribe")
+    }
+
+    fn parse(s: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self(String::from(s)))
+    }
+
+    fn display(&self) -> lettre::message::header::HeaderValue {
+        lettre::message::header::HeaderValue::new(Self::name(), self.0.clone())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ListUnsubscribePost;
+
+impl lettre::message::header::Header for ListUnsubscribePost {
+    fn name() -> lettre::message::header::HeaderName {
+        lettre::message::header::HeaderName::new_from_ascii_str("List-Unsubscribe-Post")
+    }
+
+    fn parse(_: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self)
+    }
+
+    fn display(&self) -> lettre::message::header::HeaderValue {
+        lettre::message::header::HeaderValue::new(
+            Self::name(),
+            String::from("List-Unsubscribe=One-Click"),
+        )
+    }
+}
+
+#[async_trait::async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, email: Email) -> Result<(), MailError>;
+}
+
+/// Builds the mailer configured by the settings.
+pub fn mailer_from_settings(settings: &MailSettings) -> Result<Arc<dyn Mailer>, MailError> {
+    let from = Mailbox::from_str(&settings.from)
+        .map_err(|_| MailError::InvalidAddress(settings.from.clone()))?;
+    Ok(match &settings.transport {
+        MailTransportSettings::Smtp {
+            host,
+            port,
+            username,
+            password,
+            starttls,
+        } => {
+            let builder = match starttls {
+                true => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)?,
+                false => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host),
+            }
+            .port(*port);
+            let builder = match (username, password) {
+                (Some(username), Some(password)) => builder.credentials(Credentials::new(
+                    username.clone(),
+                    password.expose_secret().clone(),
+                )),
+                _ => builder,
+            };
+            Arc::new(SmtpMailer {
+                transport: builder.build(),
+                from,
+            })
+        }
+        MailTransportSettings::File { dir } => Arc::new(FileMailer {
+            dir: PathBuf::from(dir),
+            from,
+        }),
+    })
+}
+
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+#[async_trait::async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, email: Email) -> Result<(), MailError> {
+        self.transport
+            .send(email.into_message(&self.from)?)
+            .await
+            .map(|_| ())
+            .map_err(MailError::from)
+    }
+}
+
+/// Writes every email as an `.eml` file in a directory, for development.
+pub struct FileMailer {
+    dir: PathBuf,
+    from: Mailbox,
+}
+
+#[async_trait::async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, email: Email) -> Result<(), MailError> {
+        let message = email.into_message(&self.from)?;
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let path = self.dir.join(format!("{}.eml", uuid::Uuid::new_v4()));
+        tokio::fs::write(path, message.formatted()).await?;
+        Ok(())
+    }
+}
+
+/// Keeps the emails in memory, for tests.
+#[derive(Default)]
+pub struct InMemoryMailer {
+    sent: Mutex<Vec<Email>>,
+}
+
+impl InMemoryMailer {
+    pub fn sent(&self) -> Vec<Email> {
+        self.sent.lock().unwrap().clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl Mailer for InMemoryMailer {
+    async fn send(&self, email: Email) -> Result<(), MailError> {
+        self.sent.lock().unwrap().push(email);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email_address() {
+        assert!(EmailAddress::parse("reader@example.com").is_ok());
+        assert!(EmailAddress::parse("reader").is_err());
+        assert!(serde_json::from_str::<EmailAddress>("\"not an address\"").is_err());
+    }
+
+    #[test]
+    fn test_message_headers() {
+        let email = Email {
+            to: EmailAddress::parse("reader@example.com").unwrap(),
+            subject: String::from("Digest"),
+            text: String::from("text"),
+            html: Some(String::from("<p>html</p>")),
+            unsubscribe_url: Some(String::from("http://localhost/unsubscribe")),
+        };
+        let from = Mailbox::from_str("Tales <no-reply@example.com>").unwrap();
+        let formatted = String::from_utf8(email.into_message(&from).unwrap().formatted()).unwrap();
+
+        assert!(formatted.contains("List-Unsubscribe: <http://localhost/unsubscribe>"));
+        assert!(formatted.contains("List-Unsubscribe-Post: List-Unsubscribe=One-Click"));
+        assert!(formatted.contains("multipart/alternative"));
+    }
+}
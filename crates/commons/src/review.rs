@@ -5,3 +5,15 @@ use sqlx::Type;
 #[sqlx(transparent)]
 #[serde(transparent)]
 pub struct Comment(String);
+
+impl From<String> for Comment {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl AsRef<str> for Comment {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
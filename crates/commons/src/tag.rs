@@ -0,0 +1,92 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+pub const MAX_TAG_LENGTH: usize = 32;
+
+/// A normalized, free-form label such as `plot-twist` or `sci-fi`.
+///
+/// Tags are lowercase ASCII letters, digits and single dashes. Whitespace and underscores
+/// are read as dashes, so `Plot Twist` and `plot_twist` are the same tag.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Type, Serialize, Deserialize)]
+#[sqlx(transparent)]
+#[serde(transparent)]
+pub struct Tag(String);
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum InvalidTag {
+    #[error("Tag can not be empty")]
+    Empty,
+
+    #[error("Tag can not be longer than {MAX_TAG_LENGTH} characters")]
+    TooLong,
+
+    #[error("Tag can only contain letters, digits and dashes")]
+    InvalidCharacter(char),
+}
+
+impl Tag {
+    pub fn parse(value: &str) -> Result<Self, InvalidTag> {
+        let mut tag = String::with_capacity(value.len());
+        for c in value.trim().chars() {
+            match c.to_ascii_lowercase() {
+                c @ ('a'..='z' | '0'..='9') => tag.push(c),
+                '-' | '_' => tag.push('-'),
+                c if c.is_whitespace() => tag.push('-'),
+                c => return Err(InvalidTag::InvalidCharacter(c)),
+            }
+            if tag.ends_with("--") {
+                tag.pop();
+            }
+        }
+        let tag = tag.trim_matches('-');
+
+        if tag.is_empty() {
+            return Err(InvalidTag::Empty);
+        }
+        if tag.len() > MAX_TAG_LENGTH {
+            return Err(InvalidTag::TooLong);
+        }
+        Ok(Self(tag.to_owned()))
+    }
+}
+
+impl AsRef<str> for Tag {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for Tag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_normalizes() {
+        assert_eq!(Tag::parse("sci-fi").unwrap().as_ref(), "sci-fi");
+        assert_eq!(Tag::parse("  Plot Twist ").unwrap().as_ref(), "plot-twist");
+        assert_eq!(Tag::parse("plot_twist").unwrap().as_ref(), "plot-twist");
+        assert_eq!(
+            Tag::parse("--slow  -_burn--").unwrap().as_ref(),
+            "slow-burn"
+        );
+        assert_eq!(Tag::parse("1984").unwrap().as_ref(), "1984");
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid() {
+        assert_eq!(Tag::parse("   "), Err(InvalidTag::Empty));
+        assert_eq!(Tag::parse("-_-"), Err(InvalidTag::Empty));
+        assert_eq!(Tag::parse("a#b"), Err(InvalidTag::InvalidCharacter('#')));
+        assert_eq!(Tag::parse("café"), Err(InvalidTag::InvalidCharacter('é')));
+        assert_eq!(Tag::parse(&"a".repeat(33)), Err(InvalidTag::TooLong));
+        assert!(Tag::parse(&"a".repeat(32)).is_ok());
+    }
+}
@@ -1,6 +1,7 @@
-use chrono::{NaiveDateTime, Utc};
+use chrono::{Duration, NaiveDateTime, Utc};
 use mockall::automock;
 use serde::{Deserialize, Serialize};
+use std::ops::{Add, Sub};
 
 #[automock]
 pub trait Clock: Send + Sync {
@@ -15,7 +16,9 @@ impl Clock for SystemClock {
     }
 }
 
-#[derive(Debug, Clone, Copy, sqlx::Type, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(
+    Debug, Clone, Copy, sqlx::Type, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
 #[sqlx(transparent)]
 #[serde(transparent)]
 pub struct DateTime(NaiveDateTime);
@@ -26,3 +29,19 @@ impl DateTime {
         Self(Utc::now().naive_utc())
     }
 }
+
+impl Add<Duration> for DateTime {
+    type Output = Self;
+
+    fn add(self, rhs: Duration) -> Self::Output {
+        Self(self.0 + rhs)
+    }
+}
+
+impl Sub<Duration> for DateTime {
+    type Output = Self;
+
+    fn sub(self, rhs: Duration) -> Self::Output {
+        Self(self.0 - rhs)
+    }
+}
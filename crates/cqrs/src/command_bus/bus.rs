@@ -1,7 +1,14 @@
-use super::{command::Command, error::CommandBusError};
-use crate::events::Event;
+use super::{
+    command::{
+        close_poll::ClosePollCommand, expire_fork::ExpireForkCommand,
+        publish_fragment::PublishFragmentCommand, Command,
+    },
+    error::CommandBusError,
+};
+use crate::{events::Event, projections};
 use commons::{
-    actor::ActorTrait,
+    actor::{Actor, ActorTrait, ActorType},
+    commands::CommandType,
     id::{Id, IdGenerator},
     time::{Clock, DateTime},
 };
@@ -11,10 +18,10 @@ use std::{marker::PhantomData, sync::Arc};
 use storage::{
     model::{
         event::{DbEvent, DbEventBuilder, EventData},
-        task::TaskBuilder,
+        task::{Task, TaskBuilder},
+        user::User,
     },
-    query::{event::QueryEvent, task::QueryTask},
-    StorageError,
+    query::{event::QueryEvent, task::QueryTask, user::QueryUser},
 };
 use tap::TapFallible;
 
@@ -47,18 +54,79 @@ impl CommandBus {
             return Err(CommandBusError::ActorNotSupported(Box::new(actor)));
         };
 
-        Ok(TaskBuilder::default()
-            .id(Id::new())
-            .command_type(command.command_type())
-            .commnad_data(command.into())
-            .actor_type(actor.actor_type())
-            .actor_id(actor.id())
-            .scheduled_at(schedule_to.unwrap_or_else(DateTime::now))
-            .build()
-            .map_err(anyhow::Error::from)?
-            .save(&self.pool)
+        command
+            .validate(&self.pool, &actor)
             .await
-            .map(|t| *t.id())?)
+            .tap_err(|e| tracing::error!("Command [{command:?}] can not be scheduled: {e}"))?;
+
+        let now = self.clock.now();
+        Ok(new_task(
+            self.ids.new_id(),
+            command,
+            &actor,
+            now,
+            schedule_to.unwrap_or(now),
+        )?
+        .save(&self.pool)
+        .await
+        .map(|t| *t.id())?)
+    }
+
+    /// Runs every scheduled task that is due, returning how many were run.
+    ///
+    /// Tasks are claimed one at a time, so concurrent runners never run the same task, and
+    /// completed once their command finished, whatever the outcome. Failures are recorded on
+    /// the task and not retried. They run as the user that dispatched them, with the role
+    /// the user has when they run.
+    pub async fn run_due_tasks(&self) -> Result<usize, CommandBusError> {
+        let mut count = 0;
+        while let Some(task) = Task::claim_due(&self.pool, &self.clock.now())
+            .await
+            .tap_err(|e| tracing::error!("Failed to claim due task: {e}"))?
+        {
+            count += 1;
+            let result = match (task.actor_type(), task.actor_id()) {
+                (ActorType::User, Some(id)) => match User::find(&self.pool, id)
+                    .await
+                    .tap_err(|e| tracing::error!("Failed to find task user [{id}]: {e}"))?
+                {
+                    Some(user) => self.run_task(user, &task).await,
+                    None => Err(anyhow::anyhow!("User [{id}] not found").into()),
+                },
+                _ => self.run_task(Actor::System, &task).await,
+            };
+
+            let id = *task.id();
+            task.set_completed_at(Some(self.clock.now()))
+                .set_failure(result.err().map(|e| e.to_string()))
+                .update(&self.pool)
+                .await
+                .tap_err(|e| tracing::error!("Failed to complete task [{id}]: {e}"))?;
+        }
+
+        Ok(count)
+    }
+
+    async fn run_task<A>(&self, actor: A, task: &Task) -> Result<(), CommandBusError>
+    where
+        A: ActorTrait + Clone + 'static,
+    {
+        let data = task.command_data().clone();
+        match task.command_type() {
+            CommandType::PublishFragment => {
+                self.execute(actor, data.into_command::<PublishFragmentCommand>())
+                    .await
+            }
+            CommandType::ClosePoll => {
+                self.execute(actor, data.into_command::<ClosePollCommand>())
+                    .await
+            }
+            CommandType::ExpireFork => {
+                self.execute(actor, data.into_command::<ExpireForkCommand>())
+                    .await
+            }
+            other => Err(anyhow::anyhow!("Command [{other:?}] can not be scheduled").into()),
+        }
     }
 
     pub async fn async_execute<C, A, EV>(&self, actor: A, command: C) -> Result<(), CommandBusError>
@@ -158,10 +226,19 @@ where
 
         match result {
             Ok(result) => {
+                let mut events = Vec::new();
                 if let Some(event) = result {
-                    self.save_event(&mut ctx, event)
+                    events.push(db_event(ctx.ids().new_id(), &event));
+                }
+                events.append(&mut ctx.follow_ups);
+                for event in events {
+                    let event = event
+                        .save(ctx.tx().as_mut())
                         .await
                         .tap_err(|e| tracing::error!("Failed to save event: {e}"))?;
+                    projections::project(&mut ctx, &event)
+                        .await
+                        .tap_err(|e| tracing::error!("Failed to project event: {e}"))?;
                 }
 
                 ctx.tx()
@@ -181,24 +258,41 @@ where
             }
         }
     }
+}
 
-    async fn save_event<'ctx, E: Event + Serialize>(
-        &self,
-        ctx: &mut Ctx<'ctx>,
-        event: E,
-    ) -> Result<DbEvent, StorageError> {
-        DbEventBuilder::default()
-            .id(ctx.ids().new_id())
-            .timestamp(event.timestamp())
-            .event_type(event.event_type())
-            .event_data(EventData::from(&event))
-            .actor_id(event.actor().id())
-            .actor_type((&event.actor()).into())
-            .build()
-            .unwrap()
-            .save(ctx.tx().as_mut())
-            .await
-    }
+fn db_event<E: Event + Serialize>(id: Id, event: &E) -> DbEvent {
+    DbEventBuilder::default()
+        .id(id)
+        .timestamp(event.timestamp())
+        .event_type(event.event_type())
+        .event_data(EventData::from(event))
+        .actor_id(event.actor().id())
+        .actor_type((&event.actor()).into())
+        .build()
+        .unwrap()
+}
+
+fn new_task<C, A>(
+    id: Id,
+    command: C,
+    actor: &A,
+    now: DateTime,
+    scheduled_at: DateTime,
+) -> Result<Task, CommandBusError>
+where
+    C: Command + Serialize,
+    A: ActorTrait + ?Sized,
+{
+    Ok(TaskBuilder::default()
+        .id(id)
+        .command_type(command.command_type())
+        .command_data(command.into())
+        .actor_type(actor.actor_type())
+        .actor_id(actor.id())
+        .created_at(now)
+        .scheduled_at(scheduled_at)
+        .build()
+        .map_err(anyhow::Error::from)?)
 }
 
 pub struct Ctx<'ctx> {
@@ -207,6 +301,7 @@ pub struct Ctx<'ctx> {
     tx: Transaction<'ctx, Postgres>,
     clock: &'ctx dyn Clock,
     ids: &'ctx dyn IdGenerator,
+    follow_ups: Vec<DbEvent>,
 }
 
 impl<'ctx> Ctx<'ctx> {
@@ -222,6 +317,7 @@ impl<'ctx> Ctx<'ctx> {
             tx: pool.begin().await.map_err(CommandBusError::from)?,
             clock,
             ids,
+            follow_ups: Vec::new(),
         })
     }
     pub fn pool(&self) -> &PgPool {
@@ -243,4 +339,28 @@ impl<'ctx> Ctx<'ctx> {
     pub fn ids(&self) -> &dyn IdGenerator {
         self.ids
     }
+
+    /// Schedules `command` to be run by the system at `at`. The task is saved in the
+    /// transaction, so it only exists once the command scheduling it commits.
+    pub async fn schedule<C: Command + Serialize>(
+        &mut self,
+        command: C,
+        at: DateTime,
+    ) -> Result<Id, CommandBusError> {
+        let task = new_task(
+            self.ids.new_id(),
+            command,
+            &Actor::System,
+            self.clock.now(),
+            at,
+        )?;
+        Ok(task.save(self.tx.as_mut()).await.map(|t| *t.id())?)
+    }
+
+    /// Records `event` as following the event returned by the command. Follow-up events are
+    /// saved and projected after it, in the order they were recorded.
+    pub fn follow_with<E: Event + Serialize>(&mut self, event: E) {
+        let event = db_event(self.ids.new_id(), &event);
+        self.follow_ups.push(event);
+    }
 }
@@ -1,19 +1,61 @@
 use super::{bus::Ctx, error::CommandBusError};
 use crate::events::Event;
 use commons::{actor::ActorTrait, commands::CommandType};
+use sqlx::PgPool;
 use std::fmt::Debug;
 
+pub mod accept_maintainer_invitation;
+pub mod accept_suggestion;
+pub mod add_tag;
+pub mod assign_role;
+pub mod cancel_publication;
+pub mod cast_vote;
+pub mod close_poll;
+pub mod create_comment;
 pub mod create_fragment;
+pub mod create_story;
+pub mod create_webhook;
+pub mod decline_maintainer_invitation;
+pub mod delete_comment;
 pub mod delete_fragment;
+pub mod delete_webhook;
+pub mod disable_webhook;
 pub mod dislike_fragment;
+pub mod edit_comment;
+pub mod enable_webhook;
+pub mod expire_fork;
 pub mod follow_user;
 pub mod fork_fragment;
+pub mod invite_maintainer;
 pub mod like_fragment;
+pub mod mark_notifications_read;
+pub mod moderate_comment;
+pub mod open_poll;
 pub mod publish_fragment;
+pub mod purge_fragments;
+pub mod record_digest;
+pub mod register_user;
+pub mod reject_suggestion;
+pub mod remove_tag;
+pub mod restore_fragment;
+pub mod resubmit_fork;
+pub mod revert_fragment;
 pub mod review_fork;
+pub mod set_canonical_branch;
+pub mod set_fork_policy;
+pub mod set_notification_preferences;
+pub mod set_review_quorum;
+pub mod set_review_sla;
 pub mod submit_fork;
+pub mod trust_contributor;
 pub mod unfollow_user;
+pub mod unpublish_fragment;
+pub mod unsubscribe_digest;
+pub mod untrust_contributor;
 pub mod update_fragment;
+pub mod update_profile;
+pub mod update_story;
+pub mod withdraw_fork;
 
 #[async_trait::async_trait]
 pub trait Command: Send + Sync + Debug {
@@ -29,4 +71,14 @@ pub trait Command: Send + Sync + Debug {
     fn supports<A>(&self, actor: &A) -> bool
     where
         A: ActorTrait;
+
+    /// Checks run by `CommandBus::dispatch` before scheduling the command. Scheduled commands
+    /// are handled later in a different state, so `handle` must not rely on them.
+    async fn validate(
+        &self,
+        _pool: &PgPool,
+        _actor: &dyn ActorTrait,
+    ) -> Result<(), CommandBusError> {
+        Ok(())
+    }
 }
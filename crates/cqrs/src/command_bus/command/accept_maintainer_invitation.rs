@@ -0,0 +1,65 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::MaintainerInvitationAcceptedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use storage::{
+    model::maintainer::{Maintainer, MaintainerState},
+    query::maintainer::QueryMaintainer,
+};
+use tap::TapFallible;
+
+/// Accepts a pending invitation to co-maintain the story rooted at `root_id`.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct AcceptMaintainerInvitationCommand {
+    pub root_id: Id,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum AcceptMaintainerInvitationCommandError {
+    #[error("Invitation not found: {0}")]
+    InvitationNotFound(Id),
+}
+
+#[async_trait::async_trait]
+impl Command for AcceptMaintainerInvitationCommand {
+    type Event = MaintainerInvitationAcceptedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::AcceptMaintainerInvitation
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::RespondMaintainerInvitation, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let user = ctx.actor().id().unwrap();
+        let invitation = Maintainer::find(ctx.pool(), &self.root_id, &user)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find maintainer: {e:?}"))?
+            .filter(Maintainer::is_invited)
+            .ok_or(AcceptMaintainerInvitationCommandError::InvitationNotFound(
+                self.root_id,
+            ))?;
+
+        let now = ctx.clock().now();
+        invitation
+            .set_state(MaintainerState::Accepted)
+            .set_responded_at(now)
+            .update(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to update maintainer: {e:?}"))?;
+
+        Ok(Some(MaintainerInvitationAcceptedEvent {
+            root_id: self.root_id,
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
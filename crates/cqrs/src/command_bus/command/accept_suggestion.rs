@@ -0,0 +1,135 @@
+use super::update_fragment::UpdateFragmentCommandBuilder;
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::SuggestionAcceptedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use derive_getters::Getters;
+use storage::{
+    model::{
+        fragment::Fragment,
+        suggestion::{Suggestion, SuggestionStatus},
+    },
+    query::{fragment::QueryFragment, suggestion::QuerySuggestion},
+};
+use tap::TapFallible;
+
+/// Applies a reviewer suggestion to the fork content, the same way an update from the fork
+/// author would.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize, Getters)]
+#[builder(setter(into))]
+pub struct AcceptSuggestionCommand {
+    /// Fork the suggestion was made on.
+    fragment_id: Id,
+    suggestion_id: Id,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum AcceptSuggestionCommandError {
+    #[error("Suggestion not found: {0}")]
+    SuggestionNotFound(Id),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+
+    #[error("Suggestion already resolved: {0}")]
+    AlreadyResolved(Id),
+
+    #[error("Fragment is not editable")]
+    NonEditableFragment(Id),
+
+    #[error("Suggestion no longer matches the fragment content: {0}")]
+    Outdated(Id),
+}
+
+#[async_trait::async_trait]
+impl Command for AcceptSuggestionCommand {
+    type Event = SuggestionAcceptedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::AcceptSuggestion
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        // Locking the fragment first serializes accepts and edits, so the suggestion read
+        // below carries the offsets left by the last of them.
+        let fragment = Fragment::find_for_update(ctx.tx().as_mut(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find fragment: {e}"))?
+            .ok_or(AcceptSuggestionCommandError::SuggestionNotFound(
+                self.suggestion_id,
+            ))?;
+        let suggestion = Suggestion::find(ctx.tx().as_mut(), &self.suggestion_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find suggestion: {e}"))?
+            .filter(|suggestion| suggestion.fragment_id() == fragment.id())
+            .ok_or(AcceptSuggestionCommandError::SuggestionNotFound(
+                self.suggestion_id,
+            ))?;
+
+        authorize(
+            ctx.actor(),
+            Action::ResolveSuggestion,
+            Resource::Fragment(&fragment),
+        )
+        .map_err(|e| AcceptSuggestionCommandError::Forbidden(e.reason()))?;
+
+        if !suggestion.is_pending() {
+            return Err(AcceptSuggestionCommandError::AlreadyResolved(self.suggestion_id).into());
+        }
+
+        if !fragment.is_editable() {
+            return Err(AcceptSuggestionCommandError::NonEditableFragment(*fragment.id()).into());
+        }
+
+        let content = suggestion
+            .apply(fragment.content())
+            .ok_or(AcceptSuggestionCommandError::Outdated(self.suggestion_id))?;
+
+        UpdateFragmentCommandBuilder::default()
+            .fragment_id(*fragment.id())
+            .content(content.clone())
+            .end(None)
+            .build()
+            .map_err(anyhow::Error::from)?
+            .handle(ctx)
+            .await?;
+
+        let now = ctx.clock().now();
+        let replaced = suggestion.range_end() - suggestion.range_start();
+        let inserted = i32::try_from(suggestion.replacement().as_ref().chars().count())
+            .map_err(anyhow::Error::from)?;
+        let suggestion = suggestion
+            .set_status(SuggestionStatus::Accepted)
+            .set_resolved_at(now)
+            .update(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to update suggestion: {e}"))?;
+
+        Suggestion::shift_pending(
+            ctx.tx().as_mut(),
+            suggestion.fragment_id(),
+            *suggestion.range_end(),
+            inserted - replaced,
+        )
+        .await
+        .tap_err(|e| tracing::error!("Failed to shift pending suggestions: {e}"))?;
+
+        Ok(Some(SuggestionAcceptedEvent {
+            suggestion_id: self.suggestion_id,
+            review_id: *suggestion.review_id(),
+            fragment_id: *suggestion.fragment_id(),
+            content,
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::ResolveSuggestion, Resource::Any).is_ok()
+    }
+}
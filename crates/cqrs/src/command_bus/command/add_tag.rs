@@ -0,0 +1,126 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::TagAddedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id, tag::Tag};
+use sqlx::PgPool;
+use storage::{
+    model::{
+        fragment::Fragment,
+        story::Story,
+        tag::{TagTarget, Tagging, TaggingBuilder},
+    },
+    query::{fragment::QueryFragment, story::QueryStory, tag::QueryTagging},
+    StorageError,
+};
+use tap::TapFallible;
+
+const MAX_TAGS: usize = 10;
+
+/// Puts a tag on a fragment or a story. Tagging twice with the same tag is a no-op.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct AddTagCommand {
+    target: TagTarget,
+    target_id: Id,
+    tag: String,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum AddTagCommandError {
+    #[error("Tag target not found: {0}")]
+    TargetNotFound(Id),
+
+    #[error("{0}")]
+    InvalidTag(String),
+
+    #[error("At most {MAX_TAGS} tags are allowed")]
+    TooManyTags,
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for AddTagCommand {
+    type Event = TagAddedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::AddTag
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::TagContent, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let tag =
+            Tag::parse(&self.tag).map_err(|e| AddTagCommandError::InvalidTag(e.to_string()))?;
+
+        let target = Tagged::find(ctx.pool(), self.target, &self.target_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find tag target: {e:?}"))?
+            .ok_or(AddTagCommandError::TargetNotFound(self.target_id))?;
+        authorize(ctx.actor(), Action::TagContent, target.resource())
+            .map_err(|e| AddTagCommandError::Forbidden(e.reason()))?;
+
+        let tags = Tagging::find_by_target(ctx.pool(), self.target, &self.target_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find tags: {e:?}"))?;
+        if tags.iter().any(|t| t.tag() == &tag) {
+            return Ok(None);
+        }
+        if tags.len() >= MAX_TAGS {
+            return Err(AddTagCommandError::TooManyTags.into());
+        }
+
+        let tagging = TaggingBuilder::default()
+            .target(self.target)
+            .target_id(self.target_id)
+            .tag(tag)
+            .created_at(ctx.clock().now())
+            .build()
+            .map_err(anyhow::Error::from)?
+            .save(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save tag: {e:?}"))?;
+
+        Ok(Some(TagAddedEvent {
+            target: *tagging.target(),
+            target_id: *tagging.target_id(),
+            tag: tagging.tag().clone(),
+            timestamp: *tagging.created_at(),
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
+
+/// Fragment or story being tagged.
+pub(super) enum Tagged {
+    Fragment(Fragment),
+    Story(Story),
+}
+
+impl Tagged {
+    pub(super) async fn find(
+        pool: &PgPool,
+        target: TagTarget,
+        id: &Id,
+    ) -> Result<Option<Self>, StorageError> {
+        Ok(match target {
+            TagTarget::Fragment => Fragment::find(pool, id).await?.map(Self::Fragment),
+            TagTarget::Story => Story::find(pool, id).await?.map(Self::Story),
+        })
+    }
+
+    pub(super) fn resource(&self) -> Resource<'_> {
+        match self {
+            Self::Fragment(fragment) => Resource::Fragment(fragment),
+            Self::Story(story) => Resource::Story(story),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::RoleAssignedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{
+    actor::{ActorTrait, Role},
+    commands::CommandType,
+    id::Id,
+};
+use derive_getters::Getters;
+use storage::{model::user::User, query::user::QueryUser};
+use tap::TapFallible;
+
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize, Getters)]
+#[builder(setter(into))]
+pub struct AssignRoleCommand {
+    user_id: Id,
+    role: Role,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum AssignRoleCommandError {
+    #[error("User not found: {0}")]
+    UserNotFound(Id),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for AssignRoleCommand {
+    type Event = RoleAssignedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::AssignRole
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        authorize(
+            ctx.actor(),
+            Action::AssignRole,
+            Resource::User(self.user_id),
+        )
+        .map_err(|e| AssignRoleCommandError::Forbidden(e.reason()))?;
+
+        let user = User::find(ctx.pool(), &self.user_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find user [{}]: {e}", self.user_id))?
+            .ok_or(AssignRoleCommandError::UserNotFound(self.user_id))?;
+
+        if *user.role() == self.role {
+            return Ok(None);
+        }
+
+        user.set_role(self.role)
+            .update(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to update user [{}]: {e}", self.user_id))?;
+
+        Ok(Some(RoleAssignedEvent {
+            user_id: self.user_id,
+            role: self.role,
+            timestamp: ctx.clock().now(),
+            actor: ctx.actor().actor(),
+        }))
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::AssignRole, Resource::Any).is_ok()
+    }
+}
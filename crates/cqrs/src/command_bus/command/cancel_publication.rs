@@ -0,0 +1,94 @@
+use super::{publish_fragment::PublishFragmentCommand, Command};
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::PublicationCancelledEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use storage::{
+    model::{
+        fragment::Fragment,
+        task::{CommandData, Task},
+    },
+    query::{fragment::QueryFragment, task::QueryTask},
+};
+use tap::TapFallible;
+
+/// Cancels the pending scheduled publication of a fragment.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct CancelPublicationCommand {
+    pub fragment_id: Id,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum CancelPublicationCommandError {
+    #[error("Fragment not found: {0}")]
+    FragmentNotFound(Id),
+
+    #[error("Fragment publication is not scheduled: {0}")]
+    NotScheduled(Id),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for CancelPublicationCommand {
+    type Event = PublicationCancelledEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::CancelPublication
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::PublishFragment, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let fragment = Fragment::find(ctx.pool(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find fragment: {e:?}"))?
+            .ok_or(CancelPublicationCommandError::FragmentNotFound(
+                self.fragment_id,
+            ))?;
+
+        authorize(
+            ctx.actor(),
+            Action::PublishFragment,
+            Resource::Fragment(&fragment),
+        )
+        .map_err(|e| CancelPublicationCommandError::Forbidden(e.reason()))?;
+
+        let publication = PublishFragmentCommand {
+            fragment_id: self.fragment_id,
+        };
+        let task = Task::find_pending(
+            ctx.pool(),
+            &publication.command_type(),
+            &CommandData::from(publication),
+        )
+        .await
+        .tap_err(|e| tracing::error!("Failed to find scheduled publication: {e:?}"))?
+        .ok_or(CancelPublicationCommandError::NotScheduled(
+            self.fragment_id,
+        ))?;
+
+        let now = ctx.clock().now();
+        let task = task
+            .set_cancelled_at(Some(now))
+            .update(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to cancel task: {e:?}"))?;
+
+        Ok(Some(PublicationCancelledEvent {
+            fragment_id: self.fragment_id,
+            task_id: *task.id(),
+            scheduled_at: *task.scheduled_at(),
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
@@ -0,0 +1,103 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::VoteCastEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use storage::{
+    model::{
+        fragment::Fragment,
+        poll::{Poll, Vote, VoteBuilder},
+    },
+    query::{
+        fragment::QueryFragment,
+        poll::{QueryPoll, QueryVote},
+    },
+};
+use tap::TapFallible;
+
+/// Votes for one of the published children of a polled fragment. Each user votes once.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct CastVoteCommand {
+    poll_id: Id,
+    child_id: Id,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum CastVoteCommandError {
+    #[error("Poll not found: {0}")]
+    PollNotFound(Id),
+
+    #[error("Poll is closed: {0}")]
+    PollClosed(Id),
+
+    #[error("Already voted in poll: {0}")]
+    AlreadyVoted(Id),
+
+    #[error("{0}")]
+    InvalidChoice(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for CastVoteCommand {
+    type Event = VoteCastEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::CastVote
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::CastVote, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let poll = Poll::find(ctx.pool(), &self.poll_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find poll: {e:?}"))?
+            .ok_or(CastVoteCommandError::PollNotFound(self.poll_id))?;
+
+        let now = ctx.clock().now();
+        if !poll.is_open(&now) {
+            return Err(CastVoteCommandError::PollClosed(self.poll_id).into());
+        }
+
+        Fragment::find(ctx.pool(), &self.child_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find child fragment: {e:?}"))?
+            .filter(|child| child.parent_id() == &Some(*poll.fragment_id()))
+            .filter(Fragment::is_published)
+            .ok_or(CastVoteCommandError::InvalidChoice(
+                "Votes go to a published child of the polled fragment",
+            ))?;
+
+        let user = ctx.actor().id().unwrap();
+        let vote = Vote::find(ctx.pool(), &self.poll_id, &user)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find vote: {e:?}"))?;
+        if vote.is_some() {
+            return Err(CastVoteCommandError::AlreadyVoted(self.poll_id).into());
+        }
+
+        VoteBuilder::default()
+            .poll_id(self.poll_id)
+            .user_id(user)
+            .child_id(self.child_id)
+            .created_at(now)
+            .build()
+            .map_err(anyhow::Error::from)?
+            .save(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save vote: {e:?}"))?;
+
+        Ok(Some(VoteCastEvent {
+            poll_id: self.poll_id,
+            child_id: self.child_id,
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
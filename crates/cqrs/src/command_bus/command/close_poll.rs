@@ -0,0 +1,94 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::PollClosedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use storage::{
+    model::{canonical_branch::CanonicalBranchBuilder, poll::Poll},
+    query::{canonical_branch::QueryCanonicalBranch, poll::QueryPoll},
+};
+use tap::TapFallible;
+
+/// Closes a poll once its window is over and makes the most voted child the canonical
+/// branch of the fragment. Run by the system, as a task scheduled when the poll opens.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct ClosePollCommand {
+    poll_id: Id,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum ClosePollCommandError {
+    #[error("Poll not found: {0}")]
+    PollNotFound(Id),
+
+    #[error("{0}")]
+    InvalidState(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for ClosePollCommand {
+    type Event = PollClosedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::ClosePoll
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::ClosePoll, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let poll = Poll::find(ctx.pool(), &self.poll_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find poll: {e:?}"))?
+            .ok_or(ClosePollCommandError::PollNotFound(self.poll_id))?;
+
+        let now = ctx.clock().now();
+        if poll.is_closed() {
+            return Err(ClosePollCommandError::InvalidState("Poll is already closed").into());
+        }
+        if poll.is_open(&now) {
+            return Err(ClosePollCommandError::InvalidState("Poll window is not over").into());
+        }
+
+        let results = poll
+            .results(ctx.pool())
+            .await
+            .tap_err(|e| tracing::error!("Failed to count votes: {e:?}"))?;
+        let winner_id = results.first().map(|result| *result.child_id());
+
+        if let Some(winner_id) = winner_id {
+            CanonicalBranchBuilder::default()
+                .fragment_id(*poll.fragment_id())
+                .child_id(winner_id)
+                .chosen_by(None)
+                .chosen_at(now)
+                .build()
+                .map_err(anyhow::Error::from)?
+                .save(ctx.tx().as_mut())
+                .await
+                .tap_err(|e| tracing::error!("Failed to save canonical branch: {e:?}"))?;
+        }
+
+        let poll = poll
+            .set_closed_at(Some(now))
+            .set_winner_id(winner_id)
+            .update(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to close poll: {e:?}"))?;
+
+        Ok(Some(PollClosedEvent {
+            poll_id: *poll.id(),
+            fragment_id: *poll.fragment_id(),
+            winner_id,
+            results,
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
@@ -0,0 +1,167 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::CommentCreatedEvent;
+use crate::policy::{authorize, authorize_view, Action, Resource, ViewError};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id, review};
+use derive_getters::Getters;
+use storage::{
+    model::{
+        comment::{Comment, CommentBuilder},
+        fragment::Fragment,
+        maintainer::Maintainer,
+        review::Review,
+    },
+    query::{
+        comment::QueryComment, fragment::QueryFragment, maintainer::QueryMaintainer,
+        review::QueryReview,
+    },
+};
+use tap::TapFallible;
+
+/// Starts a thread on a fragment, or on one of its reviews when `review_id` is set, or
+/// replies to the comment `parent_id`.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize, Getters)]
+#[builder(setter(into))]
+pub struct CreateCommentCommand {
+    comment_id: Id,
+    fragment_id: Id,
+    #[builder(default)]
+    review_id: Option<Id>,
+    #[builder(default)]
+    parent_id: Option<Id>,
+    body: review::Comment,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum CreateCommentCommandError {
+    #[error("Fragment not found: {0}")]
+    FragmentNotFound(Id),
+
+    #[error("Review not found: {0}")]
+    ReviewNotFound(Id),
+
+    #[error("Comment not found: {0}")]
+    ParentNotFound(Id),
+
+    #[error("Cannot reply to a deleted comment")]
+    DeletedParent(Id),
+
+    #[error("Comment cannot be empty")]
+    EmptyComment,
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for CreateCommentCommand {
+    type Event = CommentCreatedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::CreateComment
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let user = ctx.actor().id().unwrap();
+        if self.body.as_ref().trim().is_empty() {
+            return Err(CreateCommentCommandError::EmptyComment.into());
+        }
+
+        let fragment = Fragment::find(ctx.pool(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find fragment [{}]: {e}", self.fragment_id))?
+            .ok_or(CreateCommentCommandError::FragmentNotFound(
+                self.fragment_id,
+            ))?;
+
+        match self.review_id {
+            Some(review_id) => {
+                Review::find(ctx.pool(), &review_id)
+                    .await
+                    .tap_err(|e| tracing::error!("Failed to find review [{review_id}]: {e}"))?
+                    .filter(|review| review.fragment_id() == fragment.id())
+                    .ok_or(CreateCommentCommandError::ReviewNotFound(review_id))?;
+                let parent = fragment
+                    .get_parent(ctx.pool())
+                    .await?
+                    .ok_or(CreateCommentCommandError::ReviewNotFound(review_id))?;
+                let maintainer = Maintainer::is_maintainer(ctx.pool(), &fragment.root_id(), &user)
+                    .await
+                    .tap_err(|e| tracing::error!("Failed to check story maintainers: {e}"))?;
+
+                authorize(
+                    ctx.actor(),
+                    Action::DiscussReview,
+                    Resource::Fork {
+                        fork: &fragment,
+                        parent: &parent,
+                        maintainer,
+                    },
+                )
+            }
+            // Users can only comment on the fragments they can read.
+            None => {
+                let mut conn = ctx.pool().acquire().await?;
+                match authorize_view(&mut conn, ctx.actor(), Action::ViewFragment, &fragment).await
+                {
+                    Ok(()) => authorize(
+                        ctx.actor(),
+                        Action::CreateComment,
+                        Resource::Fragment(&fragment),
+                    ),
+                    Err(ViewError::Forbidden(e)) => Err(e),
+                    Err(ViewError::Storage(e)) => return Err(e.into()),
+                }
+            }
+        }
+        .map_err(|e| CreateCommentCommandError::Forbidden(e.reason()))?;
+
+        if let Some(parent_id) = self.parent_id {
+            let parent = Comment::find(ctx.pool(), &parent_id)
+                .await
+                .tap_err(|e| tracing::error!("Failed to find comment [{parent_id}]: {e}"))?
+                .filter(|parent| {
+                    parent.fragment_id() == fragment.id() && *parent.review_id() == self.review_id
+                })
+                .ok_or(CreateCommentCommandError::ParentNotFound(parent_id))?;
+
+            if parent.is_deleted() {
+                return Err(CreateCommentCommandError::DeletedParent(parent_id).into());
+            }
+        }
+
+        let now = ctx.clock().now();
+        let comment = CommentBuilder::default()
+            .id(self.comment_id)
+            .fragment_id(self.fragment_id)
+            .review_id(self.review_id)
+            .parent_id(self.parent_id)
+            .author_id(user)
+            .body(self.body.clone())
+            .created_at(now)
+            .last_modified_at(now)
+            .build()
+            .map_err(anyhow::Error::from)?
+            .save(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save comment: {e}"))?;
+
+        Ok(Some(CommentCreatedEvent {
+            comment_id: *comment.id(),
+            fragment_id: *comment.fragment_id(),
+            review_id: *comment.review_id(),
+            parent_id: *comment.parent_id(),
+            body: comment.body().clone(),
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::CreateComment, Resource::Any).is_ok()
+    }
+}
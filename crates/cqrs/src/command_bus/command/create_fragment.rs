@@ -2,14 +2,18 @@ use super::Command;
 use crate::command_bus::bus::Ctx;
 use crate::command_bus::error::CommandBusError;
 use crate::events::{FragmentCreatedEvent, FragmentCreatedEventBuilder};
+use crate::policy::{authorize, Action, Resource};
 use commons::fragment::Content;
 use commons::{actor::ActorTrait, commands::CommandType, id::Id};
 use derive_builder::Builder;
 use derive_getters::Getters;
 use serde::{Deserialize, Serialize};
 use storage::{
-    model::fragment::{Fragment, FragmentBuilder},
-    query::fragment::QueryFragment,
+    model::{
+        fragment::{Fragment, FragmentBuilder},
+        revision::Revision,
+    },
+    query::{fragment::QueryFragment, revision::QueryRevision},
 };
 use tap::TapFallible;
 
@@ -35,9 +39,10 @@ impl Command for CreateFragmentCommand {
         ctx: &mut Ctx<'ctx>,
     ) -> Result<Option<Self::Event>, CommandBusError> {
         let now = ctx.clock().now();
-        Ok(FragmentBuilder::default()
+        let author = ctx.actor().actor().id().unwrap();
+        let fragment = FragmentBuilder::default()
             .id(self.fragment_id)
-            .author_id(ctx.actor().actor().id().unwrap())
+            .author_id(author)
             .content(self.content.clone())
             .created_at(now)
             .last_modified_at(now)
@@ -47,13 +52,18 @@ impl Command for CreateFragmentCommand {
             .save(ctx.tx().as_mut())
             .await
             .tap_ok(|_| tracing::info!("Fragment created"))
-            .map(Into::into)
-            .map(Some)
-            .tap_err(|e| tracing::error!("Failed to save fragment:{e}"))?)
+            .tap_err(|e| tracing::error!("Failed to save fragment:{e}"))?;
+
+        Revision::of(&fragment, author)
+            .save(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save revision: {e}"))?;
+
+        Ok(Some(fragment.into()))
     }
 
     fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
-        actor.actor().is_user()
+        authorize(actor, Action::CreateFragment, Resource::Any).is_ok()
     }
 }
 
@@ -0,0 +1,184 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::StoryCreatedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use storage::{
+    model::{
+        fragment::Fragment,
+        story::{MaturityRating, Story, StoryBuilder},
+    },
+    query::{fragment::QueryFragment, story::QueryStory},
+};
+use tap::TapFallible;
+
+const MAX_TITLE_LENGTH: usize = 200;
+const MAX_GENRES: usize = 5;
+
+/// Creates the story of a root fragment. A root fragment has at most one story.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct CreateStoryCommand {
+    story_id: Id,
+    fragment_id: Id,
+    title: String,
+    #[builder(default)]
+    synopsis: Option<String>,
+    #[builder(default)]
+    genres: Vec<String>,
+    language: String,
+    #[builder(default)]
+    maturity: MaturityRating,
+    #[builder(default)]
+    cover_url: Option<String>,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum CreateStoryCommandError {
+    #[error("Fragment not found: {0}")]
+    FragmentNotFound(Id),
+
+    #[error("Fragment already has a story: {0}")]
+    AlreadyExists(Id),
+
+    #[error("{0}")]
+    InvalidStory(&'static str),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for CreateStoryCommand {
+    type Event = StoryCreatedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::CreateStory
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::CreateStory, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let fragment = Fragment::find(ctx.pool(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find fragment: {e:?}"))?
+            .ok_or(CreateStoryCommandError::FragmentNotFound(self.fragment_id))?;
+
+        authorize(
+            ctx.actor(),
+            Action::CreateStory,
+            Resource::Fragment(&fragment),
+        )
+        .map_err(|e| CreateStoryCommandError::Forbidden(e.reason()))?;
+
+        if !fragment.is_root() {
+            return Err(CreateStoryCommandError::InvalidStory(
+                "Stories can only start from a root fragment",
+            )
+            .into());
+        }
+
+        let existing = Story::find_by_fragment(ctx.pool(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find story: {e:?}"))?;
+        if existing.is_some() {
+            return Err(CreateStoryCommandError::AlreadyExists(self.fragment_id).into());
+        }
+
+        let now = ctx.clock().now();
+        let story = StoryBuilder::default()
+            .id(self.story_id)
+            .fragment_id(self.fragment_id)
+            .author_id(*fragment.author_id())
+            .title(title(&self.title).map_err(CreateStoryCommandError::InvalidStory)?)
+            .synopsis(optional(&self.synopsis))
+            .genres(genres(&self.genres).map_err(CreateStoryCommandError::InvalidStory)?)
+            .language(language(&self.language).map_err(CreateStoryCommandError::InvalidStory)?)
+            .maturity(self.maturity)
+            .cover_url(
+                optional(&self.cover_url)
+                    .map(|value| cover_url(&value))
+                    .transpose()
+                    .map_err(CreateStoryCommandError::InvalidStory)?,
+            )
+            .created_at(now)
+            .last_modified_at(now)
+            .build()
+            .map_err(anyhow::Error::from)?
+            .save(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save story: {e:?}"))?;
+
+        Ok(Some(StoryCreatedEvent {
+            story_id: *story.id(),
+            fragment_id: *story.fragment_id(),
+            title: story.title().clone(),
+            genres: story.genres().clone(),
+            language: story.language().clone(),
+            maturity: *story.maturity(),
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
+
+pub(super) fn title(value: &str) -> Result<String, &'static str> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err("Story title can not be empty");
+    }
+    if value.chars().count() > MAX_TITLE_LENGTH {
+        return Err("Story title is too long");
+    }
+    Ok(value.to_owned())
+}
+
+/// Genres are compared lowercase, without duplicates.
+pub(super) fn genres(values: &[String]) -> Result<Vec<String>, &'static str> {
+    let mut genres: Vec<String> = Vec::with_capacity(values.len());
+    for genre in values.iter().map(|g| g.trim().to_lowercase()) {
+        if genre.is_empty() {
+            return Err("Story genres can not be empty");
+        }
+        if !genres.contains(&genre) {
+            genres.push(genre);
+        }
+    }
+    if genres.len() > MAX_GENRES {
+        return Err("Stories can have at most 5 genres");
+    }
+    Ok(genres)
+}
+
+/// Expects an ISO 639-1 code.
+pub(super) fn language(value: &str) -> Result<String, &'static str> {
+    let value = value.trim().to_lowercase();
+    if value.len() != 2 || !value.chars().all(|c| c.is_ascii_lowercase()) {
+        return Err("Story language must be a two letter ISO 639-1 code");
+    }
+    Ok(value)
+}
+
+/// Expects an absolute http or https URL.
+pub(super) fn cover_url(value: &str) -> Result<String, &'static str> {
+    let value = value.trim();
+    match url::Url::parse(value) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => Ok(value.to_owned()),
+        _ => Err("Story cover must be an http or https URL"),
+    }
+}
+
+/// Blank values are treated as absent.
+fn optional(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_owned)
+}
@@ -0,0 +1,135 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::WebhookCreatedEvent;
+use crate::policy::{authorize, Action, Resource};
+use crate::webhooks::check_endpoint;
+use commons::{
+    actor::ActorTrait, auth::WebhookSecret, commands::CommandType, events::EventType, id::Id,
+};
+use storage::{
+    model::{story::Story, webhook::WebhookBuilder},
+    query::{story::QueryStory, webhook::QueryWebhook},
+};
+use tap::TapFallible;
+use url::Url;
+
+const MIN_SECRET_LENGTH: usize = 16;
+
+/// Subscribes an HTTP endpoint to events, of a single story or of every story.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct CreateWebhookCommand {
+    webhook_id: Id,
+    url: String,
+    secret: WebhookSecret,
+    /// Event types to receive, all of them when empty.
+    #[builder(default)]
+    event_types: Vec<EventType>,
+    #[builder(default)]
+    story_id: Option<Id>,
+    /// Hosts trusted even though they resolve to internal addresses.
+    #[builder(default)]
+    #[serde(default)]
+    allowed_hosts: Vec<String>,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum CreateWebhookCommandError {
+    #[error("Story not found: {0}")]
+    StoryNotFound(Id),
+
+    #[error("{0}")]
+    InvalidWebhook(&'static str),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for CreateWebhookCommand {
+    type Event = WebhookCreatedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::CreateWebhook
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::ManageWebhooks, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        match self.story_id {
+            Some(story_id) => {
+                let story = Story::find(ctx.pool(), &story_id)
+                    .await
+                    .tap_err(|e| tracing::error!("Failed to find story: {e:?}"))?
+                    .ok_or(CreateWebhookCommandError::StoryNotFound(story_id))?;
+                authorize(ctx.actor(), Action::ManageWebhooks, Resource::Story(&story))
+            }
+            None => authorize(ctx.actor(), Action::ManageGlobalWebhooks, Resource::Any),
+        }
+        .map_err(|e| CreateWebhookCommandError::Forbidden(e.reason()))?;
+
+        let owner_id = ctx
+            .actor()
+            .id()
+            .ok_or(CreateWebhookCommandError::Forbidden(
+                "Only users can own webhooks",
+            ))?;
+        check_endpoint(&self.url, &self.allowed_hosts)
+            .await
+            .map_err(CreateWebhookCommandError::InvalidWebhook)?;
+        let url = url(&self.url).map_err(CreateWebhookCommandError::InvalidWebhook)?;
+        if self.secret.len() < MIN_SECRET_LENGTH {
+            return Err(CreateWebhookCommandError::InvalidWebhook(
+                "Webhook secret must be at least 16 characters long",
+            )
+            .into());
+        }
+        let mut event_types: Vec<EventType> = Vec::with_capacity(self.event_types.len());
+        for event_type in &self.event_types {
+            if !event_types.contains(event_type) {
+                event_types.push(*event_type);
+            }
+        }
+
+        let now = ctx.clock().now();
+        let webhook = WebhookBuilder::default()
+            .id(self.webhook_id)
+            .owner_id(owner_id)
+            .url(url)
+            .secret(self.secret.clone())
+            .event_types(event_types)
+            .story_id(self.story_id)
+            .created_at(now)
+            .updated_at(now)
+            .build()
+            .map_err(anyhow::Error::from)?
+            .save(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save webhook: {e:?}"))?;
+
+        Ok(Some(WebhookCreatedEvent {
+            webhook_id: *webhook.id(),
+            owner_id,
+            url: webhook.url().clone(),
+            event_types: webhook.event_types().clone(),
+            story_id: *webhook.story_id(),
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
+
+/// Only absolute HTTP(S) URLs can be called.
+fn url(value: &str) -> Result<String, &'static str> {
+    let url = Url::parse(value.trim()).map_err(|_| "Webhook URL is not valid")?;
+    if !matches!(url.scheme(), "http" | "https") || url.host().is_none() {
+        return Err("Webhook URL must be an HTTP or HTTPS URL");
+    }
+    Ok(url.to_string())
+}
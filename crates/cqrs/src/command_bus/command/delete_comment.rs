@@ -0,0 +1,72 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::CommentDeletedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use derive_getters::Getters;
+use storage::{model::comment::Comment, query::comment::QueryComment};
+use tap::TapFallible;
+
+/// Soft deletes a comment. Its replies are kept in the thread.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize, Getters)]
+#[builder(setter(into))]
+pub struct DeleteCommentCommand {
+    /// Fragment the comment was made on.
+    fragment_id: Id,
+    comment_id: Id,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum DeleteCommentCommandError {
+    #[error("Comment not found: {0}")]
+    CommentNotFound(Id),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for DeleteCommentCommand {
+    type Event = CommentDeletedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::DeleteComment
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let comment = Comment::find(ctx.pool(), &self.comment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find comment [{}]: {e}", self.comment_id))?
+            .filter(|comment| comment.fragment_id() == &self.fragment_id)
+            .filter(|comment| !comment.is_deleted())
+            .ok_or(DeleteCommentCommandError::CommentNotFound(self.comment_id))?;
+
+        authorize(
+            ctx.actor(),
+            Action::DeleteComment,
+            Resource::Comment(&comment),
+        )
+        .map_err(|e| DeleteCommentCommandError::Forbidden(e.reason()))?;
+
+        let now = ctx.clock().now();
+        comment
+            .set_deleted_at(now)
+            .update(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to delete comment [{}]: {e}", self.comment_id))?;
+
+        Ok(Some(CommentDeletedEvent {
+            comment_id: self.comment_id,
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::DeleteComment, Resource::Any).is_ok()
+    }
+}
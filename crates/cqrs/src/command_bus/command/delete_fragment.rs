@@ -1 +1,76 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::FragmentDeletedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use storage::{model::fragment::Fragment, query::fragment::QueryFragment};
+use tap::TapFallible;
 
+/// Soft deletes a fragment.
+///
+/// The fragment and its whole subtree (forks of it and their forks) become invisible,
+/// together with their likes and reviews. Nothing is reparented: restoring the fragment
+/// brings the subtree back untouched. Hard deletion is done by `PurgeFragmentsCommand`
+/// once the retention period is over.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct DeleteFragmentCommand {
+    fragment_id: Id,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum DeleteFragmentCommandError {
+    #[error("Fragment not found: {0}")]
+    FragmentNotFound(Id),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for DeleteFragmentCommand {
+    type Event = FragmentDeletedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::DeleteFragment
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let fragment = Fragment::find(ctx.pool(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find fragment [{}]: {e}", self.fragment_id))?
+            .ok_or(DeleteFragmentCommandError::FragmentNotFound(
+                self.fragment_id,
+            ))?;
+
+        authorize(
+            ctx.actor(),
+            Action::DeleteFragment,
+            Resource::Fragment(&fragment),
+        )
+        .map_err(|e| DeleteFragmentCommandError::Forbidden(e.reason()))?;
+
+        let now = ctx.clock().now();
+        fragment
+            .set_deleted_at(Some(now))
+            .update(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| {
+                tracing::error!("Failed to delete fragment [{}]: {e}", self.fragment_id)
+            })?;
+
+        Ok(Some(FragmentDeletedEvent {
+            fragment_id: self.fragment_id,
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::DeleteFragment, Resource::Any).is_ok()
+    }
+}
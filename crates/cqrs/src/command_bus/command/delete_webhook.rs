@@ -0,0 +1,65 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::WebhookDeletedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use storage::{model::webhook::Webhook, query::webhook::QueryWebhook};
+use tap::TapFallible;
+
+/// Deletes a webhook along with its delivery history.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct DeleteWebhookCommand {
+    webhook_id: Id,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum DeleteWebhookCommandError {
+    #[error("Webhook not found: {0}")]
+    WebhookNotFound(Id),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for DeleteWebhookCommand {
+    type Event = WebhookDeletedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::DeleteWebhook
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::ManageWebhooks, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let webhook = Webhook::find(ctx.pool(), &self.webhook_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find webhook: {e:?}"))?
+            .ok_or(DeleteWebhookCommandError::WebhookNotFound(self.webhook_id))?;
+
+        authorize(
+            ctx.actor(),
+            Action::ManageWebhooks,
+            Resource::User(*webhook.owner_id()),
+        )
+        .map_err(|e| DeleteWebhookCommandError::Forbidden(e.reason()))?;
+
+        webhook
+            .delete(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to delete webhook: {e:?}"))?;
+
+        Ok(Some(WebhookDeletedEvent {
+            webhook_id: self.webhook_id,
+            timestamp: ctx.clock().now(),
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
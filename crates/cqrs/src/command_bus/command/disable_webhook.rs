@@ -0,0 +1,58 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::WebhookDisabledEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use storage::{model::webhook::Webhook, query::webhook::QueryWebhook};
+use tap::TapFallible;
+
+/// Disables a webhook whose endpoint keeps failing, run by the delivery job.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct DisableWebhookCommand {
+    webhook_id: Id,
+}
+
+#[async_trait::async_trait]
+impl Command for DisableWebhookCommand {
+    type Event = WebhookDisabledEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::DisableWebhook
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::DisableWebhook, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let Some(webhook) = Webhook::find(ctx.pool(), &self.webhook_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find webhook: {e:?}"))?
+        else {
+            return Ok(None);
+        };
+        if !*webhook.enabled() {
+            return Ok(None);
+        }
+
+        let now = ctx.clock().now();
+        let webhook = webhook
+            .set_enabled(false)
+            .set_updated_at(now)
+            .update(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to update webhook: {e:?}"))?;
+
+        Ok(Some(WebhookDisabledEvent {
+            webhook_id: self.webhook_id,
+            consecutive_failures: *webhook.consecutive_failures(),
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
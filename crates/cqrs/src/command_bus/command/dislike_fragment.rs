@@ -2,6 +2,7 @@ use super::Command;
 use crate::command_bus::bus::Ctx;
 use crate::command_bus::error::CommandBusError;
 use crate::events::FragmentDislikedEvent;
+use crate::policy::{authorize, Action, Resource};
 use commons::{actor::ActorTrait, commands::CommandType, id::Id};
 use storage::{
     model::{fragment::Fragment, like::Like},
@@ -62,6 +63,6 @@ impl Command for DislikeFragmentCommand {
     }
 
     fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
-        actor.actor().is_user()
+        authorize(actor, Action::DislikeFragment, Resource::Any).is_ok()
     }
 }
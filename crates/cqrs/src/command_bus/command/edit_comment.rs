@@ -0,0 +1,81 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::CommentEditedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id, review};
+use derive_getters::Getters;
+use storage::{model::comment::Comment, query::comment::QueryComment};
+use tap::TapFallible;
+
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize, Getters)]
+#[builder(setter(into))]
+pub struct EditCommentCommand {
+    /// Fragment the comment was made on.
+    fragment_id: Id,
+    comment_id: Id,
+    body: review::Comment,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum EditCommentCommandError {
+    #[error("Comment not found: {0}")]
+    CommentNotFound(Id),
+
+    #[error("Comment cannot be empty")]
+    EmptyComment,
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for EditCommentCommand {
+    type Event = CommentEditedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::EditComment
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        if self.body.as_ref().trim().is_empty() {
+            return Err(EditCommentCommandError::EmptyComment.into());
+        }
+
+        let comment = Comment::find(ctx.pool(), &self.comment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find comment [{}]: {e}", self.comment_id))?
+            .filter(|comment| comment.fragment_id() == &self.fragment_id)
+            .filter(|comment| !comment.is_deleted())
+            .ok_or(EditCommentCommandError::CommentNotFound(self.comment_id))?;
+
+        authorize(
+            ctx.actor(),
+            Action::EditComment,
+            Resource::Comment(&comment),
+        )
+        .map_err(|e| EditCommentCommandError::Forbidden(e.reason()))?;
+
+        let now = ctx.clock().now();
+        let comment = comment
+            .set_body(self.body.clone())
+            .set_last_modified_at(now)
+            .update(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to update comment [{}]: {e}", self.comment_id))?;
+
+        Ok(Some(CommentEditedEvent {
+            comment_id: self.comment_id,
+            body: comment.body().clone(),
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::EditComment, Resource::Any).is_ok()
+    }
+}
@@ -0,0 +1,74 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::WebhookEnabledEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use storage::{model::webhook::Webhook, query::webhook::QueryWebhook};
+use tap::TapFallible;
+
+/// Turns a disabled webhook back on. Its pending deliveries resume with a clean failure
+/// count.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct EnableWebhookCommand {
+    webhook_id: Id,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum EnableWebhookCommandError {
+    #[error("Webhook not found: {0}")]
+    WebhookNotFound(Id),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for EnableWebhookCommand {
+    type Event = WebhookEnabledEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::EnableWebhook
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::ManageWebhooks, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let webhook = Webhook::find(ctx.pool(), &self.webhook_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find webhook: {e:?}"))?
+            .ok_or(EnableWebhookCommandError::WebhookNotFound(self.webhook_id))?;
+
+        authorize(
+            ctx.actor(),
+            Action::ManageWebhooks,
+            Resource::User(*webhook.owner_id()),
+        )
+        .map_err(|e| EnableWebhookCommandError::Forbidden(e.reason()))?;
+
+        if *webhook.enabled() {
+            return Ok(None);
+        }
+
+        let now = ctx.clock().now();
+        webhook
+            .set_enabled(true)
+            .set_consecutive_failures(0)
+            .set_updated_at(now)
+            .update(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to update webhook: {e:?}"))?;
+
+        Ok(Some(WebhookEnabledEvent {
+            webhook_id: self.webhook_id,
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
@@ -0,0 +1,150 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::ForkExpiredEvent;
+use crate::policy::{authorize, lifecycle_role, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use storage::{
+    model::{
+        fragment::{Fragment, Transition},
+        maintainer::Maintainer,
+        review::Review,
+        review_sla::{ReviewSla, SlaOutcome},
+        state_transition::StateTransition,
+    },
+    query::{
+        fragment::QueryFragment, maintainer::QueryMaintainer, review::QueryReview,
+        review_sla::QueryReviewSla, state_transition::QueryStateTransition,
+    },
+};
+use tap::TapFallible;
+
+/// Applies the review SLA of its story to a submitted fork nobody reviewed in time.
+///
+/// Scheduled by the system at submission. It is a no-op when the fork was reviewed,
+/// withdrawn or resubmitted since, or when the story SLA was removed or extended.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct ExpireForkCommand {
+    pub fragment_id: Id,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum ExpireForkCommandError {
+    #[error("Fork not found: {0}")]
+    ForkNotFound(Id),
+}
+
+#[async_trait::async_trait]
+impl Command for ExpireForkCommand {
+    type Event = ForkExpiredEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::ExpireFork
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::ExpireFork, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let fork = Fragment::find(ctx.pool(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find fork [{}]: {e}", self.fragment_id))?
+            .filter(Fragment::is_fork)
+            .ok_or(ExpireForkCommandError::ForkNotFound(self.fragment_id))?;
+        if !fork.is_submitted() {
+            return Ok(None);
+        }
+
+        let root_id = fork.root_id();
+        let Some(sla) = ReviewSla::find(ctx.pool(), &root_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find review SLA: {e}"))?
+        else {
+            return Ok(None);
+        };
+
+        let submitted_at = StateTransition::last_submitted_at(ctx.pool(), fork.id())
+            .await
+            .tap_err(|e| tracing::error!("Failed to find submission: {e}"))?
+            .unwrap_or(*fork.last_modified_at());
+        let now = ctx.clock().now();
+        if now < sla.deadline(submitted_at) {
+            return Ok(None);
+        }
+
+        let reviewed = !Review::find_current_round(ctx.pool(), fork.id())
+            .await
+            .tap_err(|e| tracing::error!("Failed to find reviews: {e}"))?
+            .is_empty();
+        if reviewed {
+            return Ok(None);
+        }
+
+        let author_id = *fork.author_id();
+        let transition = match sla.outcome() {
+            SlaOutcome::Reject => Some(Transition::Reject),
+            SlaOutcome::ReturnToDraft => Some(Transition::Withdraw),
+            SlaOutcome::Escalate => None,
+        };
+        let maintainers = match transition {
+            Some(transition) => {
+                let role = lifecycle_role(ctx.actor(), &fork);
+                let (fork, transition) = fork
+                    .transition(transition, ctx.actor().actor(), role, now)
+                    .map_err(anyhow::Error::from)?;
+                fork.update(ctx.tx().as_mut())
+                    .await
+                    .tap_err(|e| tracing::error!("Failed to update fork: {e}"))?;
+                transition
+                    .save(ctx.tx().as_mut())
+                    .await
+                    .tap_err(|e| tracing::error!("Failed to save transition: {e}"))?;
+                Vec::new()
+            }
+            None => Maintainer::find_by_root(ctx.pool(), &root_id)
+                .await
+                .tap_err(|e| tracing::error!("Failed to find maintainers: {e}"))?
+                .into_iter()
+                .filter(Maintainer::is_accepted)
+                .map(|m| *m.user_id())
+                .collect(),
+        };
+
+        Ok(Some(ForkExpiredEvent {
+            fragment_id: self.fragment_id,
+            author_id,
+            outcome: *sla.outcome(),
+            maintainers,
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
+
+/// Schedules the expiry of a fork just submitted for review, when its story has a review SLA.
+pub(crate) async fn schedule_expiry(
+    ctx: &mut Ctx<'_>,
+    fork: &Fragment,
+) -> Result<(), CommandBusError> {
+    let Some(sla) = ReviewSla::find(ctx.tx().as_mut(), &fork.root_id())
+        .await
+        .tap_err(|e| tracing::error!("Failed to find review SLA: {e}"))?
+    else {
+        return Ok(());
+    };
+
+    let command = ExpireForkCommandBuilder::default()
+        .fragment_id(*fork.id())
+        .build()
+        .map_err(anyhow::Error::from)?;
+    let deadline = sla.deadline(ctx.clock().now());
+    ctx.schedule(command, deadline)
+        .await
+        .tap_err(|e| tracing::error!("Failed to schedule fork expiry: {e}"))?;
+    Ok(())
+}
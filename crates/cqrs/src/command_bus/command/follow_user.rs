@@ -2,6 +2,7 @@ use super::Command;
 use crate::command_bus::bus::Ctx;
 use crate::command_bus::error::CommandBusError;
 use crate::events::UserFollowedEvent;
+use crate::policy::{authorize, Action, Resource};
 use commons::{commands::CommandType, id::Id};
 use storage::{
     model::follow::{Follow, FollowBuilder},
@@ -39,6 +40,7 @@ impl Command for FollowUserCommand {
         Ok(FollowBuilder::default()
             .follower_id(user)
             .following_id(self.following_user_id)
+            .created_at(ctx.clock().now())
             .build()
             .map_err(anyhow::Error::from)?
             .save(ctx.tx().as_mut())
@@ -48,7 +50,7 @@ impl Command for FollowUserCommand {
     }
 
     fn supports<A: commons::actor::ActorTrait>(&self, actor: &A) -> bool {
-        actor.actor().is_user()
+        authorize(actor, Action::FollowUser, Resource::Any).is_ok()
     }
 }
 
@@ -2,12 +2,15 @@ use super::Command;
 use crate::command_bus::bus::Ctx;
 use crate::command_bus::error::CommandBusError;
 use crate::events::FragmentForkedEvent;
-use commons::actor::ActorType;
+use crate::policy::{authorize, Action, ForkAudience, Resource};
 use commons::fragment::Content;
 use commons::{actor::ActorTrait, commands::CommandType, id::Id};
-use storage::query::user::QueryUser;
+use storage::model::{follow::Follow, fork_invite::ForkInvite, revision::Revision};
+use storage::query::{
+    follow::QueryFollow, fork_invite::QueryForkInvite, revision::QueryRevision, user::QueryUser,
+};
 use storage::{
-    model::fragment::{Fragment, FragmentBuilder},
+    model::fragment::{ForkPolicy, Fragment, FragmentBuilder},
     query::fragment::QueryFragment,
 };
 use tap::TapFallible;
@@ -52,7 +55,7 @@ impl Command for ForkFragmentCommand {
                 tracing::error!("Failed to find fragment [{}]: {e}", self.parent_fragment_id)
             })?
             .ok_or(ForkFragmentCommandError::ParentFragmentNotFound(
-                self.fork_id,
+                self.parent_fragment_id,
             ))?;
 
         if !parent_frag.is_published() {
@@ -61,30 +64,28 @@ impl Command for ForkFragmentCommand {
             );
         }
 
-        if parent_frag.is_author(user) {
-            return Err(
-                ForkFragmentCommandError::Forbidden("Cannot fork your own fragment").into(),
-            );
-        }
-
         if *parent_frag.end() {
             return Err(ForkFragmentCommandError::Forbidden("Cannot fork an end fragment").into());
         }
 
-        let friend = parent_frag
-            .author(ctx.pool())
-            .await?
-            .is_friend(ctx.pool(), user)
-            .await?;
+        let root = parent_frag
+            .root(ctx.pool())
+            .await
+            .tap_err(|e| tracing::error!("Failed to find root of [{}]: {e}", parent_frag.id()))?;
 
-        if !friend {
-            return Err(
-                ForkFragmentCommandError::Forbidden("You must be fragment author friend").into(),
-            );
-        }
+        authorize(
+            ctx.actor(),
+            Action::ForkFragment,
+            Resource::ForkTarget {
+                parent: &parent_frag,
+                policy: *root.fork_policy(),
+                audience: audience(ctx, &root, user).await?,
+            },
+        )
+        .map_err(|e| ForkFragmentCommandError::Forbidden(e.reason()))?;
 
         let now = ctx.clock().now();
-        Ok(FragmentBuilder::default()
+        let fork = FragmentBuilder::default()
             .id(self.fork_id)
             .author_id(user)
             .content(self.content.clone())
@@ -98,15 +99,51 @@ impl Command for ForkFragmentCommand {
             .map_err(anyhow::Error::from)?
             .save(ctx.tx().as_mut())
             .await
-            .map(|f| Some(f.into()))
-            .tap_err(|e| tracing::error!("Failed to save fragment: {e}"))?)
+            .tap_err(|e| tracing::error!("Failed to save fragment: {e}"))?;
+
+        Revision::of(&fork, user)
+            .save(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save revision: {e}"))?;
+
+        Ok(Some(fork.into()))
     }
 
     fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
-        ActorType::User == actor.actor_type()
+        authorize(actor, Action::ForkFragment, Resource::Any).is_ok()
     }
 }
 
+/// Loads only the relationship the story fork policy depends on.
+async fn audience(
+    ctx: &Ctx<'_>,
+    root: &Fragment,
+    user: Id,
+) -> Result<ForkAudience, CommandBusError> {
+    let author = root.author_id();
+    Ok(match root.fork_policy() {
+        ForkPolicy::Followers => ForkAudience {
+            follows_author: Follow::find(ctx.pool(), &user, author).await?.is_some(),
+            ..Default::default()
+        },
+        ForkPolicy::MutualFriends => ForkAudience {
+            is_friend: root
+                .author(ctx.pool())
+                .await?
+                .is_friend(ctx.pool(), user)
+                .await?,
+            ..Default::default()
+        },
+        ForkPolicy::InviteOnly => ForkAudience {
+            is_invited: ForkInvite::find(ctx.pool(), root.id(), &user)
+                .await?
+                .is_some(),
+            ..Default::default()
+        },
+        ForkPolicy::Anyone | ForkPolicy::Locked => ForkAudience::default(),
+    })
+}
+
 impl From<Fragment> for FragmentForkedEvent {
     fn from(value: Fragment) -> Self {
         Self {
@@ -0,0 +1,118 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::MaintainerInvitedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use storage::{
+    model::{
+        fragment::Fragment,
+        maintainer::{Maintainer, MaintainerBuilder},
+        user::User,
+    },
+    query::{fragment::QueryFragment, maintainer::QueryMaintainer, user::QueryUser},
+};
+use tap::TapFallible;
+
+/// Invites a user to co-maintain the story rooted at `root_id`. Once accepted, the
+/// maintainer can review forks anywhere in the story tree, except their own.
+///
+/// A declined invitation can be sent again.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct InviteMaintainerCommand {
+    pub root_id: Id,
+    pub user_id: Id,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum InviteMaintainerCommandError {
+    #[error("Fragment not found: {0}")]
+    FragmentNotFound(Id),
+
+    #[error("User not found: {0}")]
+    UserNotFound(Id),
+
+    #[error("{0}")]
+    InvalidInvitation(&'static str),
+
+    #[error("User already invited: {0}")]
+    AlreadyInvited(Id),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for InviteMaintainerCommand {
+    type Event = MaintainerInvitedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::InviteMaintainer
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::InviteMaintainer, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let root = Fragment::find(ctx.pool(), &self.root_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find fragment: {e:?}"))?
+            .ok_or(InviteMaintainerCommandError::FragmentNotFound(self.root_id))?;
+
+        authorize(
+            ctx.actor(),
+            Action::InviteMaintainer,
+            Resource::Fragment(&root),
+        )
+        .map_err(|e| InviteMaintainerCommandError::Forbidden(e.reason()))?;
+
+        if root.is_fork() {
+            return Err(InviteMaintainerCommandError::InvalidInvitation(
+                "Maintainers can only be invited on a story root",
+            )
+            .into());
+        }
+        if root.is_author(self.user_id) {
+            return Err(InviteMaintainerCommandError::InvalidInvitation(
+                "The story author already maintains it",
+            )
+            .into());
+        }
+
+        User::find(ctx.pool(), &self.user_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find user [{}]: {e}", self.user_id))?
+            .ok_or(InviteMaintainerCommandError::UserNotFound(self.user_id))?;
+
+        let existing = Maintainer::find(ctx.pool(), &self.root_id, &self.user_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find maintainer: {e:?}"))?;
+        if existing.is_some_and(|m| m.is_invited() || m.is_accepted()) {
+            return Err(InviteMaintainerCommandError::AlreadyInvited(self.user_id).into());
+        }
+
+        let now = ctx.clock().now();
+        MaintainerBuilder::default()
+            .root_id(self.root_id)
+            .user_id(self.user_id)
+            .invited_by(ctx.actor().id().unwrap())
+            .invited_at(now)
+            .build()
+            .map_err(anyhow::Error::from)?
+            .save(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save maintainer: {e:?}"))?;
+
+        Ok(Some(MaintainerInvitedEvent {
+            root_id: self.root_id,
+            user_id: self.user_id,
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
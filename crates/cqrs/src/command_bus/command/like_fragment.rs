@@ -2,6 +2,7 @@ use super::Command;
 use crate::command_bus::bus::Ctx;
 use crate::command_bus::error::CommandBusError;
 use crate::events::FragmentLikedEvent;
+use crate::policy::{authorize, Action, Resource};
 use commons::{commands::CommandType, id::Id};
 use storage::{
     model::{
@@ -68,7 +69,7 @@ impl Command for LikeFragmentCommand {
     }
 
     fn supports<A: commons::actor::ActorTrait>(&self, actor: &A) -> bool {
-        actor.actor().is_user()
+        authorize(actor, Action::LikeFragment, Resource::Any).is_ok()
     }
 }
 
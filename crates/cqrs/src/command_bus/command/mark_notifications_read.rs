@@ -0,0 +1,78 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::NotificationsReadEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use storage::{model::notification::Notification, query::notification::QueryNotification};
+use tap::TapFallible;
+
+/// Marks a notification of the user as read, or the whole inbox when `notification_id` is
+/// missing. Nothing is recorded when everything was already read.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct MarkNotificationsReadCommand {
+    pub user_id: Id,
+    #[builder(default)]
+    pub notification_id: Option<Id>,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum MarkNotificationsReadCommandError {
+    #[error("Notification not found: {0}")]
+    NotificationNotFound(Id),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for MarkNotificationsReadCommand {
+    type Event = NotificationsReadEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::MarkNotificationsRead
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::ReadNotifications, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        authorize(
+            ctx.actor(),
+            Action::ReadNotifications,
+            Resource::User(self.user_id),
+        )
+        .map_err(|e| MarkNotificationsReadCommandError::Forbidden(e.reason()))?;
+
+        if let Some(id) = self.notification_id {
+            Notification::find(ctx.pool(), &id)
+                .await
+                .tap_err(|e| tracing::error!("Failed to find notification [{id}]: {e}"))?
+                .filter(|n| *n.user_id() == self.user_id)
+                .ok_or(MarkNotificationsReadCommandError::NotificationNotFound(id))?;
+        }
+
+        let now = ctx.clock().now();
+        let count = Notification::mark_read(
+            ctx.tx().as_mut(),
+            &self.user_id,
+            self.notification_id.as_ref(),
+            &now,
+        )
+        .await
+        .tap_err(|e| tracing::error!("Failed to mark notifications as read: {e}"))?;
+
+        Ok((count > 0).then(|| NotificationsReadEvent {
+            user_id: self.user_id,
+            notification_id: self.notification_id,
+            count,
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
@@ -0,0 +1,73 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::CommentModeratedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use derive_getters::Getters;
+use storage::{model::comment::Comment, query::comment::QueryComment};
+use tap::TapFallible;
+
+/// Hides a comment from the thread, or shows it again. Only moderators can do it.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize, Getters)]
+#[builder(setter(into))]
+pub struct ModerateCommentCommand {
+    /// Fragment the comment was made on.
+    fragment_id: Id,
+    comment_id: Id,
+    hidden: bool,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum ModerateCommentCommandError {
+    #[error("Comment not found: {0}")]
+    CommentNotFound(Id),
+}
+
+#[async_trait::async_trait]
+impl Command for ModerateCommentCommand {
+    type Event = CommentModeratedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::ModerateComment
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let comment = Comment::find(ctx.pool(), &self.comment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find comment [{}]: {e}", self.comment_id))?
+            .filter(|comment| comment.fragment_id() == &self.fragment_id)
+            .ok_or(ModerateCommentCommandError::CommentNotFound(
+                self.comment_id,
+            ))?;
+
+        let now = ctx.clock().now();
+        let (hidden_at, hidden_by) = if self.hidden {
+            (Some(now), ctx.actor().id())
+        } else {
+            (None, None)
+        };
+        comment
+            .set_hidden_at(hidden_at)
+            .set_hidden_by(hidden_by)
+            .update(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| {
+                tracing::error!("Failed to moderate comment [{}]: {e}", self.comment_id)
+            })?;
+
+        Ok(Some(CommentModeratedEvent {
+            comment_id: self.comment_id,
+            hidden: self.hidden,
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::ModerateComment, Resource::Any).is_ok()
+    }
+}
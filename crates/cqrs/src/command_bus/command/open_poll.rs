@@ -0,0 +1,128 @@
+use super::close_poll::ClosePollCommandBuilder;
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::PollOpenedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id, time::DateTime};
+use storage::{
+    model::{
+        fragment::Fragment,
+        poll::{Poll, PollBuilder},
+    },
+    query::{fragment::QueryFragment, poll::QueryPoll},
+};
+use tap::TapFallible;
+
+const MIN_CHOICES: usize = 2;
+
+/// Lets readers vote for the canonical branch of a fragment among its published children.
+/// The poll is closed by a `ClosePollCommand` scheduled along with it at `closes_at`.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct OpenPollCommand {
+    poll_id: Id,
+    fragment_id: Id,
+    closes_at: DateTime,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum OpenPollCommandError {
+    #[error("Fragment not found: {0}")]
+    FragmentNotFound(Id),
+
+    #[error("A poll is already open: {0}")]
+    AlreadyOpen(Id),
+
+    #[error("{0}")]
+    InvalidPoll(&'static str),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for OpenPollCommand {
+    type Event = PollOpenedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::OpenPoll
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::OpenPoll, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let fragment = Fragment::find(ctx.pool(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find fragment: {e:?}"))?
+            .ok_or(OpenPollCommandError::FragmentNotFound(self.fragment_id))?;
+
+        authorize(ctx.actor(), Action::OpenPoll, Resource::Fragment(&fragment))
+            .map_err(|e| OpenPollCommandError::Forbidden(e.reason()))?;
+
+        if !fragment.is_published() {
+            return Err(OpenPollCommandError::InvalidPoll(
+                "Polls can only be opened on published fragments",
+            )
+            .into());
+        }
+        let now = ctx.clock().now();
+        if self.closes_at <= now {
+            return Err(OpenPollCommandError::InvalidPoll("Poll must close in the future").into());
+        }
+
+        let choices = fragment
+            .children(ctx.pool())
+            .await
+            .tap_err(|e| tracing::error!("Failed to find children: {e:?}"))?
+            .iter()
+            .filter(|child| child.is_published())
+            .count();
+        if choices < MIN_CHOICES {
+            return Err(OpenPollCommandError::InvalidPoll(
+                "Polls need at least two published children",
+            )
+            .into());
+        }
+
+        let open = Poll::find_open_by_fragment(ctx.pool(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find open poll: {e:?}"))?;
+        if let Some(open) = open {
+            return Err(OpenPollCommandError::AlreadyOpen(*open.id()).into());
+        }
+
+        let poll = PollBuilder::default()
+            .id(self.poll_id)
+            .fragment_id(self.fragment_id)
+            .created_by(ctx.actor().id().unwrap())
+            .closes_at(self.closes_at)
+            .created_at(now)
+            .build()
+            .map_err(anyhow::Error::from)?
+            .save(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save poll: {e:?}"))?;
+
+        let close = ClosePollCommandBuilder::default()
+            .poll_id(self.poll_id)
+            .build()
+            .map_err(anyhow::Error::from)?;
+        ctx.schedule(close, self.closes_at)
+            .await
+            .tap_err(|e| tracing::error!("Failed to schedule poll closing: {e}"))?;
+
+        Ok(Some(PollOpenedEvent {
+            poll_id: *poll.id(),
+            fragment_id: *poll.fragment_id(),
+            closes_at: *poll.closes_at(),
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
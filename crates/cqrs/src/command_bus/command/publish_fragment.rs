@@ -2,14 +2,21 @@ use super::Command;
 use crate::command_bus::bus::Ctx;
 use crate::command_bus::error::CommandBusError;
 use crate::events::FragmentPublishedEvent;
-use commons::actor::{Actor, ActorType};
+use crate::policy::{authorize, lifecycle_role, Action, Resource};
+use commons::actor::{Actor, ActorTrait};
 use commons::{commands::CommandType, id::Id};
+use sqlx::PgPool;
 use storage::{
-    model::fragment::{Fragment, FragmentState},
-    query::fragment::QueryFragment,
+    model::{
+        fragment::{Fragment, Transition},
+        task::{CommandData, Task},
+    },
+    query::{fragment::QueryFragment, state_transition::QueryStateTransition, task::QueryTask},
 };
 use tap::TapFallible;
 
+/// Publishes a fragment, right away or when dispatched as a scheduled task. A fragment has
+/// at most one pending scheduled publication, cancelled with `CancelPublicationCommand`.
 #[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
 #[builder(setter(into))]
 pub struct PublishFragmentCommand {
@@ -26,6 +33,9 @@ pub enum PublishFragmentCommandError {
 
     #[error("{0}")]
     Forbidden(&'static str),
+
+    #[error("Fragment publication is already scheduled: {0}")]
+    AlreadyScheduled(Id),
 }
 
 #[async_trait::async_trait]
@@ -36,44 +46,92 @@ impl Command for PublishFragmentCommand {
         CommandType::PublishFragment
     }
 
-    fn supports<A: commons::actor::ActorTrait>(&self, actor: &A) -> bool {
-        ActorType::User == actor.actor_type()
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::PublishFragment, Resource::Any).is_ok()
     }
 
     async fn handle<'ctx>(
         &self,
         ctx: &mut Ctx<'ctx>,
     ) -> Result<Option<Self::Event>, CommandBusError> {
-        let user = ctx.actor().id().unwrap();
+        let fragment = self.publishable(ctx.pool(), ctx.actor()).await?;
+
+        let role = lifecycle_role(ctx.actor(), &fragment);
+        // Publishing as a moderator lifts a takedown.
+        let (fragment, transition) = fragment
+            .set_taken_down_at(None)
+            .transition(
+                Transition::Publish,
+                ctx.actor().actor(),
+                role,
+                ctx.clock().now(),
+            )
+            .map_err(anyhow::Error::from)?;
+
+        let fragment = fragment
+            .update(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to update fragment: {e}"))?;
+        transition
+            .save(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save transition: {e}"))?;
+
+        Ok(Some(FragmentPublishedEvent {
+            actor: ctx.actor().actor(),
+            ..fragment.into()
+        }))
+    }
+
+    async fn validate(&self, pool: &PgPool, actor: &dyn ActorTrait) -> Result<(), CommandBusError> {
+        self.publishable(pool, actor).await?;
 
-        let fragment = Fragment::find(ctx.pool(), &self.fragment_id)
+        let scheduled = Task::find_pending(pool, &self.command_type(), &CommandData::from(self))
+            .await
+            .tap_err(|e| tracing::error!("Failed to find scheduled publication: {e:?}"))?;
+        if scheduled.is_some() {
+            return Err(PublishFragmentCommandError::AlreadyScheduled(self.fragment_id).into());
+        }
+
+        Ok(())
+    }
+}
+
+impl PublishFragmentCommand {
+    /// Finds the fragment, checking `actor` can publish it in its current state.
+    async fn publishable(
+        &self,
+        pool: &PgPool,
+        actor: &dyn ActorTrait,
+    ) -> Result<Fragment, CommandBusError> {
+        let fragment = Fragment::find(pool, &self.fragment_id)
             .await
             .tap_err(|e| tracing::error!("Failed to find fragment: {e:?}"))?
             .ok_or(PublishFragmentCommandError::FragmentNotFound(
                 self.fragment_id,
             ))?;
 
-        if !fragment.is_author(user) {
+        authorize(
+            actor,
+            Action::PublishFragment,
+            Resource::Fragment(&fragment),
+        )
+        .map_err(|e| PublishFragmentCommandError::Forbidden(e.reason()))?;
+
+        if fragment.is_taken_down() && !actor.role().is_moderator() {
             return Err(PublishFragmentCommandError::Forbidden(
-                "Only the fragment author can publish it",
+                "Fragment was taken down by a moderator",
             )
             .into());
         }
 
-        if fragment.is_publishable() {
+        if !fragment.can(Transition::Publish) {
             return Err(
                 PublishFragmentCommandError::InvalidState("fragment is not publishable").into(),
             );
         }
 
-        Ok(fragment
-            .set_state(FragmentState::Published)
-            .set_last_modified_at(ctx.clock().now())
-            .update(ctx.tx().as_mut())
-            .await
-            .map(Into::into)
-            .map(Some)
-            .tap_err(|e| tracing::error!("Failed to update fragment: {e}"))?)
+        Ok(fragment)
     }
 }
 
@@ -0,0 +1,59 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::FragmentsPurgedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, time::DateTime};
+use storage::{model::fragment::Fragment, query::fragment::QueryFragment};
+use tap::TapFallible;
+
+/// Hard deletes every fragment soft deleted before `deleted_before`, with its subtree.
+/// Only the system actor runs it, from the periodic purge job.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct PurgeFragmentsCommand {
+    deleted_before: DateTime,
+}
+
+#[async_trait::async_trait]
+impl Command for PurgeFragmentsCommand {
+    type Event = FragmentsPurgedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::PurgeFragments
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let deleted = Fragment::find_deleted_before(ctx.pool(), &self.deleted_before)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find deleted fragments: {e}"))?;
+
+        let mut purged = Vec::new();
+        for fragment in deleted {
+            let id = *fragment.id();
+            purged.extend(
+                fragment
+                    .purge(ctx.tx().as_mut())
+                    .await
+                    .tap_err(|e| tracing::error!("Failed to purge fragment [{id}]: {e}"))?,
+            );
+        }
+
+        if purged.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(FragmentsPurgedEvent {
+            fragment_ids: purged,
+            timestamp: ctx.clock().now(),
+            actor: ctx.actor().actor(),
+        }))
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::PurgeFragments, Resource::Any).is_ok()
+    }
+}
@@ -0,0 +1,58 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::DigestSentEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use storage::{
+    model::notification_preferences::NotificationPreferences,
+    query::notification_preferences::QueryNotificationPreferences,
+};
+use tap::TapFallible;
+
+/// Records that a digest was sent to the user, starting the next digest period.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct RecordDigestCommand {
+    pub user_id: Id,
+    pub count: u64,
+}
+
+#[async_trait::async_trait]
+impl Command for RecordDigestCommand {
+    type Event = DigestSentEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::RecordDigest
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::RecordDigest, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let Some(preferences) = NotificationPreferences::find(ctx.pool(), &self.user_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find notification preferences: {e}"))?
+        else {
+            return Ok(None);
+        };
+
+        let now = ctx.clock().now();
+        preferences
+            .set_last_digest_at(Some(now))
+            .save(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save notification preferences: {e}"))?;
+
+        Ok(Some(DigestSentEvent {
+            user_id: self.user_id,
+            count: self.count,
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
@@ -0,0 +1,126 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::UserRegisteredEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::auth::PasswordHash;
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use derive_getters::Getters;
+use storage::{
+    model::{
+        credential::CredentialBuilder,
+        user::{User, UserBuilder},
+    },
+    query::{credential::QueryCredential, user::QueryUser},
+};
+use tap::TapFallible;
+
+const USERNAME_MIN_LENGTH: usize = 3;
+const USERNAME_MAX_LENGTH: usize = 32;
+const USERNAME_CONSTRAINT: &str = "users_uk_username";
+
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize, Getters)]
+#[builder(setter(into))]
+pub struct RegisterUserCommand {
+    user_id: Id,
+    username: String,
+    password_hash: PasswordHash,
+    #[builder(default)]
+    display_name: Option<String>,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum RegisterUserCommandError {
+    #[error("Invalid username: {0}")]
+    InvalidUsername(String),
+
+    #[error("Username already taken: {0}")]
+    UsernameTaken(String),
+}
+
+#[async_trait::async_trait]
+impl Command for RegisterUserCommand {
+    type Event = UserRegisteredEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::RegisterUser
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let username = normalize_username(&self.username);
+
+        if !is_valid_username(&username) {
+            return Err(RegisterUserCommandError::InvalidUsername(username).into());
+        }
+
+        if User::find_by_username(ctx.pool(), &username)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find user [{username}]: {e}"))?
+            .is_some()
+        {
+            return Err(RegisterUserCommandError::UsernameTaken(username).into());
+        }
+
+        let now = ctx.clock().now();
+        let user = UserBuilder::default()
+            .id(self.user_id)
+            .username(username.clone())
+            .display_name(self.display_name.clone())
+            .created_at(now)
+            .build()
+            .tap_err(|e| tracing::error!("Failed to build user: {e}"))
+            .map_err(anyhow::Error::from)?
+            .save(ctx.tx().as_mut())
+            .await
+            .map_err(|e| {
+                // Another registration may take the username after the check above.
+                if e.violates(USERNAME_CONSTRAINT) {
+                    return RegisterUserCommandError::UsernameTaken(username.clone()).into();
+                }
+                tracing::error!("Failed to save user: {e}");
+                CommandBusError::from(e)
+            })?;
+
+        CredentialBuilder::default()
+            .user_id(self.user_id)
+            .login(username)
+            .password_hash(self.password_hash.clone())
+            .created_at(now)
+            .build()
+            .map_err(anyhow::Error::from)?
+            .save(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save credential: {e}"))?;
+
+        Ok(Some(user.into()))
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::RegisterUser, Resource::User(self.user_id)).is_ok()
+    }
+}
+
+fn normalize_username(username: &str) -> String {
+    username.trim().to_lowercase()
+}
+
+fn is_valid_username(username: &str) -> bool {
+    (USERNAME_MIN_LENGTH..=USERNAME_MAX_LENGTH).contains(&username.len())
+        && username
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+impl From<User> for UserRegisteredEvent {
+    fn from(value: User) -> Self {
+        Self {
+            user_id: *value.id(),
+            username: value.username().clone(),
+            display_name: value.display_name().clone(),
+            timestamp: *value.created_at(),
+        }
+    }
+}
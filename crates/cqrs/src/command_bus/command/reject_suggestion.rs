@@ -0,0 +1,94 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::SuggestionRejectedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use derive_getters::Getters;
+use storage::{
+    model::{
+        fragment::Fragment,
+        suggestion::{Suggestion, SuggestionStatus},
+    },
+    query::{fragment::QueryFragment, suggestion::QuerySuggestion},
+};
+use tap::TapFallible;
+
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize, Getters)]
+#[builder(setter(into))]
+pub struct RejectSuggestionCommand {
+    /// Fork the suggestion was made on.
+    fragment_id: Id,
+    suggestion_id: Id,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum RejectSuggestionCommandError {
+    #[error("Suggestion not found: {0}")]
+    SuggestionNotFound(Id),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+
+    #[error("Suggestion already resolved: {0}")]
+    AlreadyResolved(Id),
+}
+
+#[async_trait::async_trait]
+impl Command for RejectSuggestionCommand {
+    type Event = SuggestionRejectedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::RejectSuggestion
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let suggestion = Suggestion::find(ctx.pool(), &self.suggestion_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find suggestion: {e}"))?
+            .filter(|suggestion| suggestion.fragment_id() == &self.fragment_id)
+            .ok_or(RejectSuggestionCommandError::SuggestionNotFound(
+                self.suggestion_id,
+            ))?;
+        let fragment = Fragment::find(ctx.pool(), suggestion.fragment_id())
+            .await
+            .tap_err(|e| tracing::error!("Failed to find fragment: {e}"))?
+            .ok_or(RejectSuggestionCommandError::SuggestionNotFound(
+                self.suggestion_id,
+            ))?;
+
+        authorize(
+            ctx.actor(),
+            Action::ResolveSuggestion,
+            Resource::Fragment(&fragment),
+        )
+        .map_err(|e| RejectSuggestionCommandError::Forbidden(e.reason()))?;
+
+        if !suggestion.is_pending() {
+            return Err(RejectSuggestionCommandError::AlreadyResolved(self.suggestion_id).into());
+        }
+
+        let now = ctx.clock().now();
+        let suggestion = suggestion
+            .set_status(SuggestionStatus::Rejected)
+            .set_resolved_at(now)
+            .update(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to update suggestion: {e}"))?;
+
+        Ok(Some(SuggestionRejectedEvent {
+            suggestion_id: self.suggestion_id,
+            review_id: *suggestion.review_id(),
+            fragment_id: *suggestion.fragment_id(),
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::ResolveSuggestion, Resource::Any).is_ok()
+    }
+}
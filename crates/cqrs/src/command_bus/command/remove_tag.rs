@@ -0,0 +1,84 @@
+use super::add_tag::Tagged;
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::TagRemovedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id, tag::Tag};
+use storage::{
+    model::tag::{TagTarget, TaggingBuilder},
+    query::tag::QueryTagging,
+};
+use tap::TapFallible;
+
+/// Removes a tag from a fragment or a story. Removing a missing tag is a no-op.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct RemoveTagCommand {
+    target: TagTarget,
+    target_id: Id,
+    tag: String,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum RemoveTagCommandError {
+    #[error("Tag target not found: {0}")]
+    TargetNotFound(Id),
+
+    #[error("{0}")]
+    InvalidTag(String),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for RemoveTagCommand {
+    type Event = TagRemovedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::RemoveTag
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::TagContent, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let tag =
+            Tag::parse(&self.tag).map_err(|e| RemoveTagCommandError::InvalidTag(e.to_string()))?;
+
+        let target = Tagged::find(ctx.pool(), self.target, &self.target_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find tag target: {e:?}"))?
+            .ok_or(RemoveTagCommandError::TargetNotFound(self.target_id))?;
+        authorize(ctx.actor(), Action::TagContent, target.resource())
+            .map_err(|e| RemoveTagCommandError::Forbidden(e.reason()))?;
+
+        let now = ctx.clock().now();
+        let removed = TaggingBuilder::default()
+            .target(self.target)
+            .target_id(self.target_id)
+            .tag(tag.clone())
+            .created_at(now)
+            .build()
+            .map_err(anyhow::Error::from)?
+            .delete(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to delete tag: {e:?}"))?;
+        if !removed {
+            return Ok(None);
+        }
+
+        Ok(Some(TagRemovedEvent {
+            target: self.target,
+            target_id: self.target_id,
+            tag,
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
@@ -0,0 +1,84 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::FragmentRestoredEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use storage::{model::fragment::Fragment, query::fragment::QueryFragment};
+use tap::TapFallible;
+
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct RestoreFragmentCommand {
+    fragment_id: Id,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum RestoreFragmentCommandError {
+    #[error("Fragment not found: {0}")]
+    FragmentNotFound(Id),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+
+    #[error("{0}")]
+    InvalidState(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for RestoreFragmentCommand {
+    type Event = FragmentRestoredEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::RestoreFragment
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let fragment = Fragment::find_with_deleted(ctx.pool(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find fragment [{}]: {e}", self.fragment_id))?
+            .ok_or(RestoreFragmentCommandError::FragmentNotFound(
+                self.fragment_id,
+            ))?;
+
+        authorize(
+            ctx.actor(),
+            Action::RestoreFragment,
+            Resource::Fragment(&fragment),
+        )
+        .map_err(|e| RestoreFragmentCommandError::Forbidden(e.reason()))?;
+
+        if !fragment.is_deleted() {
+            return Err(
+                RestoreFragmentCommandError::InvalidState("Fragment is not deleted").into(),
+            );
+        }
+
+        if fragment.is_fork() && fragment.get_parent(ctx.pool()).await?.is_none() {
+            return Err(
+                RestoreFragmentCommandError::InvalidState("Parent fragment is deleted").into(),
+            );
+        }
+
+        fragment
+            .set_deleted_at(None)
+            .update(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| {
+                tracing::error!("Failed to restore fragment [{}]: {e}", self.fragment_id)
+            })?;
+
+        Ok(Some(FragmentRestoredEvent {
+            fragment_id: self.fragment_id,
+            timestamp: ctx.clock().now(),
+            actor: ctx.actor().actor(),
+        }))
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::RestoreFragment, Resource::Any).is_ok()
+    }
+}
@@ -0,0 +1,134 @@
+use super::expire_fork::schedule_expiry;
+use super::Command;
+use crate::{
+    command_bus::{bus::Ctx, error::CommandBusError},
+    events::ForkResubmittedEvent,
+    policy::{authorize, lifecycle_role, Action, Resource},
+};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id, review};
+use storage::{
+    model::{
+        comment::CommentBuilder,
+        fragment::{Fragment, Transition},
+        review::{Review, ReviewAction},
+    },
+    query::{
+        comment::QueryComment, fragment::QueryFragment, review::QueryReview,
+        state_transition::QueryStateTransition,
+    },
+};
+use tap::TapFallible;
+
+/// Sends a rejected fork back to review. Every rejection allows one more attempt, up to
+/// `max_attempts`. The optional appeal is posted on the thread of the last rejection.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct ResubmitForkCommand {
+    pub fragment_id: Id,
+    #[builder(default)]
+    pub appeal: Option<review::Comment>,
+    pub max_attempts: u32,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ResubmitForkCommandError {
+    #[error("Fork not found: {0}")]
+    ForkNotFound(Id),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+
+    #[error("{0}")]
+    InvalidState(&'static str),
+
+    #[error("Fork was already resubmitted {0} times")]
+    AttemptsExhausted(u32),
+}
+
+#[async_trait::async_trait]
+impl Command for ResubmitForkCommand {
+    type Event = ForkResubmittedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::ResubmitFork
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::ResubmitFork, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let fragment = Fragment::find(ctx.pool(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find fork: {e:?}"))?
+            .filter(Fragment::is_fork)
+            .ok_or(ResubmitForkCommandError::ForkNotFound(self.fragment_id))?;
+
+        authorize(
+            ctx.actor(),
+            Action::ResubmitFork,
+            Resource::Fragment(&fragment),
+        )
+        .map_err(|e| ResubmitForkCommandError::Forbidden(e.reason()))?;
+
+        if !fragment.can(Transition::Resubmit) {
+            return Err(ResubmitForkCommandError::InvalidState(
+                "Only rejected forks can be resubmitted",
+            )
+            .into());
+        }
+
+        let rejections: Vec<Review> = Review::find_by_fragment(ctx.pool(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find reviews: {e:?}"))?
+            .into_iter()
+            .filter(|review| *review.action() == ReviewAction::Reject)
+            .collect();
+        let attempt = u32::try_from(rejections.len()).map_err(anyhow::Error::from)?;
+        if attempt > self.max_attempts {
+            return Err(ResubmitForkCommandError::AttemptsExhausted(self.max_attempts).into());
+        }
+
+        let now = ctx.clock().now();
+        if let (Some(appeal), Some(rejection)) = (&self.appeal, rejections.last()) {
+            CommentBuilder::default()
+                .id(ctx.ids().new_id())
+                .fragment_id(self.fragment_id)
+                .review_id(Some(*rejection.id()))
+                .author_id(*fragment.author_id())
+                .body(appeal.clone())
+                .created_at(now)
+                .last_modified_at(now)
+                .build()
+                .map_err(anyhow::Error::from)?
+                .save(ctx.tx().as_mut())
+                .await
+                .tap_err(|e| tracing::error!("Failed to save appeal: {e:?}"))?;
+        }
+
+        let role = lifecycle_role(ctx.actor(), &fragment);
+        let (fragment, transition) = fragment
+            .transition(Transition::Resubmit, ctx.actor().actor(), role, now)
+            .map_err(anyhow::Error::from)?;
+        let fragment = fragment
+            .update(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save fork: {e:?}"))?;
+        transition
+            .save(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save transition: {e:?}"))?;
+        schedule_expiry(ctx, &fragment).await?;
+
+        Ok(Some(ForkResubmittedEvent {
+            fragment_id: self.fragment_id,
+            attempt,
+            appeal: self.appeal.clone(),
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
@@ -0,0 +1,103 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::FragmentRevertedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use derive_getters::Getters;
+use storage::{
+    model::{fragment::Fragment, revision::Revision},
+    query::{fragment::QueryFragment, revision::QueryRevision},
+};
+use tap::TapFallible;
+
+/// Restores the content of an older revision. The history is kept: the reverted content
+/// is saved as a new revision.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize, Getters)]
+#[builder(setter(into))]
+pub struct RevertFragmentCommand {
+    fragment_id: Id,
+    revision: i32,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum RevertFragmentCommandError {
+    #[error("Fragment not found: {0}")]
+    FragmentNotFound(Id),
+
+    #[error("Revision not found: {0}")]
+    RevisionNotFound(i32),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+
+    #[error("Fragment is not editable")]
+    NonEditableFragment(Id),
+}
+
+#[async_trait::async_trait]
+impl Command for RevertFragmentCommand {
+    type Event = FragmentRevertedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::RevertFragment
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let user = ctx.actor().id().unwrap();
+        // Locked so that concurrent edits save their revisions one after the other.
+        let fragment = Fragment::find_for_update(ctx.tx().as_mut(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find fragment [{}]: {e}", self.fragment_id))?
+            .ok_or(RevertFragmentCommandError::FragmentNotFound(
+                self.fragment_id,
+            ))?;
+
+        authorize(
+            ctx.actor(),
+            Action::RevertFragment,
+            Resource::Fragment(&fragment),
+        )
+        .map_err(|e| RevertFragmentCommandError::Forbidden(e.reason()))?;
+
+        if !fragment.is_editable() {
+            return Err(RevertFragmentCommandError::NonEditableFragment(self.fragment_id).into());
+        }
+
+        let target = Revision::find(ctx.pool(), &self.fragment_id, self.revision)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find revision [{}]: {e}", self.revision))?
+            .ok_or(RevertFragmentCommandError::RevisionNotFound(self.revision))?;
+
+        let now = ctx.clock().now();
+        let fragment = fragment
+            .set_content(target.content().clone())
+            .set_last_modified_at(now)
+            .update(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| {
+                tracing::error!("Failed to update fragment [{}]: {e}", self.fragment_id)
+            })?;
+
+        let revision = Revision::of(&fragment, user)
+            .save(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save revision: {e}"))?;
+
+        Ok(Some(FragmentRevertedEvent {
+            fragment_id: self.fragment_id,
+            reverted_to: self.revision,
+            revision: *revision.number(),
+            content: fragment.content().clone(),
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::RevertFragment, Resource::Any).is_ok()
+    }
+}
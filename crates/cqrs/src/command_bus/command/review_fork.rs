@@ -2,18 +2,29 @@ use super::Command;
 use crate::command_bus::bus::Ctx;
 use crate::command_bus::error::CommandBusError;
 use crate::events::FragmentForkReviewedEvent;
-use commons::actor::{Actor, ActorType};
+use crate::policy::{authorize, lifecycle_role, Action, Resource};
+use commons::actor::Actor;
+use commons::fragment::Content;
 use commons::review::Comment;
 use commons::{commands::CommandType, id::Id};
 use storage::{
     model::{
-        fragment::{Fragment, FragmentState},
+        fragment::{Fragment, Transition},
+        maintainer::Maintainer,
         review::{Review, ReviewAction, ReviewBuilder},
+        review_quorum::ReviewQuorum,
+        suggestion::SuggestionBuilder,
+    },
+    query::{
+        fragment::QueryFragment, maintainer::QueryMaintainer, review::QueryReview,
+        review_quorum::QueryReviewQuorum, state_transition::QueryStateTransition,
+        suggestion::QuerySuggestion,
     },
-    query::{fragment::QueryFragment, review::QueryReview},
 };
 use tap::TapFallible;
 
+/// Records a review of a submitted fork. The fork only changes state once the review
+/// quorum of its story is reached.
 #[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
 #[builder(setter(into))]
 pub struct ReviewForkCommand {
@@ -21,6 +32,17 @@ pub struct ReviewForkCommand {
     pub fragment_id: Id,
     pub action: ReviewAction,
     pub comment: Option<Comment>,
+    /// Only allowed when requesting changes.
+    #[builder(default)]
+    pub suggestions: Vec<SuggestedEdit>,
+}
+
+/// Replacement for the characters `start..end` of the fork content.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct SuggestedEdit {
+    pub start: usize,
+    pub end: usize,
+    pub replacement: Content,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -30,6 +52,12 @@ pub enum ReviewForkCommandError {
 
     #[error("{0}")]
     InvalidState(&'static str),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+
+    #[error("{0}")]
+    InvalidSuggestion(&'static str),
 }
 
 #[async_trait::async_trait]
@@ -41,7 +69,7 @@ impl Command for ReviewForkCommand {
     }
 
     fn supports<A: commons::actor::ActorTrait>(&self, actor: &A) -> bool {
-        ActorType::User == actor.actor_type()
+        authorize(actor, Action::ReviewFork, Resource::Any).is_ok()
     }
 
     async fn handle<'ctx>(
@@ -49,7 +77,9 @@ impl Command for ReviewForkCommand {
         ctx: &mut Ctx<'ctx>,
     ) -> Result<Option<Self::Event>, CommandBusError> {
         let user = ctx.actor().id().unwrap();
-        let frag = Fragment::find(ctx.pool(), &self.fragment_id)
+        // Locked so that concurrent reviews see each other when evaluating the quorum. Reads
+        // go through the transaction from then on, not to wait for a connection while locking.
+        let frag = Fragment::find_for_update(ctx.tx().as_mut(), &self.fragment_id)
             .await?
             .ok_or(ReviewForkCommandError::FragmentNotFound(self.fragment_id))?;
 
@@ -57,22 +87,49 @@ impl Command for ReviewForkCommand {
             return Err(ReviewForkCommandError::InvalidState("Only forks can be reviewed").into());
         }
 
-        if !frag.is_submitted() {
+        if !frag.can(Transition::from(self.action)) {
             return Err(ReviewForkCommandError::InvalidState(
                 "fragment should be in waiting review state to be reviewed",
             )
             .into());
         }
 
-        let parent = frag.get_parent(ctx.pool()).await?.unwrap();
+        let parent = frag.get_parent(ctx.tx().as_mut()).await?.unwrap();
+        let maintainer = Maintainer::is_maintainer(ctx.tx().as_mut(), &frag.root_id(), &user)
+            .await
+            .tap_err(|e| tracing::error!("Failed to check story maintainers: {e}"))?;
 
-        if !parent.is_author(user) {
-            return Err(ReviewForkCommandError::InvalidState(
-                "only the parent author can review this fork",
+        authorize(
+            ctx.actor(),
+            Action::ReviewFork,
+            Resource::Fork {
+                fork: &frag,
+                parent: &parent,
+                maintainer,
+            },
+        )
+        .map_err(|e| ReviewForkCommandError::Forbidden(e.reason()))?;
+
+        if !self.suggestions.is_empty() && self.action != ReviewAction::RequestChanges {
+            return Err(ReviewForkCommandError::InvalidSuggestion(
+                "Suggestions can only be made when requesting changes",
             )
             .into());
         }
 
+        let chars: Vec<char> = frag.content().as_ref().chars().collect();
+        let mut originals = Vec::with_capacity(self.suggestions.len());
+        for edit in &self.suggestions {
+            let original: String = chars
+                .get(edit.start..edit.end)
+                .ok_or(ReviewForkCommandError::InvalidSuggestion(
+                    "Suggestion range is outside of the fork content",
+                ))?
+                .iter()
+                .collect();
+            originals.push(Content::from(original));
+        }
+
         let review = ReviewBuilder::default()
             .id(self.review_id)
             .fragment_id(self.fragment_id)
@@ -86,23 +143,59 @@ impl Command for ReviewForkCommand {
             .await
             .tap_err(|e| tracing::error!("Failed to save review: {e}"))?;
 
-        frag.set_state(FragmentState::from(self.action))
-            .set_last_modified_at(ctx.clock().now())
-            .update(ctx.tx().as_mut())
-            .await?;
+        for (edit, original) in self.suggestions.iter().zip(originals) {
+            SuggestionBuilder::default()
+                .id(ctx.ids().new_id())
+                .review_id(self.review_id)
+                .fragment_id(self.fragment_id)
+                .range_start(i32::try_from(edit.start).map_err(anyhow::Error::from)?)
+                .range_end(i32::try_from(edit.end).map_err(anyhow::Error::from)?)
+                .original(original)
+                .replacement(edit.replacement.clone())
+                .created_at(ctx.clock().now())
+                .build()
+                .map_err(anyhow::Error::from)?
+                .save(ctx.tx().as_mut())
+                .await
+                .tap_err(|e| tracing::error!("Failed to save suggestion: {e}"))?;
+        }
 
-        Ok(Some(review.into()))
-    }
-}
+        let round = Review::find_current_round(ctx.tx().as_mut(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find reviews: {e}"))?;
+        let quorum = ReviewQuorum::find(ctx.tx().as_mut(), &frag.root_id())
+            .await
+            .tap_err(|e| tracing::error!("Failed to find review quorum: {e}"))?
+            .unwrap_or_else(|| ReviewQuorum::single(frag.root_id(), ctx.clock().now()));
 
-impl From<Review> for FragmentForkReviewedEvent {
-    fn from(value: Review) -> Self {
-        Self {
-            fragment_id: *value.fragment_id(),
-            action: *value.action(),
-            comment: value.comment().clone(),
-            timestamp: *value.created_at(),
-            actor: Actor::User(*value.reviewer_id()),
-        }
+        let state = match quorum.evaluate(&round) {
+            Some(outcome) => {
+                let role = lifecycle_role(ctx.actor(), &frag);
+                let (frag, transition) = frag
+                    .transition(
+                        Transition::from(outcome),
+                        ctx.actor().actor(),
+                        role,
+                        ctx.clock().now(),
+                    )
+                    .map_err(anyhow::Error::from)?;
+                let frag = frag.update(ctx.tx().as_mut()).await?;
+                transition
+                    .save(ctx.tx().as_mut())
+                    .await
+                    .tap_err(|e| tracing::error!("Failed to save transition: {e}"))?;
+                *frag.state()
+            }
+            None => *frag.state(),
+        };
+
+        Ok(Some(FragmentForkReviewedEvent {
+            fragment_id: *review.fragment_id(),
+            action: *review.action(),
+            comment: review.comment().clone(),
+            state,
+            timestamp: *review.created_at(),
+            actor: Actor::User(user),
+        }))
     }
 }
@@ -0,0 +1,148 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::CanonicalBranchSetEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use storage::{
+    model::{
+        canonical_branch::{CanonicalBranch, CanonicalBranchBuilder},
+        fragment::Fragment,
+        maintainer::Maintainer,
+        poll::Poll,
+    },
+    query::{
+        canonical_branch::QueryCanonicalBranch, fragment::QueryFragment,
+        maintainer::QueryMaintainer, poll::QueryPoll,
+    },
+};
+use tap::TapFallible;
+
+/// Marks one published child of a fragment as its canonical continuation, or clears the
+/// mark when `child_id` is missing. Decided for the whole tree by the root author or the
+/// story maintainers, unless readers are voting for it in a poll.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct SetCanonicalBranchCommand {
+    fragment_id: Id,
+    #[builder(default)]
+    child_id: Option<Id>,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum SetCanonicalBranchCommandError {
+    #[error("Fragment not found: {0}")]
+    FragmentNotFound(Id),
+
+    #[error("{0}")]
+    InvalidBranch(&'static str),
+
+    #[error("Canonical branch is being voted in poll: {0}")]
+    PollInProgress(Id),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for SetCanonicalBranchCommand {
+    type Event = CanonicalBranchSetEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::SetCanonicalBranch
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::SetCanonicalBranch, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let fragment = Fragment::find(ctx.pool(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find fragment: {e:?}"))?
+            .ok_or(SetCanonicalBranchCommandError::FragmentNotFound(
+                self.fragment_id,
+            ))?;
+        let root = fragment
+            .root(ctx.pool())
+            .await
+            .tap_err(|e| tracing::error!("Failed to find root fragment: {e:?}"))?;
+        let maintainer = match ctx.actor().id() {
+            Some(id) => Maintainer::is_maintainer(ctx.pool(), root.id(), &id)
+                .await
+                .tap_err(|e| tracing::error!("Failed to check story maintainers: {e}"))?,
+            None => false,
+        };
+
+        authorize(
+            ctx.actor(),
+            Action::SetCanonicalBranch,
+            Resource::Root {
+                root: &root,
+                maintainer,
+            },
+        )
+        .map_err(|e| SetCanonicalBranchCommandError::Forbidden(e.reason()))?;
+
+        let poll = Poll::find_open_by_fragment(ctx.pool(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find open poll: {e:?}"))?;
+        if let Some(poll) = poll {
+            return Err(SetCanonicalBranchCommandError::PollInProgress(*poll.id()).into());
+        }
+
+        if let Some(child_id) = self.child_id {
+            let child = Fragment::find(ctx.pool(), &child_id)
+                .await
+                .tap_err(|e| tracing::error!("Failed to find child fragment: {e:?}"))?
+                .filter(|child| child.parent_id() == &Some(self.fragment_id))
+                .ok_or(SetCanonicalBranchCommandError::InvalidBranch(
+                    "Canonical branch must be a child of the fragment",
+                ))?;
+            if !child.is_published() {
+                return Err(SetCanonicalBranchCommandError::InvalidBranch(
+                    "Canonical branch must be published",
+                )
+                .into());
+            }
+        }
+
+        let previous = CanonicalBranch::find(ctx.pool(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find canonical branch: {e:?}"))?;
+        let previous_child_id = previous.map(|branch| *branch.child_id());
+        if previous_child_id == self.child_id {
+            return Ok(None);
+        }
+
+        let now = ctx.clock().now();
+        if let Some(child_id) = self.child_id {
+            CanonicalBranchBuilder::default()
+                .fragment_id(self.fragment_id)
+                .child_id(child_id)
+                .chosen_by(ctx.actor().id())
+                .chosen_at(now)
+                .build()
+                .map_err(anyhow::Error::from)?
+                .save(ctx.tx().as_mut())
+                .await
+                .tap_err(|e| tracing::error!("Failed to save canonical branch: {e:?}"))?;
+        } else if let Some(previous) = previous {
+            previous
+                .delete(ctx.tx().as_mut())
+                .await
+                .tap_err(|e| tracing::error!("Failed to delete canonical branch: {e:?}"))?;
+        }
+
+        Ok(Some(CanonicalBranchSetEvent {
+            fragment_id: self.fragment_id,
+            child_id: self.child_id,
+            previous_child_id,
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
@@ -0,0 +1,135 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::ForkPolicyChangedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use derive_getters::Getters;
+use storage::{
+    model::{
+        fork_invite::{ForkInvite, ForkInviteBuilder},
+        fragment::{ForkPolicy, Fragment},
+        user::User,
+    },
+    query::{fork_invite::QueryForkInvite, fragment::QueryFragment, user::QueryUser},
+};
+use tap::TapFallible;
+
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize, Getters)]
+#[builder(setter(into))]
+pub struct SetForkPolicyCommand {
+    fragment_id: Id,
+    policy: ForkPolicy,
+
+    /// Users allowed to fork when the policy is `InviteOnly`. Replaces the current list.
+    #[builder(default)]
+    invitees: Vec<Id>,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum SetForkPolicyCommandError {
+    #[error("Fragment not found: {0}")]
+    FragmentNotFound(Id),
+
+    #[error("User not found: {0}")]
+    UserNotFound(Id),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+
+    #[error("{0}")]
+    InvalidState(&'static str),
+
+    #[error("Invitees are only allowed with the invite only policy")]
+    InviteesNotAllowed,
+}
+
+#[async_trait::async_trait]
+impl Command for SetForkPolicyCommand {
+    type Event = ForkPolicyChangedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::SetForkPolicy
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let fragment = Fragment::find(ctx.pool(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find fragment [{}]: {e}", self.fragment_id))?
+            .ok_or(SetForkPolicyCommandError::FragmentNotFound(
+                self.fragment_id,
+            ))?;
+
+        authorize(
+            ctx.actor(),
+            Action::SetForkPolicy,
+            Resource::Fragment(&fragment),
+        )
+        .map_err(|e| SetForkPolicyCommandError::Forbidden(e.reason()))?;
+
+        if !fragment.is_root() {
+            return Err(SetForkPolicyCommandError::InvalidState(
+                "Fork policy can only be set on the story root",
+            )
+            .into());
+        }
+
+        if self.policy != ForkPolicy::InviteOnly && !self.invitees.is_empty() {
+            return Err(SetForkPolicyCommandError::InviteesNotAllowed.into());
+        }
+
+        let invitees = self.invitees.iter().fold(Vec::new(), |mut acc, id| {
+            if !acc.contains(id) {
+                acc.push(*id);
+            }
+            acc
+        });
+
+        for invitee in &invitees {
+            User::find(ctx.pool(), invitee)
+                .await
+                .tap_err(|e| tracing::error!("Failed to find user [{invitee}]: {e}"))?
+                .ok_or(SetForkPolicyCommandError::UserNotFound(*invitee))?;
+        }
+
+        fragment
+            .set_fork_policy(self.policy)
+            .update(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| {
+                tracing::error!("Failed to update fragment [{}]: {e}", self.fragment_id)
+            })?;
+
+        ForkInvite::delete_all(ctx.tx().as_mut(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to delete fork invites: {e}"))?;
+
+        let now = ctx.clock().now();
+        for invitee in &invitees {
+            ForkInviteBuilder::default()
+                .fragment_id(self.fragment_id)
+                .user_id(*invitee)
+                .created_at(now)
+                .build()
+                .map_err(anyhow::Error::from)?
+                .save(ctx.tx().as_mut())
+                .await
+                .tap_err(|e| tracing::error!("Failed to save fork invite: {e}"))?;
+        }
+
+        Ok(Some(ForkPolicyChangedEvent {
+            fragment_id: self.fragment_id,
+            policy: self.policy,
+            invitees,
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::SetForkPolicy, Resource::Any).is_ok()
+    }
+}
@@ -0,0 +1,95 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::NotificationPreferencesChangedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id, mail::EmailAddress};
+use storage::{
+    model::{
+        notification::NotificationKind,
+        notification_preferences::{DigestFrequency, NotificationPreferences},
+    },
+    query::notification_preferences::QueryNotificationPreferences,
+};
+use tap::TapFallible;
+
+/// Replaces the notification preferences of a user.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct SetNotificationPreferencesCommand {
+    pub user_id: Id,
+    #[builder(default)]
+    pub email: Option<EmailAddress>,
+    #[builder(default)]
+    pub digest: DigestFrequency,
+    #[builder(default)]
+    pub muted_kinds: Vec<NotificationKind>,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum SetNotificationPreferencesCommandError {
+    #[error("{0}")]
+    InvalidPreferences(&'static str),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for SetNotificationPreferencesCommand {
+    type Event = NotificationPreferencesChangedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::SetNotificationPreferences
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::UpdateNotificationPreferences, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        authorize(
+            ctx.actor(),
+            Action::UpdateNotificationPreferences,
+            Resource::User(self.user_id),
+        )
+        .map_err(|e| SetNotificationPreferencesCommandError::Forbidden(e.reason()))?;
+
+        if self.digest != DigestFrequency::Never && self.email.is_none() {
+            return Err(SetNotificationPreferencesCommandError::InvalidPreferences(
+                "An email address is required to receive digests",
+            )
+            .into());
+        }
+
+        let now = ctx.clock().now();
+        let muted_kinds = self.muted_kinds.iter().fold(Vec::new(), |mut kinds, kind| {
+            if !kinds.contains(kind) {
+                kinds.push(*kind);
+            }
+            kinds
+        });
+        NotificationPreferences::find(ctx.pool(), &self.user_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find notification preferences: {e}"))?
+            .unwrap_or_else(|| NotificationPreferences::default_for(self.user_id, now))
+            .set_email(self.email.clone())
+            .set_digest(self.digest)
+            .set_muted_kinds(muted_kinds.clone())
+            .set_updated_at(now)
+            .save(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save notification preferences: {e}"))?;
+
+        Ok(Some(NotificationPreferencesChangedEvent {
+            user_id: self.user_id,
+            digest: self.digest,
+            muted_kinds,
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
@@ -0,0 +1,102 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::ReviewQuorumChangedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use storage::{
+    model::{fragment::Fragment, review_quorum::ReviewQuorumBuilder},
+    query::{fragment::QueryFragment, review_quorum::QueryReviewQuorum},
+};
+use tap::TapFallible;
+
+pub const MAX_APPROVALS: i32 = 10;
+
+/// Sets how many approvals forks of a story need, and whether one rejection vetoes them.
+///
+/// Forks already under review are evaluated against the new quorum on their next review.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct SetReviewQuorumCommand {
+    pub fragment_id: Id,
+    pub approvals: i32,
+    #[builder(default)]
+    pub veto: bool,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum SetReviewQuorumCommandError {
+    #[error("Fragment not found: {0}")]
+    FragmentNotFound(Id),
+
+    #[error("{0}")]
+    InvalidQuorum(&'static str),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for SetReviewQuorumCommand {
+    type Event = ReviewQuorumChangedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::SetReviewQuorum
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::SetReviewQuorum, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let root = Fragment::find(ctx.pool(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find fragment [{}]: {e}", self.fragment_id))?
+            .ok_or(SetReviewQuorumCommandError::FragmentNotFound(
+                self.fragment_id,
+            ))?;
+
+        authorize(
+            ctx.actor(),
+            Action::SetReviewQuorum,
+            Resource::Fragment(&root),
+        )
+        .map_err(|e| SetReviewQuorumCommandError::Forbidden(e.reason()))?;
+
+        if !root.is_root() {
+            return Err(SetReviewQuorumCommandError::InvalidQuorum(
+                "Review quorum can only be set on the story root",
+            )
+            .into());
+        }
+        if !(1..=MAX_APPROVALS).contains(&self.approvals) {
+            return Err(SetReviewQuorumCommandError::InvalidQuorum(
+                "Approvals must be between 1 and 10",
+            )
+            .into());
+        }
+
+        let now = ctx.clock().now();
+        ReviewQuorumBuilder::default()
+            .root_id(self.fragment_id)
+            .approvals(self.approvals)
+            .veto(self.veto)
+            .updated_at(now)
+            .build()
+            .map_err(anyhow::Error::from)?
+            .save(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save review quorum: {e}"))?;
+
+        Ok(Some(ReviewQuorumChangedEvent {
+            fragment_id: self.fragment_id,
+            approvals: self.approvals,
+            veto: self.veto,
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
@@ -0,0 +1,100 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::ReviewSlaChangedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use storage::{
+    model::{
+        fragment::Fragment,
+        review_sla::{ReviewSlaBuilder, SlaOutcome},
+    },
+    query::{fragment::QueryFragment, review_sla::QueryReviewSla},
+};
+use tap::TapFallible;
+
+/// 30 days.
+pub const MAX_WINDOW_HOURS: i32 = 720;
+
+/// Sets how long submitted forks of a story may wait for a review, and what happens to them
+/// once the window is over.
+///
+/// Only forks submitted afterwards are scheduled for expiry.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct SetReviewSlaCommand {
+    pub fragment_id: Id,
+    pub window_hours: i32,
+    pub outcome: SlaOutcome,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum SetReviewSlaCommandError {
+    #[error("Fragment not found: {0}")]
+    FragmentNotFound(Id),
+
+    #[error("{0}")]
+    InvalidSla(&'static str),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for SetReviewSlaCommand {
+    type Event = ReviewSlaChangedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::SetReviewSla
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::SetReviewSla, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let root = Fragment::find(ctx.pool(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find fragment [{}]: {e}", self.fragment_id))?
+            .ok_or(SetReviewSlaCommandError::FragmentNotFound(self.fragment_id))?;
+
+        authorize(ctx.actor(), Action::SetReviewSla, Resource::Fragment(&root))
+            .map_err(|e| SetReviewSlaCommandError::Forbidden(e.reason()))?;
+
+        if !root.is_root() {
+            return Err(SetReviewSlaCommandError::InvalidSla(
+                "Review SLA can only be set on the story root",
+            )
+            .into());
+        }
+        if !(1..=MAX_WINDOW_HOURS).contains(&self.window_hours) {
+            return Err(SetReviewSlaCommandError::InvalidSla(
+                "Review window must be between 1 hour and 30 days",
+            )
+            .into());
+        }
+
+        let now = ctx.clock().now();
+        ReviewSlaBuilder::default()
+            .root_id(self.fragment_id)
+            .window_hours(self.window_hours)
+            .outcome(self.outcome)
+            .updated_at(now)
+            .build()
+            .map_err(anyhow::Error::from)?
+            .save(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save review SLA: {e}"))?;
+
+        Ok(Some(ReviewSlaChangedEvent {
+            fragment_id: self.fragment_id,
+            window_hours: self.window_hours,
+            outcome: self.outcome,
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
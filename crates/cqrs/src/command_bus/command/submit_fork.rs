@@ -1,19 +1,30 @@
+use super::expire_fork::schedule_expiry;
 use super::Command;
 use crate::{
     command_bus::{bus::Ctx, error::CommandBusError},
-    events::ForkSubmittedEvent,
+    events::{ForkSubmittedEvent, FragmentForkReviewedEvent, FragmentPublishedEvent},
+    policy::{authorize, lifecycle_role, Action, Resource},
 };
 use commons::{
-    actor::{Actor, ActorTrait, ActorType},
+    actor::{Actor, ActorTrait},
     commands::CommandType,
     id::Id,
 };
 use storage::{
-    model::fragment::{Fragment, FragmentState},
-    query::fragment::QueryFragment,
+    model::{
+        fragment::{Fragment, LifecycleRole, Transition},
+        review::{ReviewAction, ReviewBuilder},
+        trusted_contributor::TrustedContributor,
+    },
+    query::{
+        fragment::QueryFragment, review::QueryReview, state_transition::QueryStateTransition,
+        trusted_contributor::QueryTrustedContributor,
+    },
 };
 use tap::TapFallible;
 
+/// Submits a fork for review. Forks of contributors trusted by the parent author are
+/// approved by the system right away, and published too if the author asked for it.
 #[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
 #[builder(setter(into))]
 pub struct SubmitForkCommand {
@@ -40,7 +51,7 @@ impl Command for SubmitForkCommand {
     }
 
     fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
-        ActorType::User == actor.actor_type()
+        authorize(actor, Action::SubmitFork, Resource::Any).is_ok()
     }
 
     async fn handle<'ctx>(
@@ -52,24 +63,88 @@ impl Command for SubmitForkCommand {
             .tap_err(|e| tracing::error!("Failed to find fork: {e:?}"))?
             .ok_or(SubmitForkCommandError::ForkNotFound(self.fragment_id))?;
 
-        if !fragment.is_author(ctx.actor().id().unwrap()) {
-            return Err(
-                SubmitForkCommandError::Forbidden("Only the fork author can submit it").into(),
-            );
-        }
+        authorize(
+            ctx.actor(),
+            Action::SubmitFork,
+            Resource::Fragment(&fragment),
+        )
+        .map_err(|e| SubmitForkCommandError::Forbidden(e.reason()))?;
+
+        let trust = match fragment.get_parent(ctx.pool()).await? {
+            Some(parent) => {
+                TrustedContributor::find(ctx.pool(), parent.author_id(), fragment.author_id())
+                    .await
+                    .tap_err(|e| tracing::error!("Failed to find trusted contributor: {e}"))?
+            }
+            None => None,
+        };
+
+        let now = ctx.clock().now();
+        let role = lifecycle_role(ctx.actor(), &fragment);
+        let (mut fragment, transition) = fragment
+            .transition(Transition::Submit, ctx.actor().actor(), role, now)
+            .map_err(|_| SubmitForkCommandError::InvalidState("Fork can not be submitted"))?;
+        let mut transitions = vec![transition];
+
+        if let Some(trust) = trust {
+            ReviewBuilder::default()
+                .id(ctx.ids().new_id())
+                .fragment_id(self.fragment_id)
+                .reviewer_id(None)
+                .comment(None)
+                .created_at(now)
+                .action(ReviewAction::Approve)
+                .build()
+                .map_err(anyhow::Error::from)?
+                .save(ctx.tx().as_mut())
+                .await
+                .tap_err(|e| tracing::error!("Failed to save automatic review: {e}"))?;
 
-        if !fragment.is_submittable() {
-            return Err(SubmitForkCommandError::InvalidState("Fork can not be submitted").into());
+            let mut automatic = vec![Transition::Approve];
+            if *trust.auto_publish() {
+                automatic.push(Transition::Publish);
+            }
+            for next in automatic {
+                let (next_fragment, transition) = fragment
+                    .transition(next, Actor::System, LifecycleRole::System, now)
+                    .map_err(anyhow::Error::from)?;
+                fragment = next_fragment;
+                transitions.push(transition);
+
+                // Announced like the manual review and publication would be.
+                match next {
+                    Transition::Approve => ctx.follow_with(FragmentForkReviewedEvent {
+                        fragment_id: self.fragment_id,
+                        timestamp: now,
+                        comment: None,
+                        action: ReviewAction::Approve,
+                        state: *fragment.state(),
+                        actor: Actor::System,
+                    }),
+                    _ => ctx.follow_with(FragmentPublishedEvent {
+                        fragment_id: self.fragment_id,
+                        timestamp: now,
+                        actor: Actor::System,
+                    }),
+                }
+            }
         }
 
-        Ok(fragment
-            .set_state(FragmentState::Submitted)
-            .set_last_modified_at(ctx.clock().now())
-            .save(ctx.tx().as_mut())
+        let fragment = fragment
+            .update(ctx.tx().as_mut())
             .await
-            .tap_err(|e| tracing::error!("Failed to save fork: {e:?}"))
-            .map(Into::into)
-            .map(Some)?)
+            .tap_err(|e| tracing::error!("Failed to save fork: {e:?}"))?;
+        for transition in transitions {
+            transition
+                .save(ctx.tx().as_mut())
+                .await
+                .tap_err(|e| tracing::error!("Failed to save transition: {e:?}"))?;
+        }
+        if fragment.is_submitted() {
+            schedule_expiry(ctx, &fragment).await?;
+        }
+
+        Ok(Some(fragment.into()))
     }
 }
 
@@ -77,6 +152,7 @@ impl From<Fragment> for ForkSubmittedEvent {
     fn from(value: Fragment) -> Self {
         Self {
             fragment_id: *value.id(),
+            state: *value.state(),
             timestamp: *value.last_modified_at(),
             actor: Actor::User(*value.author_id()),
         }
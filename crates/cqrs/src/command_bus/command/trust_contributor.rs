@@ -0,0 +1,93 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::ContributorTrustedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use storage::{
+    model::{trusted_contributor::TrustedContributorBuilder, user::User},
+    query::{trusted_contributor::QueryTrustedContributor, user::QueryUser},
+};
+use tap::TapFallible;
+
+/// Adds a user to the trusted contributors of `author_id`. Forks they submit under the
+/// author's fragments skip the review step. Trusting a user again updates `auto_publish`.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct TrustContributorCommand {
+    pub author_id: Id,
+    pub contributor_id: Id,
+    #[builder(default)]
+    pub auto_publish: bool,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum TrustContributorCommandError {
+    #[error("User not found: {0}")]
+    UserNotFound(Id),
+
+    #[error("{0}")]
+    InvalidContributor(&'static str),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for TrustContributorCommand {
+    type Event = ContributorTrustedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::TrustContributor
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::ManageTrustedContributors, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        authorize(
+            ctx.actor(),
+            Action::ManageTrustedContributors,
+            Resource::User(self.author_id),
+        )
+        .map_err(|e| TrustContributorCommandError::Forbidden(e.reason()))?;
+
+        if self.author_id == self.contributor_id {
+            return Err(TrustContributorCommandError::InvalidContributor(
+                "Authors can not trust themselves",
+            )
+            .into());
+        }
+
+        User::find(ctx.pool(), &self.contributor_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find user [{}]: {e}", self.contributor_id))?
+            .ok_or(TrustContributorCommandError::UserNotFound(
+                self.contributor_id,
+            ))?;
+
+        let now = ctx.clock().now();
+        TrustedContributorBuilder::default()
+            .author_id(self.author_id)
+            .contributor_id(self.contributor_id)
+            .auto_publish(self.auto_publish)
+            .created_at(now)
+            .build()
+            .map_err(anyhow::Error::from)?
+            .save(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save trusted contributor: {e:?}"))?;
+
+        Ok(Some(ContributorTrustedEvent {
+            author_id: self.author_id,
+            contributor_id: self.contributor_id,
+            auto_publish: self.auto_publish,
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
@@ -2,6 +2,7 @@ use super::Command;
 use crate::command_bus::bus::Ctx;
 use crate::command_bus::error::CommandBusError;
 use crate::events::UserUnfollowedEvent;
+use crate::policy::{authorize, Action, Resource};
 use commons::{commands::CommandType, id::Id};
 use storage::{model::follow::Follow, query::follow::QueryFollow};
 use tap::TapFallible;
@@ -43,6 +44,6 @@ impl Command for UnfollowUserCommand {
     }
 
     fn supports<A: commons::actor::ActorTrait>(&self, actor: &A) -> bool {
-        actor.actor().is_user()
+        authorize(actor, Action::UnfollowUser, Resource::Any).is_ok()
     }
 }
@@ -0,0 +1,110 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::FragmentUnpublishedEvent;
+use crate::policy::{authorize, lifecycle_role, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use storage::{
+    model::fragment::{Fragment, Transition},
+    query::{fragment::QueryFragment, state_transition::QueryStateTransition},
+};
+use tap::TapFallible;
+
+/// Retracts a published fragment back to draft, either by its author or as a moderator
+/// takedown. A fragment taken down can only be published again by a moderator.
+///
+/// Published forks beneath it are kept as they are: they belong to other authors and stay
+/// readable, they just hang off a draft until the fragment is published again. Their ids
+/// are reported in the event. Likes are kept too, but since only published fragments can be
+/// liked or disliked they are frozen until republication.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct UnpublishFragmentCommand {
+    pub fragment_id: Id,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum UnpublishFragmentCommandError {
+    #[error("Fragment not found: {0}")]
+    FragmentNotFound(Id),
+
+    #[error("{0}")]
+    InvalidState(&'static str),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for UnpublishFragmentCommand {
+    type Event = FragmentUnpublishedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::UnpublishFragment
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::UnpublishFragment, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let fragment = Fragment::find(ctx.pool(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find fragment: {e:?}"))?
+            .ok_or(UnpublishFragmentCommandError::FragmentNotFound(
+                self.fragment_id,
+            ))?;
+
+        authorize(
+            ctx.actor(),
+            Action::UnpublishFragment,
+            Resource::Fragment(&fragment),
+        )
+        .map_err(|e| UnpublishFragmentCommandError::Forbidden(e.reason()))?;
+
+        let published_forks = fragment
+            .children(ctx.pool())
+            .await
+            .tap_err(|e| tracing::error!("Failed to find forks: {e:?}"))?
+            .into_iter()
+            .filter(Fragment::is_published)
+            .map(|fork| *fork.id())
+            .collect();
+
+        let now = ctx.clock().now();
+        let role = lifecycle_role(ctx.actor(), &fragment);
+        let taken_down = ctx.actor().id().is_some_and(|id| !fragment.is_author(id));
+        let (fragment, transition) = fragment
+            .transition(Transition::Unpublish, ctx.actor().actor(), role, now)
+            .map_err(|_| {
+                UnpublishFragmentCommandError::InvalidState(
+                    "Only published fragments can be unpublished",
+                )
+            })?;
+
+        let fragment = if taken_down {
+            fragment.set_taken_down_at(Some(now))
+        } else {
+            fragment
+        };
+        fragment
+            .update(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to update fragment: {e}"))?;
+        transition
+            .save(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save transition: {e}"))?;
+
+        Ok(Some(FragmentUnpublishedEvent {
+            fragment_id: self.fragment_id,
+            published_forks,
+            taken_down,
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
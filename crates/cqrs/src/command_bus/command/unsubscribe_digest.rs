@@ -0,0 +1,66 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::DigestUnsubscribedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, auth::UnsubscribeToken, commands::CommandType};
+use storage::{
+    model::notification_preferences::{DigestFrequency, NotificationPreferences},
+    query::notification_preferences::QueryNotificationPreferences,
+};
+use tap::TapFallible;
+
+/// Turns digests off for the owner of the unsubscribe token put in every digest. Run by
+/// the system since the token is the only credential of the link.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct UnsubscribeDigestCommand {
+    pub token: UnsubscribeToken,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum UnsubscribeDigestCommandError {
+    #[error("Invalid unsubscribe token")]
+    InvalidToken,
+}
+
+#[async_trait::async_trait]
+impl Command for UnsubscribeDigestCommand {
+    type Event = DigestUnsubscribedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::UnsubscribeDigest
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::UnsubscribeDigest, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let preferences = NotificationPreferences::find_by_token(ctx.pool(), &self.token)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find notification preferences: {e}"))?
+            .ok_or(UnsubscribeDigestCommandError::InvalidToken)?;
+
+        if *preferences.digest() == DigestFrequency::Never {
+            return Ok(None);
+        }
+
+        let now = ctx.clock().now();
+        let preferences = preferences
+            .set_digest(DigestFrequency::Never)
+            .set_updated_at(now)
+            .save(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save notification preferences: {e}"))?;
+
+        Ok(Some(DigestUnsubscribedEvent {
+            user_id: *preferences.user_id(),
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
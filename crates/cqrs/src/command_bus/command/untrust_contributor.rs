@@ -0,0 +1,71 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::ContributorUntrustedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use storage::{
+    model::trusted_contributor::TrustedContributor,
+    query::trusted_contributor::QueryTrustedContributor,
+};
+use tap::TapFallible;
+
+/// Removes a user from the trusted contributors of `author_id`. Forks already approved
+/// are left untouched.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct UntrustContributorCommand {
+    pub author_id: Id,
+    pub contributor_id: Id,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum UntrustContributorCommandError {
+    #[error("User not trusted: {0}")]
+    NotTrusted(Id),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for UntrustContributorCommand {
+    type Event = ContributorUntrustedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::UntrustContributor
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::ManageTrustedContributors, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        authorize(
+            ctx.actor(),
+            Action::ManageTrustedContributors,
+            Resource::User(self.author_id),
+        )
+        .map_err(|e| UntrustContributorCommandError::Forbidden(e.reason()))?;
+
+        TrustedContributor::find(ctx.pool(), &self.author_id, &self.contributor_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find trusted contributor: {e:?}"))?
+            .ok_or(UntrustContributorCommandError::NotTrusted(
+                self.contributor_id,
+            ))?
+            .delete(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to delete trusted contributor: {e:?}"))?;
+
+        Ok(Some(ContributorUntrustedEvent {
+            author_id: self.author_id,
+            contributor_id: self.contributor_id,
+            timestamp: ctx.clock().now(),
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
@@ -1,11 +1,15 @@
 use crate::command_bus::bus::Ctx;
 use crate::command_bus::error::CommandBusError;
 use crate::events::FragmentUpdatedEvent;
-use commons::actor::{Actor, ActorType};
+use crate::policy::{authorize, Action, Resource};
+use commons::actor::Actor;
 use commons::fragment::Content;
 use commons::{commands::CommandType, id::Id};
 use derive_getters::Getters;
-use storage::{model::fragment::Fragment, query::fragment::QueryFragment};
+use storage::{
+    model::{fragment::Fragment, revision::Revision},
+    query::{fragment::QueryFragment, revision::QueryRevision},
+};
 use tap::TapFallible;
 
 use super::Command;
@@ -47,7 +51,8 @@ impl Command for UpdateFragmentCommand {
     ) -> Result<Option<Self::Event>, CommandBusError> {
         let user = ctx.actor().id().unwrap();
 
-        let fragment = Fragment::find(ctx.pool(), &self.fragment_id)
+        // Locked so that concurrent edits save their revisions one after the other.
+        let fragment = Fragment::find_for_update(ctx.tx().as_mut(), &self.fragment_id)
             .await
             .tap_err(|e| tracing::error!("Failed to find fragment: {e}"))?
             .ok_or(UpdateFragmentCommandError::FragmentNotFound(
@@ -58,29 +63,52 @@ impl Command for UpdateFragmentCommand {
             return Err(UpdateFragmentCommandError::NonEditableFragment(self.fragment_id).into());
         }
 
-        if !fragment.is_author(user) {
-            return Err(UpdateFragmentCommandError::UserWithoutPermission(user).into());
-        }
+        authorize(
+            ctx.actor(),
+            Action::UpdateFragment,
+            Resource::Fragment(&fragment),
+        )
+        .map_err(|_| UpdateFragmentCommandError::UserWithoutPermission(user))?;
 
         // if self.end && !fragment.is_fork() {
         //     return Err(UpdateFragmentCommandError::NonEndabledFragment(self.fragment_id).into());
         // }
 
-        Ok(fragment
-            .set_content(self.content.clone().unwrap())
+        let content_changed = self
+            .content
+            .as_ref()
+            .is_some_and(|content| content != fragment.content());
+        let content = self
+            .content
+            .clone()
+            .unwrap_or_else(|| fragment.content().clone());
+        let end = self.end.unwrap_or(*fragment.end());
+
+        let fragment = fragment
+            .set_content(content)
             .set_last_modified_at(ctx.clock().now())
-            .set_end(self.end.unwrap())
+            .set_end(end)
             .update(ctx.tx().as_mut())
             .await
-            .map(Into::into)
-            .map(Some)
             .tap_err(|e| {
                 tracing::error!("Failed to update fragment [{:?}]: {e}", self.fragment_id)
-            })?)
+            })?;
+
+        if content_changed {
+            Revision::of(&fragment, user)
+                .save(ctx.tx().as_mut())
+                .await
+                .tap_err(|e| tracing::error!("Failed to save revision: {e}"))?;
+        }
+
+        Ok(Some(FragmentUpdatedEvent {
+            actor: ctx.actor().actor(),
+            ..fragment.into()
+        }))
     }
 
     fn supports<A: commons::actor::ActorTrait>(&self, actor: &A) -> bool {
-        ActorType::User == actor.actor_type()
+        authorize(actor, Action::UpdateFragment, Resource::Any).is_ok()
     }
 }
 
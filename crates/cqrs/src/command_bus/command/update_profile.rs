@@ -0,0 +1,90 @@
+use super::Command;
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::ProfileUpdatedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use derive_getters::Getters;
+use storage::{model::user::User, query::user::QueryUser};
+use tap::TapFallible;
+
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize, Getters)]
+#[builder(setter(into))]
+pub struct UpdateProfileCommand {
+    user_id: Id,
+    #[builder(default)]
+    display_name: Option<String>,
+    #[builder(default)]
+    bio: Option<String>,
+    #[builder(default)]
+    avatar_url: Option<String>,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum UpdateProfileCommandError {
+    #[error("User not found: {0}")]
+    UserNotFound(Id),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for UpdateProfileCommand {
+    type Event = ProfileUpdatedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::UpdateProfile
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let user = User::find(ctx.pool(), &self.user_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find user [{}]: {e}", self.user_id))?
+            .ok_or(UpdateProfileCommandError::UserNotFound(self.user_id))?;
+
+        authorize(
+            ctx.actor(),
+            Action::UpdateProfile,
+            Resource::User(self.user_id),
+        )
+        .map_err(|e| UpdateProfileCommandError::Forbidden(e.reason()))?;
+
+        let display_name = merge(&self.display_name, user.display_name());
+        let bio = merge(&self.bio, user.bio());
+        let avatar_url = merge(&self.avatar_url, user.avatar_url());
+
+        let user = user
+            .set_display_name(display_name)
+            .set_bio(bio)
+            .set_avatar_url(avatar_url)
+            .update(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to update user [{}]: {e}", self.user_id))?;
+
+        Ok(Some(ProfileUpdatedEvent {
+            user_id: *user.id(),
+            display_name: user.display_name().clone(),
+            bio: user.bio().clone(),
+            avatar_url: user.avatar_url().clone(),
+            timestamp: ctx.clock().now(),
+            actor: ctx.actor().actor(),
+        }))
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::UpdateProfile, Resource::Any).is_ok()
+    }
+}
+
+/// Absent fields are kept, blank ones clear the current value.
+fn merge(new: &Option<String>, current: &Option<String>) -> Option<String> {
+    match new.as_deref().map(str::trim) {
+        None => current.clone(),
+        Some("") => None,
+        Some(value) => Some(value.to_owned()),
+    }
+}
@@ -0,0 +1,125 @@
+use super::{
+    create_story::{cover_url, genres, language, title},
+    Command,
+};
+use crate::command_bus::bus::Ctx;
+use crate::command_bus::error::CommandBusError;
+use crate::events::StoryUpdatedEvent;
+use crate::policy::{authorize, Action, Resource};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use storage::{
+    model::story::{MaturityRating, Story},
+    query::story::QueryStory,
+};
+use tap::TapFallible;
+
+/// Updates story metadata. Absent fields are kept; a blank synopsis or cover clears it.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct UpdateStoryCommand {
+    story_id: Id,
+    #[builder(default)]
+    title: Option<String>,
+    #[builder(default)]
+    synopsis: Option<String>,
+    #[builder(default)]
+    genres: Option<Vec<String>>,
+    #[builder(default)]
+    language: Option<String>,
+    #[builder(default)]
+    maturity: Option<MaturityRating>,
+    #[builder(default)]
+    cover_url: Option<String>,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum UpdateStoryCommandError {
+    #[error("Story not found: {0}")]
+    StoryNotFound(Id),
+
+    #[error("{0}")]
+    InvalidStory(&'static str),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for UpdateStoryCommand {
+    type Event = StoryUpdatedEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::UpdateStory
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::UpdateStory, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let story = Story::find(ctx.pool(), &self.story_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find story: {e:?}"))?
+            .ok_or(UpdateStoryCommandError::StoryNotFound(self.story_id))?;
+
+        authorize(ctx.actor(), Action::UpdateStory, Resource::Story(&story))
+            .map_err(|e| UpdateStoryCommandError::Forbidden(e.reason()))?;
+
+        let title = match &self.title {
+            Some(value) => title(value).map_err(UpdateStoryCommandError::InvalidStory)?,
+            None => story.title().clone(),
+        };
+        let genres = match &self.genres {
+            Some(values) => genres(values).map_err(UpdateStoryCommandError::InvalidStory)?,
+            None => story.genres().clone(),
+        };
+        let language = match &self.language {
+            Some(value) => language(value).map_err(UpdateStoryCommandError::InvalidStory)?,
+            None => story.language().clone(),
+        };
+        let maturity = self.maturity.unwrap_or(*story.maturity());
+        let synopsis = merge(&self.synopsis, story.synopsis());
+        let cover_url = match self.cover_url.as_deref().map(str::trim) {
+            None => story.cover_url().clone(),
+            Some("") => None,
+            Some(value) => Some(cover_url(value).map_err(UpdateStoryCommandError::InvalidStory)?),
+        };
+
+        let now = ctx.clock().now();
+        let story = story
+            .set_title(title)
+            .set_synopsis(synopsis)
+            .set_genres(genres)
+            .set_language(language)
+            .set_maturity(maturity)
+            .set_cover_url(cover_url)
+            .set_last_modified_at(now)
+            .update(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to update story: {e:?}"))?;
+
+        Ok(Some(StoryUpdatedEvent {
+            story_id: *story.id(),
+            title: story.title().clone(),
+            synopsis: story.synopsis().clone(),
+            genres: story.genres().clone(),
+            language: story.language().clone(),
+            maturity: *story.maturity(),
+            cover_url: story.cover_url().clone(),
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
+
+/// Absent fields are kept, blank ones clear the current value.
+fn merge(new: &Option<String>, current: &Option<String>) -> Option<String> {
+    match new.as_deref().map(str::trim) {
+        None => current.clone(),
+        Some("") => None,
+        Some(value) => Some(value.to_owned()),
+    }
+}
@@ -0,0 +1,85 @@
+use super::Command;
+use crate::{
+    command_bus::{bus::Ctx, error::CommandBusError},
+    events::ForkWithdrawnEvent,
+    policy::{authorize, lifecycle_role, Action, Resource},
+};
+use commons::{actor::ActorTrait, commands::CommandType, id::Id};
+use storage::{
+    model::fragment::{Fragment, Transition},
+    query::{fragment::QueryFragment, state_transition::QueryStateTransition},
+};
+use tap::TapFallible;
+
+/// Pulls a submitted fork back to draft, before it gets reviewed.
+#[derive(Debug, derive_builder::Builder, serde::Deserialize, serde::Serialize)]
+#[builder(setter(into))]
+pub struct WithdrawForkCommand {
+    pub fragment_id: Id,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum WithdrawForkCommandError {
+    #[error("Fork not found: {0}")]
+    ForkNotFound(Id),
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+
+    #[error("{0}")]
+    InvalidState(&'static str),
+}
+
+#[async_trait::async_trait]
+impl Command for WithdrawForkCommand {
+    type Event = ForkWithdrawnEvent;
+
+    fn command_type(&self) -> CommandType {
+        CommandType::WithdrawFork
+    }
+
+    fn supports<A: ActorTrait>(&self, actor: &A) -> bool {
+        authorize(actor, Action::WithdrawFork, Resource::Any).is_ok()
+    }
+
+    async fn handle<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+    ) -> Result<Option<Self::Event>, CommandBusError> {
+        let fragment = Fragment::find(ctx.pool(), &self.fragment_id)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find fork: {e:?}"))?
+            .filter(Fragment::is_fork)
+            .ok_or(WithdrawForkCommandError::ForkNotFound(self.fragment_id))?;
+
+        authorize(
+            ctx.actor(),
+            Action::WithdrawFork,
+            Resource::Fragment(&fragment),
+        )
+        .map_err(|e| WithdrawForkCommandError::Forbidden(e.reason()))?;
+
+        let now = ctx.clock().now();
+        let role = lifecycle_role(ctx.actor(), &fragment);
+        let (fragment, transition) = fragment
+            .transition(Transition::Withdraw, ctx.actor().actor(), role, now)
+            .map_err(|_| {
+                WithdrawForkCommandError::InvalidState("Only submitted forks can be withdrawn")
+            })?;
+
+        fragment
+            .update(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save fork: {e:?}"))?;
+        transition
+            .save(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save transition: {e:?}"))?;
+
+        Ok(Some(ForkWithdrawnEvent {
+            fragment_id: self.fragment_id,
+            timestamp: now,
+            actor: ctx.actor().actor(),
+        }))
+    }
+}
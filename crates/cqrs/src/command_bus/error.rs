@@ -1,8 +1,32 @@
 use super::command::{
-    create_fragment::CreateFragmentCommandError, dislike_fragment::DislikeFragmentCommandError,
-    fork_fragment::ForkFragmentCommandError, like_fragment::LikeFragmentCommandError,
-    publish_fragment::PublishFragmentCommandError, review_fork::ReviewForkCommandError,
-    submit_fork::SubmitForkCommandError, update_fragment::UpdateFragmentCommandError,
+    accept_maintainer_invitation::AcceptMaintainerInvitationCommandError,
+    accept_suggestion::AcceptSuggestionCommandError, add_tag::AddTagCommandError,
+    assign_role::AssignRoleCommandError, cancel_publication::CancelPublicationCommandError,
+    cast_vote::CastVoteCommandError, close_poll::ClosePollCommandError,
+    create_comment::CreateCommentCommandError, create_fragment::CreateFragmentCommandError,
+    create_story::CreateStoryCommandError, create_webhook::CreateWebhookCommandError,
+    decline_maintainer_invitation::DeclineMaintainerInvitationCommandError,
+    delete_comment::DeleteCommentCommandError, delete_fragment::DeleteFragmentCommandError,
+    delete_webhook::DeleteWebhookCommandError, dislike_fragment::DislikeFragmentCommandError,
+    edit_comment::EditCommentCommandError, enable_webhook::EnableWebhookCommandError,
+    expire_fork::ExpireForkCommandError, fork_fragment::ForkFragmentCommandError,
+    invite_maintainer::InviteMaintainerCommandError, like_fragment::LikeFragmentCommandError,
+    mark_notifications_read::MarkNotificationsReadCommandError,
+    moderate_comment::ModerateCommentCommandError, open_poll::OpenPollCommandError,
+    publish_fragment::PublishFragmentCommandError, register_user::RegisterUserCommandError,
+    reject_suggestion::RejectSuggestionCommandError, remove_tag::RemoveTagCommandError,
+    restore_fragment::RestoreFragmentCommandError, resubmit_fork::ResubmitForkCommandError,
+    revert_fragment::RevertFragmentCommandError, review_fork::ReviewForkCommandError,
+    set_canonical_branch::SetCanonicalBranchCommandError,
+    set_fork_policy::SetForkPolicyCommandError,
+    set_notification_preferences::SetNotificationPreferencesCommandError,
+    set_review_quorum::SetReviewQuorumCommandError, set_review_sla::SetReviewSlaCommandError,
+    submit_fork::SubmitForkCommandError, trust_contributor::TrustContributorCommandError,
+    unpublish_fragment::UnpublishFragmentCommandError,
+    unsubscribe_digest::UnsubscribeDigestCommandError,
+    untrust_contributor::UntrustContributorCommandError,
+    update_fragment::UpdateFragmentCommandError, update_profile::UpdateProfileCommandError,
+    update_story::UpdateStoryCommandError, withdraw_fork::WithdrawForkCommandError,
 };
 use commons::actor::ActorTrait;
 use storage::StorageError;
@@ -35,6 +59,123 @@ pub enum CommandBusError {
     #[error(transparent)]
     SubmitForkCommand(#[from] SubmitForkCommandError),
 
+    #[error(transparent)]
+    RegisterUserCommand(#[from] RegisterUserCommandError),
+
+    #[error(transparent)]
+    UpdateProfileCommand(#[from] UpdateProfileCommandError),
+
+    #[error(transparent)]
+    AssignRoleCommand(#[from] AssignRoleCommandError),
+
+    #[error(transparent)]
+    SetForkPolicyCommand(#[from] SetForkPolicyCommandError),
+
+    #[error(transparent)]
+    DeleteFragmentCommand(#[from] DeleteFragmentCommandError),
+
+    #[error(transparent)]
+    RestoreFragmentCommand(#[from] RestoreFragmentCommandError),
+
+    #[error(transparent)]
+    RevertFragmentCommand(#[from] RevertFragmentCommandError),
+
+    #[error(transparent)]
+    AcceptSuggestionCommand(#[from] AcceptSuggestionCommandError),
+
+    #[error(transparent)]
+    RejectSuggestionCommand(#[from] RejectSuggestionCommandError),
+
+    #[error(transparent)]
+    CreateCommentCommand(#[from] CreateCommentCommandError),
+
+    #[error(transparent)]
+    EditCommentCommand(#[from] EditCommentCommandError),
+
+    #[error(transparent)]
+    DeleteCommentCommand(#[from] DeleteCommentCommandError),
+
+    #[error(transparent)]
+    ModerateCommentCommand(#[from] ModerateCommentCommandError),
+
+    #[error(transparent)]
+    WithdrawForkCommand(#[from] WithdrawForkCommandError),
+
+    #[error(transparent)]
+    ResubmitForkCommand(#[from] ResubmitForkCommandError),
+
+    #[error(transparent)]
+    UnpublishFragmentCommand(#[from] UnpublishFragmentCommandError),
+
+    #[error(transparent)]
+    CancelPublicationCommand(#[from] CancelPublicationCommandError),
+
+    #[error(transparent)]
+    CreateStoryCommand(#[from] CreateStoryCommandError),
+
+    #[error(transparent)]
+    UpdateStoryCommand(#[from] UpdateStoryCommandError),
+
+    #[error(transparent)]
+    AddTagCommand(#[from] AddTagCommandError),
+
+    #[error(transparent)]
+    RemoveTagCommand(#[from] RemoveTagCommandError),
+
+    #[error(transparent)]
+    SetCanonicalBranchCommand(#[from] SetCanonicalBranchCommandError),
+
+    #[error(transparent)]
+    OpenPollCommand(#[from] OpenPollCommandError),
+
+    #[error(transparent)]
+    CastVoteCommand(#[from] CastVoteCommandError),
+
+    #[error(transparent)]
+    ClosePollCommand(#[from] ClosePollCommandError),
+
+    #[error(transparent)]
+    InviteMaintainerCommand(#[from] InviteMaintainerCommandError),
+
+    #[error(transparent)]
+    AcceptMaintainerInvitationCommand(#[from] AcceptMaintainerInvitationCommandError),
+
+    #[error(transparent)]
+    DeclineMaintainerInvitationCommand(#[from] DeclineMaintainerInvitationCommandError),
+
+    #[error(transparent)]
+    SetReviewQuorumCommand(#[from] SetReviewQuorumCommandError),
+
+    #[error(transparent)]
+    SetReviewSlaCommand(#[from] SetReviewSlaCommandError),
+
+    #[error(transparent)]
+    ExpireForkCommand(#[from] ExpireForkCommandError),
+
+    #[error(transparent)]
+    TrustContributorCommand(#[from] TrustContributorCommandError),
+
+    #[error(transparent)]
+    UntrustContributorCommand(#[from] UntrustContributorCommandError),
+
+    #[error(transparent)]
+    MarkNotificationsReadCommand(#[from] MarkNotificationsReadCommandError),
+
+    #[error(transparent)]
+    SetNotificationPreferencesCommand(#[from] SetNotificationPreferencesCommandError),
+
+    #[error(transparent)]
+    UnsubscribeDigestCommand(#[from] UnsubscribeDigestCommandError),
+
+    #[error(transparent)]
+    CreateWebhookCommand(#[from] CreateWebhookCommandError),
+
+    #[error(transparent)]
+    DeleteWebhookCommand(#[from] DeleteWebhookCommandError),
+
+    #[error(transparent)]
+    EnableWebhookCommand(#[from] EnableWebhookCommandError),
+
     #[error(transparent)]
     Storage(#[from] StorageError),
 
@@ -0,0 +1,227 @@
+use crate::command_bus::{
+    bus::CommandBus, command::record_digest::RecordDigestCommandBuilder, error::CommandBusError,
+};
+use askama::Template;
+use commons::{
+    actor::Actor,
+    mail::{Email, EmailAddress, Mailer},
+    time::Clock,
+};
+use sqlx::PgPool;
+use std::sync::Arc;
+use storage::{
+    model::{
+        notification::{Notification, NotificationKind},
+        notification_preferences::{DigestFrequency, NotificationPreferences},
+    },
+    query::{
+        notification::QueryNotification, notification_preferences::QueryNotificationPreferences,
+    },
+};
+use tap::TapFallible;
+
+/// Line of a digest, summarizing one grouped notification.
+struct DigestItem {
+    summary: String,
+    url: String,
+}
+
+#[derive(Template)]
+#[template(path = "digest.txt")]
+struct DigestText<'a> {
+    frequency: &'a str,
+    items: &'a [DigestItem],
+    unsubscribe_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "digest.html")]
+struct DigestHtml<'a> {
+    frequency: &'a str,
+    items: &'a [DigestItem],
+    unsubscribe_url: &'a str,
+}
+
+/// Emails users the unread notifications they got since their previous digest.
+pub struct DigestJob {
+    pool: PgPool,
+    command_bus: Arc<CommandBus>,
+    mailer: Arc<dyn Mailer>,
+    clock: Arc<dyn Clock>,
+    public_url: String,
+}
+
+impl DigestJob {
+    pub fn new(
+        pool: PgPool,
+        command_bus: Arc<CommandBus>,
+        mailer: Arc<dyn Mailer>,
+        clock: Arc<dyn Clock>,
+        public_url: impl Into<String>,
+    ) -> Self {
+        Self {
+            pool,
+            command_bus,
+            mailer,
+            clock,
+            public_url: public_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Sends every digest that is due, returning how many were sent.
+    ///
+    /// Users without unread notifications since their previous digest get nothing. Digests
+    /// are claimed before being sent, so concurrent runs never send the same one twice. A
+    /// digest that fails to send is retried on the next run.
+    pub async fn run(&self) -> Result<usize, CommandBusError> {
+        let now = self.clock.now();
+        let due = NotificationPreferences::find_due_digests(&self.pool, &now)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find due digests: {e}"))?;
+
+        let mut sent = 0;
+        for preferences in due {
+            let (Some(email), Some(period)) = (preferences.email(), preferences.digest().period())
+            else {
+                continue;
+            };
+            let since = preferences.last_digest_at().unwrap_or(now - period);
+            let notifications = Notification::find_for_digest(
+                &self.pool,
+                preferences.user_id(),
+                &since,
+                preferences.muted_kinds(),
+            )
+            .await
+            .tap_err(|e| tracing::error!("Failed to find digest notifications: {e}"))?;
+            if notifications.is_empty() {
+                continue;
+            }
+
+            let email = self
+                .render(&preferences, email.clone(), &notifications)
+                .map_err(anyhow::Error::from)?;
+            let claimed = NotificationPreferences::claim_digest(
+                &self.pool,
+                preferences.user_id(),
+                preferences.last_digest_at().as_ref(),
+                &now,
+            )
+            .await
+            .tap_err(|e| tracing::error!("Failed to claim digest: {e}"))?;
+            if !claimed {
+                continue;
+            }
+            if let Err(e) = self.mailer.send(email).await {
+                tracing::error!(
+                    "Failed to send digest to user [{}]: {e}",
+                    preferences.user_id()
+                );
+                NotificationPreferences::release_digest(
+                    &self.pool,
+                    preferences.user_id(),
+                    &now,
+                    preferences.last_digest_at().as_ref(),
+                )
+                .await
+                .tap_err(|e| tracing::error!("Failed to release digest: {e}"))?;
+                continue;
+            }
+
+            let command = RecordDigestCommandBuilder::default()
+                .user_id(*preferences.user_id())
+                .count(notifications.len() as u64)
+                .build()
+                .map_err(anyhow::Error::from)?;
+            self.command_bus.execute(Actor::System, command).await?;
+            sent += 1;
+        }
+
+        Ok(sent)
+    }
+
+    fn render(
+        &self,
+        preferences: &NotificationPreferences,
+        to: EmailAddress,
+        notifications: &[Notification],
+    ) -> Result<Email, askama::Error> {
+        let frequency = match preferences.digest() {
+            DigestFrequency::Weekly => "weekly",
+            _ => "daily",
+        };
+        let items = notifications
+            .iter()
+            .map(|notification| self.item(notification))
+            .collect::<Vec<_>>();
+        let unsubscribe_url = format!(
+            "{}/api/v1/unsubscribe/{}",
+            self.public_url,
+            preferences.unsubscribe_token().as_str()
+        );
+
+        let text = DigestText {
+            frequency,
+            items: &items,
+            unsubscribe_url: &unsubscribe_url,
+        }
+        .render()?;
+        let html = DigestHtml {
+            frequency,
+            items: &items,
+            unsubscribe_url: &unsubscribe_url,
+        }
+        .render()?;
+
+        Ok(Email {
+            to,
+            subject: match items.len() {
+                1 => String::from("Your digest: 1 new notification"),
+                count => format!("Your digest: {count} new notifications"),
+            },
+            text,
+            html: Some(html),
+            unsubscribe_url: Some(unsubscribe_url),
+        })
+    }
+
+    fn item(&self, notification: &Notification) -> DigestItem {
+        let who = match notification.actor_ids().len() {
+            1 => String::from("Someone"),
+            count => format!("{count} people"),
+        };
+        let (summary, resource) = match notification.kind() {
+            NotificationKind::ForkReviewed => (format!("{who} reviewed your fork"), "fragments"),
+            NotificationKind::FragmentForked => {
+                (format!("{who} forked your fragment"), "fragments")
+            }
+            NotificationKind::ForkSubmitted => (
+                format!("{who} submitted a fork of your fragment"),
+                "fragments",
+            ),
+            NotificationKind::FragmentLiked => (format!("{who} liked your fragment"), "fragments"),
+            NotificationKind::UserFollowed => (format!("{who} started following you"), "users"),
+            NotificationKind::ForkAutoRejected => (
+                String::from("Your fork was rejected, nobody reviewed it in time"),
+                "fragments",
+            ),
+            NotificationKind::ForkReturnedToDraft => (
+                String::from("Your fork is back to draft, nobody reviewed it in time"),
+                "fragments",
+            ),
+            NotificationKind::ForkEscalated => (
+                String::from("A fork is waiting for your review past its deadline"),
+                "fragments",
+            ),
+        };
+
+        DigestItem {
+            summary,
+            url: format!(
+                "{}/api/v1/{resource}/{}",
+                self.public_url,
+                notification.subject_id()
+            ),
+        }
+    }
+}
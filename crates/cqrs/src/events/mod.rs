@@ -1,16 +1,33 @@
 use commons::{
-    actor::Actor, events::EventType, fragment::Content, id::Id, review::Comment, time::DateTime,
+    actor::{Actor, Role},
+    events::EventType,
+    fragment::Content,
+    id::Id,
+    review::Comment,
+    tag::Tag,
+    time::DateTime,
 };
 use derive_builder::Builder;
 use derive_getters::Getters;
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
-use storage::model::review::ReviewAction;
+use storage::model::{
+    fragment::{ForkPolicy, FragmentState},
+    notification::NotificationKind,
+    notification_preferences::DigestFrequency,
+    poll::PollResult,
+    review::ReviewAction,
+    review_sla::SlaOutcome,
+    story::MaturityRating,
+    tag::TagTarget,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
 #[builder(setter(into))]
 pub struct ForkSubmittedEvent {
     pub fragment_id: Id,
+    /// Approved or published when the author is trusted by the parent author.
+    pub state: FragmentState,
     pub timestamp: DateTime,
     pub actor: Actor,
 }
@@ -27,6 +44,49 @@ impl Event for ForkSubmittedEvent {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct ForkWithdrawnEvent {
+    pub fragment_id: Id,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for ForkWithdrawnEvent {
+    fn event_type(&self) -> EventType {
+        EventType::ForkWithdrawn
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct ForkResubmittedEvent {
+    pub fragment_id: Id,
+    /// Number of this resubmission, starting at 1 after the first rejection.
+    pub attempt: u32,
+    pub appeal: Option<Comment>,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for ForkResubmittedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::ForkResubmitted
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
 #[builder(setter(into))]
 pub struct FragmentCreatedEvent {
@@ -141,6 +201,8 @@ pub struct FragmentForkReviewedEvent {
     pub timestamp: DateTime,
     pub comment: Option<Comment>,
     pub action: ReviewAction,
+    /// Fork state once the review is counted, still `Submitted` until the quorum is reached.
+    pub state: FragmentState,
     pub actor: Actor,
 }
 
@@ -216,6 +278,892 @@ impl Event for UserUnfollowedEvent {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct UserRegisteredEvent {
+    pub user_id: Id,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub timestamp: DateTime,
+}
+
+impl Event for UserRegisteredEvent {
+    fn event_type(&self) -> EventType {
+        EventType::UserRegistered
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        Actor::User(self.user_id)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct ProfileUpdatedEvent {
+    pub user_id: Id,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for ProfileUpdatedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::ProfileUpdated
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct RoleAssignedEvent {
+    pub user_id: Id,
+    pub role: Role,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for RoleAssignedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::RoleAssigned
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct ForkPolicyChangedEvent {
+    pub fragment_id: Id,
+    pub policy: ForkPolicy,
+    pub invitees: Vec<Id>,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for ForkPolicyChangedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::ForkPolicyChanged
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct FragmentDeletedEvent {
+    pub fragment_id: Id,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for FragmentDeletedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::FragmentDeleted
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct FragmentRestoredEvent {
+    pub fragment_id: Id,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for FragmentRestoredEvent {
+    fn event_type(&self) -> EventType {
+        EventType::FragmentRestored
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct FragmentsPurgedEvent {
+    pub fragment_ids: Vec<Id>,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for FragmentsPurgedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::FragmentsPurged
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct FragmentRevertedEvent {
+    pub fragment_id: Id,
+    pub reverted_to: i32,
+    pub revision: i32,
+    pub content: Content,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for FragmentRevertedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::FragmentReverted
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct SuggestionAcceptedEvent {
+    pub suggestion_id: Id,
+    pub review_id: Id,
+    pub fragment_id: Id,
+    pub content: Content,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for SuggestionAcceptedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::SuggestionAccepted
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct SuggestionRejectedEvent {
+    pub suggestion_id: Id,
+    pub review_id: Id,
+    pub fragment_id: Id,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for SuggestionRejectedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::SuggestionRejected
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct CommentCreatedEvent {
+    pub comment_id: Id,
+    pub fragment_id: Id,
+    pub review_id: Option<Id>,
+    pub parent_id: Option<Id>,
+    pub body: Comment,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for CommentCreatedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::CommentCreated
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct CommentEditedEvent {
+    pub comment_id: Id,
+    pub body: Comment,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for CommentEditedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::CommentEdited
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct CommentDeletedEvent {
+    pub comment_id: Id,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for CommentDeletedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::CommentDeleted
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct CommentModeratedEvent {
+    pub comment_id: Id,
+    pub hidden: bool,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for CommentModeratedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::CommentModerated
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct FragmentUnpublishedEvent {
+    pub fragment_id: Id,
+    /// Direct forks that were published and stay so, now hanging off a draft.
+    pub published_forks: Vec<Id>,
+    /// Whether a moderator took the fragment down, keeping its author from republishing it.
+    #[serde(default)]
+    #[builder(default)]
+    pub taken_down: bool,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for FragmentUnpublishedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::FragmentUnpublished
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct PublicationCancelledEvent {
+    pub fragment_id: Id,
+    pub task_id: Id,
+    pub scheduled_at: DateTime,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for PublicationCancelledEvent {
+    fn event_type(&self) -> EventType {
+        EventType::PublicationCancelled
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct StoryCreatedEvent {
+    pub story_id: Id,
+    pub fragment_id: Id,
+    pub title: String,
+    pub genres: Vec<String>,
+    pub language: String,
+    pub maturity: MaturityRating,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for StoryCreatedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::StoryCreated
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct StoryUpdatedEvent {
+    pub story_id: Id,
+    pub title: String,
+    pub synopsis: Option<String>,
+    pub genres: Vec<String>,
+    pub language: String,
+    pub maturity: MaturityRating,
+    pub cover_url: Option<String>,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for StoryUpdatedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::StoryUpdated
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct TagAddedEvent {
+    pub target: TagTarget,
+    pub target_id: Id,
+    pub tag: Tag,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for TagAddedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::TagAdded
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct TagRemovedEvent {
+    pub target: TagTarget,
+    pub target_id: Id,
+    pub tag: Tag,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for TagRemovedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::TagRemoved
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct CanonicalBranchSetEvent {
+    pub fragment_id: Id,
+    /// Missing when the canonical branch was cleared.
+    pub child_id: Option<Id>,
+    pub previous_child_id: Option<Id>,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for CanonicalBranchSetEvent {
+    fn event_type(&self) -> EventType {
+        EventType::CanonicalBranchSet
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct PollOpenedEvent {
+    pub poll_id: Id,
+    pub fragment_id: Id,
+    pub closes_at: DateTime,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for PollOpenedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::PollOpened
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct VoteCastEvent {
+    pub poll_id: Id,
+    pub child_id: Id,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for VoteCastEvent {
+    fn event_type(&self) -> EventType {
+        EventType::VoteCast
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct PollClosedEvent {
+    pub poll_id: Id,
+    pub fragment_id: Id,
+    /// Missing when nobody voted for a published child.
+    pub winner_id: Option<Id>,
+    /// Final tally, most voted first.
+    pub results: Vec<PollResult>,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for PollClosedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::PollClosed
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct MaintainerInvitedEvent {
+    pub root_id: Id,
+    pub user_id: Id,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for MaintainerInvitedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::MaintainerInvited
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct MaintainerInvitationAcceptedEvent {
+    pub root_id: Id,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for MaintainerInvitationAcceptedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::MaintainerInvitationAccepted
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct MaintainerInvitationDeclinedEvent {
+    pub root_id: Id,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for MaintainerInvitationDeclinedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::MaintainerInvitationDeclined
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct ReviewQuorumChangedEvent {
+    pub fragment_id: Id,
+    pub approvals: i32,
+    pub veto: bool,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for ReviewQuorumChangedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::ReviewQuorumChanged
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct ReviewSlaChangedEvent {
+    pub fragment_id: Id,
+    pub window_hours: i32,
+    pub outcome: SlaOutcome,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for ReviewSlaChangedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::ReviewSlaChanged
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+/// A submitted fork got no review in time. Addressed to the fork author, and to the story
+/// maintainers when escalated.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct ForkExpiredEvent {
+    pub fragment_id: Id,
+    pub author_id: Id,
+    pub outcome: SlaOutcome,
+    /// Maintainers the fork was escalated to, empty for the other outcomes.
+    pub maintainers: Vec<Id>,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for ForkExpiredEvent {
+    fn event_type(&self) -> EventType {
+        match self.outcome {
+            SlaOutcome::Reject => EventType::ForkAutoRejected,
+            SlaOutcome::ReturnToDraft => EventType::ForkReturnedToDraft,
+            SlaOutcome::Escalate => EventType::ForkEscalated,
+        }
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct ContributorTrustedEvent {
+    pub author_id: Id,
+    pub contributor_id: Id,
+    pub auto_publish: bool,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for ContributorTrustedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::ContributorTrusted
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct ContributorUntrustedEvent {
+    pub author_id: Id,
+    pub contributor_id: Id,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for ContributorUntrustedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::ContributorUntrusted
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct NotificationsReadEvent {
+    pub user_id: Id,
+    /// Missing when the whole inbox was marked as read.
+    pub notification_id: Option<Id>,
+    pub count: u64,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for NotificationsReadEvent {
+    fn event_type(&self) -> EventType {
+        EventType::NotificationsRead
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct NotificationPreferencesChangedEvent {
+    pub user_id: Id,
+    pub digest: DigestFrequency,
+    pub muted_kinds: Vec<NotificationKind>,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for NotificationPreferencesChangedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::NotificationPreferencesChanged
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct DigestSentEvent {
+    pub user_id: Id,
+    /// Notifications listed in the digest.
+    pub count: u64,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for DigestSentEvent {
+    fn event_type(&self) -> EventType {
+        EventType::DigestSent
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct DigestUnsubscribedEvent {
+    pub user_id: Id,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for DigestUnsubscribedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::DigestUnsubscribed
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct WebhookCreatedEvent {
+    pub webhook_id: Id,
+    pub owner_id: Id,
+    pub url: String,
+    pub event_types: Vec<EventType>,
+    pub story_id: Option<Id>,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for WebhookCreatedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::WebhookCreated
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct WebhookDeletedEvent {
+    pub webhook_id: Id,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for WebhookDeletedEvent {
+    fn event_type(&self) -> EventType {
+        EventType::WebhookDeleted
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct WebhookEnabledEvent {
+    pub webhook_id: Id,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for WebhookEnabledEvent {
+    fn event_type(&self) -> EventType {
+        EventType::WebhookEnabled
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Builder, Getters)]
+#[builder(setter(into))]
+pub struct WebhookDisabledEvent {
+    pub webhook_id: Id,
+    /// Failed attempts in a row that led to disabling the endpoint.
+    pub consecutive_failures: i32,
+    pub timestamp: DateTime,
+    pub actor: Actor,
+}
+
+impl Event for WebhookDisabledEvent {
+    fn event_type(&self) -> EventType {
+        EventType::WebhookDisabled
+    }
+    fn timestamp(&self) -> DateTime {
+        self.timestamp
+    }
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
 pub trait Event: Send + Sync + Debug {
     fn event_type(&self) -> EventType;
     fn data(&self) -> &Self {
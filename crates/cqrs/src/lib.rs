@@ -1,2 +1,7 @@
 pub mod command_bus;
+pub mod digest;
 pub mod events;
+pub mod live;
+pub mod policy;
+pub mod projections;
+pub mod webhooks;
@@ -0,0 +1,224 @@
+use crate::policy::{authorize, authorize_view, Action, PolicyError, Resource, ViewError};
+use commons::{events::EventType, id::Id, time::DateTime};
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::{PgConnection, PgPool};
+use storage::{
+    model::{comment::Comment, event::DbEvent, fragment::Fragment, story::Story, user::User},
+    query::{comment::QueryComment, event::QueryEvent, fragment::QueryFragment, story::QueryStory},
+    StorageError,
+};
+
+/// Events read per query when a stream catches up.
+const REPLAY_BATCH: i64 = 100;
+
+/// Fields of the event data naming the users an event is about.
+const USER_FIELDS: &[&str] = &[
+    "user_id",
+    "author_id",
+    "follower_id",
+    "following_id",
+    "contributor_id",
+    "owner_id",
+];
+
+/// What a live stream follows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiveScope {
+    /// Events about a fragment and every fragment below it.
+    Subtree(Id),
+    /// Events about the user, its fragments and the forks of its fragments.
+    User(Id),
+}
+
+/// Event as pushed to live streams.
+#[derive(Debug, Clone, Serialize)]
+pub struct LiveEvent {
+    pub position: i64,
+    pub id: Id,
+    pub event_type: EventType,
+    /// Fragment the event is about, if any.
+    pub fragment_id: Option<Id>,
+    pub timestamp: DateTime,
+    pub data: Value,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LiveError {
+    #[error("Fragment not found")]
+    FragmentNotFound,
+
+    #[error("Position {0} is too far behind to resume from")]
+    TooFarBehind(i64),
+
+    #[error(transparent)]
+    Forbidden(#[from] PolicyError),
+
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+}
+
+/// Picks the events of a live stream, leaving out the ones its viewer is not allowed to
+/// see, such as drafts of other users.
+pub struct LiveFilter {
+    pool: PgPool,
+    viewer: User,
+    scope: LiveScope,
+}
+
+impl LiveFilter {
+    /// Opens a filter on `scope`, which must itself be visible to the viewer.
+    pub async fn open(pool: PgPool, viewer: User, scope: LiveScope) -> Result<Self, LiveError> {
+        let filter = Self {
+            pool,
+            viewer,
+            scope,
+        };
+        match scope {
+            LiveScope::User(id) => {
+                authorize(&filter.viewer, Action::WatchEvents, Resource::User(id))?
+            }
+            LiveScope::Subtree(id) => {
+                let fragment = Fragment::find(&filter.pool, &id)
+                    .await?
+                    .ok_or(LiveError::FragmentNotFound)?;
+                filter.authorize(&fragment).await?;
+            }
+        }
+        Ok(filter)
+    }
+
+    /// Event as the viewer gets it, or `None` when it is out of scope or not visible.
+    pub async fn accept(&self, event: &DbEvent) -> Result<Option<LiveEvent>, StorageError> {
+        let Some(position) = *event.position() else {
+            return Ok(None);
+        };
+        let data: Value = event.event_data().into_event();
+        let fragment = {
+            let mut conn = self.pool.acquire().await?;
+            event_fragment(&mut conn, &data).await?
+        };
+
+        let in_scope = match (self.scope, &fragment) {
+            (LiveScope::Subtree(root), Some(fragment)) => {
+                fragment.id() == &root || fragment.path().as_ref().contains(&root)
+            }
+            (LiveScope::Subtree(_), None) => false,
+            (LiveScope::User(user), fragment) => {
+                *event.actor_id() == Some(user)
+                    || mentions(&data, &user)
+                    || match fragment {
+                        Some(fragment) => self.is_authored(fragment, &user).await?,
+                        None => false,
+                    }
+            }
+        };
+        if !in_scope {
+            return Ok(None);
+        }
+        if let Some(fragment) = &fragment {
+            match self.authorize(fragment).await {
+                Ok(()) => {}
+                Err(LiveError::Storage(e)) => return Err(e),
+                Err(_) => return Ok(None),
+            }
+        }
+
+        Ok(Some(LiveEvent {
+            position,
+            id: *event.id(),
+            event_type: *event.event_type(),
+            fragment_id: fragment.map(|fragment| *fragment.id()),
+            timestamp: *event.timestamp(),
+            data,
+        }))
+    }
+
+    /// Checks the stream can be resumed from `position` by replaying at most `max_replay`
+    /// events, as replaying filters every event one by one.
+    pub async fn check_resume(&self, position: i64, max_replay: i64) -> Result<(), LiveError> {
+        let last = DbEvent::last_position(&self.pool)
+            .await?
+            .unwrap_or_default();
+        if last.saturating_sub(position) > max_replay {
+            return Err(LiveError::TooFarBehind(position));
+        }
+        Ok(())
+    }
+
+    /// Visible events saved after `position`, together with the position read up to.
+    /// Callers keep replaying until the returned position stops moving.
+    pub async fn replay(&self, position: i64) -> Result<(Vec<LiveEvent>, i64), StorageError> {
+        let events = DbEvent::find_after(&self.pool, position, REPLAY_BATCH).await?;
+        let reached = events
+            .last()
+            .and_then(|event| *event.position())
+            .unwrap_or(position);
+
+        let mut accepted = Vec::new();
+        for event in &events {
+            if let Some(event) = self.accept(event).await? {
+                accepted.push(event);
+            }
+        }
+        Ok((accepted, reached))
+    }
+
+    /// Whether the user wrote the fragment or the fragment it forks.
+    async fn is_authored(&self, fragment: &Fragment, user: &Id) -> Result<bool, StorageError> {
+        if fragment.is_author(*user) {
+            return Ok(true);
+        }
+        Ok(fragment
+            .get_parent(&self.pool)
+            .await?
+            .is_some_and(|parent| parent.is_author(*user)))
+    }
+
+    async fn authorize(&self, fragment: &Fragment) -> Result<(), LiveError> {
+        let mut conn = self.pool.acquire().await.map_err(StorageError::from)?;
+        authorize_view(&mut conn, &self.viewer, Action::WatchEvents, fragment)
+            .await
+            .map_err(|e| match e {
+                ViewError::Forbidden(e) => LiveError::Forbidden(e),
+                ViewError::Storage(e) => LiveError::Storage(e),
+            })
+    }
+}
+
+/// Fragment an event is about, found from the fragment, story or comment it names.
+pub(crate) async fn event_fragment(
+    conn: &mut PgConnection,
+    data: &Value,
+) -> Result<Option<Fragment>, StorageError> {
+    let id = |field: &str| {
+        data.get(field)
+            .and_then(|value| serde_json::from_value::<Id>(value.clone()).ok())
+    };
+    let fragment_id = if let Some(id) = id("fragment_id").or_else(|| id("root_id")) {
+        Some(id)
+    } else if let Some(story_id) = id("story_id") {
+        Story::find(&mut *conn, &story_id)
+            .await?
+            .map(|story| *story.fragment_id())
+    } else if let Some(comment_id) = id("comment_id") {
+        Comment::find(&mut *conn, &comment_id)
+            .await?
+            .map(|comment| *comment.fragment_id())
+    } else {
+        id("parent_fragment_id")
+    };
+
+    match fragment_id {
+        Some(id) => Fragment::find(conn, &id).await,
+        None => Ok(None),
+    }
+}
+
+fn mentions(data: &Value, user: &Id) -> bool {
+    USER_FIELDS.iter().any(|field| {
+        data.get(*field)
+            .and_then(|value| serde_json::from_value::<Id>(value.clone()).ok())
+            .is_some_and(|id| &id == user)
+    })
+}
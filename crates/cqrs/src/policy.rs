@@ -0,0 +1,970 @@
+use commons::{
+    actor::{ActorTrait, ActorType},
+    id::Id,
+};
+use sqlx::PgConnection;
+use storage::{
+    model::{
+        comment::Comment,
+        fragment::{ForkPolicy, Fragment, LifecycleRole},
+        maintainer::Maintainer,
+        story::Story,
+    },
+    query::{fragment::QueryFragment, maintainer::QueryMaintainer},
+    StorageError,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    CreateFragment,
+    UpdateFragment,
+    PublishFragment,
+    UnpublishFragment,
+    ForkFragment,
+    SubmitFork,
+    WithdrawFork,
+    ResubmitFork,
+    ReviewFork,
+    LikeFragment,
+    DislikeFragment,
+    FollowUser,
+    UnfollowUser,
+    RegisterUser,
+    UpdateProfile,
+    AssignRole,
+    SetForkPolicy,
+    DeleteFragment,
+    RestoreFragment,
+    PurgeFragments,
+    RevertFragment,
+    ViewReviewContext,
+    ResolveSuggestion,
+    CreateComment,
+    DiscussReview,
+    EditComment,
+    DeleteComment,
+    ModerateComment,
+    CreateStory,
+    UpdateStory,
+    TagContent,
+    SetCanonicalBranch,
+    OpenPoll,
+    CastVote,
+    ClosePoll,
+    InviteMaintainer,
+    RespondMaintainerInvitation,
+    SetReviewQuorum,
+    SetReviewSla,
+    ExpireFork,
+    ManageTrustedContributors,
+    ReadNotifications,
+    UpdateNotificationPreferences,
+    RecordDigest,
+    UnsubscribeDigest,
+    ManageWebhooks,
+    ManageGlobalWebhooks,
+    DisableWebhook,
+    WatchEvents,
+    ViewFragment,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Resource<'r> {
+    /// Actor level check, done before the target resource is loaded.
+    Any,
+    Fragment(&'r Fragment),
+    /// Fragment about to be forked, with the fork policy of its story.
+    ForkTarget {
+        parent: &'r Fragment,
+        policy: ForkPolicy,
+        audience: ForkAudience,
+    },
+    /// Submitted fork and the fragment it branches from.
+    Fork {
+        fork: &'r Fragment,
+        parent: &'r Fragment,
+        /// Whether the actor co-maintains the story the fork belongs to.
+        maintainer: bool,
+    },
+    /// Root fragment of a story.
+    Root {
+        root: &'r Fragment,
+        /// Whether the actor co-maintains the story.
+        maintainer: bool,
+    },
+    Comment(&'r Comment),
+    Story(&'r Story),
+    User(Id),
+}
+
+/// Relationship between the forking user and the story author.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ForkAudience {
+    pub follows_author: bool,
+    pub is_friend: bool,
+    pub is_invited: bool,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ViewError {
+    #[error(transparent)]
+    Forbidden(#[from] PolicyError),
+
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum PolicyError {
+    #[error("Actor type not allowed")]
+    ActorNotAllowed,
+
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+impl PolicyError {
+    pub const fn reason(&self) -> &'static str {
+        match self {
+            Self::ActorNotAllowed => "Actor type not allowed",
+            Self::Forbidden(reason) => reason,
+        }
+    }
+}
+
+/// Single entry point for authorization decisions taken by commands.
+pub fn authorize<A: ActorTrait + ?Sized>(
+    actor: &A,
+    action: Action,
+    resource: Resource<'_>,
+) -> Result<(), PolicyError> {
+    if actor.actor_type() != ActorType::User {
+        return match action {
+            Action::PurgeFragments
+            | Action::AssignRole
+            | Action::ClosePoll
+            | Action::ExpireFork
+            | Action::RecordDigest
+            | Action::UnsubscribeDigest
+            | Action::DisableWebhook => Ok(()),
+            _ => Err(PolicyError::ActorNotAllowed),
+        };
+    }
+
+    let user = actor.id().ok_or(PolicyError::ActorNotAllowed)?;
+    let role = actor.role();
+
+    match (action, resource) {
+        (Action::AssignRole, _) => allow_if(role.is_admin(), "Only admins can assign roles"),
+        (
+            Action::PurgeFragments
+            | Action::ClosePoll
+            | Action::ExpireFork
+            | Action::RecordDigest
+            | Action::UnsubscribeDigest
+            | Action::DisableWebhook,
+            _,
+        ) => Err(PolicyError::ActorNotAllowed),
+        (Action::ModerateComment, _) => {
+            allow_if(role.is_moderator(), "Only moderators can moderate comments")
+        }
+        (Action::ManageGlobalWebhooks, _) => allow_if(
+            role.is_admin(),
+            "Only admins can subscribe to the events of every story",
+        ),
+        (_, Resource::Any) => Ok(()),
+        (Action::RegisterUser, Resource::User(id)) => {
+            allow_if(user == id, "Users can only register themselves")
+        }
+        (Action::UpdateProfile, Resource::User(id)) => allow_if(
+            user == id || role.is_admin(),
+            "Only the user can update its profile",
+        ),
+        (Action::ManageTrustedContributors, Resource::User(id)) => allow_if(
+            user == id,
+            "Users can only manage their own trusted contributors",
+        ),
+        (Action::ReadNotifications, Resource::User(id)) => {
+            allow_if(user == id, "Users can only read their own notifications")
+        }
+        (Action::UpdateNotificationPreferences, Resource::User(id)) => allow_if(
+            user == id,
+            "Users can only change their own notification preferences",
+        ),
+        (Action::ManageWebhooks, Resource::User(id)) => allow_if(
+            user == id || role.is_admin(),
+            "Users can only manage their own webhooks",
+        ),
+        (Action::ManageWebhooks, Resource::Story(story)) => allow_if(
+            story.is_author(user) || role.is_admin(),
+            "Only the story author can subscribe to its events",
+        ),
+        (Action::WatchEvents, Resource::User(id)) => {
+            allow_if(user == id, "Users can only watch their own events")
+        }
+        (Action::ViewFragment | Action::WatchEvents, Resource::Fragment(fragment)) => allow_if(
+            fragment.is_published() || fragment.is_author(user) || role.is_moderator(),
+            "Unpublished fragments are only visible to their author",
+        ),
+        (
+            Action::ViewFragment | Action::WatchEvents,
+            Resource::Fork {
+                fork,
+                parent,
+                maintainer,
+            },
+        ) => allow_if(
+            fork.is_published()
+                || fork.is_author(user)
+                || (!fork.is_draft() && (parent.is_author(user) || maintainer))
+                || role.is_moderator(),
+            "Unpublished forks are only visible to their author and reviewers",
+        ),
+        (Action::UpdateFragment | Action::RevertFragment, Resource::Fragment(fragment)) => {
+            allow_if(
+                fragment.is_author(user) || role.is_moderator(),
+                "Only the fragment author can update it",
+            )
+        }
+        (Action::PublishFragment, Resource::Fragment(fragment)) => allow_if(
+            fragment.is_author(user) || role.is_moderator(),
+            "Only the fragment author can publish it",
+        ),
+        (Action::UnpublishFragment, Resource::Fragment(fragment)) => allow_if(
+            fragment.is_author(user) || role.is_moderator(),
+            "Only the fragment author or a moderator can unpublish it",
+        ),
+        (Action::DeleteFragment | Action::RestoreFragment, Resource::Fragment(fragment)) => {
+            allow_if(
+                fragment.is_author(user) || role.is_moderator(),
+                "Only the fragment author can delete or restore it",
+            )
+        }
+        (
+            Action::SubmitFork | Action::WithdrawFork | Action::ResubmitFork,
+            Resource::Fragment(fragment),
+        ) => allow_if(
+            fragment.is_author(user),
+            "Only the fork author can submit it",
+        ),
+        (Action::ResolveSuggestion, Resource::Fragment(fragment)) => allow_if(
+            fragment.is_author(user),
+            "Only the fork author can resolve suggestions",
+        ),
+        (Action::CreateStory, Resource::Fragment(fragment)) => allow_if(
+            fragment.is_author(user),
+            "Only the root fragment author can create its story",
+        ),
+        (Action::UpdateStory, Resource::Story(story)) => allow_if(
+            story.is_author(user) || role.is_moderator(),
+            "Only the story author can update it",
+        ),
+        (Action::TagContent, Resource::Fragment(fragment)) => allow_if(
+            fragment.is_author(user),
+            "Only the fragment author can tag it",
+        ),
+        (Action::TagContent, Resource::Story(story)) => {
+            allow_if(story.is_author(user), "Only the story author can tag it")
+        }
+        (Action::SetCanonicalBranch, Resource::Root { root, maintainer }) => allow_if(
+            root.is_author(user) || maintainer,
+            "Only the story author or its maintainers can choose its canonical branches",
+        ),
+        (Action::InviteMaintainer, Resource::Fragment(root)) => allow_if(
+            root.is_author(user),
+            "Only the story author can invite maintainers",
+        ),
+        (Action::OpenPoll, Resource::Fragment(fragment)) => allow_if(
+            fragment.is_author(user),
+            "Only the fragment author can open a poll on its children",
+        ),
+        (Action::SetReviewQuorum | Action::SetReviewSla, Resource::Fragment(root)) => allow_if(
+            root.is_author(user),
+            "Only the story author can change its review settings",
+        ),
+        (Action::SetForkPolicy, Resource::Fragment(fragment)) => allow_if(
+            fragment.is_author(user),
+            "Only the story author can change its fork policy",
+        ),
+        (
+            Action::ForkFragment,
+            Resource::ForkTarget {
+                parent,
+                policy,
+                audience,
+            },
+        ) => {
+            if parent.is_author(user) {
+                return Err(PolicyError::Forbidden("Cannot fork your own fragment"));
+            }
+            match policy {
+                ForkPolicy::Locked => Err(PolicyError::Forbidden("Story is locked for forks")),
+                _ if role.is_moderator() => Ok(()),
+                ForkPolicy::Anyone => Ok(()),
+                ForkPolicy::Followers => {
+                    allow_if(audience.follows_author, "You must follow the story author")
+                }
+                ForkPolicy::MutualFriends => {
+                    allow_if(audience.is_friend, "You must be fragment author friend")
+                }
+                ForkPolicy::InviteOnly => allow_if(
+                    audience.is_invited,
+                    "You must be invited to fork this story",
+                ),
+            }
+        }
+        (
+            Action::ReviewFork,
+            Resource::Fork {
+                fork,
+                parent,
+                maintainer,
+            },
+        ) => allow_if(
+            !fork.is_author(user) && (parent.is_author(user) || maintainer || role.is_moderator()),
+            "only the parent author or a story maintainer can review this fork",
+        ),
+        (
+            Action::ViewReviewContext | Action::DiscussReview,
+            Resource::Fork {
+                fork,
+                parent,
+                maintainer,
+            },
+        ) => allow_if(
+            parent.is_author(user) || fork.is_author(user) || maintainer || role.is_moderator(),
+            "Only the fork and parent authors can see the review context",
+        ),
+        (Action::EditComment, Resource::Comment(comment)) => allow_if(
+            comment.is_author(user),
+            "Only the comment author can edit it",
+        ),
+        (Action::DeleteComment, Resource::Comment(comment)) => allow_if(
+            comment.is_author(user) || role.is_moderator(),
+            "Only the comment author can delete it",
+        ),
+        (
+            Action::CreateFragment
+            | Action::CreateComment
+            | Action::LikeFragment
+            | Action::DislikeFragment
+            | Action::FollowUser
+            | Action::UnfollowUser,
+            _,
+        ) => Ok(()),
+        _ => Err(PolicyError::Forbidden(
+            "Action not allowed on this resource",
+        )),
+    }
+}
+
+/// Role an actor authorized to move the fragment along its lifecycle does so as. Users who
+/// neither wrote the fragment nor moderate can only have been authorized as reviewers.
+pub fn lifecycle_role<A: ActorTrait + ?Sized>(actor: &A, fragment: &Fragment) -> LifecycleRole {
+    match actor.id() {
+        None => LifecycleRole::System,
+        Some(_) if actor.role().is_moderator() => LifecycleRole::Moderator,
+        Some(id) if fragment.is_author(id) => LifecycleRole::Author,
+        Some(_) => LifecycleRole::Reviewer,
+    }
+}
+
+/// Checks the actor may see the fragment, or the events about it. Unpublished forks are
+/// only visible to their author and, once submitted, to their reviewers.
+pub async fn authorize_view<A: ActorTrait + ?Sized>(
+    conn: &mut PgConnection,
+    actor: &A,
+    action: Action,
+    fragment: &Fragment,
+) -> Result<(), ViewError> {
+    if !fragment.is_fork() || fragment.is_published() {
+        return Ok(authorize(actor, action, Resource::Fragment(fragment))?);
+    }
+
+    let Some(parent) = fragment.get_parent(&mut *conn).await? else {
+        return Ok(authorize(actor, action, Resource::Fragment(fragment))?);
+    };
+    let maintainer = match actor.id() {
+        Some(id) => Maintainer::is_maintainer(conn, &fragment.root_id(), &id).await?,
+        None => false,
+    };
+    Ok(authorize(
+        actor,
+        action,
+        Resource::Fork {
+            fork: fragment,
+            parent: &parent,
+            maintainer,
+        },
+    )?)
+}
+
+const fn allow_if(condition: bool, reason: &'static str) -> Result<(), PolicyError> {
+    if condition {
+        Ok(())
+    } else {
+        Err(PolicyError::Forbidden(reason))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commons::{
+        actor::{Actor, Role},
+        time::DateTime,
+    };
+    use storage::model::{
+        comment::CommentBuilder,
+        fragment::{FragmentBuilder, FragmentState, Transition, TransitionError},
+    };
+
+    #[derive(Debug)]
+    struct TestActor(Actor, Role);
+
+    impl ActorTrait for TestActor {
+        fn actor(&self) -> Actor {
+            self.0
+        }
+
+        fn role(&self) -> Role {
+            self.1
+        }
+    }
+
+    fn user(role: Role) -> TestActor {
+        TestActor(Actor::User(Id::new()), role)
+    }
+
+    fn fragment(author: &TestActor) -> Fragment {
+        FragmentBuilder::default()
+            .id(Id::new())
+            .author_id(author.id().unwrap())
+            .content("content")
+            .state(FragmentState::Published)
+            .created_at(DateTime::now())
+            .last_modified_at(DateTime::now())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_lifecycle_role() {
+        let author = user(Role::User);
+        let reviewer = user(Role::User);
+        let moderator = user(Role::Moderator);
+        let fork = FragmentBuilder::default()
+            .id(Id::new())
+            .author_id(author.id().unwrap())
+            .parent_id(Some(Id::new()))
+            .content("content")
+            .state(FragmentState::Submitted)
+            .created_at(DateTime::now())
+            .last_modified_at(DateTime::now())
+            .build()
+            .unwrap();
+        let approve = |actor: &TestActor| {
+            fork.clone().transition(
+                Transition::Approve,
+                actor.actor(),
+                lifecycle_role(actor, &fork),
+                DateTime::now(),
+            )
+        };
+
+        assert_eq!(lifecycle_role(&author, &fork), LifecycleRole::Author);
+        assert!(matches!(
+            approve(&author),
+            Err(TransitionError::Forbidden {
+                role: LifecycleRole::Reviewer,
+                ..
+            })
+        ));
+        assert!(approve(&reviewer).is_ok());
+        assert!(approve(&moderator).is_ok());
+        let system = TestActor(Actor::System, Role::User);
+        assert_eq!(lifecycle_role(&system, &fork), LifecycleRole::System);
+        assert!(approve(&system).is_ok());
+    }
+
+    #[test]
+    fn test_system_actor_is_not_allowed() {
+        let system = TestActor(Actor::System, Role::User);
+        assert_eq!(
+            authorize(&system, Action::CreateFragment, Resource::Any),
+            Err(PolicyError::ActorNotAllowed)
+        );
+    }
+
+    #[test]
+    fn test_purge_is_system_only() {
+        let system = TestActor(Actor::System, Role::User);
+        assert!(authorize(&system, Action::PurgeFragments, Resource::Any).is_ok());
+        assert_eq!(
+            authorize(&user(Role::Admin), Action::PurgeFragments, Resource::Any),
+            Err(PolicyError::ActorNotAllowed)
+        );
+    }
+
+    #[test]
+    fn test_system_assigns_roles() {
+        let system = TestActor(Actor::System, Role::User);
+        assert!(authorize(&system, Action::AssignRole, Resource::Any).is_ok());
+        assert!(authorize(&user(Role::Admin), Action::AssignRole, Resource::Any).is_ok());
+        assert!(authorize(&user(Role::Moderator), Action::AssignRole, Resource::Any).is_err());
+    }
+
+    #[test]
+    fn test_close_poll_is_system_only() {
+        let system = TestActor(Actor::System, Role::User);
+        assert!(authorize(&system, Action::ClosePoll, Resource::Any).is_ok());
+        assert!(authorize(&system, Action::OpenPoll, Resource::Any).is_err());
+        assert_eq!(
+            authorize(&user(Role::Admin), Action::ClosePoll, Resource::Any),
+            Err(PolicyError::ActorNotAllowed)
+        );
+    }
+
+    #[test]
+    fn test_expire_fork_is_system_only() {
+        let system = TestActor(Actor::System, Role::User);
+        assert!(authorize(&system, Action::ExpireFork, Resource::Any).is_ok());
+        assert!(authorize(&system, Action::SetReviewSla, Resource::Any).is_err());
+        assert_eq!(
+            authorize(&user(Role::Moderator), Action::ExpireFork, Resource::Any),
+            Err(PolicyError::ActorNotAllowed)
+        );
+    }
+
+    #[test]
+    fn test_manage_trusted_contributors() {
+        let author = user(Role::User);
+        let id = author.id().unwrap();
+        assert!(authorize(
+            &author,
+            Action::ManageTrustedContributors,
+            Resource::User(id)
+        )
+        .is_ok());
+        assert!(authorize(
+            &user(Role::Admin),
+            Action::ManageTrustedContributors,
+            Resource::User(id)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_read_notifications() {
+        let owner = user(Role::User);
+        let id = owner.id().unwrap();
+        assert!(authorize(&owner, Action::ReadNotifications, Resource::User(id)).is_ok());
+        assert_eq!(
+            authorize(
+                &user(Role::Admin),
+                Action::ReadNotifications,
+                Resource::User(id)
+            ),
+            Err(PolicyError::Forbidden(
+                "Users can only read their own notifications"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_notification_preferences() {
+        let owner = user(Role::User);
+        let id = owner.id().unwrap();
+        assert!(authorize(
+            &owner,
+            Action::UpdateNotificationPreferences,
+            Resource::User(id)
+        )
+        .is_ok());
+        assert!(authorize(
+            &user(Role::Admin),
+            Action::UpdateNotificationPreferences,
+            Resource::User(id)
+        )
+        .is_err());
+
+        let system = TestActor(Actor::System, Role::User);
+        assert!(authorize(&system, Action::RecordDigest, Resource::Any).is_ok());
+        assert!(authorize(&system, Action::UnsubscribeDigest, Resource::Any).is_ok());
+        assert_eq!(
+            authorize(&owner, Action::UnsubscribeDigest, Resource::Any),
+            Err(PolicyError::ActorNotAllowed)
+        );
+    }
+
+    #[test]
+    fn test_manage_webhooks() {
+        let owner = user(Role::User);
+        let id = owner.id().unwrap();
+        assert!(authorize(&owner, Action::ManageWebhooks, Resource::User(id)).is_ok());
+        assert!(authorize(
+            &user(Role::Admin),
+            Action::ManageWebhooks,
+            Resource::User(id)
+        )
+        .is_ok());
+        assert_eq!(
+            authorize(
+                &user(Role::Moderator),
+                Action::ManageWebhooks,
+                Resource::User(id)
+            ),
+            Err(PolicyError::Forbidden(
+                "Users can only manage their own webhooks"
+            ))
+        );
+        assert!(authorize(&owner, Action::ManageGlobalWebhooks, Resource::Any).is_err());
+        assert!(authorize(
+            &user(Role::Admin),
+            Action::ManageGlobalWebhooks,
+            Resource::Any
+        )
+        .is_ok());
+
+        let system = TestActor(Actor::System, Role::User);
+        assert!(authorize(&system, Action::DisableWebhook, Resource::Any).is_ok());
+        assert_eq!(
+            authorize(&user(Role::Admin), Action::DisableWebhook, Resource::Any),
+            Err(PolicyError::ActorNotAllowed)
+        );
+    }
+
+    #[test]
+    fn test_watch_events() {
+        let author = user(Role::User);
+        let id = author.id().unwrap();
+        assert!(authorize(&author, Action::WatchEvents, Resource::User(id)).is_ok());
+        assert!(authorize(&user(Role::Admin), Action::WatchEvents, Resource::User(id)).is_err());
+
+        let published = fragment(&author);
+        let draft = fragment(&author).set_state(FragmentState::Draft);
+        let reader = user(Role::User);
+        assert!(authorize(&reader, Action::WatchEvents, Resource::Fragment(&published)).is_ok());
+        assert!(authorize(&author, Action::WatchEvents, Resource::Fragment(&draft)).is_ok());
+        assert!(authorize(&author, Action::ViewFragment, Resource::Fragment(&draft)).is_ok());
+        assert!(authorize(&reader, Action::ViewFragment, Resource::Fragment(&draft)).is_err());
+        assert_eq!(
+            authorize(&reader, Action::WatchEvents, Resource::Fragment(&draft)),
+            Err(PolicyError::Forbidden(
+                "Unpublished fragments are only visible to their author"
+            ))
+        );
+
+        let fork = fragment(&reader).set_state(FragmentState::Draft);
+        let resource = Resource::Fork {
+            fork: &fork,
+            parent: &published,
+            maintainer: false,
+        };
+        assert!(authorize(&reader, Action::WatchEvents, resource).is_ok());
+        assert!(authorize(&author, Action::WatchEvents, resource).is_err());
+
+        let fork = fork.set_state(FragmentState::Submitted);
+        let resource = Resource::Fork {
+            fork: &fork,
+            parent: &published,
+            maintainer: false,
+        };
+        assert!(authorize(&author, Action::WatchEvents, resource).is_ok());
+        assert!(authorize(&user(Role::User), Action::WatchEvents, resource).is_err());
+    }
+
+    #[test]
+    fn test_delete_fragment() {
+        let author = user(Role::User);
+        let frag = fragment(&author);
+
+        assert!(authorize(&author, Action::DeleteFragment, Resource::Fragment(&frag)).is_ok());
+        assert!(authorize(
+            &user(Role::User),
+            Action::DeleteFragment,
+            Resource::Fragment(&frag)
+        )
+        .is_err());
+        assert!(authorize(
+            &user(Role::Moderator),
+            Action::RestoreFragment,
+            Resource::Fragment(&frag)
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_update_fragment() {
+        let author = user(Role::User);
+        let frag = fragment(&author);
+
+        assert!(authorize(&author, Action::UpdateFragment, Resource::Fragment(&frag)).is_ok());
+        assert!(authorize(
+            &user(Role::User),
+            Action::UpdateFragment,
+            Resource::Fragment(&frag)
+        )
+        .is_err());
+        assert!(authorize(
+            &user(Role::Moderator),
+            Action::UpdateFragment,
+            Resource::Fragment(&frag)
+        )
+        .is_ok());
+        assert!(authorize(
+            &user(Role::Admin),
+            Action::UpdateFragment,
+            Resource::Fragment(&frag)
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_submit_fork_is_author_only() {
+        let author = user(Role::User);
+        let frag = fragment(&author);
+
+        assert!(authorize(&author, Action::SubmitFork, Resource::Fragment(&frag)).is_ok());
+        assert!(authorize(
+            &user(Role::Moderator),
+            Action::SubmitFork,
+            Resource::Fragment(&frag)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_fork_fragment() {
+        let author = user(Role::User);
+        let parent = fragment(&author);
+        let target = |is_friend| Resource::ForkTarget {
+            parent: &parent,
+            policy: ForkPolicy::MutualFriends,
+            audience: ForkAudience {
+                is_friend,
+                ..Default::default()
+            },
+        };
+
+        assert_eq!(
+            authorize(&author, Action::ForkFragment, target(true)),
+            Err(PolicyError::Forbidden("Cannot fork your own fragment"))
+        );
+        assert!(authorize(&user(Role::User), Action::ForkFragment, target(true)).is_ok());
+        assert!(authorize(&user(Role::User), Action::ForkFragment, target(false)).is_err());
+        assert!(authorize(&user(Role::Moderator), Action::ForkFragment, target(false)).is_ok());
+    }
+
+    #[test]
+    fn test_fork_policies() {
+        let author = user(Role::User);
+        let parent = fragment(&author);
+        let forker = user(Role::User);
+        let target = |policy, audience| Resource::ForkTarget {
+            parent: &parent,
+            policy,
+            audience,
+        };
+        let stranger = ForkAudience::default();
+        let follower = ForkAudience {
+            follows_author: true,
+            ..Default::default()
+        };
+        let invited = ForkAudience {
+            is_invited: true,
+            ..Default::default()
+        };
+
+        assert!(authorize(
+            &forker,
+            Action::ForkFragment,
+            target(ForkPolicy::Anyone, stranger)
+        )
+        .is_ok());
+        assert!(authorize(
+            &forker,
+            Action::ForkFragment,
+            target(ForkPolicy::Followers, stranger)
+        )
+        .is_err());
+        assert!(authorize(
+            &forker,
+            Action::ForkFragment,
+            target(ForkPolicy::Followers, follower)
+        )
+        .is_ok());
+        assert!(authorize(
+            &forker,
+            Action::ForkFragment,
+            target(ForkPolicy::InviteOnly, follower)
+        )
+        .is_err());
+        assert!(authorize(
+            &forker,
+            Action::ForkFragment,
+            target(ForkPolicy::InviteOnly, invited)
+        )
+        .is_ok());
+        assert_eq!(
+            authorize(
+                &user(Role::Admin),
+                Action::ForkFragment,
+                target(ForkPolicy::Locked, invited)
+            ),
+            Err(PolicyError::Forbidden("Story is locked for forks"))
+        );
+    }
+
+    #[test]
+    fn test_set_fork_policy_is_author_only() {
+        let author = user(Role::User);
+        let root = fragment(&author);
+
+        assert!(authorize(&author, Action::SetForkPolicy, Resource::Fragment(&root)).is_ok());
+        assert!(authorize(
+            &user(Role::Admin),
+            Action::SetForkPolicy,
+            Resource::Fragment(&root)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_tag_content_is_author_only() {
+        let author = user(Role::User);
+        let root = fragment(&author);
+
+        assert!(authorize(&author, Action::TagContent, Resource::Fragment(&root)).is_ok());
+        assert!(authorize(
+            &user(Role::Moderator),
+            Action::TagContent,
+            Resource::Fragment(&root)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_review_fork() {
+        let parent_author = user(Role::User);
+        let fork_author = user(Role::User);
+        let parent = fragment(&parent_author);
+        let fork = fragment(&fork_author);
+        let resource = Resource::Fork {
+            fork: &fork,
+            parent: &parent,
+            maintainer: false,
+        };
+
+        assert!(authorize(&parent_author, Action::ReviewFork, resource).is_ok());
+        assert!(authorize(&fork_author, Action::ReviewFork, resource).is_err());
+        assert!(authorize(&user(Role::Moderator), Action::ReviewFork, resource).is_ok());
+
+        let moderator = user(Role::Moderator);
+        let own_fork = fragment(&moderator);
+        let own = Resource::Fork {
+            fork: &own_fork,
+            parent: &parent,
+            maintainer: false,
+        };
+        assert!(authorize(&moderator, Action::ReviewFork, own).is_err());
+
+        assert!(authorize(&parent_author, Action::ViewReviewContext, resource).is_ok());
+        assert!(authorize(&fork_author, Action::ViewReviewContext, resource).is_ok());
+        assert!(authorize(&user(Role::User), Action::ViewReviewContext, resource).is_err());
+        assert!(authorize(&fork_author, Action::DiscussReview, resource).is_ok());
+        assert!(authorize(&user(Role::User), Action::DiscussReview, resource).is_err());
+
+        let fork = Resource::Fragment(&fork);
+        assert!(authorize(&fork_author, Action::ResolveSuggestion, fork).is_ok());
+        assert!(authorize(&parent_author, Action::ResolveSuggestion, fork).is_err());
+        assert!(authorize(&user(Role::Moderator), Action::ResolveSuggestion, fork).is_err());
+    }
+
+    #[test]
+    fn test_maintainer_reviews_fork() {
+        let maintainer = user(Role::User);
+        let parent = fragment(&user(Role::User));
+        let fork = fragment(&user(Role::User));
+        let own_fork = fragment(&maintainer);
+
+        let resource = Resource::Fork {
+            fork: &fork,
+            parent: &parent,
+            maintainer: true,
+        };
+        assert!(authorize(&maintainer, Action::ReviewFork, resource).is_ok());
+        assert!(authorize(&maintainer, Action::ViewReviewContext, resource).is_ok());
+        assert!(authorize(&maintainer, Action::DiscussReview, resource).is_ok());
+
+        let resource = Resource::Fork {
+            fork: &own_fork,
+            parent: &parent,
+            maintainer: true,
+        };
+        assert!(authorize(&maintainer, Action::ReviewFork, resource).is_err());
+        assert!(authorize(&maintainer, Action::ViewReviewContext, resource).is_ok());
+
+        let root = fragment(&maintainer);
+        assert!(authorize(
+            &maintainer,
+            Action::InviteMaintainer,
+            Resource::Fragment(&root)
+        )
+        .is_ok());
+        assert!(authorize(
+            &user(Role::Moderator),
+            Action::InviteMaintainer,
+            Resource::Fragment(&root)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_comments() {
+        let author = user(Role::User);
+        let comment = CommentBuilder::default()
+            .id(Id::new())
+            .fragment_id(Id::new())
+            .author_id(author.id().unwrap())
+            .body(commons::review::Comment::from(String::from("comment")))
+            .created_at(DateTime::now())
+            .last_modified_at(DateTime::now())
+            .build()
+            .unwrap();
+        let resource = Resource::Comment(&comment);
+
+        assert!(authorize(&author, Action::EditComment, resource).is_ok());
+        assert!(authorize(&user(Role::Moderator), Action::EditComment, resource).is_err());
+        assert!(authorize(&author, Action::DeleteComment, resource).is_ok());
+        assert!(authorize(&user(Role::User), Action::DeleteComment, resource).is_err());
+        assert!(authorize(&user(Role::Moderator), Action::DeleteComment, resource).is_ok());
+        assert!(authorize(&author, Action::ModerateComment, Resource::Any).is_err());
+        assert!(authorize(
+            &user(Role::Moderator),
+            Action::ModerateComment,
+            Resource::Any
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_assign_role_is_admin_only() {
+        let target = Resource::User(Id::new());
+
+        assert!(authorize(&user(Role::Admin), Action::AssignRole, target).is_ok());
+        assert!(authorize(&user(Role::Moderator), Action::AssignRole, target).is_err());
+        assert!(authorize(&user(Role::User), Action::AssignRole, Resource::Any).is_err());
+    }
+
+    #[test]
+    fn test_profile_updates() {
+        let owner = user(Role::User);
+        let profile = Resource::User(owner.id().unwrap());
+
+        assert!(authorize(&owner, Action::UpdateProfile, profile).is_ok());
+        assert!(authorize(&user(Role::User), Action::UpdateProfile, profile).is_err());
+        assert!(authorize(&user(Role::Admin), Action::UpdateProfile, profile).is_ok());
+        assert!(authorize(&user(Role::User), Action::RegisterUser, profile).is_err());
+    }
+}
@@ -0,0 +1,23 @@
+use super::Projection;
+use crate::command_bus::{bus::Ctx, error::CommandBusError};
+use storage::{model::event::DbEvent, query::event::QueryEvent};
+use tap::TapFallible;
+
+/// Announces the event to live streams. The notification is sent with the transaction,
+/// so streams never see an event that ends up rolled back.
+pub struct LiveProjection;
+
+#[async_trait::async_trait]
+impl Projection for LiveProjection {
+    async fn project<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+        event: &DbEvent,
+    ) -> Result<(), CommandBusError> {
+        event
+            .notify(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to notify event: {e}"))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+pub mod live;
+pub mod notifications;
+pub mod webhooks;
+
+use crate::command_bus::{bus::Ctx, error::CommandBusError};
+use live::LiveProjection;
+use notifications::NotificationProjection;
+use storage::model::event::DbEvent;
+use webhooks::WebhookProjection;
+
+/// Read model derived from the events of the command bus. Projections run in the
+/// transaction of the command that produced the event, so they never lag behind it.
+#[async_trait::async_trait]
+pub trait Projection: Send + Sync {
+    async fn project<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+        event: &DbEvent,
+    ) -> Result<(), CommandBusError>;
+}
+
+/// Every projection fed by the command bus.
+const PROJECTIONS: &[&dyn Projection] =
+    &[&NotificationProjection, &WebhookProjection, &LiveProjection];
+
+pub(crate) async fn project(ctx: &mut Ctx<'_>, event: &DbEvent) -> Result<(), CommandBusError> {
+    for projection in PROJECTIONS {
+        projection.project(ctx, event).await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,200 @@
+use super::Projection;
+use crate::{
+    command_bus::{bus::Ctx, error::CommandBusError},
+    events::{
+        ForkExpiredEvent, ForkSubmittedEvent, FragmentForkReviewedEvent, FragmentForkedEvent,
+        FragmentLikedEvent, UserFollowedEvent,
+    },
+};
+use commons::{events::EventType, id::Id, time::DateTime};
+use storage::{
+    model::{
+        event::DbEvent,
+        fragment::Fragment,
+        notification::{NotificationBuilder, NotificationKind},
+        notification_preferences::NotificationPreferences,
+        review_sla::SlaOutcome,
+    },
+    query::{
+        fragment::QueryFragment, notification::QueryNotification,
+        notification_preferences::QueryNotificationPreferences,
+    },
+};
+use tap::TapFallible;
+
+/// Fills the notification inbox of users.
+pub struct NotificationProjection;
+
+/// Notification an event should produce.
+struct Target {
+    recipient: Id,
+    kind: NotificationKind,
+    subject_id: Id,
+    /// User who triggered it, `None` for the system.
+    actor_id: Option<Id>,
+}
+
+impl NotificationProjection {
+    async fn targets(ctx: &mut Ctx<'_>, event: &DbEvent) -> Result<Vec<Target>, CommandBusError> {
+        let data = event.event_data();
+        let target = match event.event_type() {
+            EventType::FragmentForkReviewed => {
+                let event: FragmentForkReviewedEvent = data.into_event();
+                let (Some(actor_id), Some(fork)) = (
+                    event.actor.id(),
+                    Fragment::find(ctx.tx().as_mut(), &event.fragment_id).await?,
+                ) else {
+                    return Ok(Vec::new());
+                };
+                Target {
+                    recipient: *fork.author_id(),
+                    kind: NotificationKind::ForkReviewed,
+                    subject_id: *fork.id(),
+                    actor_id: Some(actor_id),
+                }
+            }
+            EventType::FragmentForked => {
+                let event: FragmentForkedEvent = data.into_event();
+                let Some(parent) =
+                    Fragment::find(ctx.tx().as_mut(), &event.parent_fragment_id).await?
+                else {
+                    return Ok(Vec::new());
+                };
+                Target {
+                    recipient: *parent.author_id(),
+                    kind: NotificationKind::FragmentForked,
+                    subject_id: *parent.id(),
+                    actor_id: Some(event.user_id),
+                }
+            }
+            EventType::ForkSubmitted => {
+                let event: ForkSubmittedEvent = data.into_event();
+                let Some(actor_id) = event.actor.id() else {
+                    return Ok(Vec::new());
+                };
+                let Some(fork) = Fragment::find(ctx.tx().as_mut(), &event.fragment_id).await?
+                else {
+                    return Ok(Vec::new());
+                };
+                let Some(parent) = fork.get_parent(ctx.tx().as_mut()).await? else {
+                    return Ok(Vec::new());
+                };
+                Target {
+                    recipient: *parent.author_id(),
+                    kind: NotificationKind::ForkSubmitted,
+                    subject_id: *parent.id(),
+                    actor_id: Some(actor_id),
+                }
+            }
+            EventType::FragmentLiked => {
+                let event: FragmentLikedEvent = data.into_event();
+                let Some(fragment) = Fragment::find(ctx.tx().as_mut(), &event.fragment_id).await?
+                else {
+                    return Ok(Vec::new());
+                };
+                Target {
+                    recipient: *fragment.author_id(),
+                    kind: NotificationKind::FragmentLiked,
+                    subject_id: *fragment.id(),
+                    actor_id: Some(event.user_id),
+                }
+            }
+            EventType::UserFollowed => {
+                let event: UserFollowedEvent = data.into_event();
+                Target {
+                    recipient: event.following_id,
+                    kind: NotificationKind::UserFollowed,
+                    subject_id: event.following_id,
+                    actor_id: Some(event.follower_id),
+                }
+            }
+            EventType::ForkAutoRejected | EventType::ForkReturnedToDraft => {
+                let event: ForkExpiredEvent = data.into_event();
+                let kind = match event.outcome {
+                    SlaOutcome::Reject => NotificationKind::ForkAutoRejected,
+                    _ => NotificationKind::ForkReturnedToDraft,
+                };
+                Target {
+                    recipient: event.author_id,
+                    kind,
+                    subject_id: event.fragment_id,
+                    actor_id: event.actor.id(),
+                }
+            }
+            EventType::ForkEscalated => {
+                let event: ForkExpiredEvent = data.into_event();
+                let Some(fork) = Fragment::find(ctx.tx().as_mut(), &event.fragment_id).await?
+                else {
+                    return Ok(Vec::new());
+                };
+                // The parent author reviews the fork along with the maintainers.
+                let mut reviewers = event.maintainers;
+                if let Some(parent) = fork.get_parent(ctx.tx().as_mut()).await? {
+                    if !reviewers.contains(parent.author_id()) {
+                        reviewers.push(*parent.author_id());
+                    }
+                }
+                return Ok(reviewers
+                    .into_iter()
+                    .map(|recipient| Target {
+                        recipient,
+                        kind: NotificationKind::ForkEscalated,
+                        subject_id: event.fragment_id,
+                        actor_id: event.actor.id(),
+                    })
+                    .collect());
+            }
+            _ => return Ok(Vec::new()),
+        };
+
+        Ok(vec![target])
+    }
+
+    async fn notify(
+        ctx: &mut Ctx<'_>,
+        target: Target,
+        at: DateTime,
+    ) -> Result<(), CommandBusError> {
+        // Nobody needs to hear about their own actions.
+        if Some(target.recipient) == target.actor_id {
+            return Ok(());
+        }
+        let muted = NotificationPreferences::find(ctx.tx().as_mut(), &target.recipient)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find notification preferences: {e}"))?
+            .is_some_and(|preferences| preferences.is_muted(target.kind));
+        if muted {
+            return Ok(());
+        }
+
+        NotificationBuilder::default()
+            .id(ctx.ids().new_id())
+            .user_id(target.recipient)
+            .kind(target.kind)
+            .subject_id(target.subject_id)
+            .actor_ids(target.actor_id.into_iter().collect::<Vec<_>>())
+            .created_at(at)
+            .updated_at(at)
+            .build()
+            .map_err(anyhow::Error::from)?
+            .save_grouped(ctx.tx().as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save notification: {e}"))?;
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Projection for NotificationProjection {
+    async fn project<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+        event: &DbEvent,
+    ) -> Result<(), CommandBusError> {
+        for target in Self::targets(ctx, event).await? {
+            Self::notify(ctx, target, *event.timestamp()).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use super::Projection;
+use crate::{
+    command_bus::{bus::Ctx, error::CommandBusError},
+    live::event_fragment,
+    policy::{authorize_view, Action, ViewError},
+};
+use commons::id::Id;
+use serde_json::{json, Value};
+use storage::{
+    model::{
+        event::DbEvent,
+        fragment::Fragment,
+        story::Story,
+        user::User,
+        webhook::{Webhook, WebhookDeliveryBuilder},
+    },
+    query::{
+        story::QueryStory,
+        user::QueryUser,
+        webhook::{QueryWebhook, QueryWebhookDelivery},
+    },
+};
+use tap::TapFallible;
+
+/// Queues a delivery of the event for every webhook subscribed to it. The delivery job
+/// sends them afterwards, so a slow endpoint never holds a command back.
+pub struct WebhookProjection;
+
+impl WebhookProjection {
+    /// Story the event belongs to, named by the event or found from its fragment.
+    async fn story_id(
+        ctx: &mut Ctx<'_>,
+        data: &Value,
+        fragment: Option<&Fragment>,
+    ) -> Result<Option<Id>, CommandBusError> {
+        if let Some(story_id) = data
+            .get("story_id")
+            .and_then(|value| serde_json::from_value::<Id>(value.clone()).ok())
+        {
+            return Ok(Some(story_id));
+        }
+        let Some(fragment) = fragment else {
+            return Ok(None);
+        };
+        Ok(
+            Story::find_by_fragment(ctx.tx().as_mut(), &fragment.root_id())
+                .await?
+                .map(|story| *story.id()),
+        )
+    }
+
+    /// Whether the webhook owner may see the fragment the event is about, so that drafts
+    /// are never sent to anyone but the users allowed to read them.
+    async fn is_visible(
+        ctx: &mut Ctx<'_>,
+        webhook: &Webhook,
+        fragment: &Fragment,
+    ) -> Result<bool, CommandBusError> {
+        let Some(owner) = User::find(ctx.tx().as_mut(), webhook.owner_id()).await? else {
+            return Ok(false);
+        };
+        match authorize_view(ctx.tx().as_mut(), &owner, Action::WatchEvents, fragment).await {
+            Ok(()) => Ok(true),
+            Err(ViewError::Storage(e)) => Err(e.into()),
+            Err(ViewError::Forbidden(_)) => Ok(false),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Projection for WebhookProjection {
+    async fn project<'ctx>(
+        &self,
+        ctx: &mut Ctx<'ctx>,
+        event: &DbEvent,
+    ) -> Result<(), CommandBusError> {
+        let webhooks = Webhook::find_subscribed(ctx.tx().as_mut(), event.event_type())
+            .await
+            .tap_err(|e| tracing::error!("Failed to find webhooks: {e}"))?;
+        if webhooks.is_empty() {
+            return Ok(());
+        }
+
+        let data: Value = event.event_data().into_event();
+        let fragment = event_fragment(ctx.tx().as_mut(), &data).await?;
+        let story_id = Self::story_id(ctx, &data, fragment.as_ref()).await?;
+        let payload = json!({
+            "id": event.id(),
+            "event_type": event.event_type(),
+            "story_id": story_id,
+            "timestamp": event.timestamp(),
+            "data": data,
+        });
+
+        for webhook in webhooks {
+            if webhook.story_id().is_some() && *webhook.story_id() != story_id {
+                continue;
+            }
+            if let Some(fragment) = &fragment {
+                if !Self::is_visible(ctx, &webhook, fragment).await? {
+                    continue;
+                }
+            }
+            WebhookDeliveryBuilder::default()
+                .id(ctx.ids().new_id())
+                .webhook_id(*webhook.id())
+                .event_id(*event.id())
+                .event_type(*event.event_type())
+                .payload(payload.clone())
+                .next_attempt_at(*event.timestamp())
+                .created_at(*event.timestamp())
+                .build()
+                .map_err(anyhow::Error::from)?
+                .save(ctx.tx().as_mut())
+                .await
+                .tap_err(|e| tracing::error!("Failed to queue webhook delivery: {e}"))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,331 @@
+use crate::command_bus::{
+    bus::CommandBus, command::disable_webhook::DisableWebhookCommandBuilder, error::CommandBusError,
+};
+use chrono::Duration;
+use commons::{
+    actor::Actor, configuration::settings::WebhookSettings, id::IdGenerator, time::Clock,
+};
+use hyper::client::connect::dns::Name;
+use reqwest::{
+    dns::{Addrs, Resolve, Resolving},
+    header::CONTENT_TYPE,
+    redirect, Client,
+};
+use sqlx::PgPool;
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+use storage::{
+    model::webhook::{
+        Webhook, WebhookAttempt, WebhookAttemptBuilder, WebhookDelivery, WebhookDeliveryState,
+    },
+    query::webhook::{QueryWebhook, QueryWebhookAttempt, QueryWebhookDelivery},
+};
+use tap::TapFallible;
+use url::{Host, Url};
+
+/// Deliveries attempted by a single run of the job.
+const BATCH_SIZE: usize = 100;
+
+/// Time a claimed delivery has to be attempted and recorded on top of the request timeout
+/// before another run may claim it again.
+const LEASE_MARGIN: i64 = 60;
+
+/// Header holding the hex encoded HMAC-SHA256 of the body, keyed with the webhook secret.
+pub const SIGNATURE_HEADER: &str = "X-Tales-Signature";
+pub const EVENT_HEADER: &str = "X-Tales-Event";
+pub const DELIVERY_HEADER: &str = "X-Tales-Delivery";
+
+/// Posts the queued events to the webhook endpoints.
+pub struct WebhookDeliveryJob {
+    pool: PgPool,
+    command_bus: Arc<CommandBus>,
+    clock: Arc<dyn Clock>,
+    ids: Arc<dyn IdGenerator>,
+    client: Client,
+    settings: WebhookSettings,
+}
+
+impl WebhookDeliveryJob {
+    pub fn new(
+        pool: PgPool,
+        command_bus: Arc<CommandBus>,
+        clock: Arc<dyn Clock>,
+        ids: Arc<dyn IdGenerator>,
+        settings: WebhookSettings,
+    ) -> Self {
+        Self {
+            pool,
+            command_bus,
+            clock,
+            ids,
+            client: Client::builder()
+                // A redirect could lead to an internal address without being checked.
+                .redirect(redirect::Policy::none())
+                .dns_resolver(Arc::new(PublicResolver {
+                    allowed_hosts: settings.allowed_hosts.clone(),
+                }))
+                .build()
+                .expect("Failed to build the webhook HTTP client"),
+            settings,
+        }
+    }
+
+    /// Attempts every delivery that is due, returning how many were attempted. Each
+    /// delivery is claimed first so that concurrent runs never attempt the same one.
+    ///
+    /// A failed delivery is retried with an exponential backoff until it runs out of
+    /// attempts. Endpoints failing too many times in a row are disabled until their owner
+    /// enables them again.
+    pub async fn run(&self) -> Result<usize, CommandBusError> {
+        let mut attempted = 0;
+        while attempted < BATCH_SIZE {
+            let now = self.clock.now();
+            let Some(delivery) =
+                WebhookDelivery::claim_due(&self.pool, &now, &(now + self.lease()))
+                    .await
+                    .tap_err(|e| tracing::error!("Failed to claim webhook delivery: {e}"))?
+            else {
+                break;
+            };
+
+            // The webhook may have been disabled since the delivery was claimed.
+            let Some(webhook) = Webhook::find(&self.pool, delivery.webhook_id())
+                .await
+                .tap_err(|e| tracing::error!("Failed to find webhook: {e}"))?
+                .filter(|webhook| *webhook.enabled())
+            else {
+                continue;
+            };
+
+            let attempt = self.attempt(&webhook, &delivery).await?;
+            self.record(webhook, delivery, attempt).await?;
+            attempted += 1;
+        }
+
+        Ok(attempted)
+    }
+
+    async fn attempt(
+        &self,
+        webhook: &Webhook,
+        delivery: &WebhookDelivery,
+    ) -> Result<WebhookAttempt, CommandBusError> {
+        let body = delivery.payload().to_string();
+        if let Err(e) = check_endpoint(webhook.url(), &self.settings.allowed_hosts).await {
+            return self.attempt_record(delivery, None, Some(e.to_string()));
+        }
+        let result = self
+            .client
+            .post(webhook.url())
+            .timeout(std::time::Duration::from_secs(self.settings.timeout))
+            .header(CONTENT_TYPE, "application/json")
+            .header(
+                EVENT_HEADER,
+                delivery.payload()["event_type"]
+                    .as_str()
+                    .unwrap_or_default(),
+            )
+            .header(DELIVERY_HEADER, delivery.id().to_string())
+            .header(
+                SIGNATURE_HEADER,
+                format!("sha256={}", webhook.secret().sign(body.as_bytes())),
+            )
+            .body(body)
+            .send()
+            .await;
+
+        let (status_code, error) = match result {
+            Ok(response) => (Some(i32::from(response.status().as_u16())), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        self.attempt_record(delivery, status_code, error)
+    }
+
+    fn attempt_record(
+        &self,
+        delivery: &WebhookDelivery,
+        status_code: Option<i32>,
+        error: Option<String>,
+    ) -> Result<WebhookAttempt, CommandBusError> {
+        Ok(WebhookAttemptBuilder::default()
+            .id(self.ids.new_id())
+            .delivery_id(*delivery.id())
+            .status_code(status_code)
+            .error(error)
+            .attempted_at(self.clock.now())
+            .build()
+            .map_err(anyhow::Error::from)?)
+    }
+
+    async fn record(
+        &self,
+        webhook: Webhook,
+        delivery: WebhookDelivery,
+        attempt: WebhookAttempt,
+    ) -> Result<(), CommandBusError> {
+        let now = self.clock.now();
+        let attempts = delivery.attempts() + 1;
+        let success = attempt.is_success();
+        let delivery = delivery.set_attempts(attempts);
+        let delivery = if success {
+            delivery
+                .set_state(WebhookDeliveryState::Delivered)
+                .set_completed_at(Some(now))
+        } else if attempts >= self.settings.max_attempts as i32 {
+            delivery
+                .set_state(WebhookDeliveryState::Failed)
+                .set_completed_at(Some(now))
+        } else {
+            delivery.set_next_attempt_at(now + self.backoff(attempts))
+        };
+        let failures = if success {
+            0
+        } else {
+            webhook.consecutive_failures() + 1
+        };
+
+        let mut tx = self.pool.begin().await?;
+        attempt
+            .save(tx.as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to save webhook attempt: {e}"))?;
+        delivery
+            .update(tx.as_mut())
+            .await
+            .tap_err(|e| tracing::error!("Failed to update webhook delivery: {e}"))?;
+        let webhook = if failures != *webhook.consecutive_failures() {
+            webhook
+                .set_consecutive_failures(failures)
+                .set_updated_at(now)
+                .update(tx.as_mut())
+                .await
+                .tap_err(|e| tracing::error!("Failed to update webhook: {e}"))?
+        } else {
+            webhook
+        };
+        tx.commit().await?;
+
+        if failures >= self.settings.failure_threshold as i32 {
+            tracing::warn!(
+                "Disabling webhook [{}] after {failures} failed attempts",
+                webhook.id()
+            );
+            let command = DisableWebhookCommandBuilder::default()
+                .webhook_id(*webhook.id())
+                .build()
+                .map_err(anyhow::Error::from)?;
+            self.command_bus.execute(Actor::System, command).await?;
+        }
+
+        Ok(())
+    }
+
+    /// How long a claimed delivery is kept from other runs.
+    fn lease(&self) -> Duration {
+        Duration::seconds(self.settings.timeout as i64 + LEASE_MARGIN)
+    }
+
+    /// Delay before the next attempt, doubled after every failed one.
+    fn backoff(&self, attempts: i32) -> Duration {
+        let factor = 2_i64.pow(attempts.saturating_sub(1).clamp(0, 16) as u32);
+        Duration::seconds(self.settings.retry_delay as i64 * factor)
+    }
+}
+
+/// Checks the endpoint can be called: an HTTP(S) URL whose host only resolves to public
+/// addresses, so that webhooks can not be used to probe the internal network. Hosts of
+/// `allowed_hosts` are trusted as they are.
+pub(crate) async fn check_endpoint(
+    url: &str,
+    allowed_hosts: &[String],
+) -> Result<(), &'static str> {
+    let url = Url::parse(url.trim()).map_err(|_| "Webhook URL is not valid")?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err("Webhook URL must be an HTTP or HTTPS URL");
+    }
+    let host = url
+        .host_str()
+        .ok_or("Webhook URL must be an HTTP or HTTPS URL")?;
+    if is_allowed(host, allowed_hosts) {
+        return Ok(());
+    }
+
+    let addresses: Vec<IpAddr> = match url.host() {
+        Some(Host::Ipv4(ip)) => vec![ip.into()],
+        Some(Host::Ipv6(ip)) => vec![ip.into()],
+        Some(Host::Domain(domain)) => {
+            tokio::net::lookup_host((domain, url.port_or_known_default().unwrap_or(80)))
+                .await
+                .map_err(|_| "Webhook URL host can not be resolved")?
+                .map(|address| address.ip())
+                .collect()
+        }
+        None => return Err("Webhook URL must be an HTTP or HTTPS URL"),
+    };
+    if addresses.is_empty() || !addresses.into_iter().all(is_public) {
+        return Err("Webhook URL must not point to an internal address");
+    }
+    Ok(())
+}
+
+fn is_allowed(host: &str, allowed_hosts: &[String]) -> bool {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    allowed_hosts
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(host))
+}
+
+/// Whether the address is reachable from the internet, as opposed to loopback, private,
+/// link-local or otherwise reserved addresses.
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                // "This network" and carrier-grade NAT ranges.
+                || a == 0
+                || (a == 100 && (64..128).contains(&b)))
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public(ip.into()),
+            None => {
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local())
+            }
+        },
+    }
+}
+
+/// Resolves endpoint hosts to their public addresses only, so a host resolving to an
+/// internal address after its webhook was created still can not be reached.
+struct PublicResolver {
+    allowed_hosts: Vec<String>,
+}
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_owned();
+        let allowed = is_allowed(&host, &self.allowed_hosts);
+        Box::pin(async move {
+            let addresses: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|address| allowed || is_public(address.ip()))
+                .collect();
+            if addresses.is_empty() {
+                return Err("Webhook host does not resolve to a public address".into());
+            }
+            Ok(Box::new(addresses.into_iter()) as Addrs)
+        })
+    }
+}
@@ -0,0 +1,84 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    commons::create_context,
+    fixtures::user::{create_user, create_user_with_role},
+    mock::{clock::fixed_clock, ids::fixed_id},
+};
+use ::commons::{
+    actor::{ActorTrait, Role},
+    id::{Id, MockIdGenerator},
+    time::{DateTime, MockClock},
+};
+use cqrs::{
+    command_bus::{
+        command::{
+            assign_role::{AssignRoleCommandBuilder, AssignRoleCommandError},
+            Command,
+        },
+        error::CommandBusError,
+    },
+    events::RoleAssignedEventBuilder,
+};
+use sqlx::PgPool;
+use storage::{model::user::User, query::user::QueryUser};
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_admin_assigns_role(pool: PgPool) {
+    let admin = create_user_with_role(&pool, Role::Admin).await;
+    let user = create_user(&pool).await;
+    let now = DateTime::now();
+    let clock = fixed_clock(now);
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &admin, &clock, &ids).await;
+
+    let command = AssignRoleCommandBuilder::default()
+        .user_id(*user.id())
+        .role(Role::Moderator)
+        .build()
+        .unwrap();
+
+    assert!(command.supports(&admin));
+    assert_eq!(
+        command.handle(&mut ctx).await.unwrap(),
+        Some(
+            RoleAssignedEventBuilder::default()
+                .user_id(*user.id())
+                .role(Role::Moderator)
+                .timestamp(now)
+                .actor(admin.actor())
+                .build()
+                .unwrap()
+        )
+    );
+
+    let user = User::find(ctx.tx().as_mut(), user.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(*user.role(), Role::Moderator);
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_non_admin_cannot_assign_role(pool: PgPool) {
+    let moderator = create_user_with_role(&pool, Role::Moderator).await;
+    let user = create_user(&pool).await;
+    let clock = MockClock::default();
+    let ids = MockIdGenerator::default();
+    let mut ctx = create_context(&pool, &moderator, &clock, &ids).await;
+
+    let command = AssignRoleCommandBuilder::default()
+        .user_id(*user.id())
+        .role(Role::Admin)
+        .build()
+        .unwrap();
+
+    assert!(!command.supports(&moderator));
+    match command.handle(&mut ctx).await {
+        Err(CommandBusError::AssignRoleCommand(AssignRoleCommandError::Forbidden(_))) => {}
+        Err(_) => panic!("Not the expected error"),
+        Ok(_) => panic!("Expected Err(CommandBusError) but got Ok(_)"),
+    }
+}
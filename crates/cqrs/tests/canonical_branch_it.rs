@@ -0,0 +1,231 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    commons::create_context,
+    fixtures::{
+        fragment::{create_fork, create_published},
+        user::create_user,
+    },
+    mock::{clock::fixed_clock, ids::fixed_id},
+};
+use ::commons::{id::Id, time::DateTime};
+use cqrs::command_bus::{
+    command::{
+        set_canonical_branch::{
+            SetCanonicalBranchCommand, SetCanonicalBranchCommandBuilder,
+            SetCanonicalBranchCommandError,
+        },
+        Command,
+    },
+    error::CommandBusError,
+};
+use sqlx::PgPool;
+use storage::{
+    model::{
+        canonical_branch::{CanonicalBranch, CanonicalBranchBuilder},
+        fragment::{Fragment, FragmentState},
+        maintainer::{MaintainerBuilder, MaintainerState},
+        user::User,
+    },
+    query::{
+        canonical_branch::QueryCanonicalBranch, fragment::QueryFragment,
+        maintainer::QueryMaintainer,
+    },
+};
+
+async fn create_published_fork(pool: &PgPool, user: &User, parent: &Fragment) -> Fragment {
+    create_fork(pool, user, parent)
+        .await
+        .set_state(FragmentState::Published)
+        .update(pool)
+        .await
+        .unwrap()
+}
+
+async fn save_branch(pool: &PgPool, fragment: &Fragment, child: &Fragment) {
+    CanonicalBranchBuilder::default()
+        .fragment_id(*fragment.id())
+        .child_id(*child.id())
+        .chosen_by(*fragment.author_id())
+        .chosen_at(DateTime::now())
+        .build()
+        .unwrap()
+        .save(pool)
+        .await
+        .unwrap();
+}
+
+async fn save_maintainer(pool: &PgPool, root: &Fragment, user: &User, state: MaintainerState) {
+    MaintainerBuilder::default()
+        .root_id(*root.id())
+        .user_id(*user.id())
+        .invited_by(*root.author_id())
+        .state(state)
+        .invited_at(DateTime::now())
+        .build()
+        .unwrap()
+        .save(pool)
+        .await
+        .unwrap();
+}
+
+fn command(fragment: &Fragment, child: Option<&Fragment>) -> SetCanonicalBranchCommand {
+    SetCanonicalBranchCommandBuilder::default()
+        .fragment_id(*fragment.id())
+        .child_id(child.map(|c| *c.id()))
+        .build()
+        .unwrap()
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_set_canonical_branch(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let fork_author = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false).await;
+    let fork = create_published_fork(&pool, &fork_author, &root).await;
+    let grandchild = create_published_fork(&pool, &author, &fork).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+    let event = command(&fork, Some(&grandchild))
+        .handle(&mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.fragment_id, *fork.id());
+    assert_eq!(event.child_id, Some(*grandchild.id()));
+    assert_eq!(event.previous_child_id, None);
+
+    let branch = CanonicalBranch::find(ctx.tx().as_mut(), fork.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(branch.child_id(), grandchild.id());
+    assert_eq!(*branch.chosen_by(), Some(*author.id()));
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_maintainer_sets_canonical_branch(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let maintainer = create_user(&pool).await;
+    let invited = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false).await;
+    let fork = create_published_fork(&pool, &invited, &root).await;
+    let grandchild = create_published_fork(&pool, &author, &fork).await;
+    save_maintainer(&pool, &root, &maintainer, MaintainerState::Accepted).await;
+    save_maintainer(&pool, &root, &invited, MaintainerState::Invited).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+
+    let mut ctx = create_context(&pool, &invited, &clock, &ids).await;
+    let result = command(&fork, Some(&grandchild)).handle(&mut ctx).await;
+    assert!(
+        matches!(
+            result,
+            Err(CommandBusError::SetCanonicalBranchCommand(
+                SetCanonicalBranchCommandError::Forbidden(_)
+            ))
+        ),
+        "{result:?}"
+    );
+    drop(ctx);
+
+    let mut ctx = create_context(&pool, &maintainer, &clock, &ids).await;
+    let event = command(&fork, Some(&grandchild))
+        .handle(&mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.child_id, Some(*grandchild.id()));
+    let branch = CanonicalBranch::find(ctx.tx().as_mut(), fork.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(*branch.chosen_by(), Some(*maintainer.id()));
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_set_canonical_branch_errors(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let fork_author = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false).await;
+    let fork = create_published_fork(&pool, &fork_author, &root).await;
+    let draft = create_fork(&pool, &fork_author, &root).await;
+    let grandchild = create_published_fork(&pool, &author, &fork).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+
+    let cases = [
+        (&fork_author, command(&fork, Some(&grandchild)), "forbidden"),
+        (&author, command(&root, Some(&draft)), "invalid"),
+        (&author, command(&root, Some(&grandchild)), "invalid"),
+        (&author, command(&root, Some(&root)), "invalid"),
+    ];
+    for (actor, command, case) in cases {
+        let mut ctx = create_context(&pool, actor, &clock, &ids).await;
+        let result = command.handle(&mut ctx).await;
+        let expected = match case {
+            "forbidden" => matches!(
+                result,
+                Err(CommandBusError::SetCanonicalBranchCommand(
+                    SetCanonicalBranchCommandError::Forbidden(_)
+                ))
+            ),
+            _ => matches!(
+                result,
+                Err(CommandBusError::SetCanonicalBranchCommand(
+                    SetCanonicalBranchCommandError::InvalidBranch(_)
+                ))
+            ),
+        };
+        assert!(expected, "{case}: {result:?}");
+    }
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_canonical_path(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false).await;
+    let fork = create_published_fork(&pool, &author, &root).await;
+    create_published_fork(&pool, &author, &root).await;
+    let grandchild = create_published_fork(&pool, &author, &fork).await;
+    save_branch(&pool, &root, &fork).await;
+    save_branch(&pool, &fork, &grandchild).await;
+
+    let path: Vec<Id> = CanonicalBranch::path(&pool, root.id())
+        .await
+        .unwrap()
+        .iter()
+        .map(|f| *f.id())
+        .collect();
+    assert_eq!(path, vec![*root.id(), *fork.id(), *grandchild.id()]);
+
+    grandchild
+        .set_state(FragmentState::Draft)
+        .update(&pool)
+        .await
+        .unwrap();
+    let path = CanonicalBranch::path(&pool, root.id()).await.unwrap();
+    assert_eq!(path.len(), 2);
+
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+    let unchanged = command(&root, Some(&fork)).handle(&mut ctx).await.unwrap();
+    assert!(unchanged.is_none());
+
+    let event = command(&root, None)
+        .handle(&mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.child_id, None);
+    assert_eq!(event.previous_child_id, Some(*fork.id()));
+    let path = CanonicalBranch::path(ctx.tx().as_mut(), root.id())
+        .await
+        .unwrap();
+    assert_eq!(path.len(), 1);
+}
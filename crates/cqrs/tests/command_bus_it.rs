@@ -1,6 +1,6 @@
 use ::commons::{
     id::{Id, MockIdGenerator},
-    time::MockClock,
+    time::{DateTime, MockClock},
 };
 use cqrs::{
     command_bus::{
@@ -22,8 +22,11 @@ mod mock;
 
 #[sqlx::test(migrator = "storage::MIGRATOR")]
 async fn test_command_bus(pool: PgPool) {
+    let user_id = Id::new();
     let user = UserBuilder::default()
-        .id(Id::new())
+        .id(user_id)
+        .username(format!("user_{}", &user_id.to_string()[..18]))
+        .created_at(DateTime::now())
         .build()
         .unwrap()
         .save(&pool)
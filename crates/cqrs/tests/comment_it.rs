@@ -0,0 +1,250 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    commons::create_context,
+    fixtures::{
+        fragment::{create_draft, create_fork, create_published},
+        user::{create_user, create_user_with_role},
+    },
+    mock::{clock::fixed_clock, ids::fixed_id},
+};
+use ::commons::{actor::Role, id::Id, review, time::DateTime};
+use cqrs::command_bus::{
+    command::{
+        create_comment::{CreateCommentCommandBuilder, CreateCommentCommandError},
+        delete_comment::DeleteCommentCommandBuilder,
+        edit_comment::{EditCommentCommandBuilder, EditCommentCommandError},
+        moderate_comment::ModerateCommentCommandBuilder,
+        Command,
+    },
+    error::CommandBusError,
+};
+use sqlx::PgPool;
+use storage::{
+    model::{
+        comment::{Comment, CommentBuilder},
+        fragment::Fragment,
+        review::{ReviewAction, ReviewBuilder},
+        user::User,
+    },
+    query::{comment::QueryComment, review::QueryReview},
+};
+
+fn body(text: &str) -> review::Comment {
+    review::Comment::from(String::from(text))
+}
+
+async fn create_comment(pool: &PgPool, author: &User, fragment: &Fragment) -> Comment {
+    CommentBuilder::default()
+        .id(Id::new())
+        .fragment_id(*fragment.id())
+        .author_id(*author.id())
+        .body(body("first"))
+        .created_at(DateTime::now())
+        .last_modified_at(DateTime::now())
+        .build()
+        .unwrap()
+        .save(pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_reply_to_comment(pool: PgPool) {
+    let fragment = create_published(&pool, &create_user(&pool).await, "story", false).await;
+    let comment = create_comment(&pool, &create_user(&pool).await, &fragment).await;
+    let user = create_user(&pool).await;
+    let clock = fixed_clock(DateTime::now());
+    let reply_id = Id::new();
+    let ids = fixed_id(reply_id);
+    let mut ctx = create_context(&pool, &user, &clock, &ids).await;
+
+    let event = CreateCommentCommandBuilder::default()
+        .comment_id(reply_id)
+        .fragment_id(*fragment.id())
+        .parent_id(Some(*comment.id()))
+        .body(body("reply"))
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.parent_id, Some(*comment.id()));
+
+    let thread = Comment::find_by_fragment(ctx.tx().as_mut(), fragment.id())
+        .await
+        .unwrap();
+    assert_eq!(thread.len(), 2);
+    assert_eq!(thread[1].id(), &reply_id);
+    assert_eq!(thread[1].parent_id(), &Some(*comment.id()));
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_drafts_are_only_open_to_readers(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let draft = create_draft(&pool, &author, "draft", false).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let command = CreateCommentCommandBuilder::default()
+        .comment_id(Id::new())
+        .fragment_id(*draft.id())
+        .body(body("first"))
+        .build()
+        .unwrap();
+
+    let stranger = create_user(&pool).await;
+    let mut ctx = create_context(&pool, &stranger, &clock, &ids).await;
+    assert!(matches!(
+        command.handle(&mut ctx).await,
+        Err(CommandBusError::CreateCommentCommand(
+            CreateCommentCommandError::Forbidden(_)
+        ))
+    ));
+
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+    command.handle(&mut ctx).await.unwrap();
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_review_thread_is_limited_to_participants(pool: PgPool) {
+    let parent_author = create_user(&pool).await;
+    let fork_author = create_user(&pool).await;
+    let parent = create_published(&pool, &parent_author, "parent", false).await;
+    let fork = create_fork(&pool, &fork_author, &parent).await;
+    let review = ReviewBuilder::default()
+        .id(Id::new())
+        .fragment_id(*fork.id())
+        .reviewer_id(*parent_author.id())
+        .action(ReviewAction::RequestChanges)
+        .comment(Some(body("needs work")))
+        .created_at(DateTime::now())
+        .build()
+        .unwrap()
+        .save(&pool)
+        .await
+        .unwrap();
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let command = CreateCommentCommandBuilder::default()
+        .comment_id(Id::new())
+        .fragment_id(*fork.id())
+        .review_id(Some(*review.id()))
+        .body(body("why?"))
+        .build()
+        .unwrap();
+
+    let outsider = create_user(&pool).await;
+    let mut ctx = create_context(&pool, &outsider, &clock, &ids).await;
+    assert!(matches!(
+        command.handle(&mut ctx).await,
+        Err(CommandBusError::CreateCommentCommand(
+            CreateCommentCommandError::Forbidden(_)
+        ))
+    ));
+
+    let mut ctx = create_context(&pool, &fork_author, &clock, &ids).await;
+    command.handle(&mut ctx).await.unwrap();
+    let thread = Comment::find_by_review(ctx.tx().as_mut(), review.id())
+        .await
+        .unwrap();
+    assert_eq!(thread.len(), 1);
+    assert!(Comment::find_by_fragment(ctx.tx().as_mut(), fork.id())
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_edit_and_delete_comment(pool: PgPool) {
+    let fragment = create_published(&pool, &create_user(&pool).await, "story", false).await;
+    let author = create_user(&pool).await;
+    let comment = create_comment(&pool, &author, &fragment).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+
+    let edit = EditCommentCommandBuilder::default()
+        .fragment_id(*fragment.id())
+        .comment_id(*comment.id())
+        .body(body("edited"))
+        .build()
+        .unwrap();
+    let other = create_user(&pool).await;
+    let mut ctx = create_context(&pool, &other, &clock, &ids).await;
+    match edit.handle(&mut ctx).await {
+        Err(CommandBusError::EditCommentCommand(e)) => {
+            assert_eq!(
+                e,
+                EditCommentCommandError::Forbidden("Only the comment author can edit it")
+            )
+        }
+        _ => panic!("expected forbidden error"),
+    }
+
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+    let elsewhere = EditCommentCommandBuilder::default()
+        .fragment_id(Id::new())
+        .comment_id(*comment.id())
+        .body(body("edited"))
+        .build()
+        .unwrap();
+    assert!(matches!(
+        elsewhere.handle(&mut ctx).await,
+        Err(CommandBusError::EditCommentCommand(
+            EditCommentCommandError::CommentNotFound(_)
+        ))
+    ));
+    edit.handle(&mut ctx).await.unwrap();
+    let edited = Comment::find(ctx.tx().as_mut(), comment.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(edited.body(), &body("edited"));
+
+    let comment = create_comment(&pool, &author, &fragment).await;
+    let moderator = create_user_with_role(&pool, Role::Moderator).await;
+    let mut ctx = create_context(&pool, &moderator, &clock, &ids).await;
+    DeleteCommentCommandBuilder::default()
+        .fragment_id(*fragment.id())
+        .comment_id(*comment.id())
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await
+        .unwrap();
+    let deleted = Comment::find(ctx.tx().as_mut(), comment.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(deleted.is_deleted());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_moderate_comment(pool: PgPool) {
+    let fragment = create_published(&pool, &create_user(&pool).await, "story", false).await;
+    let author = create_user(&pool).await;
+    let comment = create_comment(&pool, &author, &fragment).await;
+    let moderator = create_user_with_role(&pool, Role::Moderator).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let command = ModerateCommentCommandBuilder::default()
+        .fragment_id(*fragment.id())
+        .comment_id(*comment.id())
+        .hidden(true)
+        .build()
+        .unwrap();
+
+    assert!(!command.supports(&author));
+    assert!(command.supports(&moderator));
+
+    let mut ctx = create_context(&pool, &moderator, &clock, &ids).await;
+    command.handle(&mut ctx).await.unwrap();
+    let hidden = Comment::find(ctx.tx().as_mut(), comment.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(hidden.is_hidden());
+    assert_eq!(hidden.hidden_by(), &Some(*moderator.id()));
+}
@@ -0,0 +1,210 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    commons::create_context,
+    fixtures::{
+        fragment::{create_fork, create_published, soft_delete},
+        user::{create_user, create_user_with_role},
+    },
+    mock::{clock::fixed_clock, ids::fixed_id},
+};
+use ::commons::{
+    actor::{Actor, ActorTrait, Role},
+    id::Id,
+    time::DateTime,
+};
+use chrono::Duration;
+use cqrs::{
+    command_bus::{
+        bus::Ctx,
+        command::{
+            delete_fragment::{DeleteFragmentCommandBuilder, DeleteFragmentCommandError},
+            purge_fragments::PurgeFragmentsCommandBuilder,
+            restore_fragment::{RestoreFragmentCommandBuilder, RestoreFragmentCommandError},
+            Command,
+        },
+        error::CommandBusError,
+    },
+    events::FragmentDeletedEventBuilder,
+};
+use sqlx::PgPool;
+use storage::{
+    model::{
+        fragment::Fragment,
+        like::{Like, LikeBuilder},
+    },
+    query::{fragment::QueryFragment, like::QueryLike},
+};
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_delete_hides_subtree(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false).await;
+    let fork = create_fork(&pool, &create_user(&pool).await, &root).await;
+    let now = DateTime::now();
+    let clock = fixed_clock(now);
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+
+    let command = DeleteFragmentCommandBuilder::default()
+        .fragment_id(*root.id())
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        command.handle(&mut ctx).await.unwrap(),
+        Some(
+            FragmentDeletedEventBuilder::default()
+                .fragment_id(*root.id())
+                .timestamp(now)
+                .actor(author.actor())
+                .build()
+                .unwrap()
+        )
+    );
+
+    assert!(Fragment::find(ctx.tx().as_mut(), root.id())
+        .await
+        .unwrap()
+        .is_none());
+    assert!(Fragment::find(ctx.tx().as_mut(), fork.id())
+        .await
+        .unwrap()
+        .is_none());
+    assert!(Fragment::find_with_deleted(ctx.tx().as_mut(), root.id())
+        .await
+        .unwrap()
+        .unwrap()
+        .is_deleted());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_delete_requires_author_or_moderator(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false).await;
+    let command = DeleteFragmentCommandBuilder::default()
+        .fragment_id(*root.id())
+        .build()
+        .unwrap();
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+
+    let stranger = create_user(&pool).await;
+    let mut ctx = create_context(&pool, &stranger, &clock, &ids).await;
+    match command.handle(&mut ctx).await {
+        Err(CommandBusError::DeleteFragmentCommand(DeleteFragmentCommandError::Forbidden(_))) => {}
+        Err(_) => panic!("Not the expected error"),
+        Ok(_) => panic!("Expected Err(CommandBusError) but got Ok(_)"),
+    }
+
+    let moderator = create_user_with_role(&pool, Role::Moderator).await;
+    let mut ctx = create_context(&pool, &moderator, &clock, &ids).await;
+    assert!(command.handle(&mut ctx).await.unwrap().is_some());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_restore(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false).await;
+    let fork = create_fork(&pool, &create_user(&pool).await, &root).await;
+    let root = soft_delete(&pool, root, DateTime::now()).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+
+    RestoreFragmentCommandBuilder::default()
+        .fragment_id(*root.id())
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await
+        .unwrap();
+
+    assert!(Fragment::find(ctx.tx().as_mut(), root.id())
+        .await
+        .unwrap()
+        .is_some());
+    assert!(Fragment::find(ctx.tx().as_mut(), fork.id())
+        .await
+        .unwrap()
+        .is_some());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_restore_under_deleted_parent(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false).await;
+    let fork = create_fork(&pool, &author, &root).await;
+    let fork = soft_delete(&pool, fork, DateTime::now()).await;
+    soft_delete(&pool, root, DateTime::now()).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+
+    let result = RestoreFragmentCommandBuilder::default()
+        .fragment_id(*fork.id())
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await;
+
+    match result {
+        Err(CommandBusError::RestoreFragmentCommand(
+            RestoreFragmentCommandError::InvalidState(_),
+        )) => {}
+        Err(_) => panic!("Not the expected error"),
+        Ok(_) => panic!("Expected Err(CommandBusError) but got Ok(_)"),
+    }
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_purge_after_retention(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let now = DateTime::now();
+    let expired = create_published(&pool, &author, "expired", false).await;
+    let fork = create_fork(&pool, &author, &expired).await;
+    LikeBuilder::default()
+        .user_id(*author.id())
+        .fragment_id(*fork.id())
+        .created_at(now)
+        .build()
+        .unwrap()
+        .save(&pool)
+        .await
+        .unwrap();
+    let expired = soft_delete(&pool, expired, now - Duration::days(40)).await;
+    let recent = create_published(&pool, &author, "recent", false).await;
+    let recent = soft_delete(&pool, recent, now - Duration::days(1)).await;
+
+    let clock = fixed_clock(now);
+    let ids = fixed_id(Id::new());
+    let system = Actor::System;
+    let mut ctx = Ctx::new(&pool, &system, &clock, &ids).await.unwrap();
+
+    let command = PurgeFragmentsCommandBuilder::default()
+        .deleted_before(now - Duration::days(30))
+        .build()
+        .unwrap();
+    assert!(command.supports(&system));
+    assert!(!command.supports(&author));
+
+    let event = command.handle(&mut ctx).await.unwrap().unwrap();
+    assert_eq!(event.fragment_ids.len(), 2);
+    assert!(event.fragment_ids.contains(expired.id()));
+    assert!(event.fragment_ids.contains(fork.id()));
+
+    assert!(Fragment::find_with_deleted(ctx.tx().as_mut(), expired.id())
+        .await
+        .unwrap()
+        .is_none());
+    assert!(Like::find(ctx.tx().as_mut(), fork.id(), author.id())
+        .await
+        .unwrap()
+        .is_none());
+    assert!(Fragment::find_with_deleted(ctx.tx().as_mut(), recent.id())
+        .await
+        .unwrap()
+        .is_some());
+}
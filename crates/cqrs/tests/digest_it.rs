@@ -0,0 +1,297 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    fixtures::{fragment::create_published, user::create_user},
+    mock::clock::fixed_clock,
+};
+use ::commons::{
+    actor::Actor,
+    auth::UnsubscribeToken,
+    id::StdIdGenerator,
+    mail::{EmailAddress, InMemoryMailer},
+    time::DateTime,
+};
+use chrono::Duration;
+use cqrs::{
+    command_bus::{
+        bus::CommandBus,
+        command::{
+            follow_user::FollowUserCommandBuilder,
+            like_fragment::LikeFragmentCommandBuilder,
+            set_notification_preferences::{
+                SetNotificationPreferencesCommandBuilder, SetNotificationPreferencesCommandError,
+            },
+            unsubscribe_digest::{UnsubscribeDigestCommandBuilder, UnsubscribeDigestCommandError},
+        },
+        error::CommandBusError,
+    },
+    digest::DigestJob,
+};
+use sqlx::PgPool;
+use std::sync::Arc;
+use storage::{
+    model::{
+        notification::NotificationKind,
+        notification_preferences::{DigestFrequency, NotificationPreferences},
+        user::User,
+    },
+    query::notification_preferences::QueryNotificationPreferences,
+};
+
+fn bus(pool: &PgPool, now: DateTime) -> Arc<CommandBus> {
+    Arc::new(CommandBus::new(
+        pool.clone(),
+        Arc::new(fixed_clock(now)),
+        Arc::new(StdIdGenerator),
+    ))
+}
+
+fn job(pool: &PgPool, mailer: &Arc<InMemoryMailer>, now: DateTime) -> DigestJob {
+    DigestJob::new(
+        pool.clone(),
+        bus(pool, now),
+        mailer.clone(),
+        Arc::new(fixed_clock(now)),
+        "http://localhost:8080/",
+    )
+}
+
+async fn subscribe(bus: &CommandBus, user: &User, muted_kinds: Vec<NotificationKind>) {
+    bus.execute(
+        user.clone(),
+        SetNotificationPreferencesCommandBuilder::default()
+            .user_id(*user.id())
+            .email(Some(EmailAddress::parse("reader@example.com").unwrap()))
+            .digest(DigestFrequency::Daily)
+            .muted_kinds(muted_kinds)
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+}
+
+async fn preferences(pool: &PgPool, user: &User) -> NotificationPreferences {
+    NotificationPreferences::find(pool, user.id())
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_digest_is_sent_once(pool: PgPool) {
+    let now = DateTime::now();
+    let author = create_user(&pool).await;
+    let fragment = create_published(&pool, &author, "fragment", false).await;
+    let bus = bus(&pool, now);
+    subscribe(&bus, &author, vec![]).await;
+    let mailer = Arc::new(InMemoryMailer::default());
+
+    assert_eq!(job(&pool, &mailer, now).run().await.unwrap(), 0);
+
+    for _ in 0..2 {
+        bus.execute(
+            create_user(&pool).await,
+            LikeFragmentCommandBuilder::default()
+                .fragment_id(*fragment.id())
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    }
+
+    assert_eq!(job(&pool, &mailer, now).run().await.unwrap(), 1);
+    let sent = mailer.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].to.as_str(), "reader@example.com");
+    assert!(sent[0].text.contains("2 people liked your fragment"));
+    assert!(sent[0].text.contains(&format!(
+        "http://localhost:8080/api/v1/fragments/{}",
+        fragment.id()
+    )));
+    let token = preferences(&pool, &author)
+        .await
+        .unsubscribe_token()
+        .clone();
+    assert_eq!(
+        sent[0].unsubscribe_url,
+        Some(format!(
+            "http://localhost:8080/api/v1/unsubscribe/{}",
+            token.as_str()
+        ))
+    );
+    assert!(preferences(&pool, &author).await.last_digest_at().is_some());
+
+    // Not due yet, then due but with nothing new.
+    assert_eq!(job(&pool, &mailer, now).run().await.unwrap(), 0);
+    let later = now + Duration::days(2);
+    assert_eq!(job(&pool, &mailer, later).run().await.unwrap(), 0);
+    assert_eq!(mailer.sent().len(), 1);
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_concurrent_runs_send_digest_once(pool: PgPool) {
+    let now = DateTime::now();
+    let author = create_user(&pool).await;
+    let fragment = create_published(&pool, &author, "fragment", false).await;
+    let bus = bus(&pool, now);
+    subscribe(&bus, &author, vec![]).await;
+    bus.execute(
+        create_user(&pool).await,
+        LikeFragmentCommandBuilder::default()
+            .fragment_id(*fragment.id())
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mailer = Arc::new(InMemoryMailer::default());
+    let (first, second) = (job(&pool, &mailer, now), job(&pool, &mailer, now));
+    let (first, second) = tokio::join!(first.run(), second.run());
+    assert_eq!(first.unwrap() + second.unwrap(), 1);
+    assert_eq!(mailer.sent().len(), 1);
+
+    // A digest claimed by another run is skipped.
+    let later = now + Duration::days(2);
+    bus.execute(
+        create_user(&pool).await,
+        LikeFragmentCommandBuilder::default()
+            .fragment_id(*fragment.id())
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    let last_digest_at = *preferences(&pool, &author).await.last_digest_at();
+    assert!(NotificationPreferences::claim_digest(
+        &pool,
+        author.id(),
+        last_digest_at.as_ref(),
+        &later
+    )
+    .await
+    .unwrap());
+    assert!(!NotificationPreferences::claim_digest(
+        &pool,
+        author.id(),
+        last_digest_at.as_ref(),
+        &later
+    )
+    .await
+    .unwrap());
+    assert_eq!(job(&pool, &mailer, later).run().await.unwrap(), 0);
+    assert_eq!(mailer.sent().len(), 1);
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_muted_kinds_are_skipped(pool: PgPool) {
+    let now = DateTime::now();
+    let author = create_user(&pool).await;
+    let fragment = create_published(&pool, &author, "fragment", false).await;
+    let bus = bus(&pool, now);
+    subscribe(&bus, &author, vec![NotificationKind::FragmentLiked]).await;
+
+    let fan = create_user(&pool).await;
+    bus.execute(
+        fan.clone(),
+        LikeFragmentCommandBuilder::default()
+            .fragment_id(*fragment.id())
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    bus.execute(
+        fan.clone(),
+        FollowUserCommandBuilder::default()
+            .following_user_id(*author.id())
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mailer = Arc::new(InMemoryMailer::default());
+    assert_eq!(job(&pool, &mailer, now).run().await.unwrap(), 1);
+    let text = &mailer.sent()[0].text;
+    assert!(text.contains("Someone started following you"), "{text}");
+    assert!(!text.contains("liked"), "{text}");
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_unsubscribe(pool: PgPool) {
+    let now = DateTime::now();
+    let author = create_user(&pool).await;
+    let bus = bus(&pool, now);
+
+    let result = bus
+        .execute(
+            author.clone(),
+            SetNotificationPreferencesCommandBuilder::default()
+                .user_id(*author.id())
+                .digest(DigestFrequency::Weekly)
+                .build()
+                .unwrap(),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(CommandBusError::SetNotificationPreferencesCommand(
+            SetNotificationPreferencesCommandError::InvalidPreferences(_)
+        ))
+    ));
+    let result = bus
+        .execute(
+            create_user(&pool).await,
+            SetNotificationPreferencesCommandBuilder::default()
+                .user_id(*author.id())
+                .build()
+                .unwrap(),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(CommandBusError::SetNotificationPreferencesCommand(
+            SetNotificationPreferencesCommandError::Forbidden(_)
+        ))
+    ));
+
+    subscribe(&bus, &author, vec![]).await;
+    let token = preferences(&pool, &author)
+        .await
+        .unsubscribe_token()
+        .clone();
+
+    let result = bus
+        .execute(
+            Actor::System,
+            UnsubscribeDigestCommandBuilder::default()
+                .token(UnsubscribeToken::generate())
+                .build()
+                .unwrap(),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(CommandBusError::UnsubscribeDigestCommand(
+            UnsubscribeDigestCommandError::InvalidToken
+        ))
+    ));
+
+    bus.execute(
+        Actor::System,
+        UnsubscribeDigestCommandBuilder::default()
+            .token(token.clone())
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    let unsubscribed = preferences(&pool, &author).await;
+    assert_eq!(*unsubscribed.digest(), DigestFrequency::Never);
+    assert_eq!(*unsubscribed.unsubscribe_token(), token);
+}
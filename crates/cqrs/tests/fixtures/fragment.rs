@@ -43,3 +43,28 @@ pub async fn create_published(pool: &PgPool, user: &User, content: &str, end: bo
         .await
         .unwrap()
 }
+
+pub async fn create_fork(pool: &PgPool, user: &User, parent: &Fragment) -> Fragment {
+    FragmentBuilder::default()
+        .id(Id::new())
+        .content(String::from("fork"))
+        .state(FragmentState::Draft)
+        .parent_id(Some(*parent.id()))
+        .path(parent.path().append(*parent.id()))
+        .author_id(*user.id())
+        .created_at(DateTime::now())
+        .last_modified_at(DateTime::now())
+        .build()
+        .unwrap()
+        .save(pool)
+        .await
+        .unwrap()
+}
+
+pub async fn soft_delete(pool: &PgPool, fragment: Fragment, at: DateTime) -> Fragment {
+    fragment
+        .set_deleted_at(Some(at))
+        .update(pool)
+        .await
+        .unwrap()
+}
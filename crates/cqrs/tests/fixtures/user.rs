@@ -1,4 +1,4 @@
-use commons::id::Id;
+use commons::{actor::Role, id::Id, time::DateTime};
 use sqlx::PgPool;
 use storage::{
     model::user::{User, UserBuilder},
@@ -6,11 +6,23 @@ use storage::{
 };
 
 pub async fn create_user(pool: &PgPool) -> User {
+    let id = Id::new();
     UserBuilder::default()
-        .id(Id::new())
+        .id(id)
+        .username(format!("user_{}", &id.to_string()[..18]))
+        .created_at(DateTime::now())
         .build()
         .unwrap()
         .save(pool)
         .await
         .unwrap()
 }
+
+pub async fn create_user_with_role(pool: &PgPool, role: Role) -> User {
+    create_user(pool)
+        .await
+        .set_role(role)
+        .update(pool)
+        .await
+        .unwrap()
+}
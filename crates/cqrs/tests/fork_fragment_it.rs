@@ -0,0 +1,109 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    commons::create_context,
+    fixtures::{fragment::create_published, user::create_user},
+    mock::{clock::fixed_clock, ids::fixed_id},
+};
+use ::commons::{id::Id, time::DateTime};
+use cqrs::command_bus::{
+    command::{
+        fork_fragment::{ForkFragmentCommandBuilder, ForkFragmentCommandError},
+        Command,
+    },
+    error::CommandBusError,
+};
+use sqlx::PgPool;
+use storage::{
+    model::{
+        fork_invite::{ForkInvite, ForkInviteBuilder},
+        fragment::{ForkPolicy, Fragment},
+        user::User,
+    },
+    query::{fork_invite::QueryForkInvite, fragment::QueryFragment},
+};
+
+async fn fork(pool: &PgPool, user: &User, parent: &Fragment) -> Result<(), CommandBusError> {
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(pool, user, &clock, &ids).await;
+
+    ForkFragmentCommandBuilder::default()
+        .fork_id(Id::new())
+        .parent_fragment_id(*parent.id())
+        .content("fork")
+        .end(false)
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await
+        .map(|_| ())
+}
+
+async fn create_story(pool: &PgPool, policy: ForkPolicy) -> Fragment {
+    let author = create_user(pool).await;
+    create_published(pool, &author, "root", false)
+        .await
+        .set_fork_policy(policy)
+        .update(pool)
+        .await
+        .unwrap()
+}
+
+fn assert_forbidden(result: Result<(), CommandBusError>) {
+    match result {
+        Err(CommandBusError::ForkFragmentCommand(ForkFragmentCommandError::Forbidden(_))) => {}
+        Err(_) => panic!("Not the expected error"),
+        Ok(_) => panic!("Expected Err(CommandBusError) but got Ok(_)"),
+    }
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_default_policy_requires_friendship(pool: PgPool) {
+    let story = create_story(&pool, ForkPolicy::default()).await;
+    let user = create_user(&pool).await;
+
+    assert_forbidden(fork(&pool, &user, &story).await);
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_anyone_can_fork(pool: PgPool) {
+    let story = create_story(&pool, ForkPolicy::Anyone).await;
+    let user = create_user(&pool).await;
+
+    assert!(fork(&pool, &user, &story).await.is_ok());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_locked_story(pool: PgPool) {
+    let story = create_story(&pool, ForkPolicy::Locked).await;
+    let user = create_user(&pool).await;
+
+    assert_forbidden(fork(&pool, &user, &story).await);
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_invite_only_story(pool: PgPool) {
+    let story = create_story(&pool, ForkPolicy::InviteOnly).await;
+    let invited = create_user(&pool).await;
+    let stranger = create_user(&pool).await;
+
+    ForkInviteBuilder::default()
+        .fragment_id(*story.id())
+        .user_id(*invited.id())
+        .created_at(DateTime::now())
+        .build()
+        .unwrap()
+        .save(&pool)
+        .await
+        .unwrap();
+
+    assert_forbidden(fork(&pool, &stranger, &story).await);
+    assert!(fork(&pool, &invited, &story).await.is_ok());
+    assert!(ForkInvite::find(&pool, story.id(), invited.id())
+        .await
+        .unwrap()
+        .is_some());
+}
@@ -0,0 +1,266 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    fixtures::{
+        fragment::{create_draft, create_published},
+        user::create_user,
+    },
+    mock::clock::fixed_clock,
+};
+use ::commons::{
+    actor::ActorType,
+    events::EventType,
+    id::{Id, StdIdGenerator},
+    time::DateTime,
+};
+use cqrs::{
+    command_bus::{
+        bus::CommandBus,
+        command::{
+            follow_user::FollowUserCommandBuilder, fork_fragment::ForkFragmentCommandBuilder,
+            like_fragment::LikeFragmentCommandBuilder, submit_fork::SubmitForkCommandBuilder,
+        },
+    },
+    live::{LiveError, LiveFilter, LiveScope},
+};
+use sqlx::{postgres::PgListener, PgPool};
+use std::{sync::Arc, time::Duration};
+use storage::{
+    model::{
+        event::{DbEvent, DbEventBuilder, EventData},
+        fragment::{ForkPolicy, Fragment},
+        user::User,
+    },
+    query::{
+        event::{QueryEvent, EVENTS_CHANNEL},
+        fragment::QueryFragment,
+    },
+};
+
+fn bus(pool: &PgPool) -> CommandBus {
+    CommandBus::new(
+        pool.clone(),
+        Arc::new(fixed_clock(DateTime::now())),
+        Arc::new(StdIdGenerator),
+    )
+}
+
+async fn like(bus: &CommandBus, user: &User, fragment: &Fragment) {
+    bus.execute(
+        user.clone(),
+        LikeFragmentCommandBuilder::default()
+            .fragment_id(*fragment.id())
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+}
+
+async fn fork(bus: &CommandBus, user: &User, parent: &Fragment) -> Id {
+    let fork_id = Id::new();
+    bus.execute(
+        user.clone(),
+        ForkFragmentCommandBuilder::default()
+            .fork_id(fork_id)
+            .parent_fragment_id(*parent.id())
+            .content("fork")
+            .end(false)
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    fork_id
+}
+
+/// Event types replayed from the start for the viewer.
+async fn replay(pool: &PgPool, viewer: &User, scope: LiveScope) -> Vec<EventType> {
+    let filter = LiveFilter::open(pool.clone(), viewer.clone(), scope)
+        .await
+        .unwrap();
+    let (events, _) = filter.replay(0).await.unwrap();
+    events.into_iter().map(|event| event.event_type).collect()
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_committed_events_are_notified(pool: PgPool) {
+    let mut listener = PgListener::connect_with(&pool).await.unwrap();
+    listener.listen(EVENTS_CHANNEL).await.unwrap();
+    let follower = create_user(&pool).await;
+    let followed = create_user(&pool).await;
+
+    bus(&pool)
+        .execute(
+            follower,
+            FollowUserCommandBuilder::default()
+                .following_user_id(*followed.id())
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let notification = tokio::time::timeout(Duration::from_secs(5), listener.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    let id = Id::try_from(notification.payload()).unwrap();
+    let event = DbEvent::find(&pool, &id).await.unwrap().unwrap();
+    assert_eq!(*event.event_type(), EventType::UserFollowed);
+    assert!(event.position().is_some_and(|position| position > 0));
+}
+
+fn event() -> DbEvent {
+    DbEventBuilder::default()
+        .id(Id::new())
+        .event_type(EventType::UserFollowed)
+        .event_data(EventData::from(&serde_json::json!({})))
+        .timestamp(DateTime::now())
+        .actor_type(ActorType::System)
+        .actor_id(None)
+        .build()
+        .unwrap()
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_positions_follow_commit_order(pool: PgPool) {
+    let mut slow = pool.begin().await.unwrap();
+    let first = event().save(slow.as_mut()).await.unwrap();
+    assert_eq!(*first.position(), None);
+
+    let second = event().save(&pool).await.unwrap();
+    let second = DbEvent::find(&pool, second.id()).await.unwrap().unwrap();
+    slow.commit().await.unwrap();
+    let first = DbEvent::find(&pool, first.id()).await.unwrap().unwrap();
+
+    // Resuming after the event committed first still reaches the one saved before it.
+    assert!(first.position() > second.position());
+    let after = DbEvent::find_after(&pool, second.position().unwrap(), 10)
+        .await
+        .unwrap();
+    assert_eq!(after.len(), 1);
+    assert_eq!(after[0].id(), first.id());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_subtree_hides_unpublished_forks(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let forker = create_user(&pool).await;
+    let reader = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false)
+        .await
+        .set_fork_policy(ForkPolicy::Anyone)
+        .update(&pool)
+        .await
+        .unwrap();
+    let other = create_published(&pool, &author, "other", false).await;
+    let scope = LiveScope::Subtree(*root.id());
+    let bus = bus(&pool);
+
+    let fork_id = fork(&bus, &forker, &root).await;
+    assert_eq!(
+        replay(&pool, &forker, scope).await,
+        vec![EventType::FragmentForked]
+    );
+    assert!(replay(&pool, &author, scope).await.is_empty());
+
+    bus.execute(
+        forker.clone(),
+        SubmitForkCommandBuilder::default()
+            .fragment_id(fork_id)
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    like(&bus, &reader, &root).await;
+    like(&bus, &reader, &other).await;
+
+    assert_eq!(
+        replay(&pool, &author, scope).await,
+        vec![
+            EventType::FragmentForked,
+            EventType::ForkSubmitted,
+            EventType::FragmentLiked
+        ]
+    );
+    assert_eq!(
+        replay(&pool, &reader, scope).await,
+        vec![EventType::FragmentLiked]
+    );
+
+    // Resuming skips the events up to the given position.
+    let filter = LiveFilter::open(pool.clone(), author.clone(), scope)
+        .await
+        .unwrap();
+    let (events, reached) = filter.replay(0).await.unwrap();
+    let (resumed, _) = filter.replay(events[1].position).await.unwrap();
+    assert_eq!(resumed.len(), 1);
+    assert_eq!(resumed[0].fragment_id, Some(*root.id()));
+    assert!(filter.replay(reached).await.unwrap().0.is_empty());
+
+    // Resuming is limited to the last events.
+    filter.check_resume(events[1].position, 3).await.unwrap();
+    assert!(matches!(
+        filter.check_resume(0, 3).await,
+        Err(LiveError::TooFarBehind(0))
+    ));
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_user_stream(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let follower = create_user(&pool).await;
+    let stranger = create_user(&pool).await;
+    let fragment = create_published(&pool, &author, "fragment", false).await;
+    let bus = bus(&pool);
+
+    bus.execute(
+        follower.clone(),
+        FollowUserCommandBuilder::default()
+            .following_user_id(*author.id())
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    like(&bus, &stranger, &fragment).await;
+
+    assert_eq!(
+        replay(&pool, &author, LiveScope::User(*author.id())).await,
+        vec![EventType::UserFollowed, EventType::FragmentLiked]
+    );
+    assert_eq!(
+        replay(&pool, &follower, LiveScope::User(*follower.id())).await,
+        vec![EventType::UserFollowed]
+    );
+    assert_eq!(
+        replay(&pool, &stranger, LiveScope::User(*stranger.id())).await,
+        vec![EventType::FragmentLiked]
+    );
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_open_checks_scope(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let reader = create_user(&pool).await;
+    let draft = create_draft(&pool, &author, "draft", false).await;
+
+    let open = |viewer: &User, scope| LiveFilter::open(pool.clone(), viewer.clone(), scope);
+    assert!(matches!(
+        open(&reader, LiveScope::User(*author.id())).await,
+        Err(LiveError::Forbidden(_))
+    ));
+    assert!(matches!(
+        open(&reader, LiveScope::Subtree(*draft.id())).await,
+        Err(LiveError::Forbidden(_))
+    ));
+    assert!(matches!(
+        open(&reader, LiveScope::Subtree(Id::new())).await,
+        Err(LiveError::FragmentNotFound)
+    ));
+    assert!(open(&author, LiveScope::Subtree(*draft.id())).await.is_ok());
+}
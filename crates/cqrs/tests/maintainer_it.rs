@@ -0,0 +1,256 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    commons::create_context,
+    fixtures::{
+        fragment::{create_fork, create_published},
+        user::create_user,
+    },
+    mock::{clock::fixed_clock, ids::fixed_id},
+};
+use ::commons::{id::Id, time::DateTime};
+use cqrs::command_bus::{
+    command::{
+        accept_maintainer_invitation::{
+            AcceptMaintainerInvitationCommandBuilder, AcceptMaintainerInvitationCommandError,
+        },
+        decline_maintainer_invitation::DeclineMaintainerInvitationCommandBuilder,
+        invite_maintainer::{InviteMaintainerCommandBuilder, InviteMaintainerCommandError},
+        review_fork::{ReviewForkCommandBuilder, ReviewForkCommandError},
+        Command,
+    },
+    error::CommandBusError,
+};
+use sqlx::PgPool;
+use storage::{
+    model::{
+        fragment::{Fragment, FragmentState},
+        maintainer::{Maintainer, MaintainerBuilder, MaintainerState},
+        review::ReviewAction,
+        user::User,
+    },
+    query::{fragment::QueryFragment, maintainer::QueryMaintainer},
+};
+
+async fn save_maintainer(pool: &PgPool, root: &Fragment, user: &User, state: MaintainerState) {
+    MaintainerBuilder::default()
+        .root_id(*root.id())
+        .user_id(*user.id())
+        .invited_by(*root.author_id())
+        .state(state)
+        .invited_at(DateTime::now())
+        .build()
+        .unwrap()
+        .save(pool)
+        .await
+        .unwrap();
+}
+
+async fn create_submitted_fork(pool: &PgPool, user: &User, parent: &Fragment) -> Fragment {
+    create_fork(pool, user, parent)
+        .await
+        .set_state(FragmentState::Submitted)
+        .update(pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_invite_maintainer(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let invitee = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+    let event = InviteMaintainerCommandBuilder::default()
+        .root_id(*root.id())
+        .user_id(*invitee.id())
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.root_id, *root.id());
+    assert_eq!(event.user_id, *invitee.id());
+
+    let maintainer = Maintainer::find(ctx.tx().as_mut(), root.id(), invitee.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(maintainer.is_invited());
+    assert_eq!(maintainer.invited_by(), author.id());
+    assert!(
+        !Maintainer::is_maintainer(ctx.tx().as_mut(), root.id(), invitee.id())
+            .await
+            .unwrap()
+    );
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_invite_maintainer_errors(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let invitee = create_user(&pool).await;
+    let declined = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false).await;
+    let fork = create_fork(&pool, &author, &root).await;
+    save_maintainer(&pool, &root, &invitee, MaintainerState::Invited).await;
+    save_maintainer(&pool, &root, &declined, MaintainerState::Declined).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+
+    let cases = [
+        (&invitee, &root, *declined.id(), "forbidden"),
+        (&author, &root, *author.id(), "invalid"),
+        (&author, &fork, *declined.id(), "invalid"),
+        (&author, &root, Id::new(), "user"),
+        (&author, &root, *invitee.id(), "invited"),
+    ];
+    for (actor, root, user_id, case) in cases {
+        let mut ctx = create_context(&pool, actor, &clock, &ids).await;
+        let result = InviteMaintainerCommandBuilder::default()
+            .root_id(*root.id())
+            .user_id(user_id)
+            .build()
+            .unwrap()
+            .handle(&mut ctx)
+            .await;
+        let expected = match (case, result.as_ref().unwrap_err()) {
+            ("forbidden", CommandBusError::InviteMaintainerCommand(e)) => {
+                matches!(e, InviteMaintainerCommandError::Forbidden(_))
+            }
+            ("invalid", CommandBusError::InviteMaintainerCommand(e)) => {
+                matches!(e, InviteMaintainerCommandError::InvalidInvitation(_))
+            }
+            ("user", CommandBusError::InviteMaintainerCommand(e)) => {
+                matches!(e, InviteMaintainerCommandError::UserNotFound(_))
+            }
+            ("invited", CommandBusError::InviteMaintainerCommand(e)) => {
+                matches!(e, InviteMaintainerCommandError::AlreadyInvited(_))
+            }
+            _ => false,
+        };
+        assert!(expected, "{case}: {result:?}");
+    }
+
+    // Declined invitations can be sent again.
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+    InviteMaintainerCommandBuilder::default()
+        .root_id(*root.id())
+        .user_id(*declined.id())
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await
+        .unwrap();
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_respond_to_invitation(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let accepting = create_user(&pool).await;
+    let declining = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false).await;
+    save_maintainer(&pool, &root, &accepting, MaintainerState::Invited).await;
+    save_maintainer(&pool, &root, &declining, MaintainerState::Invited).await;
+    let now = DateTime::now();
+    let clock = fixed_clock(now);
+    let ids = fixed_id(Id::new());
+
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+    let result = AcceptMaintainerInvitationCommandBuilder::default()
+        .root_id(*root.id())
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await;
+    assert!(matches!(
+        result,
+        Err(CommandBusError::AcceptMaintainerInvitationCommand(
+            AcceptMaintainerInvitationCommandError::InvitationNotFound(_)
+        ))
+    ));
+
+    let mut ctx = create_context(&pool, &accepting, &clock, &ids).await;
+    AcceptMaintainerInvitationCommandBuilder::default()
+        .root_id(*root.id())
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await
+        .unwrap();
+    assert!(
+        Maintainer::is_maintainer(ctx.tx().as_mut(), root.id(), accepting.id())
+            .await
+            .unwrap()
+    );
+
+    let mut ctx = create_context(&pool, &declining, &clock, &ids).await;
+    DeclineMaintainerInvitationCommandBuilder::default()
+        .root_id(*root.id())
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await
+        .unwrap();
+    let maintainer = Maintainer::find(ctx.tx().as_mut(), root.id(), declining.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(*maintainer.state(), MaintainerState::Declined);
+    assert!(maintainer.responded_at().is_some());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_maintainer_reviews_fork(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let maintainer = create_user(&pool).await;
+    let invited = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false).await;
+    let branch = create_fork(&pool, &create_user(&pool).await, &root)
+        .await
+        .set_state(FragmentState::Published)
+        .update(&pool)
+        .await
+        .unwrap();
+    let fork = create_submitted_fork(&pool, &create_user(&pool).await, &branch).await;
+    let own_fork = create_submitted_fork(&pool, &maintainer, &branch).await;
+    save_maintainer(&pool, &root, &maintainer, MaintainerState::Accepted).await;
+    save_maintainer(&pool, &root, &invited, MaintainerState::Invited).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+
+    let review = |fork: &Fragment| {
+        ReviewForkCommandBuilder::default()
+            .review_id(Id::new())
+            .fragment_id(*fork.id())
+            .action(ReviewAction::Approve)
+            .comment(None)
+            .build()
+            .unwrap()
+    };
+
+    for (actor, fork) in [(&invited, &fork), (&maintainer, &own_fork)] {
+        let mut ctx = create_context(&pool, actor, &clock, &ids).await;
+        let result = review(fork).handle(&mut ctx).await;
+        assert!(matches!(
+            result,
+            Err(CommandBusError::ReviewForkCommand(
+                ReviewForkCommandError::Forbidden(_)
+            ))
+        ));
+    }
+
+    let mut ctx = create_context(&pool, &maintainer, &clock, &ids).await;
+    let event = review(&fork).handle(&mut ctx).await.unwrap().unwrap();
+    assert_eq!(event.fragment_id, *fork.id());
+    let fork = Fragment::find(ctx.tx().as_mut(), fork.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(fork.is_approved());
+}
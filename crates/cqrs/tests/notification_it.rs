@@ -0,0 +1,219 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    fixtures::{
+        fragment::{create_fork, create_published},
+        user::create_user,
+    },
+    mock::clock::fixed_clock,
+};
+use ::commons::{
+    id::{Id, StdIdGenerator},
+    time::DateTime,
+};
+use cqrs::command_bus::{
+    bus::CommandBus,
+    command::{
+        follow_user::FollowUserCommandBuilder,
+        like_fragment::LikeFragmentCommandBuilder,
+        mark_notifications_read::{
+            MarkNotificationsReadCommandBuilder, MarkNotificationsReadCommandError,
+        },
+        submit_fork::SubmitForkCommandBuilder,
+    },
+    error::CommandBusError,
+};
+use sqlx::PgPool;
+use std::sync::Arc;
+use storage::{
+    model::{
+        fragment::Fragment,
+        notification::{Notification, NotificationKind},
+        user::User,
+    },
+    query::notification::QueryNotification,
+};
+
+fn bus(pool: &PgPool) -> CommandBus {
+    CommandBus::new(
+        pool.clone(),
+        Arc::new(fixed_clock(DateTime::now())),
+        Arc::new(StdIdGenerator),
+    )
+}
+
+async fn like(bus: &CommandBus, user: &User, fragment: &Fragment) {
+    bus.execute(
+        user.clone(),
+        LikeFragmentCommandBuilder::default()
+            .fragment_id(*fragment.id())
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+}
+
+async fn inbox(pool: &PgPool, user: &User) -> Vec<Notification> {
+    Notification::find_by_user(pool, user.id(), false, 100, 0)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_likes_are_grouped(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let fragment = create_published(&pool, &author, "fragment", false).await;
+    let bus = bus(&pool);
+
+    like(&bus, &author, &fragment).await;
+    assert!(inbox(&pool, &author).await.is_empty());
+
+    let fans = [
+        create_user(&pool).await,
+        create_user(&pool).await,
+        create_user(&pool).await,
+    ];
+    for fan in &fans {
+        like(&bus, fan, &fragment).await;
+    }
+
+    let notifications = inbox(&pool, &author).await;
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(*notifications[0].kind(), NotificationKind::FragmentLiked);
+    assert_eq!(notifications[0].subject_id(), fragment.id());
+    let fan_ids: Vec<Id> = fans.iter().map(|f| *f.id()).collect();
+    assert_eq!(*notifications[0].actor_ids(), fan_ids);
+    assert!(!notifications[0].is_read());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_concurrent_likes_are_grouped(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let fragment = create_published(&pool, &author, "fragment", false).await;
+    let bus = bus(&pool);
+    let fans = [
+        create_user(&pool).await,
+        create_user(&pool).await,
+        create_user(&pool).await,
+    ];
+
+    tokio::join!(
+        like(&bus, &fans[0], &fragment),
+        like(&bus, &fans[1], &fragment),
+        like(&bus, &fans[2], &fragment),
+    );
+
+    let notifications = inbox(&pool, &author).await;
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].actor_ids().len(), fans.len());
+    assert!(fans
+        .iter()
+        .all(|fan| notifications[0].actor_ids().contains(fan.id())));
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_read_notifications_are_not_grouped(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let fragment = create_published(&pool, &author, "fragment", false).await;
+    let bus = bus(&pool);
+
+    like(&bus, &create_user(&pool).await, &fragment).await;
+    bus.execute(
+        author.clone(),
+        MarkNotificationsReadCommandBuilder::default()
+            .user_id(*author.id())
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    like(&bus, &create_user(&pool).await, &fragment).await;
+
+    let notifications = inbox(&pool, &author).await;
+    assert_eq!(notifications.len(), 2);
+    assert_eq!(
+        notifications.iter().filter(|n| n.is_read()).count(),
+        1,
+        "{notifications:?}"
+    );
+
+    let unread = notifications.iter().find(|n| !n.is_read()).unwrap();
+    bus.execute(
+        author.clone(),
+        MarkNotificationsReadCommandBuilder::default()
+            .user_id(*author.id())
+            .notification_id(Some(*unread.id()))
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    assert!(inbox(&pool, &author)
+        .await
+        .iter()
+        .all(Notification::is_read));
+
+    let stranger = create_user(&pool).await;
+    let result = bus
+        .execute(
+            stranger.clone(),
+            MarkNotificationsReadCommandBuilder::default()
+                .user_id(*stranger.id())
+                .notification_id(Some(*unread.id()))
+                .build()
+                .unwrap(),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(CommandBusError::MarkNotificationsReadCommand(
+            MarkNotificationsReadCommandError::NotificationNotFound(_)
+        ))
+    ));
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_fork_and_follow_notifications(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let contributor = create_user(&pool).await;
+    let parent = create_published(&pool, &author, "parent", false).await;
+    let fork = create_fork(&pool, &contributor, &parent).await;
+    let bus = bus(&pool);
+
+    bus.execute(
+        contributor.clone(),
+        SubmitForkCommandBuilder::default()
+            .fragment_id(*fork.id())
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    bus.execute(
+        contributor.clone(),
+        FollowUserCommandBuilder::default()
+            .following_user_id(*author.id())
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let notifications = inbox(&pool, &author).await;
+    let mut kinds: Vec<_> = notifications
+        .iter()
+        .map(|n| (*n.kind(), *n.subject_id()))
+        .collect();
+    kinds.sort_by_key(|(kind, _)| *kind as u8);
+    assert_eq!(
+        kinds,
+        vec![
+            (NotificationKind::ForkSubmitted, *parent.id()),
+            (NotificationKind::UserFollowed, *author.id()),
+        ]
+    );
+    assert!(inbox(&pool, &contributor).await.is_empty());
+}
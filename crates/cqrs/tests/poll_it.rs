@@ -0,0 +1,289 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    fixtures::{
+        fragment::{create_fork, create_published},
+        user::create_user,
+    },
+    mock::clock::fixed_clock,
+};
+use ::commons::{
+    actor::Actor,
+    events::EventType,
+    id::{Id, StdIdGenerator},
+    time::DateTime,
+};
+use chrono::Duration;
+use cqrs::{
+    command_bus::{
+        bus::CommandBus,
+        command::{
+            cast_vote::{CastVoteCommand, CastVoteCommandBuilder, CastVoteCommandError},
+            close_poll::{ClosePollCommand, ClosePollCommandBuilder},
+            open_poll::{OpenPollCommand, OpenPollCommandBuilder, OpenPollCommandError},
+            set_canonical_branch::{
+                SetCanonicalBranchCommandBuilder, SetCanonicalBranchCommandError,
+            },
+        },
+        error::CommandBusError,
+    },
+    events::PollClosedEvent,
+};
+use sqlx::PgPool;
+use std::sync::Arc;
+use storage::{
+    model::{
+        canonical_branch::CanonicalBranch,
+        event::DbEvent,
+        fragment::{Fragment, FragmentState},
+        poll::Poll,
+        user::User,
+    },
+    query::{
+        canonical_branch::QueryCanonicalBranch, event::QueryEvent, fragment::QueryFragment,
+        poll::QueryPoll,
+    },
+};
+
+fn bus_at(pool: &PgPool, now: DateTime) -> CommandBus {
+    CommandBus::new(
+        pool.clone(),
+        Arc::new(fixed_clock(now)),
+        Arc::new(StdIdGenerator),
+    )
+}
+
+async fn create_published_fork(pool: &PgPool, user: &User, parent: &Fragment) -> Fragment {
+    create_fork(pool, user, parent)
+        .await
+        .set_state(FragmentState::Published)
+        .update(pool)
+        .await
+        .unwrap()
+}
+
+fn open(poll_id: Id, fragment: &Fragment, closes_at: DateTime) -> OpenPollCommand {
+    OpenPollCommandBuilder::default()
+        .poll_id(poll_id)
+        .fragment_id(*fragment.id())
+        .closes_at(closes_at)
+        .build()
+        .unwrap()
+}
+
+fn vote(poll_id: Id, child: &Fragment) -> CastVoteCommand {
+    CastVoteCommandBuilder::default()
+        .poll_id(poll_id)
+        .child_id(*child.id())
+        .build()
+        .unwrap()
+}
+
+fn close(poll_id: Id) -> ClosePollCommand {
+    ClosePollCommandBuilder::default()
+        .poll_id(poll_id)
+        .build()
+        .unwrap()
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_poll_picks_canonical_branch(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false).await;
+    let first = create_published_fork(&pool, &create_user(&pool).await, &root).await;
+    let second = create_published_fork(&pool, &create_user(&pool).await, &root).await;
+    let now = DateTime::now();
+    let closes_at = now + Duration::days(1);
+    let bus = bus_at(&pool, now);
+    let poll_id = Id::new();
+
+    bus.execute(author.clone(), open(poll_id, &root, closes_at))
+        .await
+        .unwrap();
+    for choice in [&second, &first, &second] {
+        bus.execute(create_user(&pool).await, vote(poll_id, choice))
+            .await
+            .unwrap();
+    }
+
+    // Opening the poll scheduled its closing.
+    assert_eq!(bus.run_due_tasks().await.unwrap(), 0);
+    assert_eq!(bus_at(&pool, closes_at).run_due_tasks().await.unwrap(), 1);
+
+    let poll = Poll::find(&pool, &poll_id).await.unwrap().unwrap();
+    assert!(poll.is_closed());
+    assert_eq!(*poll.winner_id(), Some(*second.id()));
+    let branch = CanonicalBranch::find(&pool, root.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(branch.child_id(), second.id());
+    assert_eq!(*branch.chosen_by(), None);
+
+    let event: PollClosedEvent = DbEvent::all(&pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|e| *e.event_type() == EventType::PollClosed)
+        .unwrap()
+        .event_data()
+        .into_event();
+    assert_eq!(event.actor, Actor::System);
+    assert_eq!(event.results.len(), 2);
+    assert_eq!(*event.results[0].child_id(), *second.id());
+    assert_eq!(*event.results[0].votes(), 2);
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_open_poll_rules(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false).await;
+    let lonely = create_published(&pool, &author, "lonely", false).await;
+    create_published_fork(&pool, &author, &lonely).await;
+    create_published_fork(&pool, &author, &root).await;
+    create_published_fork(&pool, &author, &root).await;
+    let now = DateTime::now();
+    let bus = bus_at(&pool, now);
+
+    let result = bus
+        .execute(
+            create_user(&pool).await,
+            open(Id::new(), &root, now + Duration::hours(1)),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(CommandBusError::OpenPollCommand(
+            OpenPollCommandError::Forbidden(_)
+        ))
+    ));
+    for (fragment, closes_at) in [(&root, now), (&lonely, now + Duration::hours(1))] {
+        let result = bus
+            .execute(author.clone(), open(Id::new(), fragment, closes_at))
+            .await;
+        assert!(matches!(
+            result,
+            Err(CommandBusError::OpenPollCommand(
+                OpenPollCommandError::InvalidPoll(_)
+            ))
+        ));
+    }
+
+    let poll_id = Id::new();
+    bus.execute(
+        author.clone(),
+        open(poll_id, &root, now + Duration::hours(1)),
+    )
+    .await
+    .unwrap();
+    let result = bus
+        .execute(
+            author.clone(),
+            open(Id::new(), &root, now + Duration::hours(2)),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(CommandBusError::OpenPollCommand(
+            OpenPollCommandError::AlreadyOpen(id)
+        )) if id == poll_id
+    ));
+
+    let result = bus
+        .execute(
+            author.clone(),
+            SetCanonicalBranchCommandBuilder::default()
+                .fragment_id(*root.id())
+                .build()
+                .unwrap(),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(CommandBusError::SetCanonicalBranchCommand(
+            SetCanonicalBranchCommandError::PollInProgress(_)
+        ))
+    ));
+    assert!(matches!(
+        bus.execute(author.clone(), close(poll_id)).await,
+        Err(CommandBusError::ActorNotSupported(_))
+    ));
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_vote_rules(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false).await;
+    let child = create_published_fork(&pool, &author, &root).await;
+    create_published_fork(&pool, &author, &root).await;
+    let draft = create_fork(&pool, &author, &root).await;
+    let grandchild = create_published_fork(&pool, &author, &child).await;
+    let now = DateTime::now();
+    let closes_at = now + Duration::hours(1);
+    let bus = bus_at(&pool, now);
+    let poll_id = Id::new();
+    bus.execute(author.clone(), open(poll_id, &root, closes_at))
+        .await
+        .unwrap();
+
+    let voter = create_user(&pool).await;
+    for choice in [&draft, &grandchild] {
+        assert!(matches!(
+            bus.execute(voter.clone(), vote(poll_id, choice)).await,
+            Err(CommandBusError::CastVoteCommand(
+                CastVoteCommandError::InvalidChoice(_)
+            ))
+        ));
+    }
+    bus.execute(voter.clone(), vote(poll_id, &child))
+        .await
+        .unwrap();
+    assert!(matches!(
+        bus.execute(voter.clone(), vote(poll_id, &child)).await,
+        Err(CommandBusError::CastVoteCommand(
+            CastVoteCommandError::AlreadyVoted(_)
+        ))
+    ));
+    assert!(matches!(
+        bus_at(&pool, closes_at)
+            .execute(create_user(&pool).await, vote(poll_id, &child))
+            .await,
+        Err(CommandBusError::CastVoteCommand(
+            CastVoteCommandError::PollClosed(_)
+        ))
+    ));
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_poll_without_votes(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false).await;
+    create_published_fork(&pool, &author, &root).await;
+    create_published_fork(&pool, &author, &root).await;
+    let now = DateTime::now();
+    let closes_at = now + Duration::hours(1);
+    let poll_id = Id::new();
+    bus_at(&pool, now)
+        .execute(author.clone(), open(poll_id, &root, closes_at))
+        .await
+        .unwrap();
+
+    let result = bus_at(&pool, now)
+        .execute(Actor::System, close(poll_id))
+        .await;
+    assert!(result.is_err());
+
+    bus_at(&pool, closes_at)
+        .execute(Actor::System, close(poll_id))
+        .await
+        .unwrap();
+    let poll = Poll::find(&pool, &poll_id).await.unwrap().unwrap();
+    assert!(poll.is_closed());
+    assert_eq!(*poll.winner_id(), None);
+    assert!(CanonicalBranch::find(&pool, root.id())
+        .await
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,227 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    commons::create_context,
+    fixtures::{
+        fragment::{create_draft, create_fork, create_published},
+        user::{create_user, create_user_with_role},
+    },
+    mock::{clock::fixed_clock, ids::fixed_id},
+};
+use ::commons::{
+    actor::Role,
+    events::EventType,
+    id::{Id, StdIdGenerator},
+    time::DateTime,
+};
+use cqrs::command_bus::{
+    bus::CommandBus,
+    command::{
+        publish_fragment::{PublishFragmentCommandBuilder, PublishFragmentCommandError},
+        unpublish_fragment::{UnpublishFragmentCommandBuilder, UnpublishFragmentCommandError},
+        Command,
+    },
+    error::CommandBusError,
+};
+use sqlx::PgPool;
+use std::sync::Arc;
+use storage::{
+    model::{
+        fragment::{Fragment, FragmentState, Transition},
+        state_transition::StateTransition,
+    },
+    query::{fragment::QueryFragment, state_transition::QueryStateTransition},
+};
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_publish_draft(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let draft = create_draft(&pool, &author, "draft", false).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+
+    PublishFragmentCommandBuilder::default()
+        .fragment_id(*draft.id())
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await
+        .unwrap();
+
+    let fragment = Fragment::find(ctx.tx().as_mut(), draft.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(fragment.is_published());
+
+    let history = StateTransition::find_by_fragment(ctx.tx().as_mut(), draft.id())
+        .await
+        .unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(*history[0].transition(), Transition::Publish);
+    assert_eq!(*history[0].from_state(), FragmentState::Draft);
+    assert_eq!(*history[0].to_state(), FragmentState::Published);
+    assert_eq!(*history[0].actor_id(), Some(*author.id()));
+    assert_eq!(*history[0].event_type(), EventType::FragmentPublished);
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_publish_invalid_state(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let published = create_published(&pool, &author, "published", false).await;
+    let fork = create_fork(&pool, &author, &published).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+
+    for fragment in [&published, &fork] {
+        let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+        let result = PublishFragmentCommandBuilder::default()
+            .fragment_id(*fragment.id())
+            .build()
+            .unwrap()
+            .handle(&mut ctx)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(CommandBusError::PublishFragmentCommand(
+                PublishFragmentCommandError::InvalidState(_)
+            ))
+        ));
+    }
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_unpublish_keeps_published_forks(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let published = create_published(&pool, &author, "published", false).await;
+    let fork = create_fork(&pool, &create_user(&pool).await, &published)
+        .await
+        .set_state(FragmentState::Published)
+        .update(&pool)
+        .await
+        .unwrap();
+    create_fork(&pool, &create_user(&pool).await, &published).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+
+    let event = UnpublishFragmentCommandBuilder::default()
+        .fragment_id(*published.id())
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.published_forks, vec![*fork.id()]);
+
+    let fragment = Fragment::find(ctx.tx().as_mut(), published.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(fragment.is_draft());
+    let fork = Fragment::find(ctx.tx().as_mut(), fork.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(fork.is_published());
+
+    let history = StateTransition::find_by_fragment(ctx.tx().as_mut(), published.id())
+        .await
+        .unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(*history[0].transition(), Transition::Unpublish);
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_unpublish_permissions(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let stranger = create_user(&pool).await;
+    let moderator = create_user_with_role(&pool, Role::Moderator).await;
+    let published = create_published(&pool, &author, "published", false).await;
+    let draft = create_draft(&pool, &author, "draft", false).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let command = |fragment: &Fragment| {
+        UnpublishFragmentCommandBuilder::default()
+            .fragment_id(*fragment.id())
+            .build()
+            .unwrap()
+    };
+
+    let mut ctx = create_context(&pool, &stranger, &clock, &ids).await;
+    assert!(matches!(
+        command(&published).handle(&mut ctx).await,
+        Err(CommandBusError::UnpublishFragmentCommand(
+            UnpublishFragmentCommandError::Forbidden(_)
+        ))
+    ));
+
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+    assert!(matches!(
+        command(&draft).handle(&mut ctx).await,
+        Err(CommandBusError::UnpublishFragmentCommand(
+            UnpublishFragmentCommandError::InvalidState(_)
+        ))
+    ));
+
+    let mut ctx = create_context(&pool, &moderator, &clock, &ids).await;
+    command(&published).handle(&mut ctx).await.unwrap();
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_takedown_blocks_republish(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let moderator = create_user_with_role(&pool, Role::Moderator).await;
+    let published = create_published(&pool, &author, "published", false).await;
+    let bus = CommandBus::new(
+        pool.clone(),
+        Arc::new(fixed_clock(DateTime::now())),
+        Arc::new(StdIdGenerator),
+    );
+    let publish = || {
+        PublishFragmentCommandBuilder::default()
+            .fragment_id(*published.id())
+            .build()
+            .unwrap()
+    };
+    let unpublish = || {
+        UnpublishFragmentCommandBuilder::default()
+            .fragment_id(*published.id())
+            .build()
+            .unwrap()
+    };
+
+    // Authors retracting their own fragment may publish it again.
+    bus.execute(author.clone(), unpublish()).await.unwrap();
+    bus.execute(author.clone(), publish()).await.unwrap();
+
+    bus.execute(moderator.clone(), unpublish()).await.unwrap();
+    let fragment = Fragment::find(&pool, published.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(fragment.is_draft());
+    assert!(fragment.is_taken_down());
+    assert!(matches!(
+        bus.execute(author.clone(), publish()).await,
+        Err(CommandBusError::PublishFragmentCommand(
+            PublishFragmentCommandError::Forbidden(_)
+        ))
+    ));
+
+    // A moderator publishing it lifts the takedown.
+    bus.execute(moderator.clone(), publish()).await.unwrap();
+    let fragment = Fragment::find(&pool, published.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(fragment.is_published());
+    assert!(!fragment.is_taken_down());
+    bus.execute(author.clone(), unpublish()).await.unwrap();
+    bus.execute(author.clone(), publish()).await.unwrap();
+}
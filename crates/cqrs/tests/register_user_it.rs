@@ -0,0 +1,164 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    fixtures::user::create_user,
+    mock::{clock::fixed_clock, ids::fixed_id},
+};
+use ::commons::{
+    actor::Actor,
+    auth::PasswordHash,
+    id::{Id, MockIdGenerator},
+    time::{DateTime, MockClock},
+};
+use cqrs::command_bus::{
+    bus::Ctx,
+    command::{
+        register_user::{RegisterUserCommandBuilder, RegisterUserCommandError},
+        Command,
+    },
+    error::CommandBusError,
+};
+use sqlx::PgPool;
+use storage::{
+    model::{
+        credential::Credential,
+        user::{User, UserBuilder},
+    },
+    query::{credential::QueryCredential, user::QueryUser},
+};
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_register_success(pool: PgPool) {
+    let user_id = Id::new();
+    let actor = Actor::User(user_id);
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let mut ctx = Ctx::new(&pool, &actor, &clock, &ids).await.unwrap();
+
+    let command = RegisterUserCommandBuilder::default()
+        .user_id(user_id)
+        .username(" John.Doe ")
+        .password_hash(PasswordHash::generate("secret").unwrap())
+        .display_name(Some(String::from("John Doe")))
+        .build()
+        .unwrap();
+
+    let event = command.handle(&mut ctx).await.unwrap().unwrap();
+    assert_eq!(*event.user_id(), user_id);
+    assert_eq!(event.username(), "john.doe");
+    assert_eq!(event.display_name().as_deref(), Some("John Doe"));
+
+    let user = User::find(ctx.tx().as_mut(), &user_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(user.username(), "john.doe");
+
+    let credential = Credential::find_by_login(ctx.tx().as_mut(), "john.doe")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(*credential.user_id(), user_id);
+    assert!(credential.verify("secret"));
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_username_taken(pool: PgPool) {
+    let existing = create_user(&pool).await;
+    let actor = Actor::User(Id::new());
+    let clock = MockClock::default();
+    let ids = MockIdGenerator::default();
+    let mut ctx = Ctx::new(&pool, &actor, &clock, &ids).await.unwrap();
+
+    let command = RegisterUserCommandBuilder::default()
+        .user_id(actor.id().unwrap())
+        .username(existing.username().clone())
+        .password_hash(PasswordHash::generate("secret").unwrap())
+        .build()
+        .unwrap();
+
+    match command.handle(&mut ctx).await {
+        Err(CommandBusError::RegisterUserCommand(e)) => assert_eq!(
+            e,
+            RegisterUserCommandError::UsernameTaken(existing.username().clone())
+        ),
+        Err(_) => panic!("Not the expected error"),
+        Ok(_) => panic!("Expected Err(CommandBusError) but got Ok(_)"),
+    }
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_username_taken_concurrently(pool: PgPool) {
+    let actor = Actor::User(Id::new());
+    let clock = fixed_clock(DateTime::now());
+    let ids = MockIdGenerator::default();
+    let mut ctx = Ctx::new(&pool, &actor, &clock, &ids).await.unwrap();
+
+    // Saved in the command's transaction, so the lookup on the pool misses it.
+    UserBuilder::default()
+        .id(Id::new())
+        .username("racer")
+        .created_at(DateTime::now())
+        .build()
+        .unwrap()
+        .save(ctx.tx().as_mut())
+        .await
+        .unwrap();
+
+    let command = RegisterUserCommandBuilder::default()
+        .user_id(actor.id().unwrap())
+        .username("racer")
+        .password_hash(PasswordHash::generate("secret").unwrap())
+        .build()
+        .unwrap();
+
+    match command.handle(&mut ctx).await {
+        Err(CommandBusError::RegisterUserCommand(e)) => assert_eq!(
+            e,
+            RegisterUserCommandError::UsernameTaken(String::from("racer"))
+        ),
+        Err(_) => panic!("Not the expected error"),
+        Ok(_) => panic!("Expected Err(CommandBusError) but got Ok(_)"),
+    }
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_invalid_username(pool: PgPool) {
+    let actor = Actor::User(Id::new());
+    let clock = MockClock::default();
+    let ids = MockIdGenerator::default();
+    let mut ctx = Ctx::new(&pool, &actor, &clock, &ids).await.unwrap();
+
+    let command = RegisterUserCommandBuilder::default()
+        .user_id(actor.id().unwrap())
+        .username("no spaces")
+        .password_hash(PasswordHash::generate("secret").unwrap())
+        .build()
+        .unwrap();
+
+    match command.handle(&mut ctx).await {
+        Err(CommandBusError::RegisterUserCommand(e)) => assert_eq!(
+            e,
+            RegisterUserCommandError::InvalidUsername(String::from("no spaces"))
+        ),
+        Err(_) => panic!("Not the expected error"),
+        Ok(_) => panic!("Expected Err(CommandBusError) but got Ok(_)"),
+    }
+}
+
+#[test]
+fn test_supports_only_the_registering_user() {
+    let user_id = Id::new();
+    let command = RegisterUserCommandBuilder::default()
+        .user_id(user_id)
+        .username("john")
+        .password_hash(PasswordHash::generate("secret").unwrap())
+        .build()
+        .unwrap();
+
+    assert!(command.supports(&Actor::User(user_id)));
+    assert!(!command.supports(&Actor::User(Id::new())));
+    assert!(!command.supports(&Actor::System));
+}
@@ -0,0 +1,146 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    commons::create_context,
+    fixtures::{
+        fragment::{create_draft, create_published},
+        user::create_user,
+    },
+    mock::{clock::fixed_clock, ids::fixed_id},
+};
+use ::commons::{actor::ActorTrait, fragment::Content, id::Id, time::DateTime};
+use cqrs::{
+    command_bus::{
+        command::{
+            revert_fragment::{RevertFragmentCommandBuilder, RevertFragmentCommandError},
+            Command,
+        },
+        error::CommandBusError,
+    },
+    events::FragmentRevertedEventBuilder,
+};
+use sqlx::PgPool;
+use storage::{
+    model::{fragment::Fragment, revision::Revision},
+    query::{fragment::QueryFragment, revision::QueryRevision},
+};
+
+async fn save_revision(pool: &PgPool, fragment: Fragment, content: &str) -> Fragment {
+    let fragment = fragment
+        .set_content(Content::from(content))
+        .update(pool)
+        .await
+        .unwrap();
+    Revision::of(&fragment, *fragment.author_id())
+        .save(pool)
+        .await
+        .unwrap();
+    fragment
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_revert(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let draft = create_draft(&pool, &author, "first", false).await;
+    let draft = save_revision(&pool, draft, "first").await;
+    let draft = save_revision(&pool, draft, "second").await;
+    let now = DateTime::now();
+    let clock = fixed_clock(now);
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+
+    let command = RevertFragmentCommandBuilder::default()
+        .fragment_id(*draft.id())
+        .revision(1)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        command.handle(&mut ctx).await.unwrap(),
+        Some(
+            FragmentRevertedEventBuilder::default()
+                .fragment_id(*draft.id())
+                .reverted_to(1)
+                .revision(3)
+                .content("first")
+                .timestamp(now)
+                .actor(author.actor())
+                .build()
+                .unwrap()
+        )
+    );
+
+    let fragment = Fragment::find(ctx.tx().as_mut(), draft.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(fragment.content(), &Content::from("first"));
+
+    let contents = Revision::find_by_fragment(ctx.tx().as_mut(), draft.id())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|r| r.content().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        contents,
+        vec![
+            Content::from("first"),
+            Content::from("second"),
+            Content::from("first")
+        ]
+    );
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_revert_unknown_revision(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let draft = create_draft(&pool, &author, "first", false).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+
+    let result = RevertFragmentCommandBuilder::default()
+        .fragment_id(*draft.id())
+        .revision(7)
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await;
+
+    match result {
+        Err(CommandBusError::RevertFragmentCommand(e)) => {
+            assert_eq!(e, RevertFragmentCommandError::RevisionNotFound(7))
+        }
+        Err(_) => panic!("Not the expected error"),
+        Ok(_) => panic!("Expected Err(CommandBusError) but got Ok(_)"),
+    }
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_revert_published_fragment(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let published = create_published(&pool, &author, "first", false).await;
+    let published = save_revision(&pool, published, "first").await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+
+    let result = RevertFragmentCommandBuilder::default()
+        .fragment_id(*published.id())
+        .revision(1)
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await;
+
+    match result {
+        Err(CommandBusError::RevertFragmentCommand(
+            RevertFragmentCommandError::NonEditableFragment(_),
+        )) => {}
+        Err(_) => panic!("Not the expected error"),
+        Ok(_) => panic!("Expected Err(CommandBusError) but got Ok(_)"),
+    }
+}
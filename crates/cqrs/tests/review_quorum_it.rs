@@ -0,0 +1,339 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    commons::create_context,
+    fixtures::{
+        fragment::{create_fork, create_published},
+        user::create_user,
+    },
+    mock::{clock::fixed_clock, ids::fixed_id},
+};
+use ::commons::{
+    actor::{Actor, ActorTrait},
+    id::{Id, StdIdGenerator},
+    time::DateTime,
+};
+use chrono::Duration;
+use cqrs::command_bus::{
+    bus::CommandBus,
+    command::{
+        review_fork::{ReviewForkCommand, ReviewForkCommandBuilder},
+        set_review_quorum::{SetReviewQuorumCommandBuilder, SetReviewQuorumCommandError},
+        Command,
+    },
+    error::CommandBusError,
+};
+use sqlx::PgPool;
+use std::sync::Arc;
+use storage::{
+    model::{
+        fragment::{Fragment, FragmentState, LifecycleRole, Transition},
+        maintainer::{MaintainerBuilder, MaintainerState},
+        review::{Review, ReviewAction, ReviewBuilder},
+        review_quorum::{ReviewQuorum, ReviewQuorumBuilder},
+        user::User,
+    },
+    query::{
+        fragment::QueryFragment, maintainer::QueryMaintainer, review::QueryReview,
+        review_quorum::QueryReviewQuorum, state_transition::QueryStateTransition,
+    },
+};
+
+struct Story {
+    fork: Fragment,
+    reviewers: Vec<User>,
+}
+
+/// Published root with a submitted fork and three accepted maintainers.
+async fn create_story(pool: &PgPool, approvals: i32, veto: bool) -> Story {
+    let author = create_user(pool).await;
+    let root = create_published(pool, &author, "root", false).await;
+    let fork = create_fork(pool, &create_user(pool).await, &root)
+        .await
+        .set_state(FragmentState::Submitted)
+        .update(pool)
+        .await
+        .unwrap();
+
+    let mut reviewers = Vec::new();
+    for _ in 0..3 {
+        let user = create_user(pool).await;
+        MaintainerBuilder::default()
+            .root_id(*root.id())
+            .user_id(*user.id())
+            .invited_by(*author.id())
+            .state(MaintainerState::Accepted)
+            .invited_at(DateTime::now())
+            .build()
+            .unwrap()
+            .save(pool)
+            .await
+            .unwrap();
+        reviewers.push(user);
+    }
+    ReviewQuorumBuilder::default()
+        .root_id(*root.id())
+        .approvals(approvals)
+        .veto(veto)
+        .updated_at(DateTime::now())
+        .build()
+        .unwrap()
+        .save(pool)
+        .await
+        .unwrap();
+
+    Story { fork, reviewers }
+}
+
+async fn save_review(pool: &PgPool, fork: &Fragment, reviewer: &User, action: ReviewAction) {
+    ReviewBuilder::default()
+        .id(Id::new())
+        .fragment_id(*fork.id())
+        .reviewer_id(*reviewer.id())
+        .action(action)
+        .comment(None)
+        .created_at(DateTime::now() - Duration::minutes(1))
+        .build()
+        .unwrap()
+        .save(pool)
+        .await
+        .unwrap();
+}
+
+fn review(fork: &Fragment, action: ReviewAction) -> ReviewForkCommand {
+    ReviewForkCommandBuilder::default()
+        .review_id(Id::new())
+        .fragment_id(*fork.id())
+        .action(action)
+        .comment(None)
+        .build()
+        .unwrap()
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_set_review_quorum(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false).await;
+    let fork = create_fork(&pool, &author, &root).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+
+    let quorum = |fragment: &Fragment, approvals: i32| {
+        SetReviewQuorumCommandBuilder::default()
+            .fragment_id(*fragment.id())
+            .approvals(approvals)
+            .veto(true)
+            .build()
+            .unwrap()
+    };
+
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+    let event = quorum(&root, 2).handle(&mut ctx).await.unwrap().unwrap();
+    assert_eq!(event.approvals, 2);
+    assert!(event.veto);
+    let saved = ReviewQuorum::find(ctx.tx().as_mut(), root.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(*saved.approvals(), 2);
+    assert!(*saved.veto());
+
+    for (actor, fragment, approvals) in [
+        (&author, &root, 0),
+        (&author, &root, 11),
+        (&author, &fork, 2),
+    ] {
+        let mut ctx = create_context(&pool, actor, &clock, &ids).await;
+        let result = quorum(fragment, approvals).handle(&mut ctx).await;
+        assert!(matches!(
+            result,
+            Err(CommandBusError::SetReviewQuorumCommand(
+                SetReviewQuorumCommandError::InvalidQuorum(_)
+            ))
+        ));
+    }
+
+    let other = create_user(&pool).await;
+    let mut ctx = create_context(&pool, &other, &clock, &ids).await;
+    let result = quorum(&root, 2).handle(&mut ctx).await;
+    assert!(matches!(
+        result,
+        Err(CommandBusError::SetReviewQuorumCommand(
+            SetReviewQuorumCommandError::Forbidden(_)
+        ))
+    ));
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_fork_waits_for_approvals(pool: PgPool) {
+    let story = create_story(&pool, 2, false).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+
+    let mut ctx = create_context(&pool, &story.reviewers[0], &clock, &ids).await;
+    let event = review(&story.fork, ReviewAction::Approve)
+        .handle(&mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.state, FragmentState::Submitted);
+    let fork = Fragment::find(ctx.tx().as_mut(), story.fork.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(fork.is_submitted());
+    drop(ctx);
+
+    save_review(
+        &pool,
+        &story.fork,
+        &story.reviewers[0],
+        ReviewAction::Approve,
+    )
+    .await;
+    let mut ctx = create_context(&pool, &story.reviewers[1], &clock, &ids).await;
+    let event = review(&story.fork, ReviewAction::Approve)
+        .handle(&mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.state, FragmentState::Approved);
+    assert_eq!(event.actor, story.reviewers[1].actor());
+    let fork = Fragment::find(ctx.tx().as_mut(), story.fork.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(fork.is_approved());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_concurrent_approvals_reach_quorum(pool: PgPool) {
+    let story = create_story(&pool, 2, false).await;
+    let bus = CommandBus::new(
+        pool.clone(),
+        Arc::new(fixed_clock(DateTime::now())),
+        Arc::new(StdIdGenerator),
+    );
+
+    let (first, second) = tokio::join!(
+        bus.execute(
+            story.reviewers[0].clone(),
+            review(&story.fork, ReviewAction::Approve)
+        ),
+        bus.execute(
+            story.reviewers[1].clone(),
+            review(&story.fork, ReviewAction::Approve)
+        ),
+    );
+    first.unwrap();
+    second.unwrap();
+
+    let fork = Fragment::find(&pool, story.fork.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(fork.is_approved());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_rejections(pool: PgPool) {
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+
+    let story = create_story(&pool, 2, false).await;
+    let mut ctx = create_context(&pool, &story.reviewers[0], &clock, &ids).await;
+    let event = review(&story.fork, ReviewAction::Reject)
+        .handle(&mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.state, FragmentState::Submitted);
+    drop(ctx);
+
+    save_review(
+        &pool,
+        &story.fork,
+        &story.reviewers[0],
+        ReviewAction::Approve,
+    )
+    .await;
+    let mut ctx = create_context(&pool, &story.reviewers[1], &clock, &ids).await;
+    let event = review(&story.fork, ReviewAction::RequestChanges)
+        .handle(&mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.state, FragmentState::WaitingChanges);
+
+    let vetoed = create_story(&pool, 3, true).await;
+    save_review(
+        &pool,
+        &vetoed.fork,
+        &vetoed.reviewers[0],
+        ReviewAction::Approve,
+    )
+    .await;
+    save_review(
+        &pool,
+        &vetoed.fork,
+        &vetoed.reviewers[1],
+        ReviewAction::Approve,
+    )
+    .await;
+    let mut ctx = create_context(&pool, &vetoed.reviewers[2], &clock, &ids).await;
+    let event = review(&vetoed.fork, ReviewAction::Reject)
+        .handle(&mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.state, FragmentState::Rejected);
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_resubmission_starts_new_round(pool: PgPool) {
+    let story = create_story(&pool, 2, false).await;
+    save_review(
+        &pool,
+        &story.fork,
+        &story.reviewers[0],
+        ReviewAction::Approve,
+    )
+    .await;
+    save_review(
+        &pool,
+        &story.fork,
+        &story.reviewers[1],
+        ReviewAction::RequestChanges,
+    )
+    .await;
+
+    let author = Actor::User(*story.fork.author_id());
+    let (fork, transition) = story
+        .fork
+        .set_state(FragmentState::WaitingChanges)
+        .transition(
+            Transition::Submit,
+            author,
+            LifecycleRole::Author,
+            DateTime::now(),
+        )
+        .unwrap();
+    let fork = fork.update(&pool).await.unwrap();
+    transition.save(&pool).await.unwrap();
+
+    let clock = fixed_clock(DateTime::now() + Duration::minutes(1));
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &story.reviewers[1], &clock, &ids).await;
+    let event = review(&fork, ReviewAction::Approve)
+        .handle(&mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.state, FragmentState::Submitted);
+    let round = Review::find_current_round(ctx.tx().as_mut(), fork.id())
+        .await
+        .unwrap();
+    assert_eq!(round.len(), 1);
+}
@@ -0,0 +1,303 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    fixtures::{
+        fragment::{create_fork, create_published},
+        user::create_user,
+    },
+    mock::clock::fixed_clock,
+};
+use ::commons::{
+    actor::Actor,
+    events::EventType,
+    id::{Id, StdIdGenerator},
+    time::DateTime,
+};
+use chrono::Duration;
+use cqrs::{
+    command_bus::{
+        bus::CommandBus,
+        command::{
+            expire_fork::{ExpireForkCommand, ExpireForkCommandBuilder},
+            resubmit_fork::ResubmitForkCommandBuilder,
+            review_fork::ReviewForkCommandBuilder,
+            set_review_sla::{SetReviewSlaCommandBuilder, SetReviewSlaCommandError},
+            submit_fork::SubmitForkCommandBuilder,
+        },
+        error::CommandBusError,
+    },
+    events::ForkExpiredEvent,
+};
+use sqlx::PgPool;
+use std::sync::Arc;
+use storage::{
+    model::{
+        event::DbEvent,
+        fragment::{Fragment, FragmentState},
+        maintainer::{MaintainerBuilder, MaintainerState},
+        notification::{Notification, NotificationKind},
+        review::ReviewAction,
+        review_quorum::ReviewQuorumBuilder,
+        review_sla::{ReviewSla, SlaOutcome},
+        user::User,
+    },
+    query::{
+        event::QueryEvent, fragment::QueryFragment, maintainer::QueryMaintainer,
+        notification::QueryNotification, review_quorum::QueryReviewQuorum,
+        review_sla::QueryReviewSla, user::QueryUser,
+    },
+};
+
+const WINDOW_HOURS: i32 = 24;
+
+fn bus_at(pool: &PgPool, now: DateTime) -> CommandBus {
+    CommandBus::new(
+        pool.clone(),
+        Arc::new(fixed_clock(now)),
+        Arc::new(StdIdGenerator),
+    )
+}
+
+fn expire(fork: &Fragment) -> ExpireForkCommand {
+    ExpireForkCommandBuilder::default()
+        .fragment_id(*fork.id())
+        .build()
+        .unwrap()
+}
+
+/// Story with the given SLA and a fork submitted at `now`, which schedules its expiry.
+async fn submit_with_sla(
+    pool: &PgPool,
+    outcome: SlaOutcome,
+    now: DateTime,
+) -> (User, Fragment, Fragment) {
+    let author = create_user(pool).await;
+    let fork_author = create_user(pool).await;
+    let root = create_published(pool, &author, "root", false).await;
+    let fork = create_fork(pool, &fork_author, &root).await;
+    let bus = bus_at(pool, now);
+
+    bus.execute(
+        author.clone(),
+        SetReviewSlaCommandBuilder::default()
+            .fragment_id(*root.id())
+            .window_hours(WINDOW_HOURS)
+            .outcome(outcome)
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    bus.execute(
+        fork_author,
+        SubmitForkCommandBuilder::default()
+            .fragment_id(*fork.id())
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    (author, root, fork)
+}
+
+async fn inbox(pool: &PgPool, user_id: &Id) -> Vec<Notification> {
+    Notification::find_by_user(pool, user_id, false, 100, 0)
+        .await
+        .unwrap()
+}
+
+async fn expired_event(pool: &PgPool, event_type: EventType) -> ForkExpiredEvent {
+    DbEvent::all(pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|e| *e.event_type() == event_type)
+        .unwrap()
+        .event_data()
+        .into_event()
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_set_review_sla(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false).await;
+    let bus = bus_at(&pool, DateTime::now());
+    let sla = |window_hours: i32| {
+        SetReviewSlaCommandBuilder::default()
+            .fragment_id(*root.id())
+            .window_hours(window_hours)
+            .outcome(SlaOutcome::Escalate)
+            .build()
+            .unwrap()
+    };
+
+    bus.execute(author.clone(), sla(48)).await.unwrap();
+    let saved = ReviewSla::find(&pool, root.id()).await.unwrap().unwrap();
+    assert_eq!(*saved.window_hours(), 48);
+    assert_eq!(*saved.outcome(), SlaOutcome::Escalate);
+
+    for window_hours in [0, 721] {
+        assert!(matches!(
+            bus.execute(author.clone(), sla(window_hours)).await,
+            Err(CommandBusError::SetReviewSlaCommand(
+                SetReviewSlaCommandError::InvalidSla(_)
+            ))
+        ));
+    }
+    assert!(matches!(
+        bus.execute(create_user(&pool).await, sla(48)).await,
+        Err(CommandBusError::SetReviewSlaCommand(
+            SetReviewSlaCommandError::Forbidden(_)
+        ))
+    ));
+    assert!(matches!(
+        bus.execute(author, expire(&root)).await,
+        Err(CommandBusError::ActorNotSupported(_))
+    ));
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_unreviewed_fork_expires(pool: PgPool) {
+    let now = DateTime::now();
+    let deadline = now + Duration::hours(i64::from(WINDOW_HOURS));
+
+    let (_, _, rejected) = submit_with_sla(&pool, SlaOutcome::Reject, now).await;
+    let (_, _, drafted) = submit_with_sla(&pool, SlaOutcome::ReturnToDraft, now).await;
+    assert_eq!(bus_at(&pool, deadline).run_due_tasks().await.unwrap(), 2);
+
+    let rejected = Fragment::find(&pool, rejected.id()).await.unwrap().unwrap();
+    assert_eq!(*rejected.state(), FragmentState::Rejected);
+    let event = expired_event(&pool, EventType::ForkAutoRejected).await;
+    assert_eq!(event.author_id, *rejected.author_id());
+    assert_eq!(event.actor, Actor::System);
+
+    let drafted = Fragment::find(&pool, drafted.id()).await.unwrap().unwrap();
+    assert!(drafted.is_draft());
+    let event = expired_event(&pool, EventType::ForkReturnedToDraft).await;
+    assert_eq!(event.fragment_id, *drafted.id());
+    assert!(event.maintainers.is_empty());
+
+    // Fork authors hear about it from the system.
+    for (fork, kind) in [
+        (&rejected, NotificationKind::ForkAutoRejected),
+        (&drafted, NotificationKind::ForkReturnedToDraft),
+    ] {
+        let inbox = inbox(&pool, fork.author_id()).await;
+        assert_eq!(inbox.len(), 1);
+        assert_eq!(*inbox[0].kind(), kind);
+        assert_eq!(inbox[0].subject_id(), fork.id());
+        assert!(inbox[0].actor_ids().is_empty());
+    }
+
+    // Resubmitting schedules the expiry of the new round.
+    let fork_author = User::find(&pool, rejected.author_id())
+        .await
+        .unwrap()
+        .unwrap();
+    bus_at(&pool, deadline)
+        .execute(
+            fork_author,
+            ResubmitForkCommandBuilder::default()
+                .fragment_id(*rejected.id())
+                .max_attempts(1u32)
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let next_deadline = deadline + Duration::hours(i64::from(WINDOW_HOURS));
+    assert_eq!(bus_at(&pool, deadline).run_due_tasks().await.unwrap(), 0);
+    assert_eq!(
+        bus_at(&pool, next_deadline).run_due_tasks().await.unwrap(),
+        1
+    );
+    let rejected = Fragment::find(&pool, rejected.id()).await.unwrap().unwrap();
+    assert_eq!(*rejected.state(), FragmentState::Rejected);
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_unreviewed_fork_escalates(pool: PgPool) {
+    let now = DateTime::now();
+    let (author, root, fork) = submit_with_sla(&pool, SlaOutcome::Escalate, now).await;
+    let mut maintainers = Vec::new();
+    for state in [MaintainerState::Accepted, MaintainerState::Invited] {
+        let user = create_user(&pool).await;
+        MaintainerBuilder::default()
+            .root_id(*root.id())
+            .user_id(*user.id())
+            .invited_by(*author.id())
+            .state(state)
+            .invited_at(now)
+            .build()
+            .unwrap()
+            .save(&pool)
+            .await
+            .unwrap();
+        maintainers.push(*user.id());
+    }
+
+    let deadline = now + Duration::hours(i64::from(WINDOW_HOURS));
+    assert_eq!(bus_at(&pool, deadline).run_due_tasks().await.unwrap(), 1);
+
+    let fork = Fragment::find(&pool, fork.id()).await.unwrap().unwrap();
+    assert!(fork.is_submitted());
+    let event = expired_event(&pool, EventType::ForkEscalated).await;
+    assert_eq!(event.outcome, SlaOutcome::Escalate);
+    assert_eq!(event.maintainers, vec![maintainers[0]]);
+
+    // Reviewers are notified, the invited maintainer is not one yet.
+    for reviewer in [author.id(), &maintainers[0]] {
+        let escalated: Vec<_> = inbox(&pool, reviewer)
+            .await
+            .into_iter()
+            .filter(|n| *n.kind() == NotificationKind::ForkEscalated)
+            .collect();
+        assert_eq!(escalated.len(), 1);
+        assert_eq!(escalated[0].subject_id(), fork.id());
+    }
+    assert!(inbox(&pool, &maintainers[1]).await.is_empty());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_reviewed_fork_does_not_expire(pool: PgPool) {
+    let now = DateTime::now();
+    let deadline = now + Duration::hours(i64::from(WINDOW_HOURS));
+    let (author, root, fork) = submit_with_sla(&pool, SlaOutcome::Reject, now).await;
+    ReviewQuorumBuilder::default()
+        .root_id(*root.id())
+        .approvals(2)
+        .updated_at(now)
+        .build()
+        .unwrap()
+        .save(&pool)
+        .await
+        .unwrap();
+
+    bus_at(&pool, now + Duration::hours(1))
+        .execute(
+            author,
+            ReviewForkCommandBuilder::default()
+                .review_id(Id::new())
+                .fragment_id(*fork.id())
+                .action(ReviewAction::Approve)
+                .comment(None)
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let bus = bus_at(&pool, now + Duration::hours(1));
+    bus.execute(Actor::System, expire(&fork)).await.unwrap();
+    bus_at(&pool, deadline).run_due_tasks().await.unwrap();
+
+    let fork = Fragment::find(&pool, fork.id()).await.unwrap().unwrap();
+    assert!(fork.is_submitted());
+    let events = DbEvent::all(&pool).await.unwrap();
+    assert!(!events
+        .iter()
+        .any(|e| *e.event_type() == EventType::ForkAutoRejected));
+}
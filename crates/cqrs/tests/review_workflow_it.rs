@@ -0,0 +1,332 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    commons::create_context,
+    fixtures::{
+        fragment::{create_fork, create_published},
+        user::create_user,
+    },
+    mock::{clock::fixed_clock, ids::fixed_id},
+};
+use ::commons::{
+    actor::ActorTrait,
+    fragment::Content,
+    id::{Id, StdIdGenerator},
+    review::Comment,
+    time::DateTime,
+};
+use chrono::Duration;
+use cqrs::command_bus::{
+    bus::CommandBus,
+    command::{
+        resubmit_fork::{ResubmitForkCommandBuilder, ResubmitForkCommandError},
+        review_fork::ReviewForkCommandBuilder,
+        submit_fork::SubmitForkCommandBuilder,
+        update_fragment::UpdateFragmentCommandBuilder,
+        withdraw_fork::{WithdrawForkCommandBuilder, WithdrawForkCommandError},
+        Command,
+    },
+    error::CommandBusError,
+};
+use sqlx::PgPool;
+use std::sync::Arc;
+use storage::{
+    model::{
+        comment::Comment as ThreadComment,
+        fragment::{Fragment, FragmentState, Transition},
+        review::{Review, ReviewAction, ReviewBuilder},
+        revision::Revision,
+        state_transition::StateTransition,
+        user::User,
+    },
+    query::{
+        comment::QueryComment, fragment::QueryFragment, review::QueryReview,
+        revision::QueryRevision, state_transition::QueryStateTransition,
+    },
+};
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_submit_fork(pool: PgPool) {
+    let parent = create_published(&pool, &create_user(&pool).await, "parent", false).await;
+    let fork_author = create_user(&pool).await;
+    let fork = create_fork(&pool, &fork_author, &parent).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &fork_author, &clock, &ids).await;
+
+    SubmitForkCommandBuilder::default()
+        .fragment_id(*fork.id())
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await
+        .unwrap();
+
+    let fork = Fragment::find(ctx.tx().as_mut(), fork.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(fork.is_submitted());
+
+    let history = StateTransition::find_by_fragment(ctx.tx().as_mut(), fork.id())
+        .await
+        .unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(*history[0].transition(), Transition::Submit);
+    assert_eq!(*history[0].actor_id(), Some(*fork_author.id()));
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_review_fork(pool: PgPool) {
+    let parent_author = create_user(&pool).await;
+    let parent = create_published(&pool, &parent_author, "parent", false).await;
+    let fork = create_fork(&pool, &create_user(&pool).await, &parent)
+        .await
+        .set_state(FragmentState::Submitted)
+        .update(&pool)
+        .await
+        .unwrap();
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &parent_author, &clock, &ids).await;
+
+    let event = ReviewForkCommandBuilder::default()
+        .review_id(Id::new())
+        .fragment_id(*fork.id())
+        .action(ReviewAction::RequestChanges)
+        .comment(Some(Comment::from(String::from("needs work"))))
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await
+        .unwrap();
+
+    let event = event.unwrap();
+    assert_eq!(event.fragment_id, *fork.id());
+    assert_eq!(event.action, ReviewAction::RequestChanges);
+    assert_eq!(
+        event.comment,
+        Some(Comment::from(String::from("needs work")))
+    );
+    assert_eq!(event.actor, parent_author.actor());
+
+    let reviews = Review::find_by_fragment(ctx.tx().as_mut(), fork.id())
+        .await
+        .unwrap();
+    assert_eq!(reviews.len(), 1);
+    assert_eq!(reviews[0].reviewer_id(), &Some(*parent_author.id()));
+
+    let fork = Fragment::find(ctx.tx().as_mut(), fork.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(fork.is_waiting_changes());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_revisions_since_last_review(pool: PgPool) {
+    let parent_author = create_user(&pool).await;
+    let parent = create_published(&pool, &parent_author, "parent", false).await;
+    let fork_author = create_user(&pool).await;
+    let fork = create_fork(&pool, &fork_author, &parent).await;
+    let now = DateTime::now();
+    let bus = |minutes: i64| {
+        CommandBus::new(
+            pool.clone(),
+            Arc::new(fixed_clock(now + Duration::minutes(minutes))),
+            Arc::new(StdIdGenerator),
+        )
+    };
+    let edit = |minutes: i64, content: &'static str| {
+        let bus = bus(minutes);
+        let fork_author = fork_author.clone();
+        let fork_id = *fork.id();
+        async move {
+            bus.execute(
+                fork_author,
+                UpdateFragmentCommandBuilder::default()
+                    .fragment_id(fork_id)
+                    .content(Some(Content::from(String::from(content))))
+                    .end(None)
+                    .build()
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        }
+    };
+    let review = |minutes: i64, reviewer: User| {
+        let bus = bus(minutes);
+        let fork_id = *fork.id();
+        async move {
+            bus.execute(
+                reviewer,
+                ReviewForkCommandBuilder::default()
+                    .review_id(Id::new())
+                    .fragment_id(fork_id)
+                    .action(ReviewAction::RequestChanges)
+                    .comment(None)
+                    .build()
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        }
+    };
+    let contents = |revisions: Vec<Revision>| {
+        revisions
+            .into_iter()
+            .map(|r| r.content().as_ref().to_owned())
+            .collect::<Vec<_>>()
+    };
+
+    edit(1, "before review").await;
+    bus(2)
+        .execute(
+            fork_author.clone(),
+            SubmitForkCommandBuilder::default()
+                .fragment_id(*fork.id())
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    // Never reviewed, every revision is new.
+    let unreviewed = Revision::find_since_last_review(&pool, fork.id())
+        .await
+        .unwrap();
+    let all = Revision::find_by_fragment(&pool, fork.id()).await.unwrap();
+    assert_eq!(contents(unreviewed), contents(all));
+    assert!(
+        contents(Revision::find_by_fragment(&pool, fork.id()).await.unwrap())
+            .contains(&String::from("before review"))
+    );
+
+    review(3, parent_author.clone()).await;
+    edit(4, "after review").await;
+    edit(5, "after review, again").await;
+
+    let revisions = Revision::find_since_last_review(&pool, fork.id())
+        .await
+        .unwrap();
+    assert_eq!(
+        contents(revisions),
+        vec!["after review", "after review, again"]
+    );
+
+    // Only what changed after the most recent review.
+    bus(6)
+        .execute(
+            fork_author.clone(),
+            SubmitForkCommandBuilder::default()
+                .fragment_id(*fork.id())
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    review(7, parent_author.clone()).await;
+    edit(8, "after second review").await;
+    let revisions = Revision::find_since_last_review(&pool, fork.id())
+        .await
+        .unwrap();
+    assert_eq!(contents(revisions), vec!["after second review"]);
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_withdraw_fork(pool: PgPool) {
+    let parent = create_published(&pool, &create_user(&pool).await, "parent", false).await;
+    let fork_author = create_user(&pool).await;
+    let fork = create_fork(&pool, &fork_author, &parent).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let command = WithdrawForkCommandBuilder::default()
+        .fragment_id(*fork.id())
+        .build()
+        .unwrap();
+
+    let mut ctx = create_context(&pool, &fork_author, &clock, &ids).await;
+    assert!(matches!(
+        command.handle(&mut ctx).await,
+        Err(CommandBusError::WithdrawForkCommand(
+            WithdrawForkCommandError::InvalidState(_)
+        ))
+    ));
+
+    fork.set_state(FragmentState::Submitted)
+        .update(&pool)
+        .await
+        .unwrap();
+    let mut ctx = create_context(&pool, &fork_author, &clock, &ids).await;
+    command.handle(&mut ctx).await.unwrap();
+
+    let fork = Fragment::find(ctx.tx().as_mut(), &command.fragment_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(fork.is_draft());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_resubmit_rejected_fork(pool: PgPool) {
+    let parent_author = create_user(&pool).await;
+    let parent = create_published(&pool, &parent_author, "parent", false).await;
+    let fork_author = create_user(&pool).await;
+    let fork = create_fork(&pool, &fork_author, &parent)
+        .await
+        .set_state(FragmentState::Rejected)
+        .update(&pool)
+        .await
+        .unwrap();
+    let rejection = ReviewBuilder::default()
+        .id(Id::new())
+        .fragment_id(*fork.id())
+        .reviewer_id(*parent_author.id())
+        .action(ReviewAction::Reject)
+        .comment(None)
+        .created_at(DateTime::now())
+        .build()
+        .unwrap()
+        .save(&pool)
+        .await
+        .unwrap();
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let command = |max_attempts: u32| {
+        ResubmitForkCommandBuilder::default()
+            .fragment_id(*fork.id())
+            .appeal(Some(Comment::from(String::from("please reconsider"))))
+            .max_attempts(max_attempts)
+            .build()
+            .unwrap()
+    };
+
+    let mut ctx = create_context(&pool, &fork_author, &clock, &ids).await;
+    assert!(matches!(
+        command(0).handle(&mut ctx).await,
+        Err(CommandBusError::ResubmitForkCommand(
+            ResubmitForkCommandError::AttemptsExhausted(0)
+        ))
+    ));
+
+    let mut ctx = create_context(&pool, &fork_author, &clock, &ids).await;
+    let event = command(1).handle(&mut ctx).await.unwrap().unwrap();
+    assert_eq!(event.attempt, 1);
+
+    let fork = Fragment::find(ctx.tx().as_mut(), fork.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(fork.is_submitted());
+
+    let thread = ThreadComment::find_by_review(ctx.tx().as_mut(), rejection.id())
+        .await
+        .unwrap();
+    assert_eq!(thread.len(), 1);
+    assert_eq!(
+        thread[0].body(),
+        &Comment::from(String::from("please reconsider"))
+    );
+}
@@ -0,0 +1,231 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    fixtures::{
+        fragment::{create_draft, create_published},
+        user::{create_user, create_user_with_role},
+    },
+    mock::clock::fixed_clock,
+};
+use ::commons::{actor::Role, id::StdIdGenerator, time::DateTime};
+use chrono::Duration;
+use cqrs::command_bus::{
+    bus::CommandBus,
+    command::{
+        cancel_publication::{CancelPublicationCommandBuilder, CancelPublicationCommandError},
+        publish_fragment::{
+            PublishFragmentCommand, PublishFragmentCommandBuilder, PublishFragmentCommandError,
+        },
+    },
+    error::CommandBusError,
+};
+use sqlx::PgPool;
+use std::sync::Arc;
+use storage::{
+    model::{fragment::Fragment, task::Task},
+    query::{fragment::QueryFragment, task::QueryTask},
+};
+
+fn bus_at(pool: &PgPool, now: DateTime) -> CommandBus {
+    CommandBus::new(
+        pool.clone(),
+        Arc::new(fixed_clock(now)),
+        Arc::new(StdIdGenerator),
+    )
+}
+
+fn publish(fragment: &Fragment) -> PublishFragmentCommand {
+    PublishFragmentCommandBuilder::default()
+        .fragment_id(*fragment.id())
+        .build()
+        .unwrap()
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_scheduled_publication(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let draft = create_draft(&pool, &author, "draft", false).await;
+    let now = DateTime::now();
+    let publish_at = now + Duration::hours(1);
+
+    let task_id = bus_at(&pool, now)
+        .dispatch(author.clone(), publish(&draft), Some(publish_at))
+        .await
+        .unwrap();
+
+    assert_eq!(bus_at(&pool, now).run_due_tasks().await.unwrap(), 0);
+    let fragment = Fragment::find(&pool, draft.id()).await.unwrap().unwrap();
+    assert!(fragment.is_draft());
+
+    assert_eq!(bus_at(&pool, publish_at).run_due_tasks().await.unwrap(), 1);
+    let fragment = Fragment::find(&pool, draft.id()).await.unwrap().unwrap();
+    assert!(fragment.is_published());
+
+    let task = Task::find(&pool, &task_id).await.unwrap().unwrap();
+    assert!(!task.is_pending());
+    assert_eq!(*task.failure(), None);
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_concurrent_runs_run_tasks_once(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let draft = create_draft(&pool, &author, "draft", false).await;
+    let now = DateTime::now();
+    let publish_at = now + Duration::hours(1);
+    let task_id = bus_at(&pool, now)
+        .dispatch(author.clone(), publish(&draft), Some(publish_at))
+        .await
+        .unwrap();
+
+    let (first, second) = (bus_at(&pool, publish_at), bus_at(&pool, publish_at));
+    let (first, second) = tokio::join!(first.run_due_tasks(), second.run_due_tasks());
+    assert_eq!(first.unwrap() + second.unwrap(), 1);
+
+    // Running it twice would have recorded the second publication as failed.
+    let task = Task::find(&pool, &task_id).await.unwrap().unwrap();
+    assert_eq!(*task.failure(), None);
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_abandoned_tasks_run_again(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let draft = create_draft(&pool, &author, "draft", false).await;
+    let now = DateTime::now();
+    let publish_at = now + Duration::hours(1);
+    let task_id = bus_at(&pool, now)
+        .dispatch(author.clone(), publish(&draft), Some(publish_at))
+        .await
+        .unwrap();
+
+    // Claimed by a runner that stopped before running it.
+    let claimed = Task::claim_due(&pool, &publish_at).await.unwrap().unwrap();
+    assert_eq!(*claimed.id(), task_id);
+    assert!(claimed.is_pending());
+
+    assert_eq!(bus_at(&pool, publish_at).run_due_tasks().await.unwrap(), 0);
+    let later = publish_at + Duration::minutes(11);
+    assert_eq!(bus_at(&pool, later).run_due_tasks().await.unwrap(), 1);
+
+    let fragment = Fragment::find(&pool, draft.id()).await.unwrap().unwrap();
+    assert!(fragment.is_published());
+    let task = Task::find(&pool, &task_id).await.unwrap().unwrap();
+    assert!(!task.is_pending());
+    assert_eq!(*task.failure(), None);
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_scheduled_publication_keeps_role(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let moderator = create_user_with_role(&pool, Role::Moderator).await;
+    let draft = create_draft(&pool, &author, "draft", false).await;
+    let now = DateTime::now();
+    let publish_at = now + Duration::hours(1);
+
+    let task_id = bus_at(&pool, now)
+        .dispatch(moderator.clone(), publish(&draft), Some(publish_at))
+        .await
+        .unwrap();
+    assert_eq!(bus_at(&pool, publish_at).run_due_tasks().await.unwrap(), 1);
+
+    let task = Task::find(&pool, &task_id).await.unwrap().unwrap();
+    assert_eq!(*task.failure(), None);
+    let fragment = Fragment::find(&pool, draft.id()).await.unwrap().unwrap();
+    assert!(fragment.is_published());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_schedule_validation(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let published = create_published(&pool, &author, "published", false).await;
+    let draft = create_draft(&pool, &author, "draft", false).await;
+    let bus = bus_at(&pool, DateTime::now());
+    let publish_at = DateTime::now() + Duration::hours(1);
+
+    assert!(matches!(
+        bus.dispatch(author.clone(), publish(&published), Some(publish_at))
+            .await,
+        Err(CommandBusError::PublishFragmentCommand(
+            PublishFragmentCommandError::InvalidState(_)
+        ))
+    ));
+    assert!(matches!(
+        bus.dispatch(create_user(&pool).await, publish(&draft), Some(publish_at))
+            .await,
+        Err(CommandBusError::PublishFragmentCommand(
+            PublishFragmentCommandError::Forbidden(_)
+        ))
+    ));
+
+    bus.dispatch(author.clone(), publish(&draft), Some(publish_at))
+        .await
+        .unwrap();
+    assert!(matches!(
+        bus.dispatch(author.clone(), publish(&draft), Some(publish_at))
+            .await,
+        Err(CommandBusError::PublishFragmentCommand(
+            PublishFragmentCommandError::AlreadyScheduled(_)
+        ))
+    ));
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_scheduled_publication_revalidated(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let draft = create_draft(&pool, &author, "draft", false).await;
+    let now = DateTime::now();
+    let publish_at = now + Duration::hours(1);
+    let bus = bus_at(&pool, now);
+
+    let task_id = bus
+        .dispatch(author.clone(), publish(&draft), Some(publish_at))
+        .await
+        .unwrap();
+    bus.execute(author.clone(), publish(&draft)).await.unwrap();
+
+    assert_eq!(bus_at(&pool, publish_at).run_due_tasks().await.unwrap(), 1);
+    let task = Task::find(&pool, &task_id).await.unwrap().unwrap();
+    assert!(!task.is_pending());
+    assert!(task.failure().is_some());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_cancel_scheduled_publication(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let draft = create_draft(&pool, &author, "draft", false).await;
+    let now = DateTime::now();
+    let publish_at = now + Duration::hours(1);
+    let bus = bus_at(&pool, now);
+    let cancel = || {
+        CancelPublicationCommandBuilder::default()
+            .fragment_id(*draft.id())
+            .build()
+            .unwrap()
+    };
+
+    assert!(matches!(
+        bus.execute(author.clone(), cancel()).await,
+        Err(CommandBusError::CancelPublicationCommand(
+            CancelPublicationCommandError::NotScheduled(_)
+        ))
+    ));
+
+    let task_id = bus
+        .dispatch(author.clone(), publish(&draft), Some(publish_at))
+        .await
+        .unwrap();
+    assert!(matches!(
+        bus.execute(create_user(&pool).await, cancel()).await,
+        Err(CommandBusError::CancelPublicationCommand(
+            CancelPublicationCommandError::Forbidden(_)
+        ))
+    ));
+    bus.execute(author.clone(), cancel()).await.unwrap();
+
+    assert_eq!(bus_at(&pool, publish_at).run_due_tasks().await.unwrap(), 0);
+    let task = Task::find(&pool, &task_id).await.unwrap().unwrap();
+    assert!(task.cancelled_at().is_some());
+    let fragment = Fragment::find(&pool, draft.id()).await.unwrap().unwrap();
+    assert!(fragment.is_draft());
+}
@@ -0,0 +1,119 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    commons::create_context,
+    fixtures::{fragment::create_published, user::create_user},
+    mock::{clock::fixed_clock, ids::fixed_id},
+};
+use ::commons::{actor::ActorTrait, id::Id, time::DateTime};
+use cqrs::{
+    command_bus::{
+        command::{
+            set_fork_policy::{SetForkPolicyCommandBuilder, SetForkPolicyCommandError},
+            Command,
+        },
+        error::CommandBusError,
+    },
+    events::ForkPolicyChangedEventBuilder,
+};
+use sqlx::PgPool;
+use storage::{
+    model::{
+        fork_invite::ForkInvite,
+        fragment::{ForkPolicy, Fragment},
+    },
+    query::{fork_invite::QueryForkInvite, fragment::QueryFragment},
+};
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_author_sets_invite_only_policy(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let invitee = create_user(&pool).await;
+    let story = create_published(&pool, &author, "root", false).await;
+    let now = DateTime::now();
+    let clock = fixed_clock(now);
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+
+    let command = SetForkPolicyCommandBuilder::default()
+        .fragment_id(*story.id())
+        .policy(ForkPolicy::InviteOnly)
+        .invitees(vec![*invitee.id(), *invitee.id()])
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        command.handle(&mut ctx).await.unwrap(),
+        Some(
+            ForkPolicyChangedEventBuilder::default()
+                .fragment_id(*story.id())
+                .policy(ForkPolicy::InviteOnly)
+                .invitees(vec![*invitee.id()])
+                .timestamp(now)
+                .actor(author.actor())
+                .build()
+                .unwrap()
+        )
+    );
+
+    let story = Fragment::find(ctx.tx().as_mut(), story.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(*story.fork_policy(), ForkPolicy::InviteOnly);
+
+    let invites = ForkInvite::find_by_fragment(ctx.tx().as_mut(), story.id())
+        .await
+        .unwrap();
+    assert_eq!(invites.len(), 1);
+    assert_eq!(invites[0].user_id(), invitee.id());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_only_author_sets_policy(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let other = create_user(&pool).await;
+    let story = create_published(&pool, &author, "root", false).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &other, &clock, &ids).await;
+
+    let command = SetForkPolicyCommandBuilder::default()
+        .fragment_id(*story.id())
+        .policy(ForkPolicy::Anyone)
+        .build()
+        .unwrap();
+
+    match command.handle(&mut ctx).await {
+        Err(CommandBusError::SetForkPolicyCommand(SetForkPolicyCommandError::Forbidden(_))) => {}
+        Err(_) => panic!("Not the expected error"),
+        Ok(_) => panic!("Expected Err(CommandBusError) but got Ok(_)"),
+    }
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_invitees_require_invite_only_policy(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let invitee = create_user(&pool).await;
+    let story = create_published(&pool, &author, "root", false).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+
+    let command = SetForkPolicyCommandBuilder::default()
+        .fragment_id(*story.id())
+        .policy(ForkPolicy::Followers)
+        .invitees(vec![*invitee.id()])
+        .build()
+        .unwrap();
+
+    match command.handle(&mut ctx).await {
+        Err(CommandBusError::SetForkPolicyCommand(e)) => {
+            assert_eq!(e, SetForkPolicyCommandError::InviteesNotAllowed)
+        }
+        Err(_) => panic!("Not the expected error"),
+        Ok(_) => panic!("Expected Err(CommandBusError) but got Ok(_)"),
+    }
+}
@@ -0,0 +1,281 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    commons::create_context,
+    fixtures::{
+        fragment::{create_draft, create_fork, create_published},
+        user::create_user,
+    },
+    mock::{clock::fixed_clock, ids::fixed_id},
+};
+use ::commons::{id::Id, time::DateTime};
+use cqrs::command_bus::{
+    command::{
+        create_story::{CreateStoryCommand, CreateStoryCommandBuilder, CreateStoryCommandError},
+        update_story::{UpdateStoryCommandBuilder, UpdateStoryCommandError},
+        Command,
+    },
+    error::CommandBusError,
+};
+use sqlx::PgPool;
+use storage::{
+    model::{
+        fragment::Fragment,
+        story::{MaturityRating, Story, StoryBuilder, StoryFilterBuilder},
+    },
+    query::story::QueryStory,
+};
+
+fn create_command(fragment: &Fragment, genres: &[&str], language: &str) -> CreateStoryCommand {
+    CreateStoryCommandBuilder::default()
+        .story_id(Id::new())
+        .fragment_id(*fragment.id())
+        .title(" The tale ")
+        .genres(genres.iter().map(|g| g.to_string()).collect::<Vec<_>>())
+        .language(language)
+        .build()
+        .unwrap()
+}
+
+async fn save_story(pool: &PgPool, fragment: &Fragment, genres: &[&str], language: &str) -> Story {
+    StoryBuilder::default()
+        .id(Id::new())
+        .fragment_id(*fragment.id())
+        .author_id(*fragment.author_id())
+        .title("A tale")
+        .genres(genres.iter().map(|g| g.to_string()).collect::<Vec<_>>())
+        .language(language)
+        .created_at(DateTime::now())
+        .last_modified_at(DateTime::now())
+        .build()
+        .unwrap()
+        .save(pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_create_story(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let root = create_published(&pool, &author, "once upon a time", false).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+
+    let event = create_command(&root, &["Fantasy", "fantasy ", "Horror"], "EN")
+        .handle(&mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.title, "The tale");
+    assert_eq!(event.genres, vec!["fantasy", "horror"]);
+    assert_eq!(event.language, "en");
+    assert_eq!(event.maturity, MaturityRating::General);
+
+    let story = Story::find(ctx.tx().as_mut(), &event.story_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(*story.fragment_id(), *root.id());
+    assert_eq!(*story.author_id(), *author.id());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_create_story_errors(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false).await;
+    let other_root = create_published(&pool, &author, "other root", false).await;
+    let fork_author = create_user(&pool).await;
+    let fork = create_fork(&pool, &fork_author, &root).await;
+    save_story(&pool, &root, &[], "en").await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+
+    let cases = [
+        (&author, create_command(&root, &[], "en"), "already exists"),
+        (&fork_author, create_command(&fork, &[], "en"), "not a root"),
+        (
+            &fork_author,
+            create_command(&other_root, &[], "en"),
+            "forbidden",
+        ),
+        (
+            &author,
+            create_command(&other_root, &[], "english"),
+            "language",
+        ),
+        (&author, create_command(&other_root, &[" "], "en"), "genre"),
+        (
+            &author,
+            CreateStoryCommandBuilder::default()
+                .story_id(Id::new())
+                .fragment_id(*other_root.id())
+                .title("The tale")
+                .language("en")
+                .cover_url(Some(String::from("javascript:alert(1)")))
+                .build()
+                .unwrap(),
+            "cover",
+        ),
+    ];
+    for (actor, command, case) in cases {
+        let mut ctx = create_context(&pool, actor, &clock, &ids).await;
+        let result = command.handle(&mut ctx).await;
+        let expected = match case {
+            "already exists" => matches!(
+                result,
+                Err(CommandBusError::CreateStoryCommand(
+                    CreateStoryCommandError::AlreadyExists(_)
+                ))
+            ),
+            "forbidden" => matches!(
+                result,
+                Err(CommandBusError::CreateStoryCommand(
+                    CreateStoryCommandError::Forbidden(_)
+                ))
+            ),
+            _ => matches!(
+                result,
+                Err(CommandBusError::CreateStoryCommand(
+                    CreateStoryCommandError::InvalidStory(_)
+                ))
+            ),
+        };
+        assert!(expected, "{case}: {result:?}");
+    }
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_update_story(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false).await;
+    let story = save_story(&pool, &root, &["drama"], "en").await;
+    let story = story
+        .set_synopsis(Some(String::from("A synopsis")))
+        .update(&pool)
+        .await
+        .unwrap();
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+
+    let stranger = create_user(&pool).await;
+    let mut ctx = create_context(&pool, &stranger, &clock, &ids).await;
+    let result = UpdateStoryCommandBuilder::default()
+        .story_id(*story.id())
+        .title(Some(String::from("Stolen")))
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await;
+    assert!(matches!(
+        result,
+        Err(CommandBusError::UpdateStoryCommand(
+            UpdateStoryCommandError::Forbidden(_)
+        ))
+    ));
+
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+    let event = UpdateStoryCommandBuilder::default()
+        .story_id(*story.id())
+        .synopsis(Some(String::from(" ")))
+        .maturity(Some(MaturityRating::Mature))
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.title, "A tale");
+    assert_eq!(event.synopsis, None);
+    assert_eq!(event.genres, vec!["drama"]);
+    assert_eq!(event.maturity, MaturityRating::Mature);
+    drop(ctx);
+
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+    let result = UpdateStoryCommandBuilder::default()
+        .story_id(*story.id())
+        .cover_url(Some(String::from("not a url")))
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await;
+    assert!(matches!(
+        result,
+        Err(CommandBusError::UpdateStoryCommand(
+            UpdateStoryCommandError::InvalidStory(_)
+        ))
+    ));
+    drop(ctx);
+
+    let story = story
+        .set_cover_url(Some(String::from("https://example.com/cover.png")))
+        .update(&pool)
+        .await
+        .unwrap();
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+    let event = UpdateStoryCommandBuilder::default()
+        .story_id(*story.id())
+        .cover_url(Some(String::from("")))
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.cover_url, None);
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_find_published_stories(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let fantasy = save_story(
+        &pool,
+        &create_published(&pool, &author, "a", false).await,
+        &["fantasy"],
+        "en",
+    )
+    .await;
+    let spanish = save_story(
+        &pool,
+        &create_published(&pool, &author, "b", false).await,
+        &["fantasy", "horror"],
+        "es",
+    )
+    .await;
+    save_story(
+        &pool,
+        &create_draft(&pool, &author, "c", false).await,
+        &["fantasy"],
+        "en",
+    )
+    .await;
+
+    let find = |genre: Option<&str>, language: Option<&str>| {
+        let filter = StoryFilterBuilder::default()
+            .genre(genre.map(String::from))
+            .language(language.map(String::from))
+            .build()
+            .unwrap();
+        let pool = pool.clone();
+        async move {
+            let mut ids: Vec<Id> = Story::find_published(&pool, &filter)
+                .await
+                .unwrap()
+                .iter()
+                .map(|s| *s.id())
+                .collect();
+            ids.sort_by_key(Id::to_string);
+            ids
+        }
+    };
+
+    let mut both = vec![*fantasy.id(), *spanish.id()];
+    both.sort_by_key(Id::to_string);
+    assert_eq!(find(None, None).await, both);
+    assert_eq!(find(Some("fantasy"), None).await, both);
+    assert_eq!(find(Some("horror"), None).await, vec![*spanish.id()]);
+    assert_eq!(find(None, Some("en")).await, vec![*fantasy.id()]);
+    assert!(find(Some("horror"), Some("en")).await.is_empty());
+}
@@ -0,0 +1,355 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    commons::create_context,
+    fixtures::{
+        fragment::{create_fork, create_published},
+        user::create_user,
+    },
+    mock::clock::fixed_clock,
+};
+use ::commons::{
+    fragment::Content,
+    id::{Id, StdIdGenerator},
+    time::DateTime,
+};
+use cqrs::command_bus::{
+    bus::CommandBus,
+    command::{
+        accept_suggestion::{AcceptSuggestionCommandBuilder, AcceptSuggestionCommandError},
+        reject_suggestion::{RejectSuggestionCommandBuilder, RejectSuggestionCommandError},
+        review_fork::{ReviewForkCommandBuilder, ReviewForkCommandError, SuggestedEdit},
+        Command,
+    },
+    error::CommandBusError,
+};
+use sqlx::PgPool;
+use std::sync::Arc;
+use storage::{
+    model::{
+        fragment::{Fragment, FragmentState},
+        review::{ReviewAction, ReviewBuilder},
+        revision::Revision,
+        suggestion::{Suggestion, SuggestionBuilder, SuggestionStatus},
+        user::User,
+    },
+    query::{
+        fragment::QueryFragment, review::QueryReview, revision::QueryRevision,
+        suggestion::QuerySuggestion,
+    },
+};
+
+const CONTENT: &str = "the quick brown fox";
+
+async fn create_reviewed_fork(pool: &PgPool) -> (User, User, Fragment) {
+    let parent_author = create_user(pool).await;
+    let fork_author = create_user(pool).await;
+    let parent = create_published(pool, &parent_author, "parent", false).await;
+    let fork = create_fork(pool, &fork_author, &parent)
+        .await
+        .set_content(Content::from(CONTENT))
+        .set_state(FragmentState::WaitingChanges)
+        .update(pool)
+        .await
+        .unwrap();
+    (parent_author, fork_author, fork)
+}
+
+async fn create_suggestion(
+    pool: &PgPool,
+    reviewer: &User,
+    fork: &Fragment,
+    range: (i32, i32),
+    replacement: &str,
+) -> Suggestion {
+    let review = ReviewBuilder::default()
+        .id(Id::new())
+        .fragment_id(*fork.id())
+        .reviewer_id(*reviewer.id())
+        .action(ReviewAction::RequestChanges)
+        .comment(None)
+        .created_at(DateTime::now())
+        .build()
+        .unwrap()
+        .save(pool)
+        .await
+        .unwrap();
+    let original: String = CONTENT
+        .chars()
+        .skip(range.0 as usize)
+        .take((range.1 - range.0) as usize)
+        .collect();
+
+    SuggestionBuilder::default()
+        .id(Id::new())
+        .review_id(*review.id())
+        .fragment_id(*fork.id())
+        .range_start(range.0)
+        .range_end(range.1)
+        .original(Content::from(original))
+        .replacement(Content::from(replacement))
+        .created_at(DateTime::now())
+        .build()
+        .unwrap()
+        .save(pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_review_with_suggestions(pool: PgPool) {
+    let (parent_author, _, fork) = create_reviewed_fork(&pool).await;
+    let fork = fork
+        .set_state(FragmentState::Submitted)
+        .update(&pool)
+        .await
+        .unwrap();
+    let clock = fixed_clock(DateTime::now());
+    let ids = StdIdGenerator;
+    let mut ctx = create_context(&pool, &parent_author, &clock, &ids).await;
+
+    let review_id = Id::new();
+    ReviewForkCommandBuilder::default()
+        .review_id(review_id)
+        .fragment_id(*fork.id())
+        .action(ReviewAction::RequestChanges)
+        .comment(None)
+        .suggestions(vec![
+            SuggestedEdit {
+                start: 4,
+                end: 9,
+                replacement: Content::from("slow"),
+            },
+            SuggestedEdit {
+                start: 16,
+                end: 19,
+                replacement: Content::from("dog"),
+            },
+        ])
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await
+        .unwrap();
+
+    let suggestions = Suggestion::find_by_fragment(ctx.tx().as_mut(), fork.id())
+        .await
+        .unwrap();
+    assert_eq!(suggestions.len(), 2);
+    assert!(suggestions.iter().all(|s| *s.review_id() == review_id));
+    assert!(suggestions.iter().all(Suggestion::is_pending));
+    assert_eq!(suggestions[0].original(), &Content::from("quick"));
+    assert_eq!(suggestions[1].original(), &Content::from("fox"));
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_invalid_suggestions(pool: PgPool) {
+    let (parent_author, _, fork) = create_reviewed_fork(&pool).await;
+    let fork = fork
+        .set_state(FragmentState::Submitted)
+        .update(&pool)
+        .await
+        .unwrap();
+    let clock = fixed_clock(DateTime::now());
+    let ids = StdIdGenerator;
+
+    for (action, end) in [
+        (ReviewAction::Approve, 9),
+        (ReviewAction::RequestChanges, 42),
+    ] {
+        let mut ctx = create_context(&pool, &parent_author, &clock, &ids).await;
+        let result = ReviewForkCommandBuilder::default()
+            .review_id(Id::new())
+            .fragment_id(*fork.id())
+            .action(action)
+            .comment(None)
+            .suggestions(vec![SuggestedEdit {
+                start: 4,
+                end,
+                replacement: Content::from("slow"),
+            }])
+            .build()
+            .unwrap()
+            .handle(&mut ctx)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(CommandBusError::ReviewForkCommand(
+                ReviewForkCommandError::InvalidSuggestion(_)
+            ))
+        ));
+    }
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_accept_suggestion(pool: PgPool) {
+    let (parent_author, fork_author, fork) = create_reviewed_fork(&pool).await;
+    let accepted = create_suggestion(&pool, &parent_author, &fork, (4, 9), "slow").await;
+    let pending = create_suggestion(&pool, &parent_author, &fork, (16, 19), "dog").await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = StdIdGenerator;
+    let mut ctx = create_context(&pool, &fork_author, &clock, &ids).await;
+
+    let result = AcceptSuggestionCommandBuilder::default()
+        .fragment_id(Id::new())
+        .suggestion_id(*accepted.id())
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await;
+    assert!(matches!(
+        result,
+        Err(CommandBusError::AcceptSuggestionCommand(
+            AcceptSuggestionCommandError::SuggestionNotFound(_)
+        ))
+    ));
+
+    let event = AcceptSuggestionCommandBuilder::default()
+        .fragment_id(*fork.id())
+        .suggestion_id(*accepted.id())
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.content, Content::from("the slow brown fox"));
+
+    let fragment = Fragment::find(ctx.tx().as_mut(), fork.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(fragment.content(), &Content::from("the slow brown fox"));
+
+    let revisions = Revision::find_by_fragment(ctx.tx().as_mut(), fork.id())
+        .await
+        .unwrap();
+    assert_eq!(
+        revisions.last().unwrap().content(),
+        &Content::from("the slow brown fox")
+    );
+
+    let accepted = Suggestion::find(ctx.tx().as_mut(), accepted.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(accepted.status(), &SuggestionStatus::Accepted);
+
+    let pending = Suggestion::find(ctx.tx().as_mut(), pending.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!((*pending.range_start(), *pending.range_end()), (15, 18));
+    assert_eq!(
+        pending.apply(fragment.content()),
+        Some(Content::from("the slow brown dog"))
+    );
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_concurrent_accepts(pool: PgPool) {
+    let (parent_author, fork_author, fork) = create_reviewed_fork(&pool).await;
+    let first = create_suggestion(&pool, &parent_author, &fork, (4, 9), "slow").await;
+    let second = create_suggestion(&pool, &parent_author, &fork, (16, 19), "dog").await;
+    let bus = CommandBus::new(
+        pool.clone(),
+        Arc::new(fixed_clock(DateTime::now())),
+        Arc::new(StdIdGenerator),
+    );
+    let accept = |suggestion: &Suggestion| {
+        AcceptSuggestionCommandBuilder::default()
+            .fragment_id(*fork.id())
+            .suggestion_id(*suggestion.id())
+            .build()
+            .unwrap()
+    };
+
+    let (first, second) = tokio::join!(
+        bus.execute(fork_author.clone(), accept(&first)),
+        bus.execute(fork_author.clone(), accept(&second)),
+    );
+    first.unwrap();
+    second.unwrap();
+
+    let fragment = Fragment::find(&pool, fork.id()).await.unwrap().unwrap();
+    assert_eq!(fragment.content(), &Content::from("the slow brown dog"));
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_reject_suggestion(pool: PgPool) {
+    let (parent_author, fork_author, fork) = create_reviewed_fork(&pool).await;
+    let suggestion = create_suggestion(&pool, &parent_author, &fork, (4, 9), "slow").await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = StdIdGenerator;
+
+    let mut ctx = create_context(&pool, &parent_author, &clock, &ids).await;
+    let command = RejectSuggestionCommandBuilder::default()
+        .fragment_id(*fork.id())
+        .suggestion_id(*suggestion.id())
+        .build()
+        .unwrap();
+    assert!(matches!(
+        command.handle(&mut ctx).await,
+        Err(CommandBusError::RejectSuggestionCommand(
+            RejectSuggestionCommandError::Forbidden(_)
+        ))
+    ));
+
+    let mut ctx = create_context(&pool, &fork_author, &clock, &ids).await;
+    let other_fork = RejectSuggestionCommandBuilder::default()
+        .fragment_id(Id::new())
+        .suggestion_id(*suggestion.id())
+        .build()
+        .unwrap();
+    assert!(matches!(
+        other_fork.handle(&mut ctx).await,
+        Err(CommandBusError::RejectSuggestionCommand(
+            RejectSuggestionCommandError::SuggestionNotFound(_)
+        ))
+    ));
+    assert!(command.handle(&mut ctx).await.unwrap().is_some());
+
+    let suggestion = Suggestion::find(ctx.tx().as_mut(), suggestion.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(suggestion.status(), &SuggestionStatus::Rejected);
+
+    let fragment = Fragment::find(ctx.tx().as_mut(), fork.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(fragment.content(), &Content::from(CONTENT));
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_accept_outdated_suggestion(pool: PgPool) {
+    let (parent_author, fork_author, fork) = create_reviewed_fork(&pool).await;
+    let suggestion = create_suggestion(&pool, &parent_author, &fork, (4, 9), "slow").await;
+    let fork = fork
+        .set_content(Content::from("a quick brown fox"))
+        .update(&pool)
+        .await
+        .unwrap();
+    let clock = fixed_clock(DateTime::now());
+    let ids = StdIdGenerator;
+    let mut ctx = create_context(&pool, &fork_author, &clock, &ids).await;
+
+    let result = AcceptSuggestionCommandBuilder::default()
+        .fragment_id(*fork.id())
+        .suggestion_id(*suggestion.id())
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await;
+
+    match result {
+        Err(CommandBusError::AcceptSuggestionCommand(e)) => {
+            assert_eq!(e, AcceptSuggestionCommandError::Outdated(*suggestion.id()))
+        }
+        _ => panic!("expected outdated suggestion error"),
+    }
+}
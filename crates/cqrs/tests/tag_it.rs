@@ -0,0 +1,241 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    commons::create_context,
+    fixtures::{
+        fragment::{create_draft, create_published, soft_delete},
+        user::create_user,
+    },
+    mock::{clock::fixed_clock, ids::fixed_id},
+};
+use ::commons::{id::Id, tag::Tag, time::DateTime};
+use cqrs::command_bus::{
+    command::{
+        add_tag::{AddTagCommand, AddTagCommandBuilder, AddTagCommandError},
+        remove_tag::RemoveTagCommandBuilder,
+        Command,
+    },
+    error::CommandBusError,
+};
+use sqlx::PgPool;
+use storage::{
+    model::{
+        fragment::Fragment,
+        story::{Story, StoryBuilder},
+        tag::{TagTarget, Tagging, TaggingBuilder},
+    },
+    query::{fragment::QueryFragment, story::QueryStory, tag::QueryTagging},
+};
+
+fn add_command(target: TagTarget, target_id: &Id, tag: &str) -> AddTagCommand {
+    AddTagCommandBuilder::default()
+        .target(target)
+        .target_id(*target_id)
+        .tag(tag)
+        .build()
+        .unwrap()
+}
+
+async fn save_tag(pool: &PgPool, target: TagTarget, target_id: &Id, tag: &str) -> Tagging {
+    TaggingBuilder::default()
+        .target(target)
+        .target_id(*target_id)
+        .tag(Tag::parse(tag).unwrap())
+        .created_at(DateTime::now())
+        .build()
+        .unwrap()
+        .save(pool)
+        .await
+        .unwrap()
+}
+
+async fn save_story(pool: &PgPool, fragment_id: &Id) -> Story {
+    let root = Fragment::find(pool, fragment_id).await.unwrap().unwrap();
+    StoryBuilder::default()
+        .id(Id::new())
+        .fragment_id(*root.id())
+        .author_id(*root.author_id())
+        .title("A tale")
+        .language("en")
+        .created_at(DateTime::now())
+        .last_modified_at(DateTime::now())
+        .build()
+        .unwrap()
+        .save(pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_add_tag(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let root = create_draft(&pool, &author, "once upon a time", false).await;
+    save_tag(&pool, TagTarget::Fragment, root.id(), "sci-fi").await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+
+    let event = add_command(TagTarget::Fragment, root.id(), " Plot Twist")
+        .handle(&mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.tag.as_ref(), "plot-twist");
+    assert_eq!(event.target, TagTarget::Fragment);
+
+    let again = add_command(TagTarget::Fragment, root.id(), "Sci_Fi")
+        .handle(&mut ctx)
+        .await
+        .unwrap();
+    assert!(again.is_none());
+
+    let tags = Tagging::find_by_target(ctx.tx().as_mut(), TagTarget::Fragment, root.id())
+        .await
+        .unwrap();
+    assert_eq!(tags.len(), 2);
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_add_tag_errors(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false).await;
+    let crowded = create_published(&pool, &author, "crowded", false).await;
+    for i in 0..10 {
+        save_tag(
+            &pool,
+            TagTarget::Fragment,
+            crowded.id(),
+            &format!("tag-{i}"),
+        )
+        .await;
+    }
+    let deleted = create_published(&pool, &author, "deleted", false).await;
+    let deleted = soft_delete(&pool, deleted, DateTime::now()).await;
+    let stranger = create_user(&pool).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+
+    let cases = [
+        (
+            &stranger,
+            add_command(TagTarget::Fragment, root.id(), "sci-fi"),
+            "forbidden",
+        ),
+        (
+            &author,
+            add_command(TagTarget::Fragment, root.id(), "sci fi!"),
+            "invalid",
+        ),
+        (
+            &author,
+            add_command(TagTarget::Fragment, crowded.id(), "sci-fi"),
+            "too many",
+        ),
+        (
+            &author,
+            add_command(TagTarget::Fragment, deleted.id(), "sci-fi"),
+            "not found",
+        ),
+        (
+            &author,
+            add_command(TagTarget::Story, root.id(), "sci-fi"),
+            "not found",
+        ),
+    ];
+    for (actor, command, case) in cases {
+        let mut ctx = create_context(&pool, actor, &clock, &ids).await;
+        let result = command.handle(&mut ctx).await;
+        let expected = match case {
+            "forbidden" => matches!(
+                result,
+                Err(CommandBusError::AddTagCommand(
+                    AddTagCommandError::Forbidden(_)
+                ))
+            ),
+            "invalid" => matches!(
+                result,
+                Err(CommandBusError::AddTagCommand(
+                    AddTagCommandError::InvalidTag(_)
+                ))
+            ),
+            "too many" => matches!(
+                result,
+                Err(CommandBusError::AddTagCommand(
+                    AddTagCommandError::TooManyTags
+                ))
+            ),
+            _ => matches!(
+                result,
+                Err(CommandBusError::AddTagCommand(
+                    AddTagCommandError::TargetNotFound(_)
+                ))
+            ),
+        };
+        assert!(expected, "{case}: {result:?}");
+    }
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_remove_tag(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let root = create_published(&pool, &author, "root", false).await;
+    let story = save_story(&pool, root.id()).await;
+    save_tag(&pool, TagTarget::Story, story.id(), "sci-fi").await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+
+    let remove = |tag: &str| {
+        RemoveTagCommandBuilder::default()
+            .target(TagTarget::Story)
+            .target_id(*story.id())
+            .tag(tag)
+            .build()
+            .unwrap()
+    };
+    let event = remove("Sci Fi").handle(&mut ctx).await.unwrap().unwrap();
+    assert_eq!(event.tag.as_ref(), "sci-fi");
+    assert!(remove("sci-fi").handle(&mut ctx).await.unwrap().is_none());
+
+    let tags = Tagging::find_by_target(ctx.tx().as_mut(), TagTarget::Story, story.id())
+        .await
+        .unwrap();
+    assert!(tags.is_empty());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_tag_browsing(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let mut published = Vec::new();
+    for content in ["a", "b", "c"] {
+        let fragment = create_published(&pool, &author, content, false).await;
+        save_tag(&pool, TagTarget::Fragment, fragment.id(), "sci-fi").await;
+        published.push(*fragment.id());
+    }
+    let draft = create_draft(&pool, &author, "draft", false).await;
+    save_tag(&pool, TagTarget::Fragment, draft.id(), "sci-fi").await;
+    save_tag(&pool, TagTarget::Fragment, draft.id(), "unreleased").await;
+    let deleted = create_published(&pool, &author, "deleted", false).await;
+    save_tag(&pool, TagTarget::Fragment, deleted.id(), "sci-fi").await;
+    soft_delete(&pool, deleted, DateTime::now()).await;
+    let story = save_story(&pool, &published[0]).await;
+    save_tag(&pool, TagTarget::Story, story.id(), "sci-fi").await;
+
+    let tag = Tag::parse("sci-fi").unwrap();
+    let first = Tagging::fragments_tagged(&pool, &tag, 2, 0).await.unwrap();
+    let second = Tagging::fragments_tagged(&pool, &tag, 2, 2).await.unwrap();
+    assert_eq!(first.len(), 2);
+    assert_eq!(second.len(), 1);
+    let mut found: Vec<Id> = first.iter().chain(&second).map(|f| *f.id()).collect();
+    found.sort_by_key(Id::to_string);
+    published.sort_by_key(Id::to_string);
+    assert_eq!(found, published);
+
+    let counts = Tagging::counts(&pool).await.unwrap();
+    assert_eq!(counts.len(), 1);
+    assert_eq!(counts[0].tag(), &tag);
+    assert_eq!(*counts[0].fragments(), 3);
+    assert_eq!(*counts[0].stories(), 1);
+}
@@ -0,0 +1,287 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    commons::create_context,
+    fixtures::{
+        fragment::{create_fork, create_published},
+        user::create_user,
+    },
+    mock::{clock::fixed_clock, ids::fixed_id},
+};
+use ::commons::{
+    actor::Actor,
+    events::EventType,
+    id::{Id, StdIdGenerator},
+    time::DateTime,
+};
+use cqrs::command_bus::{
+    bus::CommandBus,
+    command::{
+        submit_fork::SubmitForkCommandBuilder,
+        trust_contributor::{TrustContributorCommandBuilder, TrustContributorCommandError},
+        untrust_contributor::{UntrustContributorCommandBuilder, UntrustContributorCommandError},
+        Command,
+    },
+    error::CommandBusError,
+};
+use cqrs::events::FragmentForkReviewedEvent;
+use sqlx::PgPool;
+use std::sync::Arc;
+use storage::{
+    model::{
+        event::DbEvent,
+        fragment::{Fragment, FragmentState, Transition},
+        review::{Review, ReviewAction},
+        state_transition::StateTransition,
+        trusted_contributor::{TrustedContributor, TrustedContributorBuilder},
+        user::User,
+    },
+    query::{
+        event::QueryEvent, fragment::QueryFragment, review::QueryReview,
+        state_transition::QueryStateTransition, trusted_contributor::QueryTrustedContributor,
+    },
+};
+
+async fn trust(pool: &PgPool, author: &User, contributor: &User, auto_publish: bool) {
+    TrustedContributorBuilder::default()
+        .author_id(*author.id())
+        .contributor_id(*contributor.id())
+        .auto_publish(auto_publish)
+        .created_at(DateTime::now())
+        .build()
+        .unwrap()
+        .save(pool)
+        .await
+        .unwrap();
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_trusted_fork_is_approved(pool: PgPool) {
+    let parent_author = create_user(&pool).await;
+    let contributor = create_user(&pool).await;
+    let parent = create_published(&pool, &parent_author, "parent", false).await;
+    let fork = create_fork(&pool, &contributor, &parent).await;
+    trust(&pool, &parent_author, &contributor, false).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+
+    let mut ctx = create_context(&pool, &contributor, &clock, &ids).await;
+    let event = SubmitForkCommandBuilder::default()
+        .fragment_id(*fork.id())
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.state, FragmentState::Approved);
+
+    let fork = Fragment::find(ctx.tx().as_mut(), fork.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(fork.is_approved());
+
+    let reviews = Review::find_by_fragment(ctx.tx().as_mut(), fork.id())
+        .await
+        .unwrap();
+    assert_eq!(reviews.len(), 1);
+    assert!(reviews[0].is_automatic());
+    assert_eq!(*reviews[0].action(), ReviewAction::Approve);
+
+    let history = StateTransition::find_by_fragment(ctx.tx().as_mut(), fork.id())
+        .await
+        .unwrap();
+    let transitions: Vec<_> = history.iter().map(|t| *t.transition()).collect();
+    assert_eq!(transitions, vec![Transition::Submit, Transition::Approve]);
+    assert_eq!(*history[0].actor_id(), Some(*contributor.id()));
+    assert_eq!(*history[1].actor_id(), None);
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_trusted_fork_is_published(pool: PgPool) {
+    let parent_author = create_user(&pool).await;
+    let contributor = create_user(&pool).await;
+    let parent = create_published(&pool, &parent_author, "parent", false).await;
+    let fork = create_fork(&pool, &contributor, &parent).await;
+    trust(&pool, &parent_author, &contributor, true).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+
+    let mut ctx = create_context(&pool, &contributor, &clock, &ids).await;
+    let event = SubmitForkCommandBuilder::default()
+        .fragment_id(*fork.id())
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.state, FragmentState::Published);
+
+    let history = StateTransition::find_by_fragment(ctx.tx().as_mut(), fork.id())
+        .await
+        .unwrap();
+    let transitions: Vec<_> = history.iter().map(|t| *t.transition()).collect();
+    assert_eq!(
+        transitions,
+        vec![Transition::Submit, Transition::Approve, Transition::Publish]
+    );
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_trusted_fork_emits_review_events(pool: PgPool) {
+    let parent_author = create_user(&pool).await;
+    let contributor = create_user(&pool).await;
+    let parent = create_published(&pool, &parent_author, "parent", false).await;
+    let fork = create_fork(&pool, &contributor, &parent).await;
+    trust(&pool, &parent_author, &contributor, true).await;
+    let bus = CommandBus::new(
+        pool.clone(),
+        Arc::new(fixed_clock(DateTime::now())),
+        Arc::new(StdIdGenerator),
+    );
+
+    bus.execute(
+        contributor.clone(),
+        SubmitForkCommandBuilder::default()
+            .fragment_id(*fork.id())
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let events = DbEvent::find_after(&pool, 0, 10).await.unwrap();
+    let types: Vec<_> = events.iter().map(|e| *e.event_type()).collect();
+    assert_eq!(
+        types,
+        vec![
+            EventType::ForkSubmitted,
+            EventType::FragmentForkReviewed,
+            EventType::FragmentPublished,
+        ]
+    );
+    let review: FragmentForkReviewedEvent = events[1].event_data().into_event();
+    assert_eq!(review.fragment_id, *fork.id());
+    assert_eq!(review.action, ReviewAction::Approve);
+    assert_eq!(review.state, FragmentState::Approved);
+    assert_eq!(review.actor, Actor::System);
+    assert_eq!(*events[2].actor_id(), None);
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_trust_is_per_author(pool: PgPool) {
+    let parent_author = create_user(&pool).await;
+    let other_author = create_user(&pool).await;
+    let contributor = create_user(&pool).await;
+    let parent = create_published(&pool, &parent_author, "parent", false).await;
+    let fork = create_fork(&pool, &contributor, &parent).await;
+    trust(&pool, &other_author, &contributor, true).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+
+    let mut ctx = create_context(&pool, &contributor, &clock, &ids).await;
+    let event = SubmitForkCommandBuilder::default()
+        .fragment_id(*fork.id())
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.state, FragmentState::Submitted);
+
+    let reviews = Review::find_by_fragment(ctx.tx().as_mut(), fork.id())
+        .await
+        .unwrap();
+    assert!(reviews.is_empty());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_manage_trusted_contributors(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let contributor = create_user(&pool).await;
+    let stranger = create_user(&pool).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+    let event = TrustContributorCommandBuilder::default()
+        .author_id(*author.id())
+        .contributor_id(*contributor.id())
+        .auto_publish(true)
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.contributor_id, *contributor.id());
+    assert!(event.auto_publish);
+    let trusted = TrustedContributor::find_by_author(ctx.tx().as_mut(), author.id())
+        .await
+        .unwrap();
+    assert_eq!(trusted.len(), 1);
+    drop(ctx);
+
+    for (case, actor, author_id, contributor_id) in [
+        ("self", &author, *author.id(), *author.id()),
+        ("forbidden", &stranger, *author.id(), *contributor.id()),
+        ("user", &author, *author.id(), Id::new()),
+    ] {
+        let mut ctx = create_context(&pool, actor, &clock, &ids).await;
+        let result = TrustContributorCommandBuilder::default()
+            .author_id(author_id)
+            .contributor_id(contributor_id)
+            .build()
+            .unwrap()
+            .handle(&mut ctx)
+            .await;
+        let expected = match (case, result.as_ref().unwrap_err()) {
+            ("self", CommandBusError::TrustContributorCommand(e)) => {
+                matches!(e, TrustContributorCommandError::InvalidContributor(_))
+            }
+            ("forbidden", CommandBusError::TrustContributorCommand(e)) => {
+                matches!(e, TrustContributorCommandError::Forbidden(_))
+            }
+            ("user", CommandBusError::TrustContributorCommand(e)) => {
+                matches!(e, TrustContributorCommandError::UserNotFound(_))
+            }
+            _ => false,
+        };
+        assert!(expected, "{case}: {result:?}");
+    }
+
+    trust(&pool, &author, &contributor, false).await;
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+    UntrustContributorCommandBuilder::default()
+        .author_id(*author.id())
+        .contributor_id(*contributor.id())
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await
+        .unwrap();
+    let trusted = TrustedContributor::find(ctx.tx().as_mut(), author.id(), contributor.id())
+        .await
+        .unwrap();
+    assert!(trusted.is_none());
+
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+    let result = UntrustContributorCommandBuilder::default()
+        .author_id(*author.id())
+        .contributor_id(*stranger.id())
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await;
+    assert!(matches!(
+        result,
+        Err(CommandBusError::UntrustContributorCommand(
+            UntrustContributorCommandError::NotTrusted(_)
+        ))
+    ));
+}
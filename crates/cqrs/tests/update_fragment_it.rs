@@ -6,12 +6,13 @@ use crate::{
     commons::create_context,
     fixtures::{
         fragment::{create_draft, create_published},
-        user::create_user,
+        user::{create_user, create_user_with_role},
     },
     mock::{clock::fixed_clock, ids::fixed_id},
 };
 use cqrs::{
     command_bus::{
+        bus::CommandBus,
         command::{
             update_fragment::{UpdateFragmentCommandBuilder, UpdateFragmentCommandError},
             Command,
@@ -22,15 +23,19 @@ use cqrs::{
 };
 
 use ::commons::{
-    actor::ActorTrait,
+    actor::{ActorTrait, Role},
     fragment::Content,
-    id::{Id, MockIdGenerator},
+    id::{Id, MockIdGenerator, StdIdGenerator},
     time::{DateTime, MockClock},
 };
 use sqlx::PgPool;
+use std::sync::Arc;
 use storage::{
-    model::fragment::{Fragment, FragmentBuilder},
-    query::fragment::QueryFragment,
+    model::{
+        fragment::{Fragment, FragmentBuilder},
+        revision::Revision,
+    },
+    query::{fragment::QueryFragment, revision::QueryRevision},
 };
 
 #[sqlx::test(migrator = "storage::MIGRATOR")]
@@ -171,3 +176,99 @@ fn test_fragment_not_found(pool: PgPool) {
         Ok(_) => panic!("Expected Err(CommandBusError) but got Ok(_)"),
     }
 }
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_moderator_updates_other_author_fragment(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let draft = create_draft(&pool, &author, "content", false).await;
+    let moderator = create_user_with_role(&pool, Role::Moderator).await;
+
+    let command = UpdateFragmentCommandBuilder::default()
+        .fragment_id(*draft.id())
+        .content(Some(Content::from("moderated content")))
+        .end(false)
+        .build()
+        .unwrap();
+
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &moderator, &clock, &ids).await;
+
+    let event = command.handle(&mut ctx).await.unwrap().unwrap();
+    assert_eq!(event.actor, moderator.actor());
+
+    let fragment = Fragment::find(ctx.tx().as_mut(), draft.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(fragment.content(), &Content::from("moderated content"));
+    assert!(fragment.is_author(*author.id()));
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_update_records_revision(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let draft = create_draft(&pool, &author, "content", false).await;
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &author, &clock, &ids).await;
+
+    UpdateFragmentCommandBuilder::default()
+        .fragment_id(*draft.id())
+        .content(Some(Content::from("new content")))
+        .end(None)
+        .build()
+        .unwrap()
+        .handle(&mut ctx)
+        .await
+        .unwrap();
+
+    let revisions = Revision::find_by_fragment(ctx.tx().as_mut(), draft.id())
+        .await
+        .unwrap();
+    assert_eq!(revisions.len(), 1);
+    assert_eq!(*revisions[0].number(), 1);
+    assert_eq!(revisions[0].content(), &Content::from("new content"));
+    assert_eq!(revisions[0].author_id(), author.id());
+
+    let fragment = Fragment::find(ctx.tx().as_mut(), draft.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!*fragment.end());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_concurrent_updates_number_revisions(pool: PgPool) {
+    let author = create_user(&pool).await;
+    let draft = create_draft(&pool, &author, "content", false).await;
+    let bus = CommandBus::new(
+        pool.clone(),
+        Arc::new(fixed_clock(DateTime::now())),
+        Arc::new(StdIdGenerator),
+    );
+    let updates: Vec<_> = (0..8)
+        .map(|i| {
+            let (bus, author, id) = (bus.clone(), author.clone(), *draft.id());
+            tokio::spawn(async move {
+                bus.execute(
+                    author,
+                    UpdateFragmentCommandBuilder::default()
+                        .fragment_id(id)
+                        .content(Some(Content::from(format!("content {i}"))))
+                        .end(None)
+                        .build()
+                        .unwrap(),
+                )
+                .await
+            })
+        })
+        .collect();
+    for update in updates {
+        update.await.unwrap().unwrap();
+    }
+
+    let revisions = Revision::find_by_fragment(&pool, draft.id()).await.unwrap();
+    let numbers: Vec<_> = revisions.iter().map(|r| *r.number()).collect();
+    assert_eq!(numbers, (1..=8).collect::<Vec<_>>());
+}
@@ -0,0 +1,112 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    commons::create_context,
+    fixtures::user::create_user,
+    mock::{clock::fixed_clock, ids::fixed_id},
+};
+use ::commons::{
+    actor::ActorTrait,
+    id::{Id, MockIdGenerator},
+    time::{DateTime, MockClock},
+};
+use cqrs::{
+    command_bus::{
+        command::{
+            update_profile::{UpdateProfileCommandBuilder, UpdateProfileCommandError},
+            Command,
+        },
+        error::CommandBusError,
+    },
+    events::ProfileUpdatedEventBuilder,
+};
+use sqlx::PgPool;
+use storage::{model::user::User, query::user::QueryUser};
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_update_profile_success(pool: PgPool) {
+    let user = create_user(&pool).await;
+    let now = DateTime::now();
+    let clock = fixed_clock(now);
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &user, &clock, &ids).await;
+
+    let command = UpdateProfileCommandBuilder::default()
+        .user_id(*user.id())
+        .display_name(Some(String::from("John")))
+        .bio(Some(String::from("Writer")))
+        .build()
+        .unwrap();
+
+    let event = command.handle(&mut ctx).await.unwrap().unwrap();
+    assert_eq!(
+        event,
+        ProfileUpdatedEventBuilder::default()
+            .user_id(*user.id())
+            .display_name(Some(String::from("John")))
+            .bio(Some(String::from("Writer")))
+            .avatar_url(None)
+            .timestamp(now)
+            .actor(user.actor())
+            .build()
+            .unwrap()
+    );
+
+    let updated = User::find(ctx.tx().as_mut(), user.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.display_name().as_deref(), Some("John"));
+    assert_eq!(updated.bio().as_deref(), Some("Writer"));
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_blank_field_clears_profile_value(pool: PgPool) {
+    let user = create_user(&pool)
+        .await
+        .set_display_name(Some(String::from("John")))
+        .set_bio(Some(String::from("Writer")))
+        .update(&pool)
+        .await
+        .unwrap();
+    let clock = fixed_clock(DateTime::now());
+    let ids = fixed_id(Id::new());
+    let mut ctx = create_context(&pool, &user, &clock, &ids).await;
+
+    let command = UpdateProfileCommandBuilder::default()
+        .user_id(*user.id())
+        .bio(Some(String::from(" ")))
+        .build()
+        .unwrap();
+    command.handle(&mut ctx).await.unwrap();
+
+    let updated = User::find(ctx.tx().as_mut(), user.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.display_name().as_deref(), Some("John"));
+    assert_eq!(*updated.bio(), None);
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_update_other_user_profile(pool: PgPool) {
+    let user = create_user(&pool).await;
+    let other_user = create_user(&pool).await;
+    let clock = MockClock::default();
+    let ids = MockIdGenerator::default();
+    let mut ctx = create_context(&pool, &other_user, &clock, &ids).await;
+
+    let command = UpdateProfileCommandBuilder::default()
+        .user_id(*user.id())
+        .display_name(Some(String::from("John")))
+        .build()
+        .unwrap();
+
+    match command.handle(&mut ctx).await {
+        Err(CommandBusError::UpdateProfileCommand(UpdateProfileCommandError::Forbidden(_))) => {}
+        Err(_) => panic!("Not the expected error"),
+        Ok(_) => panic!("Expected Err(CommandBusError) but got Ok(_)"),
+    }
+}
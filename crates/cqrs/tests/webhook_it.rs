@@ -0,0 +1,600 @@
+mod commons;
+mod fixtures;
+mod mock;
+
+use crate::{
+    fixtures::{
+        fragment::create_published,
+        user::{create_user, create_user_with_role},
+    },
+    mock::clock::fixed_clock,
+};
+use ::commons::{
+    actor::Role,
+    auth::WebhookSecret,
+    configuration::settings::WebhookSettings,
+    events::EventType,
+    id::{Id, StdIdGenerator},
+    time::DateTime,
+};
+use chrono::Duration;
+use cqrs::{
+    command_bus::{
+        bus::CommandBus,
+        command::{
+            create_webhook::{CreateWebhookCommandBuilder, CreateWebhookCommandError},
+            delete_webhook::{DeleteWebhookCommandBuilder, DeleteWebhookCommandError},
+            enable_webhook::EnableWebhookCommandBuilder,
+            fork_fragment::ForkFragmentCommandBuilder,
+            like_fragment::LikeFragmentCommandBuilder,
+            submit_fork::SubmitForkCommandBuilder,
+            update_fragment::UpdateFragmentCommandBuilder,
+        },
+        error::CommandBusError,
+    },
+    webhooks::{WebhookDeliveryJob, EVENT_HEADER, SIGNATURE_HEADER},
+};
+use serde_json::Value;
+use sqlx::PgPool;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU16, Ordering},
+        Arc, Mutex,
+    },
+};
+use storage::{
+    model::{
+        fragment::{ForkPolicy, Fragment},
+        story::{Story, StoryBuilder},
+        user::User,
+        webhook::{Webhook, WebhookAttempt, WebhookDelivery, WebhookDeliveryState},
+    },
+    query::{
+        fragment::QueryFragment,
+        story::QueryStory,
+        webhook::{QueryWebhook, QueryWebhookAttempt, QueryWebhookDelivery},
+    },
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+const SECRET: &str = "0123456789abcdef";
+
+/// Request received by the local endpoint, header names lowercased.
+#[derive(Debug, Clone)]
+struct Received {
+    headers: HashMap<String, String>,
+    body: String,
+}
+
+/// Local HTTP endpoint answering every request with the configured status.
+struct Receiver {
+    url: String,
+    status: Arc<AtomicU16>,
+    received: Arc<Mutex<Vec<Received>>>,
+}
+
+impl Receiver {
+    async fn start(status: u16) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let status = Arc::new(AtomicU16::new(status));
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        let (task_status, task_received) = (status.clone(), received.clone());
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let request = read_request(&mut stream).await;
+                task_received.lock().unwrap().push(request);
+                let response = format!(
+                    "HTTP/1.1 {} Status\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                    task_status.load(Ordering::SeqCst)
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        Self {
+            url,
+            status,
+            received,
+        }
+    }
+
+    fn set_status(&self, status: u16) {
+        self.status.store(status, Ordering::SeqCst);
+    }
+
+    fn received(&self) -> Vec<Received> {
+        self.received.lock().unwrap().clone()
+    }
+}
+
+async fn read_request(stream: &mut tokio::net::TcpStream) -> Received {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        let read = stream.read(&mut chunk).await.unwrap();
+        buffer.extend_from_slice(&chunk[..read]);
+        let Some(end) = buffer.windows(4).position(|w| w == b"\r\n\r\n") else {
+            continue;
+        };
+        let head = String::from_utf8_lossy(&buffer[..end]).to_string();
+        let headers: HashMap<String, String> = head
+            .lines()
+            .skip(1)
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+            .collect();
+        let length: usize = headers
+            .get("content-length")
+            .map(|value| value.parse().unwrap())
+            .unwrap_or_default();
+        while buffer.len() < end + 4 + length {
+            let read = stream.read(&mut chunk).await.unwrap();
+            buffer.extend_from_slice(&chunk[..read]);
+        }
+        let body = String::from_utf8_lossy(&buffer[end + 4..end + 4 + length]).to_string();
+        return Received { headers, body };
+    }
+}
+
+fn settings() -> WebhookSettings {
+    WebhookSettings {
+        poll_interval: 1,
+        timeout: 5,
+        max_attempts: 3,
+        retry_delay: 60,
+        failure_threshold: 3,
+        allowed_hosts: allowed_hosts(),
+    }
+}
+
+/// The receivers listen on the loopback interface, which endpoints may only use when
+/// allowed.
+fn allowed_hosts() -> Vec<String> {
+    vec!["127.0.0.1".into(), "example.com".into()]
+}
+
+fn bus(pool: &PgPool, now: DateTime) -> Arc<CommandBus> {
+    Arc::new(CommandBus::new(
+        pool.clone(),
+        Arc::new(fixed_clock(now)),
+        Arc::new(StdIdGenerator),
+    ))
+}
+
+fn job(pool: &PgPool, now: DateTime) -> WebhookDeliveryJob {
+    WebhookDeliveryJob::new(
+        pool.clone(),
+        bus(pool, now),
+        Arc::new(fixed_clock(now)),
+        Arc::new(StdIdGenerator),
+        settings(),
+    )
+}
+
+async fn save_story(pool: &PgPool, fragment: &Fragment) -> Story {
+    StoryBuilder::default()
+        .id(Id::new())
+        .fragment_id(*fragment.id())
+        .author_id(*fragment.author_id())
+        .title("A tale")
+        .language("en")
+        .created_at(DateTime::now())
+        .last_modified_at(DateTime::now())
+        .build()
+        .unwrap()
+        .save(pool)
+        .await
+        .unwrap()
+}
+
+async fn create_webhook(
+    bus: &CommandBus,
+    owner: &User,
+    url: &str,
+    story_id: Option<Id>,
+) -> Result<Id, CommandBusError> {
+    let webhook_id = Id::new();
+    bus.execute(
+        owner.clone(),
+        CreateWebhookCommandBuilder::default()
+            .webhook_id(webhook_id)
+            .url(url)
+            .secret(WebhookSecret::from(SECRET))
+            .event_types(vec![EventType::FragmentLiked])
+            .story_id(story_id)
+            .allowed_hosts(allowed_hosts())
+            .build()
+            .unwrap(),
+    )
+    .await
+    .map(|_| webhook_id)
+}
+
+async fn like(pool: &PgPool, bus: &CommandBus, fragment: &Fragment) {
+    bus.execute(
+        create_user(pool).await,
+        LikeFragmentCommandBuilder::default()
+            .fragment_id(*fragment.id())
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+}
+
+async fn deliveries(pool: &PgPool, webhook_id: &Id) -> Vec<WebhookDelivery> {
+    WebhookDelivery::find_by_webhook(pool, webhook_id, 100, 0)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_delivery_is_signed(pool: PgPool) {
+    let now = DateTime::now();
+    let author = create_user(&pool).await;
+    let fragment = create_published(&pool, &author, "fragment", false).await;
+    let story = save_story(&pool, &fragment).await;
+    let other = create_published(&pool, &author, "other", false).await;
+    let bus = bus(&pool, now);
+    let receiver = Receiver::start(200).await;
+
+    let webhook_id = create_webhook(&bus, &author, &receiver.url, Some(*story.id()))
+        .await
+        .unwrap();
+    like(&pool, &bus, &fragment).await;
+    like(&pool, &bus, &other).await;
+
+    let queued = deliveries(&pool, &webhook_id).await;
+    assert_eq!(queued.len(), 1, "{queued:?}");
+    assert_eq!(job(&pool, now).run().await.unwrap(), 1);
+
+    let received = receiver.received();
+    assert_eq!(received.len(), 1);
+    let request = &received[0];
+    assert_eq!(
+        request.headers[&SIGNATURE_HEADER.to_lowercase()],
+        format!(
+            "sha256={}",
+            WebhookSecret::from(SECRET).sign(request.body.as_bytes())
+        )
+    );
+    assert_eq!(
+        request.headers[&EVENT_HEADER.to_lowercase()],
+        "fragment_liked"
+    );
+    let payload: Value = serde_json::from_str(&request.body).unwrap();
+    assert_eq!(payload["event_type"], "fragment_liked");
+    assert_eq!(payload["story_id"], story.id().to_string());
+    assert_eq!(payload["data"]["fragment_id"], fragment.id().to_string());
+
+    let delivery = &deliveries(&pool, &webhook_id).await[0];
+    assert_eq!(*delivery.state(), WebhookDeliveryState::Delivered);
+    let attempts = WebhookAttempt::find_by_delivery(&pool, delivery.id())
+        .await
+        .unwrap();
+    assert_eq!(attempts.len(), 1);
+    assert_eq!(*attempts[0].status_code(), Some(200));
+
+    assert_eq!(job(&pool, now).run().await.unwrap(), 0);
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_concurrent_runs_attempt_once(pool: PgPool) {
+    let now = DateTime::now();
+    let admin = create_user_with_role(&pool, Role::Admin).await;
+    let fragment = create_published(&pool, &create_user(&pool).await, "fragment", false).await;
+    let bus = bus(&pool, now);
+    let receiver = Receiver::start(500).await;
+
+    create_webhook(&bus, &admin, &receiver.url, None)
+        .await
+        .unwrap();
+    for _ in 0..3 {
+        like(&pool, &bus, &fragment).await;
+    }
+
+    let (first, second) = (job(&pool, now), job(&pool, now));
+    let (first, second) = tokio::join!(first.run(), second.run());
+    assert_eq!(first.unwrap() + second.unwrap(), 3);
+    assert_eq!(receiver.received().len(), 3);
+    // Failed attempts are not retried before their backoff, claimed or not.
+    assert_eq!(job(&pool, now).run().await.unwrap(), 0);
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_failing_endpoint_is_retried_then_disabled(pool: PgPool) {
+    let now = DateTime::now();
+    let admin = create_user_with_role(&pool, Role::Admin).await;
+    let fragment = create_published(&pool, &create_user(&pool).await, "fragment", false).await;
+    let bus = bus(&pool, now);
+    let receiver = Receiver::start(500).await;
+
+    let webhook_id = create_webhook(&bus, &admin, &receiver.url, None)
+        .await
+        .unwrap();
+    like(&pool, &bus, &fragment).await;
+
+    assert_eq!(job(&pool, now).run().await.unwrap(), 1);
+    let delivery = &deliveries(&pool, &webhook_id).await[0];
+    assert_eq!(*delivery.state(), WebhookDeliveryState::Pending);
+    assert_eq!(*delivery.attempts(), 1);
+
+    // Retries back off: 60 seconds, then 120 more.
+    assert_eq!(
+        job(&pool, now + Duration::seconds(59)).run().await.unwrap(),
+        0
+    );
+    let retried = now + Duration::seconds(60);
+    assert_eq!(job(&pool, retried).run().await.unwrap(), 1);
+    assert_eq!(
+        job(&pool, retried + Duration::seconds(119))
+            .run()
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        job(&pool, retried + Duration::seconds(120))
+            .run()
+            .await
+            .unwrap(),
+        1
+    );
+
+    let delivery = &deliveries(&pool, &webhook_id).await[0];
+    assert_eq!(*delivery.state(), WebhookDeliveryState::Failed);
+    assert_eq!(*delivery.attempts(), 3);
+    assert_eq!(receiver.received().len(), 3);
+    let webhook = Webhook::find(&pool, &webhook_id).await.unwrap().unwrap();
+    assert!(!webhook.enabled());
+    assert_eq!(*webhook.consecutive_failures(), 3);
+
+    // Disabled endpoints get nothing until enabled again.
+    like(&pool, &bus, &fragment).await;
+    assert_eq!(deliveries(&pool, &webhook_id).await.len(), 1);
+
+    bus.execute(
+        admin.clone(),
+        EnableWebhookCommandBuilder::default()
+            .webhook_id(webhook_id)
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    let webhook = Webhook::find(&pool, &webhook_id).await.unwrap().unwrap();
+    assert!(webhook.enabled());
+    assert_eq!(*webhook.consecutive_failures(), 0);
+
+    receiver.set_status(204);
+    like(&pool, &bus, &fragment).await;
+    assert_eq!(job(&pool, now).run().await.unwrap(), 1);
+    let states: Vec<WebhookDeliveryState> = deliveries(&pool, &webhook_id)
+        .await
+        .iter()
+        .map(|d| *d.state())
+        .collect();
+    assert_eq!(states.len(), 2);
+    assert!(
+        states.contains(&WebhookDeliveryState::Delivered),
+        "{states:?}"
+    );
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_manage_webhooks(pool: PgPool) {
+    let now = DateTime::now();
+    let author = create_user(&pool).await;
+    let fragment = create_published(&pool, &author, "fragment", false).await;
+    let story = save_story(&pool, &fragment).await;
+    let bus = bus(&pool, now);
+    let url = "https://example.com/hook";
+
+    let result = create_webhook(&bus, &author, url, None).await;
+    assert!(matches!(
+        result,
+        Err(CommandBusError::CreateWebhookCommand(
+            CreateWebhookCommandError::Forbidden(_)
+        ))
+    ));
+    let stranger = create_user(&pool).await;
+    let result = create_webhook(&bus, &stranger, url, Some(*story.id())).await;
+    assert!(matches!(
+        result,
+        Err(CommandBusError::CreateWebhookCommand(
+            CreateWebhookCommandError::Forbidden(_)
+        ))
+    ));
+    let result = create_webhook(&bus, &author, "ftp://example.com", Some(*story.id())).await;
+    assert!(matches!(
+        result,
+        Err(CommandBusError::CreateWebhookCommand(
+            CreateWebhookCommandError::InvalidWebhook(_)
+        ))
+    ));
+    let result = bus
+        .execute(
+            author.clone(),
+            CreateWebhookCommandBuilder::default()
+                .webhook_id(Id::new())
+                .url(url)
+                .secret(WebhookSecret::from("short"))
+                .story_id(Some(*story.id()))
+                .allowed_hosts(allowed_hosts())
+                .build()
+                .unwrap(),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(CommandBusError::CreateWebhookCommand(
+            CreateWebhookCommandError::InvalidWebhook(_)
+        ))
+    ));
+
+    let webhook_id = create_webhook(&bus, &author, url, Some(*story.id()))
+        .await
+        .unwrap();
+    let delete = || {
+        DeleteWebhookCommandBuilder::default()
+            .webhook_id(webhook_id)
+            .build()
+            .unwrap()
+    };
+    let result = bus.execute(stranger.clone(), delete()).await;
+    assert!(matches!(
+        result,
+        Err(CommandBusError::DeleteWebhookCommand(
+            DeleteWebhookCommandError::Forbidden(_)
+        ))
+    ));
+
+    like(&pool, &bus, &fragment).await;
+    assert_eq!(deliveries(&pool, &webhook_id).await.len(), 1);
+    bus.execute(author.clone(), delete()).await.unwrap();
+    assert!(Webhook::find(&pool, &webhook_id).await.unwrap().is_none());
+    assert!(deliveries(&pool, &webhook_id).await.is_empty());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_internal_endpoints_are_rejected(pool: PgPool) {
+    let now = DateTime::now();
+    let admin = create_user_with_role(&pool, Role::Admin).await;
+    let fragment = create_published(&pool, &create_user(&pool).await, "fragment", false).await;
+    let bus = bus(&pool, now);
+    let receiver = Receiver::start(200).await;
+
+    for url in [
+        "http://169.254.169.254/latest/meta-data",
+        "http://localhost:5432",
+        "http://10.0.0.1/hook",
+        "http://[::1]/hook",
+        "http://[::ffff:192.168.0.1]/hook",
+    ] {
+        let result = bus
+            .execute(
+                admin.clone(),
+                CreateWebhookCommandBuilder::default()
+                    .webhook_id(Id::new())
+                    .url(url)
+                    .secret(WebhookSecret::from(SECRET))
+                    .build()
+                    .unwrap(),
+            )
+            .await;
+        assert!(
+            matches!(
+                result,
+                Err(CommandBusError::CreateWebhookCommand(
+                    CreateWebhookCommandError::InvalidWebhook(_)
+                ))
+            ),
+            "{url}"
+        );
+    }
+
+    // An endpoint allowed when created is not called once it is no longer allowed.
+    let webhook_id = create_webhook(&bus, &admin, &receiver.url, None)
+        .await
+        .unwrap();
+    like(&pool, &bus, &fragment).await;
+    let job = WebhookDeliveryJob::new(
+        pool.clone(),
+        bus.clone(),
+        Arc::new(fixed_clock(now)),
+        Arc::new(StdIdGenerator),
+        WebhookSettings {
+            allowed_hosts: vec![],
+            ..settings()
+        },
+    );
+    assert_eq!(job.run().await.unwrap(), 1);
+    assert!(receiver.received().is_empty());
+    let delivery = &deliveries(&pool, &webhook_id).await[0];
+    let attempts = WebhookAttempt::find_by_delivery(&pool, delivery.id())
+        .await
+        .unwrap();
+    assert_eq!(*attempts[0].status_code(), None);
+    assert!(attempts[0].error().is_some());
+}
+
+#[sqlx::test(migrator = "storage::MIGRATOR")]
+fn test_draft_forks_are_not_delivered(pool: PgPool) {
+    let now = DateTime::now();
+    let author = create_user(&pool).await;
+    let forker = create_user(&pool).await;
+    let fragment = create_published(&pool, &author, "fragment", false)
+        .await
+        .set_fork_policy(ForkPolicy::Anyone)
+        .update(&pool)
+        .await
+        .unwrap();
+    let story = save_story(&pool, &fragment).await;
+    let bus = bus(&pool, now);
+
+    let webhook_id = Id::new();
+    bus.execute(
+        author.clone(),
+        CreateWebhookCommandBuilder::default()
+            .webhook_id(webhook_id)
+            .url("https://example.com/hook")
+            .secret(WebhookSecret::from(SECRET))
+            .event_types(vec![
+                EventType::FragmentForked,
+                EventType::FragmentUpdated,
+                EventType::ForkSubmitted,
+            ])
+            .story_id(Some(*story.id()))
+            .allowed_hosts(allowed_hosts())
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let fork_id = Id::new();
+    bus.execute(
+        forker.clone(),
+        ForkFragmentCommandBuilder::default()
+            .fork_id(fork_id)
+            .parent_fragment_id(*fragment.id())
+            .content("secret draft")
+            .end(false)
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    bus.execute(
+        forker.clone(),
+        UpdateFragmentCommandBuilder::default()
+            .fragment_id(fork_id)
+            .content(Some("still a secret draft".into()))
+            .end(None)
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    assert!(deliveries(&pool, &webhook_id).await.is_empty());
+
+    // Once submitted, the story author reviews the fork and gets its events.
+    bus.execute(
+        forker.clone(),
+        SubmitForkCommandBuilder::default()
+            .fragment_id(fork_id)
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    let queued = deliveries(&pool, &webhook_id).await;
+    assert_eq!(queued.len(), 1);
+    assert_eq!(*queued[0].event_type(), EventType::ForkSubmitted);
+}
@@ -0,0 +1,50 @@
+use actix_web::{http::header, web::Data, FromRequest, HttpRequest};
+use commons::auth::SessionToken;
+use std::{future::Future, pin::Pin};
+use storage::{model::session::Session, query::session::QuerySession};
+
+use crate::{response::ApiError, server::AppState};
+
+const BEARER_PREFIX: &str = "Bearer ";
+
+pub struct SessionExtractor(pub Session);
+
+impl FromRequest for SessionExtractor {
+    type Error = actix_web::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut actix_web::dev::Payload) -> Self::Future {
+        let req = req.clone();
+        Box::pin(async move {
+            let state = req
+                .app_data::<Data<AppState>>()
+                .ok_or(ApiError::InternalServerError("Missing app state".into()))?;
+            let token = bearer_token(&req).ok_or(ApiError::Unauthorized)?;
+
+            Ok(find_session(state, &token)
+                .await?
+                .map(SessionExtractor)
+                .ok_or(ApiError::Unauthorized)?)
+        })
+    }
+}
+
+pub fn bearer_token(req: &HttpRequest) -> Option<SessionToken> {
+    req.headers()
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix(BEARER_PREFIX)
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(SessionToken::from)
+}
+
+pub async fn find_session(
+    state: &AppState,
+    token: &SessionToken,
+) -> Result<Option<Session>, ApiError> {
+    Session::find_active(&state.pool, &token.hash(), &state.clock.now())
+        .await
+        .map_err(|e| ApiError::InternalServerError(e.into()))
+}
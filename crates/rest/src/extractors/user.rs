@@ -1,9 +1,13 @@
-use actix_web::{web::Data, FromRequest};
+use actix_web::{web::Data, FromRequest, HttpRequest};
 use commons::id::Id;
 use std::{future::Future, pin::Pin};
 use storage::{model::user::User, query::user::QueryUser};
 
-use crate::server::AppState;
+use crate::{
+    extractors::session::{bearer_token, find_session},
+    response::ApiError,
+    server::AppState,
+};
 
 const USER_ID_HEADER_KEY: &str = "user-id";
 pub struct UserExtractor(pub User);
@@ -12,25 +16,59 @@ impl FromRequest for UserExtractor {
     type Error = actix_web::Error;
     type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
 
-    fn from_request(
-        req: &actix_web::HttpRequest,
-        _payload: &mut actix_web::dev::Payload,
-    ) -> Self::Future {
+    fn from_request(req: &HttpRequest, _payload: &mut actix_web::dev::Payload) -> Self::Future {
         let req = req.clone();
         Box::pin(async move {
-            let state = req.app_data::<Data<AppState>>().unwrap();
-            let user_id: Id = req
-                .headers()
-                .get(USER_ID_HEADER_KEY)
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .try_into()
-                .unwrap();
-
-            let user = User::find(&state.pool, &user_id).await.unwrap().unwrap();
-
+            let user = find_user(&req).await?.ok_or(ApiError::Unauthorized)?;
             Ok(UserExtractor(user))
         })
     }
 }
+
+/// User of the request when it sends credentials, for routes also open to anonymous
+/// readers. Invalid credentials are still rejected.
+pub struct OptionalUserExtractor(pub Option<User>);
+
+impl FromRequest for OptionalUserExtractor {
+    type Error = actix_web::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut actix_web::dev::Payload) -> Self::Future {
+        let req = req.clone();
+        Box::pin(async move { Ok(OptionalUserExtractor(find_user(&req).await?)) })
+    }
+}
+
+/// User authenticated by the request, or `None` when it has no credentials.
+async fn find_user(req: &HttpRequest) -> Result<Option<User>, ApiError> {
+    let state = req
+        .app_data::<Data<AppState>>()
+        .ok_or(ApiError::InternalServerError("Missing app state".into()))?;
+
+    let user_id = match bearer_token(req) {
+        Some(token) => find_session(state, &token)
+            .await?
+            .map(|s| *s.user_id())
+            .ok_or(ApiError::Unauthorized)?,
+        None if state.auth.allow_user_id_header => match header_user_id(req) {
+            Some(user_id) => user_id,
+            None => return Ok(None),
+        },
+        None => return Ok(None),
+    };
+
+    User::find(&state.pool, &user_id)
+        .await
+        .map_err(|e| ApiError::InternalServerError(e.into()))?
+        .ok_or(ApiError::Unauthorized)
+        .map(Some)
+}
+
+fn header_user_id(req: &HttpRequest) -> Option<Id> {
+    req.headers()
+        .get(USER_ID_HEADER_KEY)?
+        .to_str()
+        .ok()?
+        .try_into()
+        .ok()
+}
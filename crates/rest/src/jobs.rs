@@ -0,0 +1,122 @@
+use crate::live::EventHub;
+use chrono::Duration;
+use commons::id::Id;
+use commons::{
+    actor::Actor,
+    configuration::settings::{MailSettings, RetentionSettings, TaskSettings, WebhookSettings},
+    time::Clock,
+};
+use cqrs::{
+    command_bus::{bus::CommandBus, command::purge_fragments::PurgeFragmentsCommandBuilder},
+    digest::DigestJob,
+    webhooks::WebhookDeliveryJob,
+};
+use sqlx::{postgres::PgListener, PgPool};
+use std::sync::Arc;
+use storage::{
+    model::event::DbEvent,
+    query::event::{QueryEvent, EVENTS_CHANNEL},
+};
+use tokio::task::JoinHandle;
+
+/// Periodically hard deletes fragments whose retention period is over.
+pub fn spawn_purge_job(
+    command_bus: Arc<CommandBus>,
+    clock: Arc<dyn Clock>,
+    settings: RetentionSettings,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(settings.purge_interval));
+        loop {
+            interval.tick().await;
+            let command = PurgeFragmentsCommandBuilder::default()
+                .deleted_before(clock.now() - Duration::seconds(settings.deleted_fragments as i64))
+                .build()
+                .unwrap();
+
+            if let Err(e) = command_bus.execute(Actor::System, command).await {
+                tracing::error!("Failed to purge deleted fragments: {e}");
+            }
+        }
+    })
+}
+
+/// Periodically runs the scheduled tasks that are due.
+pub fn spawn_task_job(command_bus: Arc<CommandBus>, settings: TaskSettings) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(settings.poll_interval));
+        loop {
+            interval.tick().await;
+            if let Err(e) = command_bus.run_due_tasks().await {
+                tracing::error!("Failed to run scheduled tasks: {e}");
+            }
+        }
+    })
+}
+
+/// Periodically emails the digests that are due.
+pub fn spawn_digest_job(job: DigestJob, settings: MailSettings) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(settings.digest_interval));
+        loop {
+            interval.tick().await;
+            if let Err(e) = job.run().await {
+                tracing::error!("Failed to send digests: {e}");
+            }
+        }
+    })
+}
+
+/// Periodically posts the queued events to webhook endpoints.
+pub fn spawn_webhook_job(job: WebhookDeliveryJob, settings: WebhookSettings) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(settings.poll_interval));
+        loop {
+            interval.tick().await;
+            if let Err(e) = job.run().await {
+                tracing::error!("Failed to deliver webhooks: {e}");
+            }
+        }
+    })
+}
+
+/// Publishes to the live streams every event committed, whichever instance committed it.
+pub fn spawn_event_listener(pool: PgPool, hub: EventHub) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut listener = loop {
+            match PgListener::connect_with(&pool).await {
+                Ok(mut listener) => match listener.listen(EVENTS_CHANNEL).await {
+                    Ok(()) => break listener,
+                    Err(e) => tracing::error!("Failed to listen to events: {e}"),
+                },
+                Err(e) => tracing::error!("Failed to connect the event listener: {e}"),
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        };
+
+        loop {
+            // The listener reconnects by itself on the next call after an error.
+            let notification = match listener.recv().await {
+                Ok(notification) => notification,
+                Err(e) => {
+                    tracing::error!("Failed to receive event notification: {e}");
+                    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+                    continue;
+                }
+            };
+            let Ok(id) = Id::try_from(notification.payload()) else {
+                tracing::error!("Invalid event notification: {}", notification.payload());
+                continue;
+            };
+            match DbEvent::find(&pool, &id).await {
+                Ok(Some(event)) => hub.publish(event),
+                Ok(None) => {}
+                Err(e) => tracing::error!("Failed to find notified event: {e}"),
+            }
+        }
+    })
+}
@@ -1,5 +1,7 @@
 pub mod extractors;
+pub mod jobs;
 pub mod links;
+pub mod live;
 pub mod model;
 pub mod response;
 pub mod routes;
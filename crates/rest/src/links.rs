@@ -1,16 +1,32 @@
-use crate::routes::{fragments::FragmentsRouter, reviews::ReviewsRouter};
+use crate::model::pagination::PageQuery;
+use crate::routes::{
+    comments::CommentsRouter, fragments::FragmentsRouter, maintainers::MaintainersRouter,
+    notification_preferences::NotificationPreferencesRouter, notifications::NotificationsRouter,
+    polls::PollsRouter, reviews::ReviewsRouter, revisions::RevisionsRouter, stories::StoriesRouter,
+    suggestions::SuggestionsRouter, tags::TagsRouter,
+    trusted_contributors::TrustedContributorsRouter, user::UsersRouter, webhooks::WebhooksRouter,
+};
 use actix_web::{error::UrlGenerationError, HttpRequest};
-use commons::id::Id;
-use serde::{Deserialize, Serialize};
+use commons::{id::Id, tag::Tag};
+use serde::{Deserialize, Serialize, Serializer};
 use std::collections::HashMap;
 use url::Url;
 
-#[derive(Debug, Hash, PartialEq, Eq, Serialize)]
+#[derive(Debug, Hash, PartialEq, Eq)]
 pub enum Rel {
     Self_,
     Named(&'static str),
 }
 
+impl Serialize for Rel {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Rel::Self_ => serializer.serialize_str("self"),
+            Rel::Named(name) => serializer.serialize_str(name),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct SingleIdPath(Id);
 
@@ -28,20 +44,202 @@ impl From<&SingleIdPath> for Id {
 
 #[derive(Debug, Clone)]
 pub enum ResourceLink {
+    CanonicalPath(Id),
+    Comment(Id, Id),
+    Comments(Id),
     Fragment(Id),
+    FragmentTags(Id),
+    Maintainers(Id),
+    NotificationPreferences(Id),
+    /// Inbox page of a user, only unread notifications when the flag is set.
+    Notifications(Id, PageQuery, bool),
+    NotificationRead(Id, Id),
+    NotificationsRead(Id),
+    Poll(Id),
     Review(Id, Id),
+    ReviewComments(Id, Id),
+    ReviewContext(Id),
+    ReviewQuorum(Id),
+    ReviewSla(Id),
+    Revisions(Id),
+    Transitions(Id),
+    PublicationSchedule(Id),
+    Revision(Id, i32),
+    Stories,
+    Story(Id),
+    StoryTags(Id),
+    Suggestions(Id),
+    SuggestionAcceptance(Id, Id),
+    SuggestionRejection(Id, Id),
+    Tags,
+    TaggedFragments(Tag, PageQuery),
+    Tree(Id),
+    TrustedContributor(Id, Id),
+    TrustedContributors(Id),
+    User(Id),
+    Votes(Id),
+    Webhook(Id),
+    /// Deliveries page of a webhook.
+    WebhookDeliveries(Id, PageQuery),
+    WebhookDelivery(Id, Id),
+    WebhookEnable(Id),
+    Webhooks,
 }
 
 impl ResourceLink {
     pub fn as_url(&self, req: &actix_web::HttpRequest) -> Result<Url, UrlGenerationError> {
         match self {
+            ResourceLink::CanonicalPath(frag_id) => req.url_for(
+                FragmentsRouter::CANONICAL_PATH_RESOURCE_NAME,
+                [frag_id.to_string()],
+            ),
+            ResourceLink::Comment(frag_id, comment_id) => req.url_for(
+                CommentsRouter::SINGLE_RESOURCE_NAME,
+                [frag_id.to_string(), comment_id.to_string()],
+            ),
+            ResourceLink::Comments(frag_id) => req.url_for(
+                CommentsRouter::COLLECTION_RESOURCE_NAME,
+                [frag_id.to_string()],
+            ),
             ResourceLink::Fragment(id) => {
                 req.url_for(FragmentsRouter::SINGLE_RESOURCE_NAME, [id.to_string()])
             }
+            ResourceLink::FragmentTags(frag_id) => req.url_for(
+                TagsRouter::FRAGMENT_COLLECTION_RESOURCE_NAME,
+                [frag_id.to_string()],
+            ),
+            ResourceLink::Maintainers(frag_id) => req.url_for(
+                MaintainersRouter::COLLECTION_RESOURCE_NAME,
+                [frag_id.to_string()],
+            ),
+            ResourceLink::NotificationPreferences(user_id) => req.url_for(
+                NotificationPreferencesRouter::SINGLE_RESOURCE_NAME,
+                [user_id.to_string()],
+            ),
+            ResourceLink::Notifications(user_id, page, unread) => req
+                .url_for(
+                    NotificationsRouter::COLLECTION_RESOURCE_NAME,
+                    [user_id.to_string()],
+                )
+                .map(|mut url| {
+                    let query = match unread {
+                        true => format!("{}&unread=true", page.as_query()),
+                        false => page.as_query(),
+                    };
+                    url.set_query(Some(&query));
+                    url
+                }),
+            ResourceLink::NotificationRead(user_id, notification_id) => req.url_for(
+                NotificationsRouter::READ_RESOURCE_NAME,
+                [user_id.to_string(), notification_id.to_string()],
+            ),
+            ResourceLink::NotificationsRead(user_id) => req.url_for(
+                NotificationsRouter::READ_ALL_RESOURCE_NAME,
+                [user_id.to_string()],
+            ),
+            ResourceLink::Poll(id) => {
+                req.url_for(PollsRouter::SINGLE_RESOURCE_NAME, [id.to_string()])
+            }
             ResourceLink::Review(frag_id, review_id) => req.url_for(
                 ReviewsRouter::SINGLE_RESOURCE_NAME,
                 [frag_id.to_string(), review_id.to_string()],
             ),
+            ResourceLink::ReviewComments(frag_id, review_id) => req.url_for(
+                CommentsRouter::REVIEW_COLLECTION_RESOURCE_NAME,
+                [frag_id.to_string(), review_id.to_string()],
+            ),
+            ResourceLink::ReviewContext(frag_id) => {
+                req.url_for(ReviewsRouter::CONTEXT_RESOURCE_NAME, [frag_id.to_string()])
+            }
+            ResourceLink::ReviewQuorum(frag_id) => {
+                req.url_for(ReviewsRouter::QUORUM_RESOURCE_NAME, [frag_id.to_string()])
+            }
+            ResourceLink::ReviewSla(frag_id) => {
+                req.url_for(ReviewsRouter::SLA_RESOURCE_NAME, [frag_id.to_string()])
+            }
+            ResourceLink::Revisions(frag_id) => req.url_for(
+                RevisionsRouter::COLLECTION_RESOURCE_NAME,
+                [frag_id.to_string()],
+            ),
+            ResourceLink::PublicationSchedule(frag_id) => req.url_for(
+                FragmentsRouter::PUBLICATION_SCHEDULE_RESOURCE_NAME,
+                [frag_id.to_string()],
+            ),
+            ResourceLink::Transitions(frag_id) => req.url_for(
+                FragmentsRouter::TRANSITIONS_RESOURCE_NAME,
+                [frag_id.to_string()],
+            ),
+            ResourceLink::Revision(frag_id, number) => req.url_for(
+                RevisionsRouter::SINGLE_RESOURCE_NAME,
+                [frag_id.to_string(), number.to_string()],
+            ),
+            ResourceLink::Stories => {
+                req.url_for(StoriesRouter::COLLECTION_RESOURCE_NAME, [] as [String; 0])
+            }
+            ResourceLink::Story(id) => {
+                req.url_for(StoriesRouter::SINGLE_RESOURCE_NAME, [id.to_string()])
+            }
+            ResourceLink::StoryTags(story_id) => req.url_for(
+                TagsRouter::STORY_COLLECTION_RESOURCE_NAME,
+                [story_id.to_string()],
+            ),
+            ResourceLink::Suggestions(frag_id) => req.url_for(
+                SuggestionsRouter::COLLECTION_RESOURCE_NAME,
+                [frag_id.to_string()],
+            ),
+            ResourceLink::SuggestionAcceptance(frag_id, suggestion_id) => req.url_for(
+                SuggestionsRouter::ACCEPTANCE_RESOURCE_NAME,
+                [frag_id.to_string(), suggestion_id.to_string()],
+            ),
+            ResourceLink::SuggestionRejection(frag_id, suggestion_id) => req.url_for(
+                SuggestionsRouter::REJECTION_RESOURCE_NAME,
+                [frag_id.to_string(), suggestion_id.to_string()],
+            ),
+            ResourceLink::Tags => {
+                req.url_for(TagsRouter::COLLECTION_RESOURCE_NAME, [] as [String; 0])
+            }
+            ResourceLink::TaggedFragments(tag, page) => req
+                .url_for(TagsRouter::FRAGMENTS_RESOURCE_NAME, [tag.to_string()])
+                .map(|mut url| {
+                    url.set_query(Some(&page.as_query()));
+                    url
+                }),
+            ResourceLink::Tree(frag_id) => {
+                req.url_for(FragmentsRouter::TREE_RESOURCE_NAME, [frag_id.to_string()])
+            }
+            ResourceLink::TrustedContributor(author_id, contributor_id) => req.url_for(
+                TrustedContributorsRouter::SINGLE_RESOURCE_NAME,
+                [author_id.to_string(), contributor_id.to_string()],
+            ),
+            ResourceLink::TrustedContributors(author_id) => req.url_for(
+                TrustedContributorsRouter::COLLECTION_RESOURCE_NAME,
+                [author_id.to_string()],
+            ),
+            ResourceLink::User(id) => {
+                req.url_for(UsersRouter::SINGLE_RESOURCE_NAME, [id.to_string()])
+            }
+            ResourceLink::Votes(poll_id) => {
+                req.url_for(PollsRouter::VOTES_RESOURCE_NAME, [poll_id.to_string()])
+            }
+            ResourceLink::Webhook(id) => {
+                req.url_for(WebhooksRouter::SINGLE_RESOURCE_NAME, [id.to_string()])
+            }
+            ResourceLink::WebhookDeliveries(id, page) => req
+                .url_for(WebhooksRouter::DELIVERIES_RESOURCE_NAME, [id.to_string()])
+                .map(|mut url| {
+                    url.set_query(Some(&page.as_query()));
+                    url
+                }),
+            ResourceLink::WebhookDelivery(id, delivery_id) => req.url_for(
+                WebhooksRouter::DELIVERY_RESOURCE_NAME,
+                [id.to_string(), delivery_id.to_string()],
+            ),
+            ResourceLink::WebhookEnable(id) => {
+                req.url_for(WebhooksRouter::ENABLE_RESOURCE_NAME, [id.to_string()])
+            }
+            ResourceLink::Webhooks => {
+                req.url_for(WebhooksRouter::COLLECTION_RESOURCE_NAME, [] as [String; 0])
+            }
         }
     }
 }
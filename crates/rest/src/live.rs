@@ -0,0 +1,140 @@
+use actix_web::web::Bytes;
+use async_stream::stream;
+use cqrs::live::{LiveEvent, LiveFilter};
+use futures_util::Stream;
+use std::{sync::Arc, time::Duration};
+use storage::model::event::DbEvent;
+use tokio::sync::broadcast::{self, error::RecvError, Receiver, Sender};
+
+/// Fans the committed events out to the live streams of this instance.
+#[derive(Clone)]
+pub struct EventHub(Sender<Arc<DbEvent>>);
+
+impl EventHub {
+    pub fn new(buffer: usize) -> Self {
+        Self(broadcast::channel(buffer).0)
+    }
+
+    pub fn publish(&self, event: DbEvent) {
+        // Nobody listening is not an error.
+        let _ = self.0.send(Arc::new(event));
+    }
+
+    pub fn subscribe(&self) -> Receiver<Arc<DbEvent>> {
+        self.0.subscribe()
+    }
+}
+
+/// Item of a live stream.
+pub enum LiveMessage {
+    Event(LiveEvent),
+    /// Keeps idle connections from being closed by proxies.
+    Heartbeat,
+}
+
+impl LiveMessage {
+    /// Server-Sent Events frame of the message, with the event position as its id so
+    /// clients resume through `Last-Event-ID`.
+    pub fn to_sse(&self) -> Bytes {
+        match self {
+            Self::Event(event) => Bytes::from(format!(
+                "id: {}\nevent: {}\ndata: {}\n\n",
+                event.position,
+                serde_json::to_value(event.event_type)
+                    .unwrap()
+                    .as_str()
+                    .unwrap_or_default(),
+                serde_json::to_string(event).unwrap()
+            )),
+            Self::Heartbeat => Bytes::from_static(b": heartbeat\n\n"),
+        }
+    }
+}
+
+/// Events of a live stream: the visible ones saved after `last_position` first, then the
+/// new ones as they are committed.
+///
+/// Subscribe to the hub before calling this, so nothing committed during the replay is
+/// lost. A stream falling behind the hub catches up from the database, unless it is more
+/// than `max_replay` events behind, in which case it ends and the client resumes again.
+pub fn live_stream(
+    filter: LiveFilter,
+    mut events: Receiver<Arc<DbEvent>>,
+    last_position: Option<i64>,
+    heartbeat: Duration,
+    max_replay: i64,
+) -> impl Stream<Item = LiveMessage> {
+    stream! {
+        // Events up to this position were read from the database, the hub may repeat them.
+        let mut replayed = last_position;
+        let mut seen = last_position;
+        let mut heartbeat = tokio::time::interval_at(tokio::time::Instant::now() + heartbeat, heartbeat);
+
+        loop {
+            if let Some(mut position) = replayed {
+                loop {
+                    match filter.replay(position).await {
+                        Ok((events, reached)) => {
+                            for event in events {
+                                yield LiveMessage::Event(event);
+                            }
+                            if reached == position {
+                                break;
+                            }
+                            position = reached;
+                        }
+                        Err(e) => {
+                            tracing::error!("Failed to replay live events: {e}");
+                            return;
+                        }
+                    }
+                }
+                replayed = Some(position);
+                seen = seen.max(replayed);
+            }
+
+            let lagged = loop {
+                let received = tokio::select! {
+                    received = events.recv() => Some(received),
+                    _ = heartbeat.tick() => None,
+                };
+                let event = match received {
+                    Some(Ok(event)) => event,
+                    Some(Err(RecvError::Lagged(skipped))) => break skipped,
+                    Some(Err(RecvError::Closed)) => return,
+                    None => {
+                        yield LiveMessage::Heartbeat;
+                        continue;
+                    }
+                };
+
+                let Some(position) = *event.position() else {
+                    continue;
+                };
+                if replayed.is_some_and(|replayed| position <= replayed) {
+                    continue;
+                }
+                seen = seen.max(Some(position));
+                match filter.accept(&event).await {
+                    Ok(Some(event)) => yield LiveMessage::Event(event),
+                    Ok(None) => {}
+                    Err(e) => {
+                        tracing::error!("Failed to filter live event: {e}");
+                        return;
+                    }
+                }
+            };
+
+            // Catch up from the last event seen. Without one there is nothing to resume
+            // from and the skipped events are lost for this stream.
+            tracing::warn!("Live stream lagged behind by {lagged} events");
+            if let Some(seen) = seen {
+                if let Err(e) = filter.check_resume(seen, max_replay).await {
+                    tracing::warn!("Ending live stream: {e}");
+                    return;
+                }
+            }
+            replayed = seen;
+        }
+    }
+}
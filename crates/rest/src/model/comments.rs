@@ -0,0 +1,124 @@
+use crate::{
+    links::{Rel, ResourceLink},
+    model::resource::{CollectionResource, CollectionResourceBuilder, SingleResourceBuilder},
+    response::ResourceBuilder,
+};
+use actix_web::{web::Path, HttpRequest};
+use commons::{id::Id, review, time::DateTime};
+use serde::{Deserialize, Serialize};
+use storage::model::comment::Comment;
+
+pub type CommentPath = Path<(Id, Id)>;
+
+#[derive(Deserialize, Debug)]
+pub struct CreateCommentRequest {
+    body: String,
+    parent_id: Option<Id>,
+}
+
+impl CreateCommentRequest {
+    pub fn body(&self) -> review::Comment {
+        review::Comment::from(self.body.clone())
+    }
+
+    pub fn parent_id(&self) -> Option<Id> {
+        self.parent_id
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct EditCommentRequest {
+    body: String,
+}
+
+impl EditCommentRequest {
+    pub fn body(&self) -> review::Comment {
+        review::Comment::from(self.body.clone())
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ModerateCommentRequest {
+    pub hidden: bool,
+}
+
+#[derive(Serialize)]
+pub struct CommentResource {
+    id: Id,
+    parent_id: Option<Id>,
+    author_id: Id,
+    /// Left out for deleted and hidden comments, which stay in the thread as placeholders.
+    body: Option<review::Comment>,
+    deleted: bool,
+    hidden: bool,
+    created_at: DateTime,
+    last_modified_at: DateTime,
+}
+
+impl From<&Comment> for CommentResource {
+    fn from(value: &Comment) -> Self {
+        let visible = !value.is_deleted() && !value.is_hidden();
+        Self {
+            id: *value.id(),
+            parent_id: *value.parent_id(),
+            author_id: *value.author_id(),
+            body: visible.then(|| value.body().clone()),
+            deleted: value.is_deleted(),
+            hidden: value.is_hidden(),
+            created_at: *value.created_at(),
+            last_modified_at: *value.last_modified_at(),
+        }
+    }
+}
+
+fn comment_builder(comment: &Comment) -> SingleResourceBuilder<CommentResource> {
+    let fragment_id = *comment.fragment_id();
+    let mut builder = SingleResourceBuilder::new(CommentResource::from(comment))
+        .link(
+            Rel::Self_,
+            ResourceLink::Comment(fragment_id, *comment.id()),
+        )
+        .link(
+            Rel::Named("author"),
+            ResourceLink::User(*comment.author_id()),
+        );
+    if let Some(parent_id) = comment.parent_id() {
+        builder = builder.link(
+            Rel::Named("parent"),
+            ResourceLink::Comment(fragment_id, *parent_id),
+        );
+    }
+    if let Some(review_id) = comment.review_id() {
+        builder = builder.link(
+            Rel::Named("review"),
+            ResourceLink::Review(fragment_id, *review_id),
+        );
+    }
+    builder
+}
+
+/// Discussion thread of a fragment, or of one of its reviews.
+pub struct Thread {
+    pub fragment_id: Id,
+    pub review_id: Option<Id>,
+    pub comments: Vec<Comment>,
+}
+
+impl ResourceBuilder<CollectionResource<CommentResource>> for Thread {
+    fn build(
+        &self,
+        req: &HttpRequest,
+    ) -> Result<CollectionResource<CommentResource>, anyhow::Error> {
+        let self_link = match self.review_id {
+            Some(review_id) => ResourceLink::ReviewComments(self.fragment_id, review_id),
+            None => ResourceLink::Comments(self.fragment_id),
+        };
+        CollectionResourceBuilder::new(self.comments.iter().map(comment_builder).collect())
+            .link(Rel::Self_, self_link)
+            .link(
+                Rel::Named("fragment"),
+                ResourceLink::Fragment(self.fragment_id),
+            )
+            .build(req)
+    }
+}
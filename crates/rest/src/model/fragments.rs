@@ -1,7 +1,15 @@
-use crate::links::SingleIdPath;
-use actix_web::web::Path;
-use commons::fragment::Content;
-use serde::Deserialize;
+use crate::{
+    links::{Rel, ResourceLink, SingleIdPath},
+    model::resource::{SingleResource, SingleResourceBuilder},
+    response::ResourceBuilder,
+};
+use actix_web::{web::Path, HttpRequest};
+use commons::{fragment::Content, id::Id, review::Comment, time::DateTime};
+use serde::{Deserialize, Serialize};
+use storage::model::{
+    fragment::{ForkPolicy, Fragment, FragmentState},
+    task::Task,
+};
 
 pub type FragmentPath = Path<SingleIdPath>;
 
@@ -31,3 +39,106 @@ impl UpdateFragmentRequest {
         self.end
     }
 }
+
+#[derive(Deserialize, Debug)]
+pub struct PublishFragmentRequest {
+    /// Schedules the publication instead of publishing right away.
+    pub publish_at: Option<DateTime>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ResubmitForkRequest {
+    appeal: Option<String>,
+}
+
+impl ResubmitForkRequest {
+    pub fn appeal(&self) -> Option<Comment> {
+        self.appeal.clone().map(Comment::from)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SetForkPolicyRequest {
+    pub policy: ForkPolicy,
+    #[serde(default)]
+    pub invitees: Vec<Id>,
+}
+
+#[derive(Serialize)]
+pub struct FragmentResource {
+    content: Content,
+    state: FragmentState,
+    end: bool,
+    fork_policy: ForkPolicy,
+    created_at: DateTime,
+    last_modified_at: DateTime,
+}
+
+impl From<&Fragment> for FragmentResource {
+    fn from(value: &Fragment) -> Self {
+        Self {
+            content: value.content().clone(),
+            state: *value.state(),
+            end: *value.end(),
+            fork_policy: *value.fork_policy(),
+            created_at: *value.created_at(),
+            last_modified_at: *value.last_modified_at(),
+        }
+    }
+}
+
+pub(crate) fn fragment_builder(fragment: &Fragment) -> SingleResourceBuilder<FragmentResource> {
+    let builder = SingleResourceBuilder::new(FragmentResource::from(fragment))
+        .link(Rel::Self_, ResourceLink::Fragment(*fragment.id()))
+        .link(
+            Rel::Named("author"),
+            ResourceLink::User(*fragment.author_id()),
+        );
+
+    match fragment.parent_id() {
+        Some(parent_id) => builder.link(Rel::Named("parent"), ResourceLink::Fragment(*parent_id)),
+        None => builder,
+    }
+}
+
+impl ResourceBuilder<SingleResource<FragmentResource>> for Fragment {
+    fn build(&self, req: &HttpRequest) -> Result<SingleResource<FragmentResource>, anyhow::Error> {
+        fragment_builder(self).build(req)
+    }
+}
+
+#[derive(Serialize, Clone)]
+pub struct PublicationScheduleResource {
+    #[serde(skip)]
+    fragment_id: Id,
+    publish_at: DateTime,
+    created_at: DateTime,
+}
+
+impl PublicationScheduleResource {
+    pub fn new(fragment_id: Id, task: &Task) -> Self {
+        Self {
+            fragment_id,
+            publish_at: *task.scheduled_at(),
+            created_at: *task.created_at(),
+        }
+    }
+}
+
+impl ResourceBuilder<SingleResource<PublicationScheduleResource>> for PublicationScheduleResource {
+    fn build(
+        &self,
+        req: &HttpRequest,
+    ) -> Result<SingleResource<PublicationScheduleResource>, anyhow::Error> {
+        SingleResourceBuilder::new(self.clone())
+            .link(
+                Rel::Self_,
+                ResourceLink::PublicationSchedule(self.fragment_id),
+            )
+            .link(
+                Rel::Named("fragment"),
+                ResourceLink::Fragment(self.fragment_id),
+            )
+            .build(req)
+    }
+}
@@ -0,0 +1,11 @@
+use serde::Deserialize;
+
+/// Header sent by Server-Sent Events clients when they reconnect.
+pub const LAST_EVENT_ID_HEADER: &str = "Last-Event-ID";
+
+/// `?last_event_id` query of the live streams, for clients that can not set headers,
+/// such as browser WebSockets.
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+pub struct LiveQuery {
+    pub last_event_id: Option<i64>,
+}
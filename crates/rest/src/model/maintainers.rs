@@ -0,0 +1,52 @@
+use crate::{
+    links::{Rel, ResourceLink},
+    model::resource::{CollectionResource, CollectionResourceBuilder, SingleResourceBuilder},
+    response::ResourceBuilder,
+};
+use actix_web::HttpRequest;
+use commons::{id::Id, time::DateTime};
+use serde::{Deserialize, Serialize};
+use storage::model::maintainer::{Maintainer, MaintainerState};
+
+#[derive(Deserialize, Debug)]
+pub struct InviteMaintainerRequest {
+    pub user_id: Id,
+}
+
+#[derive(Serialize)]
+pub struct MaintainerResource {
+    state: MaintainerState,
+    invited_at: DateTime,
+    responded_at: Option<DateTime>,
+}
+
+fn maintainer_builder(maintainer: &Maintainer) -> SingleResourceBuilder<MaintainerResource> {
+    SingleResourceBuilder::new(MaintainerResource {
+        state: *maintainer.state(),
+        invited_at: *maintainer.invited_at(),
+        responded_at: *maintainer.responded_at(),
+    })
+    .link(
+        Rel::Named("user"),
+        ResourceLink::User(*maintainer.user_id()),
+    )
+    .link(
+        Rel::Named("invited_by"),
+        ResourceLink::User(*maintainer.invited_by()),
+    )
+}
+
+/// Maintainers of the story rooted at the given fragment, pending invitations included.
+pub struct Maintainers(pub Id, pub Vec<Maintainer>);
+
+impl ResourceBuilder<CollectionResource<MaintainerResource>> for Maintainers {
+    fn build(
+        &self,
+        req: &HttpRequest,
+    ) -> Result<CollectionResource<MaintainerResource>, anyhow::Error> {
+        CollectionResourceBuilder::new(self.1.iter().map(maintainer_builder).collect())
+            .link(Rel::Self_, ResourceLink::Maintainers(self.0))
+            .link(Rel::Named("fragment"), ResourceLink::Fragment(self.0))
+            .build(req)
+    }
+}
@@ -1,5 +1,22 @@
+pub mod comments;
 pub mod error;
 pub mod forks;
 pub mod fragments;
+pub mod live;
+pub mod maintainers;
+pub mod notification_preferences;
+pub mod notifications;
+pub mod pagination;
+pub mod polls;
 pub mod resource;
 pub mod reviews;
+pub mod revisions;
+pub mod sessions;
+pub mod stories;
+pub mod suggestions;
+pub mod tags;
+pub mod transitions;
+pub mod tree;
+pub mod trusted_contributors;
+pub mod users;
+pub mod webhooks;
@@ -0,0 +1,53 @@
+use crate::{
+    links::{Rel, ResourceLink},
+    model::resource::{SingleResource, SingleResourceBuilder},
+    response::ResourceBuilder,
+};
+use actix_web::{web::Path, HttpRequest};
+use commons::{mail::EmailAddress, time::DateTime};
+use serde::{Deserialize, Serialize};
+use storage::model::{
+    notification::NotificationKind,
+    notification_preferences::{DigestFrequency, NotificationPreferences},
+};
+
+pub type UnsubscribePath = Path<String>;
+
+#[derive(Deserialize, Debug)]
+pub struct NotificationPreferencesRequest {
+    pub email: Option<EmailAddress>,
+    #[serde(default)]
+    pub digest: DigestFrequency,
+    #[serde(default)]
+    pub muted_kinds: Vec<NotificationKind>,
+}
+
+#[derive(Serialize)]
+pub struct NotificationPreferencesResource {
+    email: Option<EmailAddress>,
+    digest: DigestFrequency,
+    muted_kinds: Vec<NotificationKind>,
+    last_digest_at: Option<DateTime>,
+    updated_at: DateTime,
+}
+
+impl ResourceBuilder<SingleResource<NotificationPreferencesResource>> for NotificationPreferences {
+    fn build(
+        &self,
+        req: &HttpRequest,
+    ) -> Result<SingleResource<NotificationPreferencesResource>, anyhow::Error> {
+        SingleResourceBuilder::new(NotificationPreferencesResource {
+            email: self.email().clone(),
+            digest: *self.digest(),
+            muted_kinds: self.muted_kinds().clone(),
+            last_digest_at: *self.last_digest_at(),
+            updated_at: *self.updated_at(),
+        })
+        .link(
+            Rel::Self_,
+            ResourceLink::NotificationPreferences(*self.user_id()),
+        )
+        .link(Rel::Named("user"), ResourceLink::User(*self.user_id()))
+        .build(req)
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{
+    links::{Rel, ResourceLink},
+    model::{
+        pagination::PageQuery,
+        resource::{CollectionResource, CollectionResourceBuilder, SingleResourceBuilder},
+    },
+    response::ResourceBuilder,
+};
+use actix_web::{web::Path, HttpRequest};
+use commons::{id::Id, time::DateTime};
+use serde::{Deserialize, Serialize};
+use storage::model::notification::{Notification, NotificationKind};
+
+pub type NotificationPath = Path<(Id, Id)>;
+
+/// `?unread` query of the notification inbox.
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+pub struct NotificationFilter {
+    #[serde(default)]
+    pub unread: bool,
+}
+
+#[derive(Serialize)]
+pub struct NotificationResource {
+    id: Id,
+    kind: NotificationKind,
+    /// Number of people who triggered the grouped notification.
+    actor_count: usize,
+    read_at: Option<DateTime>,
+    created_at: DateTime,
+    updated_at: DateTime,
+}
+
+fn notification_builder(
+    notification: &Notification,
+) -> SingleResourceBuilder<NotificationResource> {
+    let subject = match notification.kind() {
+        NotificationKind::UserFollowed => ResourceLink::User(*notification.subject_id()),
+        _ => ResourceLink::Fragment(*notification.subject_id()),
+    };
+    let builder = SingleResourceBuilder::new(NotificationResource {
+        id: *notification.id(),
+        kind: *notification.kind(),
+        actor_count: notification.actor_ids().len(),
+        read_at: *notification.read_at(),
+        created_at: *notification.created_at(),
+        updated_at: *notification.updated_at(),
+    })
+    .link(Rel::Named("subject"), subject)
+    .link(
+        Rel::Named("read"),
+        ResourceLink::NotificationRead(*notification.user_id(), *notification.id()),
+    );
+
+    match notification.actor_ids().last() {
+        Some(actor_id) => builder.link(Rel::Named("last_actor"), ResourceLink::User(*actor_id)),
+        None => builder,
+    }
+}
+
+/// One page of the notification inbox of a user, most recent first.
+pub struct Notifications {
+    pub user_id: Id,
+    pub page: PageQuery,
+    pub filter: NotificationFilter,
+    pub notifications: Vec<Notification>,
+    pub has_next: bool,
+}
+
+impl ResourceBuilder<CollectionResource<NotificationResource>> for Notifications {
+    fn build(
+        &self,
+        req: &HttpRequest,
+    ) -> Result<CollectionResource<NotificationResource>, anyhow::Error> {
+        let link = |page| ResourceLink::Notifications(self.user_id, page, self.filter.unread);
+        let builder = CollectionResourceBuilder::new(
+            self.notifications
+                .iter()
+                .map(notification_builder)
+                .collect(),
+        )
+        .link(Rel::Self_, link(self.page))
+        .link(
+            Rel::Named("read_all"),
+            ResourceLink::NotificationsRead(self.user_id),
+        );
+        let builder = match self.page.prev() {
+            Some(prev) => builder.link(Rel::Named("prev"), link(prev)),
+            None => builder,
+        };
+        match self.has_next {
+            true => builder.link(Rel::Named("next"), link(self.page.next())),
+            false => builder,
+        }
+        .build(req)
+    }
+}
@@ -0,0 +1,66 @@
+use serde::Deserialize;
+
+const DEFAULT_PER_PAGE: u32 = 20;
+const MAX_PER_PAGE: u32 = 100;
+
+/// `?page&per_page` query of paginated collections. Pages start at 1.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageQuery {
+    #[serde(default = "first_page")]
+    page: u32,
+    #[serde(default = "default_per_page")]
+    per_page: u32,
+}
+
+fn first_page() -> u32 {
+    1
+}
+
+fn default_per_page() -> u32 {
+    DEFAULT_PER_PAGE
+}
+
+impl Default for PageQuery {
+    fn default() -> Self {
+        Self {
+            page: first_page(),
+            per_page: default_per_page(),
+        }
+    }
+}
+
+impl PageQuery {
+    /// Clamps out of range values instead of rejecting them.
+    pub fn normalized(self) -> Self {
+        Self {
+            page: self.page.max(1),
+            per_page: self.per_page.clamp(1, MAX_PER_PAGE),
+        }
+    }
+
+    pub fn limit(&self) -> i64 {
+        i64::from(self.per_page)
+    }
+
+    pub fn offset(&self) -> i64 {
+        i64::from(self.page - 1) * self.limit()
+    }
+
+    pub fn next(&self) -> Self {
+        Self {
+            page: self.page + 1,
+            ..*self
+        }
+    }
+
+    pub fn prev(&self) -> Option<Self> {
+        (self.page > 1).then(|| Self {
+            page: self.page - 1,
+            ..*self
+        })
+    }
+
+    pub fn as_query(&self) -> String {
+        format!("page={}&per_page={}", self.page, self.per_page)
+    }
+}
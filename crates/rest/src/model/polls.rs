@@ -0,0 +1,60 @@
+use crate::{
+    links::{Rel, ResourceLink, SingleIdPath},
+    model::resource::{SingleResource, SingleResourceBuilder},
+    response::ResourceBuilder,
+};
+use actix_web::{web::Path, HttpRequest};
+use commons::{id::Id, time::DateTime};
+use serde::{Deserialize, Serialize};
+use storage::model::poll::{Poll, PollResult};
+
+pub type PollPath = Path<SingleIdPath>;
+
+#[derive(Deserialize, Debug)]
+pub struct OpenPollRequest {
+    pub closes_at: DateTime,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CastVoteRequest {
+    pub child_id: Id,
+}
+
+#[derive(Serialize)]
+pub struct PollResource {
+    closes_at: DateTime,
+    created_at: DateTime,
+    closed_at: Option<DateTime>,
+    /// Votes per child so far, most voted first.
+    results: Vec<PollResult>,
+}
+
+/// A poll with its current tally.
+pub struct PollWithResults(pub Poll, pub Vec<PollResult>);
+
+impl ResourceBuilder<SingleResource<PollResource>> for PollWithResults {
+    fn build(&self, req: &HttpRequest) -> Result<SingleResource<PollResource>, anyhow::Error> {
+        let poll = &self.0;
+        let builder = SingleResourceBuilder::new(PollResource {
+            closes_at: *poll.closes_at(),
+            created_at: *poll.created_at(),
+            closed_at: *poll.closed_at(),
+            results: self.1.clone(),
+        })
+        .link(Rel::Self_, ResourceLink::Poll(*poll.id()))
+        .link(Rel::Named("votes"), ResourceLink::Votes(*poll.id()))
+        .link(
+            Rel::Named("fragment"),
+            ResourceLink::Fragment(*poll.fragment_id()),
+        )
+        .link(Rel::Named("author"), ResourceLink::User(*poll.created_by()));
+
+        match poll.winner_id() {
+            Some(winner_id) => {
+                builder.link(Rel::Named("winner"), ResourceLink::Fragment(*winner_id))
+            }
+            None => builder,
+        }
+        .build(req)
+    }
+}
@@ -4,7 +4,7 @@ use actix_web::HttpRequest;
 use serde::Serialize;
 use url::Url;
 
-use crate::links::{Rel, ResourceLinks};
+use crate::links::{Rel, ResourceLink, ResourceLinks};
 
 pub struct SingleResourceBuilder<D> {
     data: Option<D>,
@@ -12,6 +12,20 @@ pub struct SingleResourceBuilder<D> {
 }
 
 impl<D> SingleResourceBuilder<D> {
+    pub fn new(data: D) -> Self {
+        Self {
+            data: Some(data),
+            links: ResourceLinks::default(),
+        }
+    }
+
+    pub fn link(self, rel: Rel, link: ResourceLink) -> Self {
+        Self {
+            data: self.data,
+            links: self.links.add(rel, link),
+        }
+    }
+
     pub fn build(self, req: &HttpRequest) -> Result<SingleResource<D>, anyhow::Error> {
         Ok(SingleResource {
             data: self.data,
@@ -26,7 +40,21 @@ pub struct CollectionResourceBuilder<D> {
 }
 
 impl<D> CollectionResourceBuilder<D> {
-    fn build(self, req: &HttpRequest) -> Result<CollectionResource<D>, anyhow::Error> {
+    pub fn new(data: Vec<SingleResourceBuilder<D>>) -> Self {
+        Self {
+            data,
+            links: ResourceLinks::default(),
+        }
+    }
+
+    pub fn link(self, rel: Rel, link: ResourceLink) -> Self {
+        Self {
+            data: self.data,
+            links: self.links.add(rel, link),
+        }
+    }
+
+    pub fn build(self, req: &HttpRequest) -> Result<CollectionResource<D>, anyhow::Error> {
         Ok(CollectionResource {
             data: self
                 .data
@@ -1,17 +1,200 @@
-use commons::review::Comment;
-use storage::model::review::ReviewAction;
+use crate::{
+    links::{Rel, ResourceLink},
+    model::{
+        fragments::FragmentResource,
+        resource::{SingleResource, SingleResourceBuilder},
+        revisions::RevisionResource,
+    },
+    response::ResourceBuilder,
+};
+use actix_web::{web::Path, HttpRequest};
+use commons::{
+    diff::{diff_words, Change},
+    fragment::Content,
+    id::Id,
+    review::Comment,
+    time::DateTime,
+};
+use cqrs::command_bus::command::review_fork::SuggestedEdit;
+use serde::Serialize;
+use storage::model::{
+    fragment::Fragment,
+    review::{Review, ReviewAction},
+    review_quorum::ReviewQuorum,
+    review_sla::{ReviewSla, SlaOutcome},
+    revision::Revision,
+};
+
+pub type ReviewPath = Path<(Id, Id)>;
 
 #[derive(Debug, serde::Deserialize)]
 pub struct CreateReviewRequest {
-    action: String,
+    action: ReviewAction,
+    comment: Option<String>,
+    #[serde(default)]
+    suggestions: Vec<SuggestionRequest>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct SuggestionRequest {
+    start: usize,
+    end: usize,
+    replacement: String,
 }
 
 impl CreateReviewRequest {
     pub fn action(&self) -> ReviewAction {
-        todo!()
+        self.action
     }
 
     pub fn comment(&self) -> Option<Comment> {
-        todo!()
+        self.comment.clone().map(Comment::from)
+    }
+
+    pub fn suggestions(&self) -> Vec<SuggestedEdit> {
+        self.suggestions
+            .iter()
+            .map(|s| SuggestedEdit {
+                start: s.start,
+                end: s.end,
+                replacement: Content::from(s.replacement.clone()),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct SetReviewQuorumRequest {
+    pub approvals: i32,
+    #[serde(default)]
+    pub veto: bool,
+}
+
+#[derive(Serialize)]
+pub struct ReviewQuorumResource {
+    approvals: i32,
+    veto: bool,
+}
+
+impl From<&ReviewQuorum> for ReviewQuorumResource {
+    fn from(value: &ReviewQuorum) -> Self {
+        Self {
+            approvals: *value.approvals(),
+            veto: *value.veto(),
+        }
+    }
+}
+
+impl ResourceBuilder<SingleResource<ReviewQuorumResource>> for ReviewQuorum {
+    fn build(
+        &self,
+        req: &HttpRequest,
+    ) -> Result<SingleResource<ReviewQuorumResource>, anyhow::Error> {
+        SingleResourceBuilder::new(ReviewQuorumResource::from(self))
+            .link(Rel::Self_, ResourceLink::ReviewQuorum(*self.root_id()))
+            .link(
+                Rel::Named("fragment"),
+                ResourceLink::Fragment(*self.root_id()),
+            )
+            .build(req)
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct SetReviewSlaRequest {
+    pub window_hours: i32,
+    pub outcome: SlaOutcome,
+}
+
+#[derive(Serialize)]
+pub struct ReviewSlaResource {
+    window_hours: i32,
+    outcome: SlaOutcome,
+    updated_at: DateTime,
+}
+
+impl ResourceBuilder<SingleResource<ReviewSlaResource>> for ReviewSla {
+    fn build(&self, req: &HttpRequest) -> Result<SingleResource<ReviewSlaResource>, anyhow::Error> {
+        SingleResourceBuilder::new(ReviewSlaResource {
+            window_hours: *self.window_hours(),
+            outcome: *self.outcome(),
+            updated_at: *self.updated_at(),
+        })
+        .link(Rel::Self_, ResourceLink::ReviewSla(*self.root_id()))
+        .link(
+            Rel::Named("fragment"),
+            ResourceLink::Fragment(*self.root_id()),
+        )
+        .build(req)
+    }
+}
+
+#[derive(Serialize)]
+pub struct ReviewResource {
+    id: Id,
+    /// Missing for reviews recorded automatically.
+    reviewer_id: Option<Id>,
+    action: ReviewAction,
+    comment: Option<Comment>,
+    created_at: DateTime,
+}
+
+impl From<&Review> for ReviewResource {
+    fn from(value: &Review) -> Self {
+        Self {
+            id: *value.id(),
+            reviewer_id: *value.reviewer_id(),
+            action: *value.action(),
+            comment: value.comment().clone(),
+            created_at: *value.created_at(),
+        }
+    }
+}
+
+/// Everything a reviewer needs to decide on a submitted fork.
+pub struct ReviewContext {
+    pub fork: Fragment,
+    pub parent: Fragment,
+    /// Fork revisions written after the last review.
+    pub revisions: Vec<Revision>,
+    pub reviews: Vec<Review>,
+    pub quorum: ReviewQuorum,
+}
+
+#[derive(Serialize)]
+pub struct ReviewContextResource {
+    parent: FragmentResource,
+    fork: FragmentResource,
+    /// Missing when the fork differs too much from its parent to be diffed.
+    changes: Option<Vec<Change>>,
+    revisions: Vec<RevisionResource>,
+    reviews: Vec<ReviewResource>,
+    quorum: ReviewQuorumResource,
+}
+
+impl ResourceBuilder<SingleResource<ReviewContextResource>> for ReviewContext {
+    fn build(
+        &self,
+        req: &HttpRequest,
+    ) -> Result<SingleResource<ReviewContextResource>, anyhow::Error> {
+        SingleResourceBuilder::new(ReviewContextResource {
+            parent: FragmentResource::from(&self.parent),
+            fork: FragmentResource::from(&self.fork),
+            changes: diff_words(self.parent.content().as_ref(), self.fork.content().as_ref()).ok(),
+            revisions: self.revisions.iter().map(RevisionResource::from).collect(),
+            reviews: self.reviews.iter().map(ReviewResource::from).collect(),
+            quorum: ReviewQuorumResource::from(&self.quorum),
+        })
+        .link(Rel::Self_, ResourceLink::ReviewContext(*self.fork.id()))
+        .link(Rel::Named("fork"), ResourceLink::Fragment(*self.fork.id()))
+        .link(
+            Rel::Named("parent"),
+            ResourceLink::Fragment(*self.parent.id()),
+        )
+        .link(
+            Rel::Named("review_quorum"),
+            ResourceLink::ReviewQuorum(*self.quorum.root_id()),
+        )
+        .build(req)
     }
 }
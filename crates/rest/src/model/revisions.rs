@@ -0,0 +1,113 @@
+use crate::{
+    links::{Rel, ResourceLink},
+    model::resource::{
+        CollectionResource, CollectionResourceBuilder, SingleResource, SingleResourceBuilder,
+    },
+    response::ResourceBuilder,
+};
+use actix_web::{web::Path, HttpRequest};
+use commons::{
+    diff::{diff_words, Change, DiffTooLarge},
+    fragment::Content,
+    id::Id,
+    time::DateTime,
+};
+use serde::{Deserialize, Serialize};
+use storage::model::revision::Revision;
+
+pub type RevisionPath = Path<(Id, i32)>;
+
+#[derive(Deserialize, Debug)]
+pub struct DiffQuery {
+    pub from: i32,
+    pub to: i32,
+}
+
+#[derive(Serialize)]
+pub struct RevisionResource {
+    number: i32,
+    content: Content,
+    created_at: DateTime,
+}
+
+impl From<&Revision> for RevisionResource {
+    fn from(value: &Revision) -> Self {
+        Self {
+            number: *value.number(),
+            content: value.content().clone(),
+            created_at: *value.created_at(),
+        }
+    }
+}
+
+fn revision_builder(revision: &Revision) -> SingleResourceBuilder<RevisionResource> {
+    SingleResourceBuilder::new(RevisionResource::from(revision))
+        .link(
+            Rel::Self_,
+            ResourceLink::Revision(*revision.fragment_id(), *revision.number()),
+        )
+        .link(
+            Rel::Named("fragment"),
+            ResourceLink::Fragment(*revision.fragment_id()),
+        )
+        .link(
+            Rel::Named("author"),
+            ResourceLink::User(*revision.author_id()),
+        )
+}
+
+impl ResourceBuilder<SingleResource<RevisionResource>> for Revision {
+    fn build(&self, req: &HttpRequest) -> Result<SingleResource<RevisionResource>, anyhow::Error> {
+        revision_builder(self).build(req)
+    }
+}
+
+/// Revision history of a fragment.
+pub struct Revisions(pub Id, pub Vec<Revision>);
+
+impl ResourceBuilder<CollectionResource<RevisionResource>> for Revisions {
+    fn build(
+        &self,
+        req: &HttpRequest,
+    ) -> Result<CollectionResource<RevisionResource>, anyhow::Error> {
+        CollectionResourceBuilder::new(self.1.iter().map(revision_builder).collect())
+            .link(Rel::Self_, ResourceLink::Revisions(self.0))
+            .link(Rel::Named("fragment"), ResourceLink::Fragment(self.0))
+            .build(req)
+    }
+}
+
+#[derive(Serialize, Clone)]
+pub struct DiffResource {
+    #[serde(skip)]
+    fragment_id: Id,
+    from: i32,
+    to: i32,
+    changes: Vec<Change>,
+}
+
+impl DiffResource {
+    pub fn new(from: &Revision, to: &Revision) -> Result<Self, DiffTooLarge> {
+        Ok(Self {
+            fragment_id: *from.fragment_id(),
+            from: *from.number(),
+            to: *to.number(),
+            changes: diff_words(from.content().as_ref(), to.content().as_ref())?,
+        })
+    }
+}
+
+impl ResourceBuilder<SingleResource<DiffResource>> for DiffResource {
+    fn build(&self, req: &HttpRequest) -> Result<SingleResource<DiffResource>, anyhow::Error> {
+        SingleResourceBuilder::new(self.clone())
+            .link(
+                Rel::Named("from"),
+                ResourceLink::Revision(self.fragment_id, self.from),
+            )
+            .link(
+                Rel::Named("to"),
+                ResourceLink::Revision(self.fragment_id, self.to),
+            )
+            .build(req)
+    }
+}
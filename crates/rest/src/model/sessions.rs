@@ -0,0 +1,31 @@
+use crate::response::ResourceBuilder;
+use actix_web::HttpRequest;
+use commons::{auth::SessionToken, time::DateTime};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct CreateSessionRequest {
+    pub login: String,
+    pub password: String,
+}
+
+#[derive(Serialize, Clone)]
+pub struct SessionResource {
+    token: String,
+    expires_at: DateTime,
+}
+
+impl SessionResource {
+    pub fn new(token: &SessionToken, expires_at: DateTime) -> Self {
+        Self {
+            token: token.as_str().to_owned(),
+            expires_at,
+        }
+    }
+}
+
+impl ResourceBuilder<SessionResource> for SessionResource {
+    fn build(&self, _: &HttpRequest) -> Result<SessionResource, anyhow::Error> {
+        Ok(self.clone())
+    }
+}
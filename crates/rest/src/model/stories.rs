@@ -0,0 +1,115 @@
+use crate::{
+    links::{Rel, ResourceLink, SingleIdPath},
+    model::resource::{
+        CollectionResource, CollectionResourceBuilder, SingleResource, SingleResourceBuilder,
+    },
+    response::ResourceBuilder,
+};
+use actix_web::{web::Path, HttpRequest};
+use commons::{id::Id, time::DateTime};
+use serde::{Deserialize, Serialize};
+use storage::model::story::{MaturityRating, Story, StoryFilter, StoryFilterBuilder};
+
+pub type StoryPath = Path<SingleIdPath>;
+
+#[derive(Deserialize, Debug)]
+pub struct CreateStoryRequest {
+    pub fragment_id: Id,
+    pub title: String,
+    pub synopsis: Option<String>,
+    #[serde(default)]
+    pub genres: Vec<String>,
+    pub language: String,
+    #[serde(default)]
+    pub maturity: MaturityRating,
+    pub cover_url: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UpdateStoryRequest {
+    pub title: Option<String>,
+    pub synopsis: Option<String>,
+    pub genres: Option<Vec<String>>,
+    pub language: Option<String>,
+    pub maturity: Option<MaturityRating>,
+    /// A blank cover clears it.
+    pub cover_url: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct StoriesQuery {
+    genre: Option<String>,
+    language: Option<String>,
+}
+
+impl From<StoriesQuery> for StoryFilter {
+    fn from(value: StoriesQuery) -> Self {
+        StoryFilterBuilder::default()
+            .genre(value.genre.map(|g| g.trim().to_lowercase()))
+            .language(value.language.map(|l| l.trim().to_lowercase()))
+            .build()
+            .unwrap()
+    }
+}
+
+#[derive(Serialize)]
+pub struct StoryResource {
+    title: String,
+    synopsis: Option<String>,
+    genres: Vec<String>,
+    language: String,
+    maturity: MaturityRating,
+    cover_url: Option<String>,
+    created_at: DateTime,
+    last_modified_at: DateTime,
+}
+
+impl From<&Story> for StoryResource {
+    fn from(value: &Story) -> Self {
+        Self {
+            title: value.title().clone(),
+            synopsis: value.synopsis().clone(),
+            genres: value.genres().clone(),
+            language: value.language().clone(),
+            maturity: *value.maturity(),
+            cover_url: value.cover_url().clone(),
+            created_at: *value.created_at(),
+            last_modified_at: *value.last_modified_at(),
+        }
+    }
+}
+
+fn story_builder(story: &Story) -> SingleResourceBuilder<StoryResource> {
+    SingleResourceBuilder::new(StoryResource::from(story))
+        .link(Rel::Self_, ResourceLink::Story(*story.id()))
+        .link(
+            Rel::Named("fragment"),
+            ResourceLink::Fragment(*story.fragment_id()),
+        )
+        .link(Rel::Named("author"), ResourceLink::User(*story.author_id()))
+        .link(
+            Rel::Named("canonical_path"),
+            ResourceLink::CanonicalPath(*story.fragment_id()),
+        )
+        .link(
+            Rel::Named("maintainers"),
+            ResourceLink::Maintainers(*story.fragment_id()),
+        )
+}
+
+impl ResourceBuilder<SingleResource<StoryResource>> for Story {
+    fn build(&self, req: &HttpRequest) -> Result<SingleResource<StoryResource>, anyhow::Error> {
+        story_builder(self).build(req)
+    }
+}
+
+/// Published stories matching a filter.
+pub struct Stories(pub Vec<Story>);
+
+impl ResourceBuilder<CollectionResource<StoryResource>> for Stories {
+    fn build(&self, req: &HttpRequest) -> Result<CollectionResource<StoryResource>, anyhow::Error> {
+        CollectionResourceBuilder::new(self.0.iter().map(story_builder).collect())
+            .link(Rel::Self_, ResourceLink::Stories)
+            .build(req)
+    }
+}
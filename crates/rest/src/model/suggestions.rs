@@ -0,0 +1,75 @@
+use crate::{
+    links::{Rel, ResourceLink},
+    model::resource::{CollectionResource, CollectionResourceBuilder, SingleResourceBuilder},
+    response::ResourceBuilder,
+};
+use actix_web::{web::Path, HttpRequest};
+use commons::{fragment::Content, id::Id, time::DateTime};
+use serde::Serialize;
+use storage::model::suggestion::{Suggestion, SuggestionStatus};
+
+pub type SuggestionPath = Path<(Id, Id)>;
+
+#[derive(Serialize)]
+pub struct SuggestionResource {
+    id: Id,
+    review_id: Id,
+    start: i32,
+    end: i32,
+    original: Content,
+    replacement: Content,
+    status: SuggestionStatus,
+    created_at: DateTime,
+    resolved_at: Option<DateTime>,
+}
+
+impl From<&Suggestion> for SuggestionResource {
+    fn from(value: &Suggestion) -> Self {
+        Self {
+            id: *value.id(),
+            review_id: *value.review_id(),
+            start: *value.range_start(),
+            end: *value.range_end(),
+            original: value.original().clone(),
+            replacement: value.replacement().clone(),
+            status: *value.status(),
+            created_at: *value.created_at(),
+            resolved_at: *value.resolved_at(),
+        }
+    }
+}
+
+fn suggestion_builder(suggestion: &Suggestion) -> SingleResourceBuilder<SuggestionResource> {
+    let builder = SingleResourceBuilder::new(SuggestionResource::from(suggestion)).link(
+        Rel::Named("review"),
+        ResourceLink::Review(*suggestion.fragment_id(), *suggestion.review_id()),
+    );
+    if !suggestion.is_pending() {
+        return builder;
+    }
+
+    builder
+        .link(
+            Rel::Named("accept"),
+            ResourceLink::SuggestionAcceptance(*suggestion.fragment_id(), *suggestion.id()),
+        )
+        .link(
+            Rel::Named("reject"),
+            ResourceLink::SuggestionRejection(*suggestion.fragment_id(), *suggestion.id()),
+        )
+}
+
+/// Suggestions made on a fork across all of its reviews.
+pub struct Suggestions(pub Id, pub Vec<Suggestion>);
+
+impl ResourceBuilder<CollectionResource<SuggestionResource>> for Suggestions {
+    fn build(
+        &self,
+        req: &HttpRequest,
+    ) -> Result<CollectionResource<SuggestionResource>, anyhow::Error> {
+        CollectionResourceBuilder::new(self.1.iter().map(suggestion_builder).collect())
+            .link(Rel::Self_, ResourceLink::Suggestions(self.0))
+            .link(Rel::Named("fragment"), ResourceLink::Fragment(self.0))
+            .build(req)
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{
+    links::{Rel, ResourceLink},
+    model::{
+        fragments::{fragment_builder, FragmentResource},
+        pagination::PageQuery,
+        resource::{CollectionResource, CollectionResourceBuilder, SingleResourceBuilder},
+    },
+    response::ResourceBuilder,
+};
+use actix_web::{web::Path, HttpRequest};
+use commons::{id::Id, tag::Tag, time::DateTime};
+use serde::{Deserialize, Serialize};
+use storage::model::{
+    fragment::Fragment,
+    tag::{TagCount, TagTarget, Tagging},
+};
+
+pub type TagPath = Path<String>;
+pub type TargetTagPath = Path<(Id, String)>;
+
+#[derive(Deserialize, Debug)]
+pub struct TagRequest {
+    pub tag: String,
+}
+
+#[derive(Serialize)]
+pub struct TagResource {
+    tag: Tag,
+    created_at: DateTime,
+}
+
+fn tag_builder(tagging: &Tagging) -> SingleResourceBuilder<TagResource> {
+    SingleResourceBuilder::new(TagResource {
+        tag: tagging.tag().clone(),
+        created_at: *tagging.created_at(),
+    })
+    .link(
+        Rel::Named("fragments"),
+        ResourceLink::TaggedFragments(tagging.tag().clone(), PageQuery::default()),
+    )
+}
+
+/// Tags of a fragment or a story.
+pub struct TargetTags(pub TagTarget, pub Id, pub Vec<Tagging>);
+
+impl ResourceBuilder<CollectionResource<TagResource>> for TargetTags {
+    fn build(&self, req: &HttpRequest) -> Result<CollectionResource<TagResource>, anyhow::Error> {
+        let builder = CollectionResourceBuilder::new(self.2.iter().map(tag_builder).collect());
+        match self.0 {
+            TagTarget::Fragment => builder
+                .link(Rel::Self_, ResourceLink::FragmentTags(self.1))
+                .link(Rel::Named("fragment"), ResourceLink::Fragment(self.1)),
+            TagTarget::Story => builder
+                .link(Rel::Self_, ResourceLink::StoryTags(self.1))
+                .link(Rel::Named("story"), ResourceLink::Story(self.1)),
+        }
+        .build(req)
+    }
+}
+
+#[derive(Serialize)]
+pub struct TagCountResource {
+    tag: Tag,
+    fragments: i64,
+    stories: i64,
+}
+
+fn tag_count_builder(count: &TagCount) -> SingleResourceBuilder<TagCountResource> {
+    SingleResourceBuilder::new(TagCountResource {
+        tag: count.tag().clone(),
+        fragments: *count.fragments(),
+        stories: *count.stories(),
+    })
+    .link(
+        Rel::Named("fragments"),
+        ResourceLink::TaggedFragments(count.tag().clone(), PageQuery::default()),
+    )
+}
+
+/// Tags in use on published content, with their counts.
+pub struct TagCounts(pub Vec<TagCount>);
+
+impl ResourceBuilder<CollectionResource<TagCountResource>> for TagCounts {
+    fn build(
+        &self,
+        req: &HttpRequest,
+    ) -> Result<CollectionResource<TagCountResource>, anyhow::Error> {
+        CollectionResourceBuilder::new(self.0.iter().map(tag_count_builder).collect())
+            .link(Rel::Self_, ResourceLink::Tags)
+            .build(req)
+    }
+}
+
+/// One page of the published fragments carrying a tag.
+pub struct TaggedFragments {
+    pub tag: Tag,
+    pub page: PageQuery,
+    pub fragments: Vec<Fragment>,
+    pub has_next: bool,
+}
+
+impl ResourceBuilder<CollectionResource<FragmentResource>> for TaggedFragments {
+    fn build(
+        &self,
+        req: &HttpRequest,
+    ) -> Result<CollectionResource<FragmentResource>, anyhow::Error> {
+        let builder =
+            CollectionResourceBuilder::new(self.fragments.iter().map(fragment_builder).collect())
+                .link(
+                    Rel::Self_,
+                    ResourceLink::TaggedFragments(self.tag.clone(), self.page),
+                );
+        let builder = match self.page.prev() {
+            Some(prev) => builder.link(
+                Rel::Named("prev"),
+                ResourceLink::TaggedFragments(self.tag.clone(), prev),
+            ),
+            None => builder,
+        };
+        match self.has_next {
+            true => builder.link(
+                Rel::Named("next"),
+                ResourceLink::TaggedFragments(self.tag.clone(), self.page.next()),
+            ),
+            false => builder,
+        }
+        .build(req)
+    }
+}
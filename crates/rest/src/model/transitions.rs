@@ -0,0 +1,54 @@
+use crate::{
+    links::{Rel, ResourceLink},
+    model::resource::{CollectionResource, CollectionResourceBuilder, SingleResourceBuilder},
+    response::ResourceBuilder,
+};
+use actix_web::HttpRequest;
+use commons::{id::Id, time::DateTime};
+use serde::Serialize;
+use storage::model::{
+    fragment::{FragmentState, Transition},
+    state_transition::StateTransition,
+};
+
+#[derive(Serialize)]
+pub struct TransitionResource {
+    transition: Transition,
+    from: FragmentState,
+    to: FragmentState,
+    created_at: DateTime,
+}
+
+impl From<&StateTransition> for TransitionResource {
+    fn from(value: &StateTransition) -> Self {
+        Self {
+            transition: *value.transition(),
+            from: *value.from_state(),
+            to: *value.to_state(),
+            created_at: *value.created_at(),
+        }
+    }
+}
+
+fn transition_builder(transition: &StateTransition) -> SingleResourceBuilder<TransitionResource> {
+    let builder = SingleResourceBuilder::new(TransitionResource::from(transition));
+    match transition.actor_id() {
+        Some(actor_id) => builder.link(Rel::Named("actor"), ResourceLink::User(*actor_id)),
+        None => builder,
+    }
+}
+
+/// Lifecycle history of a fragment.
+pub struct Transitions(pub Id, pub Vec<StateTransition>);
+
+impl ResourceBuilder<CollectionResource<TransitionResource>> for Transitions {
+    fn build(
+        &self,
+        req: &HttpRequest,
+    ) -> Result<CollectionResource<TransitionResource>, anyhow::Error> {
+        CollectionResourceBuilder::new(self.1.iter().map(transition_builder).collect())
+            .link(Rel::Self_, ResourceLink::Transitions(self.0))
+            .link(Rel::Named("fragment"), ResourceLink::Fragment(self.0))
+            .build(req)
+    }
+}
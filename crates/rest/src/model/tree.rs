@@ -0,0 +1,104 @@
+use crate::{
+    links::{Rel, ResourceLink},
+    model::{
+        fragments::{fragment_builder, FragmentResource},
+        resource::{CollectionResource, CollectionResourceBuilder, SingleResourceBuilder},
+    },
+    response::ResourceBuilder,
+};
+use actix_web::HttpRequest;
+use commons::id::Id;
+use serde::{Deserialize, Serialize};
+use storage::model::{canonical_branch::CanonicalBranch, fragment::Fragment};
+
+#[derive(Deserialize, Debug)]
+pub struct SetCanonicalBranchRequest {
+    pub child_id: Id,
+}
+
+#[derive(Serialize)]
+pub struct TreeNodeResource {
+    #[serde(flatten)]
+    fragment: FragmentResource,
+    /// Whether the fragment is the canonical child of its parent.
+    canonical: bool,
+}
+
+/// Published fragments below a fragment, level by level, with the canonical branches
+/// chosen among them.
+pub struct FragmentTree {
+    pub fragment: Fragment,
+    pub descendants: Vec<Fragment>,
+    pub branches: Vec<CanonicalBranch>,
+}
+
+impl FragmentTree {
+    fn node_builder(&self, fragment: &Fragment) -> SingleResourceBuilder<TreeNodeResource> {
+        let canonical = self.branches.iter().any(|branch| {
+            Some(*branch.fragment_id()) == *fragment.parent_id()
+                && branch.child_id() == fragment.id()
+        });
+        let builder = SingleResourceBuilder::new(TreeNodeResource {
+            fragment: FragmentResource::from(fragment),
+            canonical,
+        })
+        .link(Rel::Self_, ResourceLink::Fragment(*fragment.id()))
+        .link(
+            Rel::Named("author"),
+            ResourceLink::User(*fragment.author_id()),
+        );
+        let builder = match fragment.parent_id() {
+            Some(parent_id) => {
+                builder.link(Rel::Named("parent"), ResourceLink::Fragment(*parent_id))
+            }
+            None => builder,
+        };
+        match self
+            .branches
+            .iter()
+            .find(|branch| branch.fragment_id() == fragment.id())
+        {
+            Some(branch) => builder.link(
+                Rel::Named("canonical_child"),
+                ResourceLink::Fragment(*branch.child_id()),
+            ),
+            None => builder,
+        }
+    }
+}
+
+impl ResourceBuilder<CollectionResource<TreeNodeResource>> for FragmentTree {
+    fn build(
+        &self,
+        req: &HttpRequest,
+    ) -> Result<CollectionResource<TreeNodeResource>, anyhow::Error> {
+        let id = *self.fragment.id();
+        CollectionResourceBuilder::new(
+            std::iter::once(&self.fragment)
+                .chain(&self.descendants)
+                .map(|fragment| self.node_builder(fragment))
+                .collect(),
+        )
+        .link(Rel::Self_, ResourceLink::Tree(id))
+        .link(
+            Rel::Named("canonical_path"),
+            ResourceLink::CanonicalPath(id),
+        )
+        .build(req)
+    }
+}
+
+/// Default route through the tree, following canonical branches from a fragment.
+pub struct CanonicalPath(pub Id, pub Vec<Fragment>);
+
+impl ResourceBuilder<CollectionResource<FragmentResource>> for CanonicalPath {
+    fn build(
+        &self,
+        req: &HttpRequest,
+    ) -> Result<CollectionResource<FragmentResource>, anyhow::Error> {
+        CollectionResourceBuilder::new(self.1.iter().map(fragment_builder).collect())
+            .link(Rel::Self_, ResourceLink::CanonicalPath(self.0))
+            .link(Rel::Named("tree"), ResourceLink::Tree(self.0))
+            .build(req)
+    }
+}
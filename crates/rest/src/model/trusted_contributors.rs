@@ -0,0 +1,56 @@
+use crate::{
+    links::{Rel, ResourceLink},
+    model::resource::{CollectionResource, CollectionResourceBuilder, SingleResourceBuilder},
+    response::ResourceBuilder,
+};
+use actix_web::{web::Path, HttpRequest};
+use commons::{id::Id, time::DateTime};
+use serde::{Deserialize, Serialize};
+use storage::model::trusted_contributor::TrustedContributor;
+
+pub type TrustedContributorPath = Path<(Id, Id)>;
+
+#[derive(Deserialize, Debug)]
+pub struct TrustContributorRequest {
+    pub contributor_id: Id,
+    #[serde(default)]
+    pub auto_publish: bool,
+}
+
+#[derive(Serialize)]
+pub struct TrustedContributorResource {
+    auto_publish: bool,
+    created_at: DateTime,
+}
+
+fn trusted_contributor_builder(
+    trusted: &TrustedContributor,
+) -> SingleResourceBuilder<TrustedContributorResource> {
+    SingleResourceBuilder::new(TrustedContributorResource {
+        auto_publish: *trusted.auto_publish(),
+        created_at: *trusted.created_at(),
+    })
+    .link(
+        Rel::Self_,
+        ResourceLink::TrustedContributor(*trusted.author_id(), *trusted.contributor_id()),
+    )
+    .link(
+        Rel::Named("contributor"),
+        ResourceLink::User(*trusted.contributor_id()),
+    )
+}
+
+/// Contributors whose forks the given author approves without review.
+pub struct TrustedContributors(pub Id, pub Vec<TrustedContributor>);
+
+impl ResourceBuilder<CollectionResource<TrustedContributorResource>> for TrustedContributors {
+    fn build(
+        &self,
+        req: &HttpRequest,
+    ) -> Result<CollectionResource<TrustedContributorResource>, anyhow::Error> {
+        CollectionResourceBuilder::new(self.1.iter().map(trusted_contributor_builder).collect())
+            .link(Rel::Self_, ResourceLink::TrustedContributors(self.0))
+            .link(Rel::Named("author"), ResourceLink::User(self.0))
+            .build(req)
+    }
+}
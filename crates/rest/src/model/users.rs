@@ -0,0 +1,60 @@
+use crate::{
+    links::{Rel, ResourceLink},
+    model::resource::{SingleResource, SingleResourceBuilder},
+    response::ResourceBuilder,
+};
+use actix_web::HttpRequest;
+use commons::{actor::Role, time::DateTime};
+use serde::{Deserialize, Serialize};
+use storage::model::user::User;
+use url::Url;
+
+#[derive(Deserialize)]
+pub struct RegisterUserRequest {
+    pub username: String,
+    pub password: String,
+    pub display_name: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UpdateProfileRequest {
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub avatar_url: Option<Url>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AssignRoleRequest {
+    pub role: Role,
+}
+
+#[derive(Serialize)]
+pub struct UserResource {
+    username: String,
+    role: Role,
+    display_name: Option<String>,
+    bio: Option<String>,
+    avatar_url: Option<String>,
+    created_at: DateTime,
+}
+
+impl From<&User> for UserResource {
+    fn from(value: &User) -> Self {
+        Self {
+            username: value.username().clone(),
+            role: *value.role(),
+            display_name: value.display_name().clone(),
+            bio: value.bio().clone(),
+            avatar_url: value.avatar_url().clone(),
+            created_at: *value.created_at(),
+        }
+    }
+}
+
+impl ResourceBuilder<SingleResource<UserResource>> for User {
+    fn build(&self, req: &HttpRequest) -> Result<SingleResource<UserResource>, anyhow::Error> {
+        SingleResourceBuilder::new(UserResource::from(self))
+            .link(Rel::Self_, ResourceLink::User(*self.id()))
+            .build(req)
+    }
+}
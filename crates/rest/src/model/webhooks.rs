@@ -0,0 +1,196 @@
+use crate::{
+    links::{Rel, ResourceLink, SingleIdPath},
+    model::{
+        pagination::PageQuery,
+        resource::{
+            CollectionResource, CollectionResourceBuilder, SingleResource, SingleResourceBuilder,
+        },
+    },
+    response::ResourceBuilder,
+};
+use actix_web::{web::Path, HttpRequest};
+use commons::{auth::WebhookSecret, events::EventType, id::Id, time::DateTime};
+use serde::{Deserialize, Serialize};
+use storage::model::webhook::{Webhook, WebhookAttempt, WebhookDelivery, WebhookDeliveryState};
+
+pub type WebhookPath = Path<SingleIdPath>;
+pub type WebhookDeliveryPath = Path<(Id, Id)>;
+
+#[derive(Deserialize, Debug)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    pub secret: WebhookSecret,
+    #[serde(default)]
+    pub event_types: Vec<EventType>,
+    pub story_id: Option<Id>,
+}
+
+/// Webhook as shown to its owner, without its secret.
+#[derive(Serialize)]
+pub struct WebhookResource {
+    id: Id,
+    url: String,
+    event_types: Vec<EventType>,
+    story_id: Option<Id>,
+    enabled: bool,
+    consecutive_failures: i32,
+    created_at: DateTime,
+    updated_at: DateTime,
+}
+
+fn webhook_builder(webhook: &Webhook) -> SingleResourceBuilder<WebhookResource> {
+    let builder = SingleResourceBuilder::new(WebhookResource {
+        id: *webhook.id(),
+        url: webhook.url().clone(),
+        event_types: webhook.event_types().clone(),
+        story_id: *webhook.story_id(),
+        enabled: *webhook.enabled(),
+        consecutive_failures: *webhook.consecutive_failures(),
+        created_at: *webhook.created_at(),
+        updated_at: *webhook.updated_at(),
+    })
+    .link(Rel::Self_, ResourceLink::Webhook(*webhook.id()))
+    .link(
+        Rel::Named("deliveries"),
+        ResourceLink::WebhookDeliveries(*webhook.id(), PageQuery::default()),
+    )
+    .link(Rel::Named("owner"), ResourceLink::User(*webhook.owner_id()));
+    let builder = match webhook.story_id() {
+        Some(story_id) => builder.link(Rel::Named("story"), ResourceLink::Story(*story_id)),
+        None => builder,
+    };
+    match webhook.enabled() {
+        true => builder,
+        false => builder.link(
+            Rel::Named("enable"),
+            ResourceLink::WebhookEnable(*webhook.id()),
+        ),
+    }
+}
+
+impl ResourceBuilder<SingleResource<WebhookResource>> for Webhook {
+    fn build(&self, req: &HttpRequest) -> Result<SingleResource<WebhookResource>, anyhow::Error> {
+        webhook_builder(self).build(req)
+    }
+}
+
+/// Webhooks owned by a user.
+pub struct Webhooks(pub Vec<Webhook>);
+
+impl ResourceBuilder<CollectionResource<WebhookResource>> for Webhooks {
+    fn build(
+        &self,
+        req: &HttpRequest,
+    ) -> Result<CollectionResource<WebhookResource>, anyhow::Error> {
+        CollectionResourceBuilder::new(self.0.iter().map(webhook_builder).collect())
+            .link(Rel::Self_, ResourceLink::Webhooks)
+            .build(req)
+    }
+}
+
+#[derive(Serialize)]
+pub struct WebhookAttemptResource {
+    status_code: Option<i32>,
+    error: Option<String>,
+    attempted_at: DateTime,
+}
+
+impl From<&WebhookAttempt> for WebhookAttemptResource {
+    fn from(value: &WebhookAttempt) -> Self {
+        Self {
+            status_code: *value.status_code(),
+            error: value.error().clone(),
+            attempted_at: *value.attempted_at(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct WebhookDeliveryResource {
+    id: Id,
+    event_id: Id,
+    event_type: EventType,
+    state: WebhookDeliveryState,
+    attempts: i32,
+    next_attempt_at: Option<DateTime>,
+    created_at: DateTime,
+    completed_at: Option<DateTime>,
+    /// Only listed on the delivery itself.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attempt_log: Option<Vec<WebhookAttemptResource>>,
+}
+
+fn delivery_builder(
+    delivery: &WebhookDelivery,
+    attempts: Option<&[WebhookAttempt]>,
+) -> SingleResourceBuilder<WebhookDeliveryResource> {
+    SingleResourceBuilder::new(WebhookDeliveryResource {
+        id: *delivery.id(),
+        event_id: *delivery.event_id(),
+        event_type: *delivery.event_type(),
+        state: *delivery.state(),
+        attempts: *delivery.attempts(),
+        next_attempt_at: (*delivery.state() == WebhookDeliveryState::Pending)
+            .then_some(*delivery.next_attempt_at()),
+        created_at: *delivery.created_at(),
+        completed_at: *delivery.completed_at(),
+        attempt_log: attempts.map(|attempts| attempts.iter().map(Into::into).collect()),
+    })
+    .link(
+        Rel::Self_,
+        ResourceLink::WebhookDelivery(*delivery.webhook_id(), *delivery.id()),
+    )
+    .link(
+        Rel::Named("webhook"),
+        ResourceLink::Webhook(*delivery.webhook_id()),
+    )
+}
+
+/// A delivery along with every attempt made for it.
+pub struct WebhookDeliveryWithAttempts(pub WebhookDelivery, pub Vec<WebhookAttempt>);
+
+impl ResourceBuilder<SingleResource<WebhookDeliveryResource>> for WebhookDeliveryWithAttempts {
+    fn build(
+        &self,
+        req: &HttpRequest,
+    ) -> Result<SingleResource<WebhookDeliveryResource>, anyhow::Error> {
+        delivery_builder(&self.0, Some(&self.1)).build(req)
+    }
+}
+
+/// One page of the deliveries of a webhook, most recent first.
+pub struct WebhookDeliveries {
+    pub webhook_id: Id,
+    pub page: PageQuery,
+    pub deliveries: Vec<WebhookDelivery>,
+    pub has_next: bool,
+}
+
+impl ResourceBuilder<CollectionResource<WebhookDeliveryResource>> for WebhookDeliveries {
+    fn build(
+        &self,
+        req: &HttpRequest,
+    ) -> Result<CollectionResource<WebhookDeliveryResource>, anyhow::Error> {
+        let link = |page| ResourceLink::WebhookDeliveries(self.webhook_id, page);
+        let builder = CollectionResourceBuilder::new(
+            self.deliveries
+                .iter()
+                .map(|delivery| delivery_builder(delivery, None))
+                .collect(),
+        )
+        .link(Rel::Self_, link(self.page))
+        .link(
+            Rel::Named("webhook"),
+            ResourceLink::Webhook(self.webhook_id),
+        );
+        let builder = match self.page.prev() {
+            Some(prev) => builder.link(Rel::Named("prev"), link(prev)),
+            None => builder,
+        };
+        match self.has_next {
+            true => builder.link(Rel::Named("next"), link(self.page.next())),
+            false => builder,
+        }
+        .build(req)
+    }
+}
@@ -27,6 +27,8 @@ pub enum ApiError {
     Forbidden,
     Unauthorized,
     NotFound(&'static str),
+    Conflict(&'static str),
+    Gone(&'static str),
 }
 
 impl ResponseError for ApiError {
@@ -37,6 +39,8 @@ impl ResponseError for ApiError {
             ApiError::Forbidden => StatusCode::FORBIDDEN,
             ApiError::Unauthorized => StatusCode::UNAUTHORIZED,
             ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::Gone(_) => StatusCode::GONE,
         }
     }
 
@@ -94,6 +98,12 @@ where
                 ApiError::Forbidden => HttpResponse::Forbidden().finish(),
                 ApiError::Unauthorized => HttpResponse::Unauthorized().finish(),
                 ApiError::NotFound(_) => HttpResponse::NotFound().finish(),
+                ApiError::Conflict(message) => HttpResponse::Conflict().json(ErrorResponse::new(
+                    StatusCode::CONFLICT,
+                    message.to_string(),
+                )),
+                ApiError::Gone(message) => HttpResponse::Gone()
+                    .json(ErrorResponse::new(StatusCode::GONE, message.to_string())),
             },
         }
     }
@@ -0,0 +1,269 @@
+use crate::{
+    extractors::user::{OptionalUserExtractor, UserExtractor},
+    links::ResourceLink,
+    model::{
+        comments::{
+            CommentPath, CommentResource, CreateCommentRequest, EditCommentRequest,
+            ModerateCommentRequest, Thread,
+        },
+        fragments::FragmentPath,
+        resource::CollectionResource,
+        reviews::ReviewPath,
+    },
+    response::{ApiError, ApiResponse},
+    routes::fragments::find_visible,
+    server::AppState,
+};
+use actix_web::web::{Data, Json};
+use commons::id::Id;
+use cqrs::{
+    command_bus::{
+        command::{
+            create_comment::{CreateCommentCommandBuilder, CreateCommentCommandError},
+            delete_comment::{DeleteCommentCommandBuilder, DeleteCommentCommandError},
+            edit_comment::{EditCommentCommandBuilder, EditCommentCommandError},
+            moderate_comment::{ModerateCommentCommandBuilder, ModerateCommentCommandError},
+        },
+        error::CommandBusError,
+    },
+    policy::{authorize, Action, Resource},
+};
+use storage::{
+    model::{
+        comment::Comment, fragment::Fragment, maintainer::Maintainer, review::Review, user::User,
+    },
+    query::{
+        comment::QueryComment, fragment::QueryFragment, maintainer::QueryMaintainer,
+        review::QueryReview,
+    },
+};
+
+pub struct CommentsRouter;
+
+impl CommentsRouter {
+    pub const COLLECTION_RESOURCE_NAME: &str = "fragment_comments";
+    pub const SINGLE_RESOURCE_NAME: &str = "fragment_comment";
+    pub const REVIEW_COLLECTION_RESOURCE_NAME: &str = "review_comments";
+    pub const MODERATION_RESOURCE_NAME: &str = "comment_moderation";
+
+    pub async fn list(
+        state: Data<AppState>,
+        OptionalUserExtractor(user): OptionalUserExtractor,
+        path: FragmentPath,
+    ) -> ApiResponse<CollectionResource<CommentResource>> {
+        let fragment_id: Id = path.into_inner().into();
+        if let Err(e) = find_visible(&state, user.as_ref(), &fragment_id).await {
+            return e.into();
+        }
+
+        match Comment::find_by_fragment(&state.pool, &fragment_id).await {
+            Ok(comments) => ApiResponse::Ok(Some(Box::new(Thread {
+                fragment_id,
+                review_id: None,
+                comments,
+            }))),
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    pub async fn create(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: FragmentPath,
+        Json(payload): Json<CreateCommentRequest>,
+    ) -> ApiResponse<()> {
+        create_comment(&state, user, path.into_inner().into(), None, payload).await
+    }
+
+    pub async fn list_review(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: ReviewPath,
+    ) -> ApiResponse<CollectionResource<CommentResource>> {
+        let (fragment_id, review_id) = path.into_inner();
+        match review_thread(&state, &user, fragment_id, review_id).await {
+            Ok(comments) => ApiResponse::Ok(Some(Box::new(Thread {
+                fragment_id,
+                review_id: Some(review_id),
+                comments,
+            }))),
+            Err(e) => e.into(),
+        }
+    }
+
+    pub async fn create_review(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: ReviewPath,
+        Json(payload): Json<CreateCommentRequest>,
+    ) -> ApiResponse<()> {
+        let (fragment_id, review_id) = path.into_inner();
+        create_comment(&state, user, fragment_id, Some(review_id), payload).await
+    }
+
+    pub async fn edit(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: CommentPath,
+        Json(payload): Json<EditCommentRequest>,
+    ) -> ApiResponse<()> {
+        let (fragment_id, comment_id) = path.into_inner();
+        let command = EditCommentCommandBuilder::default()
+            .fragment_id(fragment_id)
+            .comment_id(comment_id)
+            .body(payload.body())
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::EditCommentCommand(e) => match e {
+                    EditCommentCommandError::CommentNotFound(_) => {
+                        ApiError::NotFound("Comment not found").into()
+                    }
+                    EditCommentCommandError::EmptyComment => ApiError::BadRequest.into(),
+                    EditCommentCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    pub async fn delete(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: CommentPath,
+    ) -> ApiResponse<()> {
+        let (fragment_id, comment_id) = path.into_inner();
+        let command = DeleteCommentCommandBuilder::default()
+            .fragment_id(fragment_id)
+            .comment_id(comment_id)
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::DeleteCommentCommand(e) => match e {
+                    DeleteCommentCommandError::CommentNotFound(_) => {
+                        ApiError::NotFound("Comment not found").into()
+                    }
+                    DeleteCommentCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    pub async fn moderate(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: CommentPath,
+        Json(payload): Json<ModerateCommentRequest>,
+    ) -> ApiResponse<()> {
+        let (fragment_id, comment_id) = path.into_inner();
+        let command = ModerateCommentCommandBuilder::default()
+            .fragment_id(fragment_id)
+            .comment_id(comment_id)
+            .hidden(payload.hidden)
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::ModerateCommentCommand(e) => match e {
+                    ModerateCommentCommandError::CommentNotFound(_) => {
+                        ApiError::NotFound("Comment not found").into()
+                    }
+                },
+                CommandBusError::ActorNotSupported(_) => ApiError::Forbidden.into(),
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+}
+
+async fn create_comment(
+    state: &AppState,
+    user: User,
+    fragment_id: Id,
+    review_id: Option<Id>,
+    payload: CreateCommentRequest,
+) -> ApiResponse<()> {
+    let comment_id = state.ids.new_id();
+    let command = CreateCommentCommandBuilder::default()
+        .comment_id(comment_id)
+        .fragment_id(fragment_id)
+        .review_id(review_id)
+        .parent_id(payload.parent_id())
+        .body(payload.body())
+        .build()
+        .unwrap();
+
+    match state.command_bus.execute(user, command).await {
+        Ok(_) => ApiResponse::Created(None, Some(ResourceLink::Comment(fragment_id, comment_id))),
+        Err(e) => match e {
+            CommandBusError::CreateCommentCommand(e) => match e {
+                CreateCommentCommandError::FragmentNotFound(_) => {
+                    ApiError::NotFound("Fragment not found").into()
+                }
+                CreateCommentCommandError::ReviewNotFound(_) => {
+                    ApiError::NotFound("Review not found").into()
+                }
+                CreateCommentCommandError::ParentNotFound(_) => {
+                    ApiError::NotFound("Comment not found").into()
+                }
+                CreateCommentCommandError::DeletedParent(_)
+                | CreateCommentCommandError::EmptyComment => ApiError::BadRequest.into(),
+                CreateCommentCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+            },
+            _ => ApiError::InternalServerError(e.into()).into(),
+        },
+    }
+}
+
+/// Review threads are only visible to the users taking part in the review.
+async fn review_thread(
+    state: &AppState,
+    user: &User,
+    fragment_id: Id,
+    review_id: Id,
+) -> Result<Vec<Comment>, ApiError> {
+    let internal = |e: storage::StorageError| ApiError::InternalServerError(e.into());
+
+    let fork = Fragment::find(&state.pool, &fragment_id)
+        .await
+        .map_err(internal)?
+        .ok_or(ApiError::NotFound("Fragment not found"))?;
+    Review::find(&state.pool, &review_id)
+        .await
+        .map_err(internal)?
+        .filter(|review| review.fragment_id() == fork.id())
+        .ok_or(ApiError::NotFound("Review not found"))?;
+    let parent = fork
+        .get_parent(&state.pool)
+        .await
+        .map_err(internal)?
+        .ok_or(ApiError::NotFound("Review not found"))?;
+
+    let maintainer = Maintainer::is_maintainer(&state.pool, &fork.root_id(), user.id())
+        .await
+        .map_err(internal)?;
+
+    authorize(
+        user,
+        Action::DiscussReview,
+        Resource::Fork {
+            fork: &fork,
+            parent: &parent,
+            maintainer,
+        },
+    )
+    .map_err(|_| ApiError::Forbidden)?;
+
+    Comment::find_by_review(&state.pool, &review_id)
+        .await
+        .map_err(internal)
+}
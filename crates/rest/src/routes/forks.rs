@@ -9,7 +9,10 @@ use actix_web::{
     web::{Data, Json},
     Responder,
 };
-use cqrs::command_bus::command::fork_fragment::ForkFragmentCommandBuilder;
+use cqrs::command_bus::{
+    command::fork_fragment::{ForkFragmentCommandBuilder, ForkFragmentCommandError},
+    error::CommandBusError,
+};
 
 pub struct ForksRouter;
 
@@ -35,6 +38,13 @@ impl ForksRouter {
         match state.command_bus.execute(user, command).await {
             Ok(_) => ApiResponse::Created(None, Some(ResourceLink::Fragment(fork_id))),
             Err(e) => match e {
+                CommandBusError::ForkFragmentCommand(e) => match e {
+                    ForkFragmentCommandError::ParentFragmentNotFound(_) => {
+                        ApiError::NotFound("Fragment not found").into()
+                    }
+                    ForkFragmentCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                    ForkFragmentCommandError::InvalidState(_) => ApiError::BadRequest.into(),
+                },
                 _ => ApiError::InternalServerError(Box::new(e)).into(),
             },
         }
@@ -1,20 +1,55 @@
 use crate::{
-    extractors::user::UserExtractor,
+    extractors::user::{OptionalUserExtractor, UserExtractor},
     links::ResourceLink,
-    model::fragments::{CreateFragmentRequest, FragmentPath, UpdateFragmentRequest},
+    model::{
+        fragments::{
+            CreateFragmentRequest, FragmentPath, FragmentResource, PublicationScheduleResource,
+            PublishFragmentRequest, ResubmitForkRequest, SetForkPolicyRequest,
+            UpdateFragmentRequest,
+        },
+        resource::{CollectionResource, SingleResource},
+        transitions::{TransitionResource, Transitions},
+        tree::{CanonicalPath, FragmentTree, SetCanonicalBranchRequest, TreeNodeResource},
+    },
     response::{ApiError, ApiResponse},
     server::AppState,
 };
 use actix_web::web::{Data, Json};
+use commons::id::Id;
 use cqrs::command_bus::{
     command::{
+        cancel_publication::{CancelPublicationCommandBuilder, CancelPublicationCommandError},
         create_fragment::CreateFragmentCommandBuilder,
-        publish_fragment::{PublishFragmentCommandBuilder, PublishFragmentCommandError},
+        delete_fragment::{DeleteFragmentCommandBuilder, DeleteFragmentCommandError},
+        publish_fragment::{
+            PublishFragmentCommand, PublishFragmentCommandBuilder, PublishFragmentCommandError,
+        },
+        restore_fragment::{RestoreFragmentCommandBuilder, RestoreFragmentCommandError},
+        resubmit_fork::{ResubmitForkCommandBuilder, ResubmitForkCommandError},
+        set_canonical_branch::{SetCanonicalBranchCommandBuilder, SetCanonicalBranchCommandError},
+        set_fork_policy::{SetForkPolicyCommandBuilder, SetForkPolicyCommandError},
         submit_fork::{SubmitForkCommandBuilder, SubmitForkCommandError},
+        unpublish_fragment::{UnpublishFragmentCommandBuilder, UnpublishFragmentCommandError},
         update_fragment::{UpdateFragmentCommandBuilder, UpdateFragmentCommandError},
+        withdraw_fork::{WithdrawForkCommandBuilder, WithdrawForkCommandError},
+        Command,
     },
     error::CommandBusError,
 };
+use cqrs::policy::{authorize, authorize_view, Action, Resource, ViewError};
+use storage::{
+    model::{
+        canonical_branch::CanonicalBranch,
+        fragment::Fragment,
+        state_transition::StateTransition,
+        task::{CommandData, Task},
+        user::User,
+    },
+    query::{
+        canonical_branch::QueryCanonicalBranch, fragment::QueryFragment,
+        state_transition::QueryStateTransition, task::QueryTask,
+    },
+};
 
 pub struct FragmentsRouter;
 
@@ -22,7 +57,16 @@ impl FragmentsRouter {
     pub const COLLECTION_RESOURCE_NAME: &str = "fragments";
     pub const SINGLE_RESOURCE_NAME: &str = "fragment";
     pub const PUBLICATION_RESOURCE_NAME: &str = "publication";
+    pub const PUBLICATION_SCHEDULE_RESOURCE_NAME: &str = "publication_schedule";
     pub const SUBMIT_RESOURCE_NAME: &str = "submit";
+    pub const FORK_POLICY_RESOURCE_NAME: &str = "fork_policy";
+    pub const RESTORATION_RESOURCE_NAME: &str = "restoration";
+    pub const WITHDRAWAL_RESOURCE_NAME: &str = "withdrawal";
+    pub const RESUBMISSION_RESOURCE_NAME: &str = "resubmission";
+    pub const TRANSITIONS_RESOURCE_NAME: &str = "fragment_transitions";
+    pub const CANONICAL_BRANCH_RESOURCE_NAME: &str = "canonical_branch";
+    pub const CANONICAL_PATH_RESOURCE_NAME: &str = "canonical_path";
+    pub const TREE_RESOURCE_NAME: &str = "fragment_tree";
 
     pub async fn create(
         state: Data<AppState>,
@@ -42,8 +86,91 @@ impl FragmentsRouter {
         }
     }
 
-    pub async fn delete(_: Data<AppState>, UserExtractor(_): UserExtractor) -> ApiResponse<()> {
-        ApiResponse::Ok(None)
+    pub async fn get(
+        state: Data<AppState>,
+        OptionalUserExtractor(user): OptionalUserExtractor,
+        path: FragmentPath,
+    ) -> ApiResponse<SingleResource<FragmentResource>> {
+        let fragment = match find_visible(&state, user.as_ref(), &path.into_inner().into()).await {
+            Ok(fragment) => fragment,
+            Err(e) => return e.into(),
+        };
+
+        // Forks are governed by the policy of their story root.
+        match fragment.root(&state.pool).await {
+            Ok(root) => {
+                let policy = *root.fork_policy();
+                ApiResponse::Ok(Some(Box::new(fragment.set_fork_policy(policy))))
+            }
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    pub async fn transitions(
+        state: Data<AppState>,
+        OptionalUserExtractor(user): OptionalUserExtractor,
+        path: FragmentPath,
+    ) -> ApiResponse<CollectionResource<TransitionResource>> {
+        let fragment_id: Id = path.into_inner().into();
+        if let Err(e) = find_visible(&state, user.as_ref(), &fragment_id).await {
+            return e.into();
+        }
+
+        match StateTransition::find_by_fragment(&state.pool, &fragment_id).await {
+            Ok(transitions) => {
+                ApiResponse::Ok(Some(Box::new(Transitions(fragment_id, transitions))))
+            }
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    pub async fn delete(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: FragmentPath,
+    ) -> ApiResponse<()> {
+        let command = DeleteFragmentCommandBuilder::default()
+            .fragment_id(path.into_inner())
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::DeleteFragmentCommand(e) => match e {
+                    DeleteFragmentCommandError::FragmentNotFound(_) => {
+                        ApiError::NotFound("Fragment not found").into()
+                    }
+                    DeleteFragmentCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    pub async fn restore(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: FragmentPath,
+    ) -> ApiResponse<()> {
+        let command = RestoreFragmentCommandBuilder::default()
+            .fragment_id(path.into_inner())
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::RestoreFragmentCommand(e) => match e {
+                    RestoreFragmentCommandError::FragmentNotFound(_) => {
+                        ApiError::NotFound("Fragment not found").into()
+                    }
+                    RestoreFragmentCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                    RestoreFragmentCommandError::InvalidState(_) => ApiError::BadRequest.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
     }
 
     pub async fn update(
@@ -76,19 +203,128 @@ impl FragmentsRouter {
     pub async fn publish(
         state: Data<AppState>,
         UserExtractor(user): UserExtractor,
+        payload: Option<Json<PublishFragmentRequest>>,
         path: FragmentPath,
     ) -> ApiResponse<()> {
+        let fragment_id: Id = path.into_inner().into();
         let command = PublishFragmentCommandBuilder::default()
+            .fragment_id(fragment_id)
+            .build()
+            .unwrap();
+        let result = match payload.and_then(|p| p.publish_at) {
+            Some(publish_at) if publish_at <= state.clock.now() => {
+                return ApiError::BadRequest.into()
+            }
+            Some(publish_at) => state
+                .command_bus
+                .dispatch(user, command, Some(publish_at))
+                .await
+                .map(|_| Some(ResourceLink::PublicationSchedule(fragment_id))),
+            None => state.command_bus.execute(user, command).await.map(|_| None),
+        };
+
+        match result {
+            Ok(None) => ApiResponse::Ok(None),
+            Ok(link) => ApiResponse::Created(None, link),
+            Err(e) => match e {
+                CommandBusError::PublishFragmentCommand(e) => match e {
+                    PublishFragmentCommandError::FragmentNotFound(_) => {
+                        ApiError::NotFound("Fragment not found").into()
+                    }
+                    PublishFragmentCommandError::InvalidState(_) => ApiError::BadRequest.into(),
+                    PublishFragmentCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                    PublishFragmentCommandError::AlreadyScheduled(_) => {
+                        ApiError::Conflict("Publication already scheduled").into()
+                    }
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    pub async fn publication_schedule(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: FragmentPath,
+    ) -> ApiResponse<SingleResource<PublicationScheduleResource>> {
+        let fragment_id: Id = path.into_inner().into();
+        let fragment = match Fragment::find(&state.pool, &fragment_id).await {
+            Ok(Some(fragment)) => fragment,
+            Ok(None) => return ApiError::NotFound("Fragment not found").into(),
+            Err(e) => return ApiError::InternalServerError(e.into()).into(),
+        };
+
+        if authorize(
+            &user,
+            Action::PublishFragment,
+            Resource::Fragment(&fragment),
+        )
+        .is_err()
+        {
+            return ApiError::Forbidden.into();
+        }
+
+        let publication = PublishFragmentCommand { fragment_id };
+        match Task::find_pending(
+            &state.pool,
+            &publication.command_type(),
+            &CommandData::from(publication),
+        )
+        .await
+        {
+            Ok(Some(task)) => ApiResponse::Ok(Some(Box::new(PublicationScheduleResource::new(
+                fragment_id,
+                &task,
+            )))),
+            Ok(None) => ApiError::NotFound("Publication not scheduled").into(),
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    pub async fn cancel_publication(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: FragmentPath,
+    ) -> ApiResponse<()> {
+        let command = CancelPublicationCommandBuilder::default()
             .fragment_id(path.into_inner())
             .build()
             .unwrap();
         match state.command_bus.execute(user, command).await {
             Ok(_) => ApiResponse::Ok(None),
             Err(e) => match e {
-                CommandBusError::PublishFragmentCommand(e) => match e {
-                    PublishFragmentCommandError::FragmentNotFound(_) => todo!(),
-                    PublishFragmentCommandError::InvalidState(_) => todo!(),
-                    PublishFragmentCommandError::Forbidden(_) => todo!(),
+                CommandBusError::CancelPublicationCommand(e) => match e {
+                    CancelPublicationCommandError::FragmentNotFound(_) => {
+                        ApiError::NotFound("Fragment not found").into()
+                    }
+                    CancelPublicationCommandError::NotScheduled(_) => {
+                        ApiError::NotFound("Publication not scheduled").into()
+                    }
+                    CancelPublicationCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    pub async fn unpublish(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: FragmentPath,
+    ) -> ApiResponse<()> {
+        let command = UnpublishFragmentCommandBuilder::default()
+            .fragment_id(path.into_inner())
+            .build()
+            .unwrap();
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::UnpublishFragmentCommand(e) => match e {
+                    UnpublishFragmentCommandError::FragmentNotFound(_) => {
+                        ApiError::NotFound("Fragment not found").into()
+                    }
+                    UnpublishFragmentCommandError::InvalidState(_) => ApiError::BadRequest.into(),
+                    UnpublishFragmentCommandError::Forbidden(_) => ApiError::Forbidden.into(),
                 },
                 _ => ApiError::InternalServerError(e.into()).into(),
             },
@@ -100,20 +336,232 @@ impl FragmentsRouter {
         UserExtractor(user): UserExtractor,
         path: FragmentPath,
     ) -> ApiResponse<()> {
+        let fork_id: Id = path.into_inner().into();
         let command = SubmitForkCommandBuilder::default()
-            .fragment_id(path.into_inner())
+            .fragment_id(fork_id)
             .build()
             .unwrap();
         match state.command_bus.execute(user, command).await {
             Ok(_) => ApiResponse::Ok(None),
             Err(e) => match e {
                 CommandBusError::SubmitForkCommand(e) => match e {
-                    SubmitForkCommandError::ForkNotFound(_) => todo!(),
-                    SubmitForkCommandError::Forbidden(_) => todo!(),
-                    SubmitForkCommandError::InvalidState(_) => todo!(),
+                    SubmitForkCommandError::ForkNotFound(_) => {
+                        ApiError::NotFound("Fork not found").into()
+                    }
+                    SubmitForkCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                    SubmitForkCommandError::InvalidState(_) => ApiError::BadRequest.into(),
                 },
                 _ => ApiError::InternalServerError(e.into()).into(),
             },
         }
     }
+
+    pub async fn withdraw(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: FragmentPath,
+    ) -> ApiResponse<()> {
+        let command = WithdrawForkCommandBuilder::default()
+            .fragment_id(path.into_inner())
+            .build()
+            .unwrap();
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::WithdrawForkCommand(e) => match e {
+                    WithdrawForkCommandError::ForkNotFound(_) => {
+                        ApiError::NotFound("Fork not found").into()
+                    }
+                    WithdrawForkCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                    WithdrawForkCommandError::InvalidState(_) => ApiError::BadRequest.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    pub async fn resubmit(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        Json(payload): Json<ResubmitForkRequest>,
+        path: FragmentPath,
+    ) -> ApiResponse<()> {
+        let fork_id: Id = path.into_inner().into();
+        let command = ResubmitForkCommandBuilder::default()
+            .fragment_id(fork_id)
+            .appeal(payload.appeal())
+            .max_attempts(state.review.max_resubmissions)
+            .build()
+            .unwrap();
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::ResubmitForkCommand(e) => match e {
+                    ResubmitForkCommandError::ForkNotFound(_) => {
+                        ApiError::NotFound("Fork not found").into()
+                    }
+                    ResubmitForkCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                    ResubmitForkCommandError::InvalidState(_) => ApiError::BadRequest.into(),
+                    ResubmitForkCommandError::AttemptsExhausted(_) => {
+                        ApiError::Conflict("No resubmission attempts left").into()
+                    }
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    pub async fn set_fork_policy(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        Json(payload): Json<SetForkPolicyRequest>,
+        path: FragmentPath,
+    ) -> ApiResponse<()> {
+        let command = SetForkPolicyCommandBuilder::default()
+            .fragment_id(path.into_inner())
+            .policy(payload.policy)
+            .invitees(payload.invitees)
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::SetForkPolicyCommand(e) => match e {
+                    SetForkPolicyCommandError::FragmentNotFound(_) => {
+                        ApiError::NotFound("Fragment not found").into()
+                    }
+                    SetForkPolicyCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                    SetForkPolicyCommandError::UserNotFound(_)
+                    | SetForkPolicyCommandError::InvalidState(_)
+                    | SetForkPolicyCommandError::InviteesNotAllowed => ApiError::BadRequest.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    pub async fn tree(
+        state: Data<AppState>,
+        path: FragmentPath,
+    ) -> ApiResponse<CollectionResource<TreeNodeResource>> {
+        let fragment = match Fragment::find(&state.pool, &path.into_inner().into()).await {
+            Ok(Some(fragment)) => fragment,
+            Ok(None) => return ApiError::NotFound("Fragment not found").into(),
+            Err(e) => return ApiError::InternalServerError(e.into()).into(),
+        };
+
+        let descendants = match fragment.published_descendants(&state.pool).await {
+            Ok(descendants) => descendants,
+            Err(e) => return ApiError::InternalServerError(e.into()).into(),
+        };
+        match CanonicalBranch::find_in_subtree(&state.pool, fragment.id()).await {
+            Ok(branches) => ApiResponse::Ok(Some(Box::new(FragmentTree {
+                fragment,
+                descendants,
+                branches,
+            }))),
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    pub async fn canonical_path(
+        state: Data<AppState>,
+        path: FragmentPath,
+    ) -> ApiResponse<CollectionResource<FragmentResource>> {
+        let fragment_id: Id = path.into_inner().into();
+        match Fragment::find(&state.pool, &fragment_id).await {
+            Ok(Some(_)) => {}
+            Ok(None) => return ApiError::NotFound("Fragment not found").into(),
+            Err(e) => return ApiError::InternalServerError(e.into()).into(),
+        }
+
+        match CanonicalBranch::path(&state.pool, &fragment_id).await {
+            Ok(fragments) => ApiResponse::Ok(Some(Box::new(CanonicalPath(fragment_id, fragments)))),
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    pub async fn set_canonical_branch(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        Json(payload): Json<SetCanonicalBranchRequest>,
+        path: FragmentPath,
+    ) -> ApiResponse<()> {
+        Self::choose_canonical_branch(
+            state,
+            user,
+            path.into_inner().into(),
+            Some(payload.child_id),
+        )
+        .await
+    }
+
+    pub async fn clear_canonical_branch(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: FragmentPath,
+    ) -> ApiResponse<()> {
+        Self::choose_canonical_branch(state, user, path.into_inner().into(), None).await
+    }
+
+    async fn choose_canonical_branch(
+        state: Data<AppState>,
+        user: User,
+        fragment_id: Id,
+        child_id: Option<Id>,
+    ) -> ApiResponse<()> {
+        let command = SetCanonicalBranchCommandBuilder::default()
+            .fragment_id(fragment_id)
+            .child_id(child_id)
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::SetCanonicalBranchCommand(e) => match e {
+                    SetCanonicalBranchCommandError::FragmentNotFound(_) => {
+                        ApiError::NotFound("Fragment not found").into()
+                    }
+                    SetCanonicalBranchCommandError::InvalidBranch(_) => ApiError::BadRequest.into(),
+                    SetCanonicalBranchCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                    SetCanonicalBranchCommandError::PollInProgress(_) => {
+                        ApiError::Conflict("Canonical branch is being voted").into()
+                    }
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+}
+
+/// Fragment as `user` sees it. Fragments it may not read are reported as not found, the
+/// same as missing ones, and anonymous users only read published fragments.
+pub(crate) async fn find_visible(
+    state: &AppState,
+    user: Option<&User>,
+    fragment_id: &Id,
+) -> Result<Fragment, ApiError> {
+    let internal = |e: storage::StorageError| ApiError::InternalServerError(e.into());
+
+    let fragment = Fragment::find(&state.pool, fragment_id)
+        .await
+        .map_err(internal)?
+        .ok_or(ApiError::NotFound("Fragment not found"))?;
+    let visible = match user {
+        Some(user) => {
+            let mut conn = state.pool.acquire().await.map_err(|e| internal(e.into()))?;
+            match authorize_view(&mut conn, user, Action::ViewFragment, &fragment).await {
+                Ok(()) => true,
+                Err(ViewError::Forbidden(_)) => false,
+                Err(ViewError::Storage(e)) => return Err(internal(e)),
+            }
+        }
+        None => fragment.is_published(),
+    };
+    if !visible {
+        return Err(ApiError::NotFound("Fragment not found"));
+    }
+    Ok(fragment)
 }
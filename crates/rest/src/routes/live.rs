@@ -0,0 +1,160 @@
+use super::user::UserPath;
+use crate::{
+    extractors::user::UserExtractor,
+    live::{live_stream, LiveMessage},
+    model::{
+        fragments::FragmentPath,
+        live::{LiveQuery, LAST_EVENT_ID_HEADER},
+    },
+    response::ApiError,
+    server::AppState,
+};
+use actix_web::{
+    http::header::{CACHE_CONTROL, CONTENT_TYPE, UPGRADE},
+    web::{Data, Payload, Query},
+    HttpRequest, HttpResponse,
+};
+use actix_ws::Message;
+use cqrs::live::{LiveError, LiveFilter, LiveScope};
+use futures_util::StreamExt;
+use std::{convert::Infallible, time::Duration};
+use storage::model::user::User;
+
+pub struct LiveRouter;
+
+impl LiveRouter {
+    pub const FRAGMENT_RESOURCE_NAME: &'static str = "fragment_live";
+    pub const USER_RESOURCE_NAME: &'static str = "user_live";
+
+    /// Events of the fragment and the fragments below it.
+    pub async fn fragment(
+        req: HttpRequest,
+        body: Payload,
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: FragmentPath,
+        Query(query): Query<LiveQuery>,
+    ) -> Result<HttpResponse, ApiError> {
+        let scope = LiveScope::Subtree(path.into_inner().into());
+        Self::stream(req, body, state, user, scope, query).await
+    }
+
+    /// Events about the user and its fragments.
+    pub async fn user(
+        req: HttpRequest,
+        body: Payload,
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: UserPath,
+        Query(query): Query<LiveQuery>,
+    ) -> Result<HttpResponse, ApiError> {
+        let scope = LiveScope::User(path.into_inner().into());
+        Self::stream(req, body, state, user, scope, query).await
+    }
+
+    /// Serves the stream over WebSocket when the client asks for an upgrade, and as
+    /// Server-Sent Events otherwise.
+    async fn stream(
+        req: HttpRequest,
+        body: Payload,
+        state: Data<AppState>,
+        user: User,
+        scope: LiveScope,
+        query: LiveQuery,
+    ) -> Result<HttpResponse, ApiError> {
+        let last_position = last_event_id(&req)?.or(query.last_event_id);
+        // Subscribe first, events committed while the filter opens are replayed or
+        // received from the hub.
+        let events = state.events.subscribe();
+        let filter = LiveFilter::open(state.pool.clone(), user, scope)
+            .await
+            .map_err(api_error)?;
+        if let Some(position) = last_position {
+            filter
+                .check_resume(position, state.live.max_replay)
+                .await
+                .map_err(api_error)?;
+        }
+        let messages = live_stream(
+            filter,
+            events,
+            last_position,
+            Duration::from_secs(state.live.heartbeat_interval),
+            state.live.max_replay,
+        );
+
+        let websocket = req
+            .headers()
+            .get(UPGRADE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.eq_ignore_ascii_case("websocket"));
+        if !websocket {
+            return Ok(HttpResponse::Ok()
+                .insert_header((CONTENT_TYPE, "text/event-stream"))
+                .insert_header((CACHE_CONTROL, "no-cache"))
+                // Keeps reverse proxies from buffering the stream.
+                .insert_header(("X-Accel-Buffering", "no"))
+                .streaming(messages.map(|message| Ok::<_, Infallible>(message.to_sse()))));
+        }
+
+        let (response, mut session, mut incoming) =
+            actix_ws::handle(&req, body).map_err(|_| ApiError::BadRequest)?;
+        actix_web::rt::spawn(async move {
+            let mut messages = Box::pin(messages);
+            loop {
+                tokio::select! {
+                    message = messages.next() => {
+                        let sent = match message {
+                            Some(LiveMessage::Event(event)) => {
+                                session.text(serde_json::to_string(&event).unwrap()).await
+                            }
+                            Some(LiveMessage::Heartbeat) => session.ping(b"").await,
+                            None => break,
+                        };
+                        if sent.is_err() {
+                            return;
+                        }
+                    }
+                    received = incoming.next() => match received {
+                        Some(Ok(Message::Ping(bytes))) => {
+                            if session.pong(&bytes).await.is_err() {
+                                return;
+                            }
+                        }
+                        Some(Ok(Message::Close(reason))) => {
+                            let _ = session.close(reason).await;
+                            return;
+                        }
+                        Some(Ok(_)) => {}
+                        Some(Err(_)) | None => return,
+                    }
+                }
+            }
+            let _ = session.close(None).await;
+        });
+        Ok(response)
+    }
+}
+
+fn api_error(e: LiveError) -> ApiError {
+    match e {
+        LiveError::FragmentNotFound => ApiError::NotFound("Fragment not found"),
+        LiveError::TooFarBehind(_) => ApiError::Gone("Last event id is too old to resume from"),
+        LiveError::Forbidden(_) => ApiError::Forbidden,
+        LiveError::Storage(e) => ApiError::InternalServerError(e.into()),
+    }
+}
+
+/// Position sent back by a reconnecting Server-Sent Events client.
+fn last_event_id(req: &HttpRequest) -> Result<Option<i64>, ApiError> {
+    req.headers()
+        .get(LAST_EVENT_ID_HEADER)
+        .map(|value| {
+            value
+                .to_str()
+                .ok()
+                .and_then(|value| value.trim().parse().ok())
+                .ok_or(ApiError::BadRequest)
+        })
+        .transpose()
+}
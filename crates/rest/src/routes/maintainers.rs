@@ -0,0 +1,136 @@
+use crate::{
+    extractors::user::UserExtractor,
+    links::ResourceLink,
+    model::{
+        fragments::FragmentPath,
+        maintainers::{InviteMaintainerRequest, MaintainerResource, Maintainers},
+        resource::CollectionResource,
+    },
+    response::{ApiError, ApiResponse},
+    server::AppState,
+};
+use actix_web::web::{Data, Json};
+use commons::id::Id;
+use cqrs::command_bus::{
+    command::{
+        accept_maintainer_invitation::{
+            AcceptMaintainerInvitationCommandBuilder, AcceptMaintainerInvitationCommandError,
+        },
+        decline_maintainer_invitation::{
+            DeclineMaintainerInvitationCommandBuilder, DeclineMaintainerInvitationCommandError,
+        },
+        invite_maintainer::{InviteMaintainerCommandBuilder, InviteMaintainerCommandError},
+    },
+    error::CommandBusError,
+};
+use storage::{
+    model::{fragment::Fragment, maintainer::Maintainer},
+    query::{fragment::QueryFragment, maintainer::QueryMaintainer},
+};
+
+pub struct MaintainersRouter;
+
+impl MaintainersRouter {
+    pub const COLLECTION_RESOURCE_NAME: &'static str = "fragment_maintainers";
+    pub const ACCEPTANCE_RESOURCE_NAME: &'static str = "maintainer_invitation_acceptance";
+    pub const REJECTION_RESOURCE_NAME: &'static str = "maintainer_invitation_rejection";
+
+    pub async fn list(
+        state: Data<AppState>,
+        path: FragmentPath,
+    ) -> ApiResponse<CollectionResource<MaintainerResource>> {
+        let root_id: Id = path.into_inner().into();
+        match Fragment::find(&state.pool, &root_id).await {
+            Ok(Some(_)) => {}
+            Ok(None) => return ApiError::NotFound("Fragment not found").into(),
+            Err(e) => return ApiError::InternalServerError(e.into()).into(),
+        }
+
+        match Maintainer::find_by_root(&state.pool, &root_id).await {
+            Ok(maintainers) => ApiResponse::Ok(Some(Box::new(Maintainers(root_id, maintainers)))),
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    pub async fn invite(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: FragmentPath,
+        Json(payload): Json<InviteMaintainerRequest>,
+    ) -> ApiResponse<()> {
+        let root_id: Id = path.into_inner().into();
+        let command = InviteMaintainerCommandBuilder::default()
+            .root_id(root_id)
+            .user_id(payload.user_id)
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Created(None, Some(ResourceLink::Maintainers(root_id))),
+            Err(e) => match e {
+                CommandBusError::InviteMaintainerCommand(e) => match e {
+                    InviteMaintainerCommandError::FragmentNotFound(_) => {
+                        ApiError::NotFound("Fragment not found").into()
+                    }
+                    InviteMaintainerCommandError::UserNotFound(_) => {
+                        ApiError::NotFound("User not found").into()
+                    }
+                    InviteMaintainerCommandError::InvalidInvitation(_) => {
+                        ApiError::BadRequest.into()
+                    }
+                    InviteMaintainerCommandError::AlreadyInvited(_) => {
+                        ApiError::Conflict("User already invited").into()
+                    }
+                    InviteMaintainerCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    pub async fn accept(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: FragmentPath,
+    ) -> ApiResponse<()> {
+        let command = AcceptMaintainerInvitationCommandBuilder::default()
+            .root_id(path.into_inner())
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::AcceptMaintainerInvitationCommand(e) => match e {
+                    AcceptMaintainerInvitationCommandError::InvitationNotFound(_) => {
+                        ApiError::NotFound("Invitation not found").into()
+                    }
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    pub async fn decline(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: FragmentPath,
+    ) -> ApiResponse<()> {
+        let command = DeclineMaintainerInvitationCommandBuilder::default()
+            .root_id(path.into_inner())
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::DeclineMaintainerInvitationCommand(e) => match e {
+                    DeclineMaintainerInvitationCommandError::InvitationNotFound(_) => {
+                        ApiError::NotFound("Invitation not found").into()
+                    }
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+}
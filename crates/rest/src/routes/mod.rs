@@ -1,14 +1,32 @@
+pub mod comments;
 pub mod follow;
 pub mod forks;
 pub mod fragments;
 pub mod health;
 pub mod likes;
+pub mod live;
+pub mod maintainers;
+pub mod notification_preferences;
+pub mod notifications;
+pub mod polls;
 pub mod reviews;
+pub mod revisions;
+pub mod sessions;
+pub mod stories;
+pub mod suggestions;
+pub mod tags;
+pub mod trusted_contributors;
 pub mod user;
+pub mod webhooks;
 
 use crate::routes::{
-    follow::FollowingsRouter, forks::ForksRouter, fragments::FragmentsRouter, health::HealthRouter,
-    likes::LikesRouter, reviews::ReviewsRouter,
+    comments::CommentsRouter, follow::FollowingsRouter, forks::ForksRouter,
+    fragments::FragmentsRouter, health::HealthRouter, likes::LikesRouter, live::LiveRouter,
+    maintainers::MaintainersRouter, notification_preferences::NotificationPreferencesRouter,
+    notifications::NotificationsRouter, polls::PollsRouter, reviews::ReviewsRouter,
+    revisions::RevisionsRouter, sessions::SessionsRouter, stories::StoriesRouter,
+    suggestions::SuggestionsRouter, tags::TagsRouter,
+    trusted_contributors::TrustedContributorsRouter, user::UsersRouter, webhooks::WebhooksRouter,
 };
 use actix_web::{
     web::{self},
@@ -18,17 +36,85 @@ use actix_web::{
 pub fn routes() -> Scope {
     const EMPTY_RESOURCE: &str = "";
 
-    let users = web::scope("/v1/users").service(
-        web::scope("/{user_id}")
-            .service(web::resource(EMPTY_RESOURCE))
-            .service(
-                web::scope("/followings").service(
+    let sessions = web::scope("/v1/sessions").service(
+        web::resource(EMPTY_RESOURCE)
+            .name(SessionsRouter::COLLECTION_RESOURCE_NAME)
+            .route(web::post().to(SessionsRouter::create))
+            .route(web::delete().to(SessionsRouter::delete)),
+    );
+
+    let users = web::scope("/v1/users")
+        .service(
+            web::resource(EMPTY_RESOURCE)
+                .name(UsersRouter::COLLECTION_RESOURCE_NAME)
+                .route(web::post().to(UsersRouter::create)),
+        )
+        .service(
+            web::scope("/{user_id}")
+                .service(
                     web::resource(EMPTY_RESOURCE)
-                        .route(web::post().to(FollowingsRouter::create))
-                        .route(web::delete().to(FollowingsRouter::delete)),
+                        .name(UsersRouter::SINGLE_RESOURCE_NAME)
+                        .route(web::get().to(UsersRouter::get))
+                        .route(web::patch().to(UsersRouter::update)),
+                )
+                .service(
+                    web::scope("/role").service(
+                        web::resource(EMPTY_RESOURCE)
+                            .name(UsersRouter::ROLE_RESOURCE_NAME)
+                            .route(web::put().to(UsersRouter::assign_role)),
+                    ),
+                )
+                .service(
+                    web::scope("/followings").service(
+                        web::resource(EMPTY_RESOURCE)
+                            .route(web::post().to(FollowingsRouter::create))
+                            .route(web::delete().to(FollowingsRouter::delete)),
+                    ),
+                )
+                .service(
+                    web::scope("/notifications")
+                        .service(
+                            web::resource(EMPTY_RESOURCE)
+                                .name(NotificationsRouter::COLLECTION_RESOURCE_NAME)
+                                .route(web::get().to(NotificationsRouter::list)),
+                        )
+                        .service(
+                            web::resource("/read")
+                                .name(NotificationsRouter::READ_ALL_RESOURCE_NAME)
+                                .route(web::post().to(NotificationsRouter::read_all)),
+                        )
+                        .service(
+                            web::resource("/{notification_id}/read")
+                                .name(NotificationsRouter::READ_RESOURCE_NAME)
+                                .route(web::post().to(NotificationsRouter::read)),
+                        ),
+                )
+                .service(
+                    web::resource("/live")
+                        .name(LiveRouter::USER_RESOURCE_NAME)
+                        .route(web::get().to(LiveRouter::user)),
+                )
+                .service(
+                    web::resource("/notification_preferences")
+                        .name(NotificationPreferencesRouter::SINGLE_RESOURCE_NAME)
+                        .route(web::get().to(NotificationPreferencesRouter::get))
+                        .route(web::put().to(NotificationPreferencesRouter::update)),
+                )
+                .service(
+                    web::scope("/trusted_contributors")
+                        .service(
+                            web::resource(EMPTY_RESOURCE)
+                                .name(TrustedContributorsRouter::COLLECTION_RESOURCE_NAME)
+                                .route(web::get().to(TrustedContributorsRouter::list))
+                                .route(web::post().to(TrustedContributorsRouter::create)),
+                        )
+                        .service(
+                            web::resource("/{contributor_id}")
+                                .name(TrustedContributorsRouter::SINGLE_RESOURCE_NAME)
+                                .route(web::delete().to(TrustedContributorsRouter::delete)),
+                        ),
                 ),
-            ),
-    );
+        );
 
     let fragments = web::scope("/v1/fragments")
         .service(
@@ -41,23 +127,167 @@ pub fn routes() -> Scope {
                 .service(
                     web::resource(EMPTY_RESOURCE)
                         .name(FragmentsRouter::SINGLE_RESOURCE_NAME)
+                        .route(web::get().to(FragmentsRouter::get))
                         .route(web::patch().to(FragmentsRouter::update))
                         .route(web::delete().to(FragmentsRouter::delete)),
                 )
                 .service(
-                    web::scope("/publication").service(
-                        web::resource(EMPTY_RESOURCE)
-                            .name(FragmentsRouter::PUBLICATION_RESOURCE_NAME)
-                            .route(web::post().to(FragmentsRouter::publish)),
-                    ),
+                    web::scope("/publication")
+                        .service(
+                            web::resource(EMPTY_RESOURCE)
+                                .name(FragmentsRouter::PUBLICATION_RESOURCE_NAME)
+                                .route(web::post().to(FragmentsRouter::publish))
+                                .route(web::delete().to(FragmentsRouter::unpublish)),
+                        )
+                        .service(
+                            web::resource("/schedule")
+                                .name(FragmentsRouter::PUBLICATION_SCHEDULE_RESOURCE_NAME)
+                                .route(web::get().to(FragmentsRouter::publication_schedule))
+                                .route(web::delete().to(FragmentsRouter::cancel_publication)),
+                        ),
                 )
                 .service(
                     web::scope("/submit").service(
                         web::resource(EMPTY_RESOURCE)
-                            .name(FragmentsRouter::PUBLICATION_RESOURCE_NAME)
+                            .name(FragmentsRouter::SUBMIT_RESOURCE_NAME)
                             .route(web::post().to(FragmentsRouter::submit)),
                     ),
                 )
+                .service(
+                    web::scope("/withdrawal").service(
+                        web::resource(EMPTY_RESOURCE)
+                            .name(FragmentsRouter::WITHDRAWAL_RESOURCE_NAME)
+                            .route(web::post().to(FragmentsRouter::withdraw)),
+                    ),
+                )
+                .service(
+                    web::scope("/resubmission").service(
+                        web::resource(EMPTY_RESOURCE)
+                            .name(FragmentsRouter::RESUBMISSION_RESOURCE_NAME)
+                            .route(web::post().to(FragmentsRouter::resubmit)),
+                    ),
+                )
+                .service(
+                    web::scope("/tags")
+                        .service(
+                            web::resource(EMPTY_RESOURCE)
+                                .name(TagsRouter::FRAGMENT_COLLECTION_RESOURCE_NAME)
+                                .route(web::get().to(TagsRouter::fragment_tags))
+                                .route(web::post().to(TagsRouter::add_fragment_tag)),
+                        )
+                        .service(
+                            web::resource("/{tag}")
+                                .name(TagsRouter::FRAGMENT_SINGLE_RESOURCE_NAME)
+                                .route(web::delete().to(TagsRouter::remove_fragment_tag)),
+                        ),
+                )
+                .service(
+                    web::resource("/transitions")
+                        .name(FragmentsRouter::TRANSITIONS_RESOURCE_NAME)
+                        .route(web::get().to(FragmentsRouter::transitions)),
+                )
+                .service(
+                    web::resource("/review_context")
+                        .name(ReviewsRouter::CONTEXT_RESOURCE_NAME)
+                        .route(web::get().to(ReviewsRouter::context)),
+                )
+                .service(
+                    web::resource("/review_quorum")
+                        .name(ReviewsRouter::QUORUM_RESOURCE_NAME)
+                        .route(web::get().to(ReviewsRouter::quorum))
+                        .route(web::put().to(ReviewsRouter::set_quorum)),
+                )
+                .service(
+                    web::resource("/review_sla")
+                        .name(ReviewsRouter::SLA_RESOURCE_NAME)
+                        .route(web::get().to(ReviewsRouter::sla))
+                        .route(web::put().to(ReviewsRouter::set_sla)),
+                )
+                .service(
+                    web::scope("/revisions")
+                        .service(
+                            web::resource(EMPTY_RESOURCE)
+                                .name(RevisionsRouter::COLLECTION_RESOURCE_NAME)
+                                .route(web::get().to(RevisionsRouter::list)),
+                        )
+                        .service(
+                            web::resource("/diff")
+                                .name(RevisionsRouter::DIFF_RESOURCE_NAME)
+                                .route(web::get().to(RevisionsRouter::diff)),
+                        )
+                        .service(
+                            web::scope("/{number}")
+                                .service(
+                                    web::resource(EMPTY_RESOURCE)
+                                        .name(RevisionsRouter::SINGLE_RESOURCE_NAME)
+                                        .route(web::get().to(RevisionsRouter::get)),
+                                )
+                                .service(
+                                    web::resource("/revert")
+                                        .name(RevisionsRouter::REVERT_RESOURCE_NAME)
+                                        .route(web::post().to(RevisionsRouter::revert)),
+                                ),
+                        ),
+                )
+                .service(
+                    web::scope("/restoration").service(
+                        web::resource(EMPTY_RESOURCE)
+                            .name(FragmentsRouter::RESTORATION_RESOURCE_NAME)
+                            .route(web::post().to(FragmentsRouter::restore)),
+                    ),
+                )
+                .service(
+                    web::resource("/canonical_branch")
+                        .name(FragmentsRouter::CANONICAL_BRANCH_RESOURCE_NAME)
+                        .route(web::put().to(FragmentsRouter::set_canonical_branch))
+                        .route(web::delete().to(FragmentsRouter::clear_canonical_branch)),
+                )
+                .service(
+                    web::resource("/polls")
+                        .name(PollsRouter::COLLECTION_RESOURCE_NAME)
+                        .route(web::post().to(PollsRouter::create)),
+                )
+                .service(
+                    web::resource("/maintainers")
+                        .name(MaintainersRouter::COLLECTION_RESOURCE_NAME)
+                        .route(web::get().to(MaintainersRouter::list))
+                        .route(web::post().to(MaintainersRouter::invite)),
+                )
+                .service(
+                    web::scope("/maintainer_invitation")
+                        .service(
+                            web::resource("/acceptance")
+                                .name(MaintainersRouter::ACCEPTANCE_RESOURCE_NAME)
+                                .route(web::post().to(MaintainersRouter::accept)),
+                        )
+                        .service(
+                            web::resource("/rejection")
+                                .name(MaintainersRouter::REJECTION_RESOURCE_NAME)
+                                .route(web::post().to(MaintainersRouter::decline)),
+                        ),
+                )
+                .service(
+                    web::resource("/canonical_path")
+                        .name(FragmentsRouter::CANONICAL_PATH_RESOURCE_NAME)
+                        .route(web::get().to(FragmentsRouter::canonical_path)),
+                )
+                .service(
+                    web::resource("/live")
+                        .name(LiveRouter::FRAGMENT_RESOURCE_NAME)
+                        .route(web::get().to(LiveRouter::fragment)),
+                )
+                .service(
+                    web::resource("/tree")
+                        .name(FragmentsRouter::TREE_RESOURCE_NAME)
+                        .route(web::get().to(FragmentsRouter::tree)),
+                )
+                .service(
+                    web::scope("/fork_policy").service(
+                        web::resource(EMPTY_RESOURCE)
+                            .name(FragmentsRouter::FORK_POLICY_RESOURCE_NAME)
+                            .route(web::put().to(FragmentsRouter::set_fork_policy)),
+                    ),
+                )
                 .service(
                     web::scope("/likes").service(
                         web::resource(EMPTY_RESOURCE)
@@ -73,9 +303,63 @@ pub fn routes() -> Scope {
                                 .name(ReviewsRouter::COLLECTION_RESOURCE_NAME)
                                 .route(web::post().to(ReviewsRouter::create)),
                         )
-                        .service(web::scope("/{review_id}").service(
-                            web::resource(EMPTY_RESOURCE).name(ReviewsRouter::SINGLE_RESOURCE_NAME),
-                        )),
+                        .service(
+                            web::scope("/{review_id}")
+                                .service(
+                                    web::resource(EMPTY_RESOURCE)
+                                        .name(ReviewsRouter::SINGLE_RESOURCE_NAME),
+                                )
+                                .service(
+                                    web::resource("/comments")
+                                        .name(CommentsRouter::REVIEW_COLLECTION_RESOURCE_NAME)
+                                        .route(web::get().to(CommentsRouter::list_review))
+                                        .route(web::post().to(CommentsRouter::create_review)),
+                                ),
+                        ),
+                )
+                .service(
+                    web::scope("/comments")
+                        .service(
+                            web::resource(EMPTY_RESOURCE)
+                                .name(CommentsRouter::COLLECTION_RESOURCE_NAME)
+                                .route(web::get().to(CommentsRouter::list))
+                                .route(web::post().to(CommentsRouter::create)),
+                        )
+                        .service(
+                            web::scope("/{comment_id}")
+                                .service(
+                                    web::resource(EMPTY_RESOURCE)
+                                        .name(CommentsRouter::SINGLE_RESOURCE_NAME)
+                                        .route(web::patch().to(CommentsRouter::edit))
+                                        .route(web::delete().to(CommentsRouter::delete)),
+                                )
+                                .service(
+                                    web::resource("/moderation")
+                                        .name(CommentsRouter::MODERATION_RESOURCE_NAME)
+                                        .route(web::put().to(CommentsRouter::moderate)),
+                                ),
+                        ),
+                )
+                .service(
+                    web::scope("/suggestions")
+                        .service(
+                            web::resource(EMPTY_RESOURCE)
+                                .name(SuggestionsRouter::COLLECTION_RESOURCE_NAME)
+                                .route(web::get().to(SuggestionsRouter::list)),
+                        )
+                        .service(
+                            web::scope("/{suggestion_id}")
+                                .service(
+                                    web::resource("/acceptance")
+                                        .name(SuggestionsRouter::ACCEPTANCE_RESOURCE_NAME)
+                                        .route(web::post().to(SuggestionsRouter::accept)),
+                                )
+                                .service(
+                                    web::resource("/rejection")
+                                        .name(SuggestionsRouter::REJECTION_RESOURCE_NAME)
+                                        .route(web::post().to(SuggestionsRouter::reject)),
+                                ),
+                        ),
                 )
                 .service(
                     web::scope("/forks").service(
@@ -86,10 +370,106 @@ pub fn routes() -> Scope {
                 ),
         );
 
+    let stories = web::scope("/v1/stories")
+        .service(
+            web::resource(EMPTY_RESOURCE)
+                .name(StoriesRouter::COLLECTION_RESOURCE_NAME)
+                .route(web::get().to(StoriesRouter::list))
+                .route(web::post().to(StoriesRouter::create)),
+        )
+        .service(
+            web::resource("/{story_id}")
+                .name(StoriesRouter::SINGLE_RESOURCE_NAME)
+                .route(web::get().to(StoriesRouter::get))
+                .route(web::patch().to(StoriesRouter::update)),
+        )
+        .service(
+            web::resource("/{story_id}/tags")
+                .name(TagsRouter::STORY_COLLECTION_RESOURCE_NAME)
+                .route(web::get().to(TagsRouter::story_tags))
+                .route(web::post().to(TagsRouter::add_story_tag)),
+        )
+        .service(
+            web::resource("/{story_id}/tags/{tag}")
+                .name(TagsRouter::STORY_SINGLE_RESOURCE_NAME)
+                .route(web::delete().to(TagsRouter::remove_story_tag)),
+        );
+
+    let polls = web::scope("/v1/polls/{poll_id}")
+        .service(
+            web::resource(EMPTY_RESOURCE)
+                .name(PollsRouter::SINGLE_RESOURCE_NAME)
+                .route(web::get().to(PollsRouter::get)),
+        )
+        .service(
+            web::resource("/votes")
+                .name(PollsRouter::VOTES_RESOURCE_NAME)
+                .route(web::post().to(PollsRouter::vote)),
+        );
+
+    let tags = web::scope("/v1/tags")
+        .service(
+            web::resource(EMPTY_RESOURCE)
+                .name(TagsRouter::COLLECTION_RESOURCE_NAME)
+                .route(web::get().to(TagsRouter::list)),
+        )
+        .service(
+            web::resource("/{tag}/fragments")
+                .name(TagsRouter::FRAGMENTS_RESOURCE_NAME)
+                .route(web::get().to(TagsRouter::fragments)),
+        );
+
+    let webhooks = web::scope("/v1/webhooks")
+        .service(
+            web::resource(EMPTY_RESOURCE)
+                .name(WebhooksRouter::COLLECTION_RESOURCE_NAME)
+                .route(web::get().to(WebhooksRouter::list))
+                .route(web::post().to(WebhooksRouter::create)),
+        )
+        .service(
+            web::scope("/{webhook_id}")
+                .service(
+                    web::resource(EMPTY_RESOURCE)
+                        .name(WebhooksRouter::SINGLE_RESOURCE_NAME)
+                        .route(web::get().to(WebhooksRouter::get))
+                        .route(web::delete().to(WebhooksRouter::delete)),
+                )
+                .service(
+                    web::resource("/enable")
+                        .name(WebhooksRouter::ENABLE_RESOURCE_NAME)
+                        .route(web::post().to(WebhooksRouter::enable)),
+                )
+                .service(
+                    web::resource("/deliveries")
+                        .name(WebhooksRouter::DELIVERIES_RESOURCE_NAME)
+                        .route(web::get().to(WebhooksRouter::deliveries)),
+                )
+                .service(
+                    web::resource("/deliveries/{delivery_id}")
+                        .name(WebhooksRouter::DELIVERY_RESOURCE_NAME)
+                        .route(web::get().to(WebhooksRouter::delivery)),
+                ),
+        );
+
+    let unsubscribe = web::resource("/v1/unsubscribe/{token}")
+        .name(NotificationPreferencesRouter::UNSUBSCRIBE_RESOURCE_NAME)
+        .route(web::get().to(NotificationPreferencesRouter::confirm_unsubscribe))
+        .route(web::post().to(NotificationPreferencesRouter::unsubscribe));
+
     web::scope("")
         .service(
             web::resource(HealthRouter::HEALTH_RESOURCE_NAME)
                 .route(web::get().to(HealthRouter::get)),
         )
-        .service(web::scope("/api").service(fragments).service(users))
+        .service(
+            web::scope("/api")
+                .service(sessions)
+                .service(fragments)
+                .service(stories)
+                .service(tags)
+                .service(polls)
+                .service(users)
+                .service(webhooks)
+                .service(unsubscribe),
+        )
 }
@@ -0,0 +1,138 @@
+use super::user::UserPath;
+use crate::{
+    extractors::user::UserExtractor,
+    model::{
+        notification_preferences::{
+            NotificationPreferencesRequest, NotificationPreferencesResource, UnsubscribePath,
+        },
+        resource::SingleResource,
+    },
+    response::{ApiError, ApiResponse},
+    server::AppState,
+};
+use actix_web::{
+    http::header::ContentType,
+    web::{Data, Json},
+    HttpResponse,
+};
+use commons::{actor::Actor, auth::UnsubscribeToken, id::Id};
+use cqrs::{
+    command_bus::{
+        command::{
+            set_notification_preferences::{
+                SetNotificationPreferencesCommandBuilder, SetNotificationPreferencesCommandError,
+            },
+            unsubscribe_digest::{UnsubscribeDigestCommandBuilder, UnsubscribeDigestCommandError},
+        },
+        error::CommandBusError,
+    },
+    policy::{authorize, Action, Resource},
+};
+use storage::{
+    model::notification_preferences::NotificationPreferences,
+    query::notification_preferences::QueryNotificationPreferences,
+};
+
+/// Page behind the unsubscribe link, posting back to it. Opening the link alone must not
+/// unsubscribe, mail scanners follow links.
+const UNSUBSCRIBE_PAGE: &str = r#"<!DOCTYPE html>
+<html>
+  <body>
+    <p>Stop receiving email digests?</p>
+    <form method="post">
+      <button type="submit">Unsubscribe</button>
+    </form>
+  </body>
+</html>
+"#;
+
+pub struct NotificationPreferencesRouter;
+
+impl NotificationPreferencesRouter {
+    pub const SINGLE_RESOURCE_NAME: &'static str = "user_notification_preferences";
+    pub const UNSUBSCRIBE_RESOURCE_NAME: &'static str = "unsubscribe";
+
+    pub async fn get(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: UserPath,
+    ) -> ApiResponse<SingleResource<NotificationPreferencesResource>> {
+        let user_id: Id = path.into_inner().into();
+        if authorize(&user, Action::ReadNotifications, Resource::User(user_id)).is_err() {
+            return ApiError::Forbidden.into();
+        }
+
+        match NotificationPreferences::find(&state.pool, &user_id).await {
+            Ok(preferences) => ApiResponse::Ok(Some(Box::new(preferences.unwrap_or_else(|| {
+                NotificationPreferences::default_for(user_id, state.clock.now())
+            })))),
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    pub async fn update(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        Json(payload): Json<NotificationPreferencesRequest>,
+        path: UserPath,
+    ) -> ApiResponse<()> {
+        let command = SetNotificationPreferencesCommandBuilder::default()
+            .user_id(path.into_inner())
+            .email(payload.email)
+            .digest(payload.digest)
+            .muted_kinds(payload.muted_kinds)
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::SetNotificationPreferencesCommand(e) => match e {
+                    SetNotificationPreferencesCommandError::InvalidPreferences(_) => {
+                        ApiError::BadRequest.into()
+                    }
+                    SetNotificationPreferencesCommandError::Forbidden(_) => {
+                        ApiError::Forbidden.into()
+                    }
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    /// Confirmation page of the unsubscribe link of digests.
+    pub async fn confirm_unsubscribe(
+        state: Data<AppState>,
+        path: UnsubscribePath,
+    ) -> Result<HttpResponse, ApiError> {
+        let token = UnsubscribeToken::from(path.as_str());
+        match NotificationPreferences::find_by_token(&state.pool, &token).await {
+            Ok(Some(_)) => Ok(HttpResponse::Ok()
+                .content_type(ContentType::html())
+                .body(UNSUBSCRIBE_PAGE)),
+            Ok(None) => Err(ApiError::NotFound("Unsubscribe link not found")),
+            Err(e) => Err(ApiError::InternalServerError(e.into())),
+        }
+    }
+
+    /// Unsubscribes from digests, posted by the confirmation page or by mail clients
+    /// supporting one-click unsubscribe (RFC 8058). Works without a session.
+    pub async fn unsubscribe(state: Data<AppState>, path: UnsubscribePath) -> ApiResponse<()> {
+        let command = UnsubscribeDigestCommandBuilder::default()
+            .token(UnsubscribeToken::from(path.as_str()))
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(Actor::System, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::UnsubscribeDigestCommand(e) => match e {
+                    UnsubscribeDigestCommandError::InvalidToken => {
+                        ApiError::NotFound("Unsubscribe link not found").into()
+                    }
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+}
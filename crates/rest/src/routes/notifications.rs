@@ -0,0 +1,117 @@
+use super::user::UserPath;
+use crate::{
+    extractors::user::UserExtractor,
+    model::{
+        notifications::{
+            NotificationFilter, NotificationPath, NotificationResource, Notifications,
+        },
+        pagination::PageQuery,
+        resource::CollectionResource,
+    },
+    response::{ApiError, ApiResponse},
+    server::AppState,
+};
+use actix_web::web::{Data, Query};
+use commons::id::Id;
+use cqrs::{
+    command_bus::{
+        command::mark_notifications_read::{
+            MarkNotificationsReadCommandBuilder, MarkNotificationsReadCommandError,
+        },
+        error::CommandBusError,
+    },
+    policy::{authorize, Action, Resource},
+};
+use storage::{
+    model::{notification::Notification, user::User},
+    query::notification::QueryNotification,
+};
+
+pub struct NotificationsRouter;
+
+impl NotificationsRouter {
+    pub const COLLECTION_RESOURCE_NAME: &'static str = "user_notifications";
+    pub const READ_ALL_RESOURCE_NAME: &'static str = "user_notifications_read";
+    pub const READ_RESOURCE_NAME: &'static str = "user_notification_read";
+
+    pub async fn list(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: UserPath,
+        Query(page): Query<PageQuery>,
+        Query(filter): Query<NotificationFilter>,
+    ) -> ApiResponse<CollectionResource<NotificationResource>> {
+        let user_id: Id = path.into_inner().into();
+        if authorize(&user, Action::ReadNotifications, Resource::User(user_id)).is_err() {
+            return ApiError::Forbidden.into();
+        }
+        let page = page.normalized();
+
+        // One extra row tells whether there is a next page.
+        match Notification::find_by_user(
+            &state.pool,
+            &user_id,
+            filter.unread,
+            page.limit() + 1,
+            page.offset(),
+        )
+        .await
+        {
+            Ok(mut notifications) => {
+                let has_next = notifications.len() as i64 > page.limit();
+                notifications.truncate(page.limit() as usize);
+                ApiResponse::Ok(Some(Box::new(Notifications {
+                    user_id,
+                    page,
+                    filter,
+                    notifications,
+                    has_next,
+                })))
+            }
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    pub async fn read_all(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: UserPath,
+    ) -> ApiResponse<()> {
+        Self::mark_read(state, user, path.into_inner().into(), None).await
+    }
+
+    pub async fn read(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: NotificationPath,
+    ) -> ApiResponse<()> {
+        let (user_id, notification_id) = path.into_inner();
+        Self::mark_read(state, user, user_id, Some(notification_id)).await
+    }
+
+    async fn mark_read(
+        state: Data<AppState>,
+        user: User,
+        user_id: Id,
+        notification_id: Option<Id>,
+    ) -> ApiResponse<()> {
+        let command = MarkNotificationsReadCommandBuilder::default()
+            .user_id(user_id)
+            .notification_id(notification_id)
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::MarkNotificationsReadCommand(e) => match e {
+                    MarkNotificationsReadCommandError::NotificationNotFound(_) => {
+                        ApiError::NotFound("Notification not found").into()
+                    }
+                    MarkNotificationsReadCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+}
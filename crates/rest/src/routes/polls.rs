@@ -0,0 +1,111 @@
+use crate::{
+    extractors::user::UserExtractor,
+    links::ResourceLink,
+    model::{
+        fragments::FragmentPath,
+        polls::{CastVoteRequest, OpenPollRequest, PollPath, PollResource, PollWithResults},
+        resource::SingleResource,
+    },
+    response::{ApiError, ApiResponse},
+    server::AppState,
+};
+use actix_web::web::{Data, Json};
+use commons::id::Id;
+use cqrs::command_bus::{
+    command::{
+        cast_vote::{CastVoteCommandBuilder, CastVoteCommandError},
+        open_poll::{OpenPollCommandBuilder, OpenPollCommandError},
+    },
+    error::CommandBusError,
+};
+use storage::{model::poll::Poll, query::poll::QueryPoll};
+
+pub struct PollsRouter;
+
+impl PollsRouter {
+    pub const COLLECTION_RESOURCE_NAME: &'static str = "polls";
+    pub const SINGLE_RESOURCE_NAME: &'static str = "poll";
+    pub const VOTES_RESOURCE_NAME: &'static str = "poll_votes";
+
+    pub async fn get(
+        state: Data<AppState>,
+        path: PollPath,
+    ) -> ApiResponse<SingleResource<PollResource>> {
+        let poll = match Poll::find(&state.pool, &path.into_inner().into()).await {
+            Ok(Some(poll)) => poll,
+            Ok(None) => return ApiError::NotFound("Poll not found").into(),
+            Err(e) => return ApiError::InternalServerError(e.into()).into(),
+        };
+
+        match poll.results(&state.pool).await {
+            Ok(results) => ApiResponse::Ok(Some(Box::new(PollWithResults(poll, results)))),
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    /// Opens the poll, closed by the system when the window ends.
+    pub async fn create(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: FragmentPath,
+        Json(payload): Json<OpenPollRequest>,
+    ) -> ApiResponse<()> {
+        let poll_id = state.ids.new_id();
+        let command = OpenPollCommandBuilder::default()
+            .poll_id(poll_id)
+            .fragment_id(path.into_inner())
+            .closes_at(payload.closes_at)
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Created(None, Some(ResourceLink::Poll(poll_id))),
+            Err(e) => match e {
+                CommandBusError::OpenPollCommand(e) => match e {
+                    OpenPollCommandError::FragmentNotFound(_) => {
+                        ApiError::NotFound("Fragment not found").into()
+                    }
+                    OpenPollCommandError::AlreadyOpen(_) => {
+                        ApiError::Conflict("A poll is already open").into()
+                    }
+                    OpenPollCommandError::InvalidPoll(_) => ApiError::BadRequest.into(),
+                    OpenPollCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    pub async fn vote(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: PollPath,
+        Json(payload): Json<CastVoteRequest>,
+    ) -> ApiResponse<()> {
+        let poll_id: Id = path.into_inner().into();
+        let command = CastVoteCommandBuilder::default()
+            .poll_id(poll_id)
+            .child_id(payload.child_id)
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Created(None, Some(ResourceLink::Poll(poll_id))),
+            Err(e) => match e {
+                CommandBusError::CastVoteCommand(e) => match e {
+                    CastVoteCommandError::PollNotFound(_) => {
+                        ApiError::NotFound("Poll not found").into()
+                    }
+                    CastVoteCommandError::PollClosed(_) => {
+                        ApiError::Conflict("Poll is closed").into()
+                    }
+                    CastVoteCommandError::AlreadyVoted(_) => {
+                        ApiError::Conflict("Already voted").into()
+                    }
+                    CastVoteCommandError::InvalidChoice(_) => ApiError::BadRequest.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+}
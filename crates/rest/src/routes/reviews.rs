@@ -1,18 +1,50 @@
 use crate::{
-    extractors::user::UserExtractor,
+    extractors::user::{OptionalUserExtractor, UserExtractor},
     links::ResourceLink,
-    model::{fragments::FragmentPath, reviews::CreateReviewRequest},
+    model::{
+        fragments::FragmentPath,
+        resource::SingleResource,
+        reviews::{
+            CreateReviewRequest, ReviewContext, ReviewContextResource, ReviewQuorumResource,
+            ReviewSlaResource, SetReviewQuorumRequest, SetReviewSlaRequest,
+        },
+    },
     response::{ApiError, ApiResponse},
+    routes::fragments::find_visible,
     server::AppState,
 };
 use actix_web::web::{Data, Json};
-use cqrs::command_bus::command::review_fork::ReviewForkCommandBuilder;
+use commons::{id::Id, time::DateTime};
+use cqrs::{
+    command_bus::{
+        command::{
+            review_fork::{ReviewForkCommandBuilder, ReviewForkCommandError},
+            set_review_quorum::{SetReviewQuorumCommandBuilder, SetReviewQuorumCommandError},
+            set_review_sla::{SetReviewSlaCommandBuilder, SetReviewSlaCommandError},
+        },
+        error::CommandBusError,
+    },
+    policy::{authorize, Action, Resource},
+};
+use storage::{
+    model::{
+        fragment::Fragment, maintainer::Maintainer, review::Review, review_quorum::ReviewQuorum,
+        review_sla::ReviewSla, revision::Revision, user::User,
+    },
+    query::{
+        fragment::QueryFragment, maintainer::QueryMaintainer, review::QueryReview,
+        review_quorum::QueryReviewQuorum, review_sla::QueryReviewSla, revision::QueryRevision,
+    },
+};
 
 pub struct ReviewsRouter;
 
 impl ReviewsRouter {
     pub const COLLECTION_RESOURCE_NAME: &str = "reviews";
     pub const SINGLE_RESOURCE_NAME: &str = "review";
+    pub const CONTEXT_RESOURCE_NAME: &str = "review_context";
+    pub const QUORUM_RESOURCE_NAME: &str = "review_quorum";
+    pub const SLA_RESOURCE_NAME: &str = "review_sla";
 
     pub async fn create(
         state: Data<AppState>,
@@ -26,6 +58,7 @@ impl ReviewsRouter {
             .fragment_id(fragment_path.as_ref())
             .action(payload.action())
             .comment(payload.comment().clone())
+            .suggestions(payload.suggestions())
             .build()
             .unwrap();
 
@@ -37,7 +70,179 @@ impl ReviewsRouter {
                     review_id,
                 )),
             ),
+            Err(e) => match e {
+                CommandBusError::ReviewForkCommand(e) => match e {
+                    ReviewForkCommandError::FragmentNotFound(_) => {
+                        ApiError::NotFound("Fragment not found").into()
+                    }
+                    ReviewForkCommandError::InvalidState(_)
+                    | ReviewForkCommandError::InvalidSuggestion(_) => ApiError::BadRequest.into(),
+                    ReviewForkCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    /// Quorum of the story the fragment belongs to.
+    pub async fn quorum(
+        state: Data<AppState>,
+        OptionalUserExtractor(user): OptionalUserExtractor,
+        path: FragmentPath,
+    ) -> ApiResponse<SingleResource<ReviewQuorumResource>> {
+        let root_id = match find_visible(&state, user.as_ref(), &path.into_inner().into()).await {
+            Ok(fragment) => fragment.root_id(),
+            Err(e) => return e.into(),
+        };
+
+        match ReviewQuorum::find(&state.pool, &root_id).await {
+            Ok(quorum) => ApiResponse::Ok(Some(Box::new(
+                quorum.unwrap_or_else(|| ReviewQuorum::single(root_id, DateTime::now())),
+            ))),
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    pub async fn set_quorum(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: FragmentPath,
+        Json(payload): Json<SetReviewQuorumRequest>,
+    ) -> ApiResponse<()> {
+        let command = SetReviewQuorumCommandBuilder::default()
+            .fragment_id(path.into_inner())
+            .approvals(payload.approvals)
+            .veto(payload.veto)
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::SetReviewQuorumCommand(e) => match e {
+                    SetReviewQuorumCommandError::FragmentNotFound(_) => {
+                        ApiError::NotFound("Fragment not found").into()
+                    }
+                    SetReviewQuorumCommandError::InvalidQuorum(_) => ApiError::BadRequest.into(),
+                    SetReviewQuorumCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    /// Review SLA of the story the fragment belongs to.
+    pub async fn sla(
+        state: Data<AppState>,
+        OptionalUserExtractor(user): OptionalUserExtractor,
+        path: FragmentPath,
+    ) -> ApiResponse<SingleResource<ReviewSlaResource>> {
+        let root_id = match find_visible(&state, user.as_ref(), &path.into_inner().into()).await {
+            Ok(fragment) => fragment.root_id(),
+            Err(e) => return e.into(),
+        };
+
+        match ReviewSla::find(&state.pool, &root_id).await {
+            Ok(Some(sla)) => ApiResponse::Ok(Some(Box::new(sla))),
+            Ok(None) => ApiError::NotFound("Review SLA not found").into(),
             Err(e) => ApiError::InternalServerError(e.into()).into(),
         }
     }
+
+    pub async fn set_sla(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: FragmentPath,
+        Json(payload): Json<SetReviewSlaRequest>,
+    ) -> ApiResponse<()> {
+        let command = SetReviewSlaCommandBuilder::default()
+            .fragment_id(path.into_inner())
+            .window_hours(payload.window_hours)
+            .outcome(payload.outcome)
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::SetReviewSlaCommand(e) => match e {
+                    SetReviewSlaCommandError::FragmentNotFound(_) => {
+                        ApiError::NotFound("Fragment not found").into()
+                    }
+                    SetReviewSlaCommandError::InvalidSla(_) => ApiError::BadRequest.into(),
+                    SetReviewSlaCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    pub async fn context(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: FragmentPath,
+    ) -> ApiResponse<SingleResource<ReviewContextResource>> {
+        match review_context(&state, &user, path.into_inner().into()).await {
+            Ok(context) => ApiResponse::Ok(Some(Box::new(context))),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+async fn review_context(
+    state: &AppState,
+    user: &User,
+    fork_id: Id,
+) -> Result<ReviewContext, ApiError> {
+    let internal = |e: storage::StorageError| ApiError::InternalServerError(e.into());
+
+    let fork = Fragment::find(&state.pool, &fork_id)
+        .await
+        .map_err(internal)?
+        .filter(Fragment::is_fork)
+        .ok_or(ApiError::NotFound("Fork not found"))?;
+
+    if !fork.is_submitted() {
+        return Err(ApiError::BadRequest);
+    }
+
+    let parent = fork
+        .get_parent(&state.pool)
+        .await
+        .map_err(internal)?
+        .ok_or(ApiError::NotFound("Parent fragment not found"))?;
+
+    let maintainer = Maintainer::is_maintainer(&state.pool, &fork.root_id(), user.id())
+        .await
+        .map_err(internal)?;
+
+    authorize(
+        user,
+        Action::ViewReviewContext,
+        Resource::Fork {
+            fork: &fork,
+            parent: &parent,
+            maintainer,
+        },
+    )
+    .map_err(|_| ApiError::Forbidden)?;
+
+    let reviews = Review::find_by_fragment(&state.pool, &fork_id)
+        .await
+        .map_err(internal)?;
+    let revisions = Revision::find_since_last_review(&state.pool, &fork_id)
+        .await
+        .map_err(internal)?;
+    let quorum = ReviewQuorum::find(&state.pool, &fork.root_id())
+        .await
+        .map_err(internal)?
+        .unwrap_or_else(|| ReviewQuorum::single(fork.root_id(), DateTime::now()));
+
+    Ok(ReviewContext {
+        fork,
+        parent,
+        revisions,
+        reviews,
+        quorum,
+    })
 }
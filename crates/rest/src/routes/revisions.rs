@@ -0,0 +1,118 @@
+use crate::{
+    extractors::user::{OptionalUserExtractor, UserExtractor},
+    model::{
+        fragments::FragmentPath,
+        resource::{CollectionResource, SingleResource},
+        revisions::{DiffQuery, DiffResource, RevisionPath, RevisionResource, Revisions},
+    },
+    response::{ApiError, ApiResponse},
+    routes::fragments::find_visible,
+    server::AppState,
+};
+use actix_web::web::{Data, Query};
+use commons::id::Id;
+use cqrs::command_bus::{
+    command::revert_fragment::{RevertFragmentCommandBuilder, RevertFragmentCommandError},
+    error::CommandBusError,
+};
+use storage::{model::revision::Revision, query::revision::QueryRevision};
+
+pub struct RevisionsRouter;
+
+impl RevisionsRouter {
+    pub const COLLECTION_RESOURCE_NAME: &'static str = "fragment_revisions";
+    pub const SINGLE_RESOURCE_NAME: &'static str = "fragment_revision";
+    pub const DIFF_RESOURCE_NAME: &'static str = "fragment_revisions_diff";
+    pub const REVERT_RESOURCE_NAME: &'static str = "fragment_revision_revert";
+
+    pub async fn list(
+        state: Data<AppState>,
+        OptionalUserExtractor(user): OptionalUserExtractor,
+        path: FragmentPath,
+    ) -> ApiResponse<CollectionResource<RevisionResource>> {
+        let fragment_id: Id = path.into_inner().into();
+        if let Err(e) = find_visible(&state, user.as_ref(), &fragment_id).await {
+            return e.into();
+        }
+
+        match Revision::find_by_fragment(&state.pool, &fragment_id).await {
+            Ok(revisions) => ApiResponse::Ok(Some(Box::new(Revisions(fragment_id, revisions)))),
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    pub async fn get(
+        state: Data<AppState>,
+        OptionalUserExtractor(user): OptionalUserExtractor,
+        path: RevisionPath,
+    ) -> ApiResponse<SingleResource<RevisionResource>> {
+        let (fragment_id, number) = path.into_inner();
+        if let Err(e) = find_visible(&state, user.as_ref(), &fragment_id).await {
+            return e.into();
+        }
+
+        match Revision::find(&state.pool, &fragment_id, number).await {
+            Ok(Some(revision)) => ApiResponse::Ok(Some(Box::new(revision))),
+            Ok(None) => ApiError::NotFound("Revision not found").into(),
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    /// Word diff between two revisions, refused when they differ by more than
+    /// [`commons::diff::MAX_DIFF_WORDS`] words.
+    pub async fn diff(
+        state: Data<AppState>,
+        OptionalUserExtractor(user): OptionalUserExtractor,
+        path: FragmentPath,
+        Query(query): Query<DiffQuery>,
+    ) -> ApiResponse<SingleResource<DiffResource>> {
+        let fragment_id: Id = path.into_inner().into();
+        if let Err(e) = find_visible(&state, user.as_ref(), &fragment_id).await {
+            return e.into();
+        }
+
+        let from = Revision::find(&state.pool, &fragment_id, query.from).await;
+        let to = Revision::find(&state.pool, &fragment_id, query.to).await;
+
+        match (from, to) {
+            (Ok(Some(from)), Ok(Some(to))) => match DiffResource::new(&from, &to) {
+                Ok(diff) => ApiResponse::Ok(Some(Box::new(diff))),
+                Err(_) => ApiError::BadRequest.into(),
+            },
+            (Err(e), _) | (_, Err(e)) => ApiError::InternalServerError(e.into()).into(),
+            _ => ApiError::NotFound("Revision not found").into(),
+        }
+    }
+
+    pub async fn revert(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: RevisionPath,
+    ) -> ApiResponse<()> {
+        let (fragment_id, number) = path.into_inner();
+        let command = RevertFragmentCommandBuilder::default()
+            .fragment_id(fragment_id)
+            .revision(number)
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::RevertFragmentCommand(e) => match e {
+                    RevertFragmentCommandError::FragmentNotFound(_) => {
+                        ApiError::NotFound("Fragment not found").into()
+                    }
+                    RevertFragmentCommandError::RevisionNotFound(_) => {
+                        ApiError::NotFound("Revision not found").into()
+                    }
+                    RevertFragmentCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                    RevertFragmentCommandError::NonEditableFragment(_) => {
+                        ApiError::BadRequest.into()
+                    }
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{
+    extractors::session::SessionExtractor,
+    model::sessions::{CreateSessionRequest, SessionResource},
+    response::{ApiError, ApiResponse},
+    server::AppState,
+};
+use actix_web::web::{Data, Json};
+use chrono::Duration;
+use commons::auth::SessionToken;
+use storage::{
+    model::{credential::Credential, session::SessionBuilder},
+    query::{credential::QueryCredential, session::QuerySession},
+};
+
+pub struct SessionsRouter;
+
+impl SessionsRouter {
+    pub const COLLECTION_RESOURCE_NAME: &str = "sessions";
+
+    pub async fn create(
+        state: Data<AppState>,
+        Json(payload): Json<CreateSessionRequest>,
+    ) -> ApiResponse<SessionResource> {
+        let credential = match Credential::find_by_login(&state.pool, &payload.login).await {
+            Ok(Some(c)) if c.verify(&payload.password) => c,
+            Ok(_) => return ApiError::Unauthorized.into(),
+            Err(e) => return ApiError::InternalServerError(e.into()).into(),
+        };
+
+        let token = SessionToken::generate();
+        let now = state.clock.now();
+        let session = SessionBuilder::default()
+            .id(state.ids.new_id())
+            .user_id(*credential.user_id())
+            .token_hash(token.hash())
+            .created_at(now)
+            .expires_at(now + Duration::seconds(state.auth.session_ttl as i64))
+            .build()
+            .unwrap();
+
+        match session.save(&state.pool).await {
+            Ok(s) => ApiResponse::Created(
+                Some(Box::new(SessionResource::new(&token, *s.expires_at()))),
+                None,
+            ),
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    pub async fn delete(
+        state: Data<AppState>,
+        SessionExtractor(session): SessionExtractor,
+    ) -> ApiResponse<()> {
+        match session.delete(&state.pool).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    extractors::user::{OptionalUserExtractor, UserExtractor},
+    links::ResourceLink,
+    model::{
+        resource::{CollectionResource, SingleResource},
+        stories::{
+            CreateStoryRequest, Stories, StoriesQuery, StoryPath, StoryResource, UpdateStoryRequest,
+        },
+    },
+    response::{ApiError, ApiResponse},
+    routes::fragments::find_visible,
+    server::AppState,
+};
+use actix_web::web::{Data, Json, Query};
+use cqrs::command_bus::{
+    command::{
+        create_story::{CreateStoryCommandBuilder, CreateStoryCommandError},
+        update_story::{UpdateStoryCommandBuilder, UpdateStoryCommandError},
+    },
+    error::CommandBusError,
+};
+use storage::{model::story::Story, query::story::QueryStory};
+
+pub struct StoriesRouter;
+
+impl StoriesRouter {
+    pub const COLLECTION_RESOURCE_NAME: &'static str = "stories";
+    pub const SINGLE_RESOURCE_NAME: &'static str = "story";
+
+    pub async fn list(
+        state: Data<AppState>,
+        Query(query): Query<StoriesQuery>,
+    ) -> ApiResponse<CollectionResource<StoryResource>> {
+        match Story::find_published(&state.pool, &query.into()).await {
+            Ok(stories) => ApiResponse::Ok(Some(Box::new(Stories(stories)))),
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    /// Stories are only visible to those who can read their root fragment.
+    pub async fn get(
+        state: Data<AppState>,
+        OptionalUserExtractor(user): OptionalUserExtractor,
+        path: StoryPath,
+    ) -> ApiResponse<SingleResource<StoryResource>> {
+        let story = match Story::find(&state.pool, &path.into_inner().into()).await {
+            Ok(Some(story)) => story,
+            Ok(None) => return ApiError::NotFound("Story not found").into(),
+            Err(e) => return ApiError::InternalServerError(e.into()).into(),
+        };
+
+        match find_visible(&state, user.as_ref(), story.fragment_id()).await {
+            Ok(_) => ApiResponse::Ok(Some(Box::new(story))),
+            Err(ApiError::NotFound(_)) => ApiError::NotFound("Story not found").into(),
+            Err(e) => e.into(),
+        }
+    }
+
+    pub async fn create(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        Json(payload): Json<CreateStoryRequest>,
+    ) -> ApiResponse<()> {
+        let story_id = state.ids.new_id();
+        let command = CreateStoryCommandBuilder::default()
+            .story_id(story_id)
+            .fragment_id(payload.fragment_id)
+            .title(payload.title)
+            .synopsis(payload.synopsis)
+            .genres(payload.genres)
+            .language(payload.language)
+            .maturity(payload.maturity)
+            .cover_url(payload.cover_url)
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Created(None, Some(ResourceLink::Story(story_id))),
+            Err(e) => match e {
+                CommandBusError::CreateStoryCommand(e) => match e {
+                    CreateStoryCommandError::FragmentNotFound(_) => {
+                        ApiError::NotFound("Fragment not found").into()
+                    }
+                    CreateStoryCommandError::AlreadyExists(_) => {
+                        ApiError::Conflict("Fragment already has a story").into()
+                    }
+                    CreateStoryCommandError::InvalidStory(_) => ApiError::BadRequest.into(),
+                    CreateStoryCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    pub async fn update(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        Json(payload): Json<UpdateStoryRequest>,
+        path: StoryPath,
+    ) -> ApiResponse<()> {
+        let command = UpdateStoryCommandBuilder::default()
+            .story_id(path.into_inner())
+            .title(payload.title)
+            .synopsis(payload.synopsis)
+            .genres(payload.genres)
+            .language(payload.language)
+            .maturity(payload.maturity)
+            .cover_url(payload.cover_url)
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::UpdateStoryCommand(e) => match e {
+                    UpdateStoryCommandError::StoryNotFound(_) => {
+                        ApiError::NotFound("Story not found").into()
+                    }
+                    UpdateStoryCommandError::InvalidStory(_) => ApiError::BadRequest.into(),
+                    UpdateStoryCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+}
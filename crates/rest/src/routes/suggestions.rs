@@ -0,0 +1,146 @@
+use crate::{
+    extractors::user::UserExtractor,
+    model::{
+        fragments::FragmentPath,
+        resource::CollectionResource,
+        suggestions::{SuggestionPath, SuggestionResource, Suggestions},
+    },
+    response::{ApiError, ApiResponse},
+    server::AppState,
+};
+use actix_web::web::Data;
+use commons::id::Id;
+use cqrs::{
+    command_bus::{
+        command::{
+            accept_suggestion::{AcceptSuggestionCommandBuilder, AcceptSuggestionCommandError},
+            reject_suggestion::{RejectSuggestionCommandBuilder, RejectSuggestionCommandError},
+        },
+        error::CommandBusError,
+    },
+    policy::{authorize, Action, Resource},
+};
+use storage::{
+    model::{fragment::Fragment, maintainer::Maintainer, suggestion::Suggestion, user::User},
+    query::{fragment::QueryFragment, maintainer::QueryMaintainer, suggestion::QuerySuggestion},
+};
+
+pub struct SuggestionsRouter;
+
+impl SuggestionsRouter {
+    pub const COLLECTION_RESOURCE_NAME: &str = "suggestions";
+    pub const ACCEPTANCE_RESOURCE_NAME: &str = "suggestion_acceptance";
+    pub const REJECTION_RESOURCE_NAME: &str = "suggestion_rejection";
+
+    pub async fn list(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: FragmentPath,
+    ) -> ApiResponse<CollectionResource<SuggestionResource>> {
+        let fork_id: Id = path.into_inner().into();
+        match suggestions(&state, &user, fork_id).await {
+            Ok(suggestions) => ApiResponse::Ok(Some(Box::new(Suggestions(fork_id, suggestions)))),
+            Err(e) => e.into(),
+        }
+    }
+
+    pub async fn accept(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: SuggestionPath,
+    ) -> ApiResponse<()> {
+        let (fragment_id, suggestion_id) = path.into_inner();
+        let command = AcceptSuggestionCommandBuilder::default()
+            .fragment_id(fragment_id)
+            .suggestion_id(suggestion_id)
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::AcceptSuggestionCommand(e) => match e {
+                    AcceptSuggestionCommandError::SuggestionNotFound(_) => {
+                        ApiError::NotFound("Suggestion not found").into()
+                    }
+                    AcceptSuggestionCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                    AcceptSuggestionCommandError::AlreadyResolved(_)
+                    | AcceptSuggestionCommandError::NonEditableFragment(_) => {
+                        ApiError::BadRequest.into()
+                    }
+                    AcceptSuggestionCommandError::Outdated(_) => {
+                        ApiError::Conflict("Suggestion no longer matches the fragment content")
+                            .into()
+                    }
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    pub async fn reject(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: SuggestionPath,
+    ) -> ApiResponse<()> {
+        let (fragment_id, suggestion_id) = path.into_inner();
+        let command = RejectSuggestionCommandBuilder::default()
+            .fragment_id(fragment_id)
+            .suggestion_id(suggestion_id)
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::RejectSuggestionCommand(e) => match e {
+                    RejectSuggestionCommandError::SuggestionNotFound(_) => {
+                        ApiError::NotFound("Suggestion not found").into()
+                    }
+                    RejectSuggestionCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                    RejectSuggestionCommandError::AlreadyResolved(_) => ApiError::BadRequest.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+}
+
+/// Suggestions are visible to the same users as the review context.
+async fn suggestions(
+    state: &AppState,
+    user: &User,
+    fork_id: Id,
+) -> Result<Vec<Suggestion>, ApiError> {
+    let internal = |e: storage::StorageError| ApiError::InternalServerError(e.into());
+
+    let fork = Fragment::find(&state.pool, &fork_id)
+        .await
+        .map_err(internal)?
+        .filter(Fragment::is_fork)
+        .ok_or(ApiError::NotFound("Fork not found"))?;
+    let parent = fork
+        .get_parent(&state.pool)
+        .await
+        .map_err(internal)?
+        .ok_or(ApiError::NotFound("Parent fragment not found"))?;
+
+    let maintainer = Maintainer::is_maintainer(&state.pool, &fork.root_id(), user.id())
+        .await
+        .map_err(internal)?;
+
+    authorize(
+        user,
+        Action::ViewReviewContext,
+        Resource::Fork {
+            fork: &fork,
+            parent: &parent,
+            maintainer,
+        },
+    )
+    .map_err(|_| ApiError::Forbidden)?;
+
+    Suggestion::find_by_fragment(&state.pool, &fork_id)
+        .await
+        .map_err(internal)
+}
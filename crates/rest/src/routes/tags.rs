@@ -0,0 +1,236 @@
+use crate::{
+    extractors::user::{OptionalUserExtractor, UserExtractor},
+    links::ResourceLink,
+    model::{
+        fragments::{FragmentPath, FragmentResource},
+        pagination::PageQuery,
+        resource::CollectionResource,
+        stories::StoryPath,
+        tags::{
+            TagCountResource, TagCounts, TagPath, TagRequest, TagResource, TaggedFragments,
+            TargetTagPath, TargetTags,
+        },
+    },
+    response::{ApiError, ApiResponse},
+    routes::fragments::find_visible,
+    server::AppState,
+};
+use actix_web::web::{Data, Json, Query};
+use commons::{id::Id, tag::Tag};
+use cqrs::command_bus::{
+    command::{
+        add_tag::{AddTagCommandBuilder, AddTagCommandError},
+        remove_tag::{RemoveTagCommandBuilder, RemoveTagCommandError},
+    },
+    error::CommandBusError,
+};
+use storage::{
+    model::{
+        story::Story,
+        tag::{TagTarget, Tagging},
+        user::User,
+    },
+    query::{story::QueryStory, tag::QueryTagging},
+};
+
+pub struct TagsRouter;
+
+impl TagsRouter {
+    pub const COLLECTION_RESOURCE_NAME: &'static str = "tags";
+    pub const FRAGMENTS_RESOURCE_NAME: &'static str = "tag_fragments";
+    pub const FRAGMENT_COLLECTION_RESOURCE_NAME: &'static str = "fragment_tags";
+    pub const FRAGMENT_SINGLE_RESOURCE_NAME: &'static str = "fragment_tag";
+    pub const STORY_COLLECTION_RESOURCE_NAME: &'static str = "story_tags";
+    pub const STORY_SINGLE_RESOURCE_NAME: &'static str = "story_tag";
+
+    pub async fn list(state: Data<AppState>) -> ApiResponse<CollectionResource<TagCountResource>> {
+        match Tagging::counts(&state.pool).await {
+            Ok(counts) => ApiResponse::Ok(Some(Box::new(TagCounts(counts)))),
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    pub async fn fragments(
+        state: Data<AppState>,
+        path: TagPath,
+        Query(page): Query<PageQuery>,
+    ) -> ApiResponse<CollectionResource<FragmentResource>> {
+        let Ok(tag) = Tag::parse(&path.into_inner()) else {
+            return ApiError::BadRequest.into();
+        };
+        let page = page.normalized();
+
+        // One extra row tells whether there is a next page.
+        match Tagging::fragments_tagged(&state.pool, &tag, page.limit() + 1, page.offset()).await {
+            Ok(mut fragments) => {
+                let has_next = fragments.len() as i64 > page.limit();
+                fragments.truncate(page.limit() as usize);
+                ApiResponse::Ok(Some(Box::new(TaggedFragments {
+                    tag,
+                    page,
+                    fragments,
+                    has_next,
+                })))
+            }
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    pub async fn fragment_tags(
+        state: Data<AppState>,
+        OptionalUserExtractor(user): OptionalUserExtractor,
+        path: FragmentPath,
+    ) -> ApiResponse<CollectionResource<TagResource>> {
+        let fragment_id: Id = path.into_inner().into();
+        match find_visible(&state, user.as_ref(), &fragment_id).await {
+            Ok(_) => Self::target_tags(state, TagTarget::Fragment, fragment_id).await,
+            Err(e) => e.into(),
+        }
+    }
+
+    pub async fn story_tags(
+        state: Data<AppState>,
+        path: StoryPath,
+    ) -> ApiResponse<CollectionResource<TagResource>> {
+        let story_id: Id = path.into_inner().into();
+        match Story::find(&state.pool, &story_id).await {
+            Ok(Some(_)) => Self::target_tags(state, TagTarget::Story, story_id).await,
+            Ok(None) => ApiError::NotFound("Story not found").into(),
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    pub async fn add_fragment_tag(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: FragmentPath,
+        Json(payload): Json<TagRequest>,
+    ) -> ApiResponse<()> {
+        Self::add(
+            state,
+            user,
+            TagTarget::Fragment,
+            path.into_inner().into(),
+            payload.tag,
+        )
+        .await
+    }
+
+    pub async fn add_story_tag(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: StoryPath,
+        Json(payload): Json<TagRequest>,
+    ) -> ApiResponse<()> {
+        Self::add(
+            state,
+            user,
+            TagTarget::Story,
+            path.into_inner().into(),
+            payload.tag,
+        )
+        .await
+    }
+
+    pub async fn remove_fragment_tag(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: TargetTagPath,
+    ) -> ApiResponse<()> {
+        let (fragment_id, tag) = path.into_inner();
+        Self::remove(state, user, TagTarget::Fragment, fragment_id, tag).await
+    }
+
+    pub async fn remove_story_tag(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: TargetTagPath,
+    ) -> ApiResponse<()> {
+        let (story_id, tag) = path.into_inner();
+        Self::remove(state, user, TagTarget::Story, story_id, tag).await
+    }
+
+    async fn target_tags(
+        state: Data<AppState>,
+        target: TagTarget,
+        target_id: Id,
+    ) -> ApiResponse<CollectionResource<TagResource>> {
+        match Tagging::find_by_target(&state.pool, target, &target_id).await {
+            Ok(tags) => ApiResponse::Ok(Some(Box::new(TargetTags(target, target_id, tags)))),
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    async fn add(
+        state: Data<AppState>,
+        user: User,
+        target: TagTarget,
+        target_id: Id,
+        tag: String,
+    ) -> ApiResponse<()> {
+        let command = AddTagCommandBuilder::default()
+            .target(target)
+            .target_id(target_id)
+            .tag(tag)
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Created(None, Some(target_tags_link(target, target_id))),
+            Err(e) => match e {
+                CommandBusError::AddTagCommand(e) => match e {
+                    AddTagCommandError::TargetNotFound(_) => {
+                        ApiError::NotFound(not_found(target)).into()
+                    }
+                    AddTagCommandError::InvalidTag(_) => ApiError::BadRequest.into(),
+                    AddTagCommandError::TooManyTags => ApiError::Conflict("Too many tags").into(),
+                    AddTagCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    async fn remove(
+        state: Data<AppState>,
+        user: User,
+        target: TagTarget,
+        target_id: Id,
+        tag: String,
+    ) -> ApiResponse<()> {
+        let command = RemoveTagCommandBuilder::default()
+            .target(target)
+            .target_id(target_id)
+            .tag(tag)
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::RemoveTagCommand(e) => match e {
+                    RemoveTagCommandError::TargetNotFound(_) => {
+                        ApiError::NotFound(not_found(target)).into()
+                    }
+                    RemoveTagCommandError::InvalidTag(_) => ApiError::BadRequest.into(),
+                    RemoveTagCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+}
+
+fn target_tags_link(target: TagTarget, target_id: Id) -> ResourceLink {
+    match target {
+        TagTarget::Fragment => ResourceLink::FragmentTags(target_id),
+        TagTarget::Story => ResourceLink::StoryTags(target_id),
+    }
+}
+
+fn not_found(target: TagTarget) -> &'static str {
+    match target {
+        TagTarget::Fragment => "Fragment not found",
+        TagTarget::Story => "Story not found",
+    }
+}
@@ -0,0 +1,124 @@
+use super::user::UserPath;
+use crate::{
+    extractors::user::UserExtractor,
+    links::ResourceLink,
+    model::{
+        resource::CollectionResource,
+        trusted_contributors::{
+            TrustContributorRequest, TrustedContributorPath, TrustedContributorResource,
+            TrustedContributors,
+        },
+    },
+    response::{ApiError, ApiResponse},
+    server::AppState,
+};
+use actix_web::web::{Data, Json};
+use commons::id::Id;
+use cqrs::{
+    command_bus::{
+        command::{
+            trust_contributor::{TrustContributorCommandBuilder, TrustContributorCommandError},
+            untrust_contributor::{
+                UntrustContributorCommandBuilder, UntrustContributorCommandError,
+            },
+        },
+        error::CommandBusError,
+    },
+    policy::{authorize, Action, Resource},
+};
+use storage::{
+    model::trusted_contributor::TrustedContributor,
+    query::trusted_contributor::QueryTrustedContributor,
+};
+
+pub struct TrustedContributorsRouter;
+
+impl TrustedContributorsRouter {
+    pub const COLLECTION_RESOURCE_NAME: &'static str = "trusted_contributors";
+    pub const SINGLE_RESOURCE_NAME: &'static str = "trusted_contributor";
+
+    pub async fn list(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: UserPath,
+    ) -> ApiResponse<CollectionResource<TrustedContributorResource>> {
+        let author_id: Id = path.into_inner().into();
+        if authorize(
+            &user,
+            Action::ManageTrustedContributors,
+            Resource::User(author_id),
+        )
+        .is_err()
+        {
+            return ApiError::Forbidden.into();
+        }
+
+        match TrustedContributor::find_by_author(&state.pool, &author_id).await {
+            Ok(trusted) => ApiResponse::Ok(Some(Box::new(TrustedContributors(author_id, trusted)))),
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    pub async fn create(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: UserPath,
+        Json(payload): Json<TrustContributorRequest>,
+    ) -> ApiResponse<()> {
+        let author_id: Id = path.into_inner().into();
+        let command = TrustContributorCommandBuilder::default()
+            .author_id(author_id)
+            .contributor_id(payload.contributor_id)
+            .auto_publish(payload.auto_publish)
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Created(
+                None,
+                Some(ResourceLink::TrustedContributor(
+                    author_id,
+                    payload.contributor_id,
+                )),
+            ),
+            Err(e) => match e {
+                CommandBusError::TrustContributorCommand(e) => match e {
+                    TrustContributorCommandError::UserNotFound(_) => {
+                        ApiError::NotFound("User not found").into()
+                    }
+                    TrustContributorCommandError::InvalidContributor(_) => {
+                        ApiError::BadRequest.into()
+                    }
+                    TrustContributorCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    pub async fn delete(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: TrustedContributorPath,
+    ) -> ApiResponse<()> {
+        let (author_id, contributor_id) = path.into_inner();
+        let command = UntrustContributorCommandBuilder::default()
+            .author_id(author_id)
+            .contributor_id(contributor_id)
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::UntrustContributorCommand(e) => match e {
+                    UntrustContributorCommandError::NotTrusted(_) => {
+                        ApiError::NotFound("Trusted contributor not found").into()
+                    }
+                    UntrustContributorCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+}
@@ -1,5 +1,24 @@
-use crate::links::SingleIdPath;
-use actix_web::web::Path;
+use crate::{
+    extractors::user::UserExtractor,
+    links::{ResourceLink, SingleIdPath},
+    model::{
+        resource::SingleResource,
+        users::{AssignRoleRequest, RegisterUserRequest, UpdateProfileRequest, UserResource},
+    },
+    response::{ApiError, ApiResponse},
+    server::AppState,
+};
+use actix_web::web::{Data, Json, Path};
+use commons::{actor::Actor, auth::PasswordHash};
+use cqrs::command_bus::{
+    command::{
+        assign_role::{AssignRoleCommandBuilder, AssignRoleCommandError},
+        register_user::{RegisterUserCommandBuilder, RegisterUserCommandError},
+        update_profile::{UpdateProfileCommandBuilder, UpdateProfileCommandError},
+    },
+    error::CommandBusError,
+};
+use storage::{model::user::User, query::user::QueryUser};
 
 pub type UserPath = Path<SingleIdPath>;
 
@@ -8,4 +27,106 @@ pub struct UsersRouter;
 impl UsersRouter {
     pub const COLLECTION_RESOURCE_NAME: &'static str = "users";
     pub const SINGLE_RESOURCE_NAME: &'static str = "user";
+    pub const ROLE_RESOURCE_NAME: &'static str = "user_role";
+
+    pub async fn create(
+        state: Data<AppState>,
+        Json(payload): Json<RegisterUserRequest>,
+    ) -> ApiResponse<()> {
+        let password_hash = match PasswordHash::generate(&payload.password) {
+            Ok(hash) => hash,
+            Err(e) => return ApiError::InternalServerError(e.into()).into(),
+        };
+        let user_id = state.ids.new_id();
+        let command = RegisterUserCommandBuilder::default()
+            .user_id(user_id)
+            .username(payload.username)
+            .password_hash(password_hash)
+            .display_name(payload.display_name)
+            .build()
+            .unwrap();
+
+        match state
+            .command_bus
+            .execute(Actor::User(user_id), command)
+            .await
+        {
+            Ok(_) => ApiResponse::Created(None, Some(ResourceLink::User(user_id))),
+            Err(e) => match e {
+                CommandBusError::RegisterUserCommand(e) => match e {
+                    RegisterUserCommandError::InvalidUsername(_) => ApiError::BadRequest.into(),
+                    RegisterUserCommandError::UsernameTaken(_) => {
+                        ApiError::Conflict("Username already taken").into()
+                    }
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    pub async fn get(
+        state: Data<AppState>,
+        path: UserPath,
+    ) -> ApiResponse<SingleResource<UserResource>> {
+        match User::find(&state.pool, &path.into_inner().into()).await {
+            Ok(Some(user)) => ApiResponse::Ok(Some(Box::new(user))),
+            Ok(None) => ApiError::NotFound("User not found").into(),
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    pub async fn update(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        Json(payload): Json<UpdateProfileRequest>,
+        path: UserPath,
+    ) -> ApiResponse<()> {
+        let command = UpdateProfileCommandBuilder::default()
+            .user_id(path.into_inner())
+            .display_name(payload.display_name)
+            .bio(payload.bio)
+            .avatar_url(payload.avatar_url.map(String::from))
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::UpdateProfileCommand(e) => match e {
+                    UpdateProfileCommandError::UserNotFound(_) => {
+                        ApiError::NotFound("User not found").into()
+                    }
+                    UpdateProfileCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    pub async fn assign_role(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        Json(payload): Json<AssignRoleRequest>,
+        path: UserPath,
+    ) -> ApiResponse<()> {
+        let command = AssignRoleCommandBuilder::default()
+            .user_id(path.into_inner())
+            .role(payload.role)
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::ActorNotSupported(_) => ApiError::Forbidden.into(),
+                CommandBusError::AssignRoleCommand(e) => match e {
+                    AssignRoleCommandError::UserNotFound(_) => {
+                        ApiError::NotFound("User not found").into()
+                    }
+                    AssignRoleCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
 }
@@ -0,0 +1,218 @@
+use crate::{
+    extractors::user::UserExtractor,
+    links::ResourceLink,
+    model::{
+        pagination::PageQuery,
+        resource::{CollectionResource, SingleResource},
+        webhooks::{
+            CreateWebhookRequest, WebhookDeliveries, WebhookDeliveryPath, WebhookDeliveryResource,
+            WebhookDeliveryWithAttempts, WebhookPath, WebhookResource, Webhooks,
+        },
+    },
+    response::{ApiError, ApiResponse},
+    server::AppState,
+};
+use actix_web::web::{Data, Json, Query};
+use commons::id::Id;
+use cqrs::{
+    command_bus::{
+        command::{
+            create_webhook::{CreateWebhookCommandBuilder, CreateWebhookCommandError},
+            delete_webhook::{DeleteWebhookCommandBuilder, DeleteWebhookCommandError},
+            enable_webhook::{EnableWebhookCommandBuilder, EnableWebhookCommandError},
+        },
+        error::CommandBusError,
+    },
+    policy::{authorize, Action, Resource},
+};
+use storage::{
+    model::{
+        user::User,
+        webhook::{Webhook, WebhookAttempt, WebhookDelivery},
+    },
+    query::webhook::{QueryWebhook, QueryWebhookAttempt, QueryWebhookDelivery},
+};
+
+pub struct WebhooksRouter;
+
+impl WebhooksRouter {
+    pub const COLLECTION_RESOURCE_NAME: &'static str = "webhooks";
+    pub const SINGLE_RESOURCE_NAME: &'static str = "webhook";
+    pub const ENABLE_RESOURCE_NAME: &'static str = "webhook_enable";
+    pub const DELIVERIES_RESOURCE_NAME: &'static str = "webhook_deliveries";
+    pub const DELIVERY_RESOURCE_NAME: &'static str = "webhook_delivery";
+
+    /// Webhooks owned by the current user.
+    pub async fn list(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+    ) -> ApiResponse<CollectionResource<WebhookResource>> {
+        match Webhook::find_by_owner(&state.pool, user.id()).await {
+            Ok(webhooks) => ApiResponse::Ok(Some(Box::new(Webhooks(webhooks)))),
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    pub async fn get(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: WebhookPath,
+    ) -> ApiResponse<SingleResource<WebhookResource>> {
+        match Self::find_owned(&state, &user, &path.into_inner().into()).await {
+            Ok(webhook) => ApiResponse::Ok(Some(Box::new(webhook))),
+            Err(e) => e.into(),
+        }
+    }
+
+    pub async fn create(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        Json(payload): Json<CreateWebhookRequest>,
+    ) -> ApiResponse<()> {
+        let webhook_id = state.ids.new_id();
+        let command = CreateWebhookCommandBuilder::default()
+            .webhook_id(webhook_id)
+            .url(payload.url)
+            .secret(payload.secret)
+            .event_types(payload.event_types)
+            .story_id(payload.story_id)
+            .allowed_hosts(state.webhooks.allowed_hosts.clone())
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Created(None, Some(ResourceLink::Webhook(webhook_id))),
+            Err(e) => match e {
+                CommandBusError::CreateWebhookCommand(e) => match e {
+                    CreateWebhookCommandError::StoryNotFound(_) => {
+                        ApiError::NotFound("Story not found").into()
+                    }
+                    CreateWebhookCommandError::InvalidWebhook(_) => ApiError::BadRequest.into(),
+                    CreateWebhookCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    pub async fn delete(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: WebhookPath,
+    ) -> ApiResponse<()> {
+        let command = DeleteWebhookCommandBuilder::default()
+            .webhook_id(path.into_inner())
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::DeleteWebhookCommand(e) => match e {
+                    DeleteWebhookCommandError::WebhookNotFound(_) => {
+                        ApiError::NotFound("Webhook not found").into()
+                    }
+                    DeleteWebhookCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    pub async fn enable(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: WebhookPath,
+    ) -> ApiResponse<()> {
+        let command = EnableWebhookCommandBuilder::default()
+            .webhook_id(path.into_inner())
+            .build()
+            .unwrap();
+
+        match state.command_bus.execute(user, command).await {
+            Ok(_) => ApiResponse::Ok(None),
+            Err(e) => match e {
+                CommandBusError::EnableWebhookCommand(e) => match e {
+                    EnableWebhookCommandError::WebhookNotFound(_) => {
+                        ApiError::NotFound("Webhook not found").into()
+                    }
+                    EnableWebhookCommandError::Forbidden(_) => ApiError::Forbidden.into(),
+                },
+                _ => ApiError::InternalServerError(e.into()).into(),
+            },
+        }
+    }
+
+    pub async fn deliveries(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: WebhookPath,
+        Query(page): Query<PageQuery>,
+    ) -> ApiResponse<CollectionResource<WebhookDeliveryResource>> {
+        let webhook_id: Id = path.into_inner().into();
+        if let Err(e) = Self::find_owned(&state, &user, &webhook_id).await {
+            return e.into();
+        }
+        let page = page.normalized();
+
+        // One extra row tells whether there is a next page.
+        match WebhookDelivery::find_by_webhook(
+            &state.pool,
+            &webhook_id,
+            page.limit() + 1,
+            page.offset(),
+        )
+        .await
+        {
+            Ok(mut deliveries) => {
+                let has_next = deliveries.len() as i64 > page.limit();
+                deliveries.truncate(page.limit() as usize);
+                ApiResponse::Ok(Some(Box::new(WebhookDeliveries {
+                    webhook_id,
+                    page,
+                    deliveries,
+                    has_next,
+                })))
+            }
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    pub async fn delivery(
+        state: Data<AppState>,
+        UserExtractor(user): UserExtractor,
+        path: WebhookDeliveryPath,
+    ) -> ApiResponse<SingleResource<WebhookDeliveryResource>> {
+        let (webhook_id, delivery_id) = path.into_inner();
+        if let Err(e) = Self::find_owned(&state, &user, &webhook_id).await {
+            return e.into();
+        }
+
+        let delivery = match WebhookDelivery::find(&state.pool, &delivery_id).await {
+            Ok(Some(delivery)) if *delivery.webhook_id() == webhook_id => delivery,
+            Ok(_) => return ApiError::NotFound("Delivery not found").into(),
+            Err(e) => return ApiError::InternalServerError(e.into()).into(),
+        };
+        match WebhookAttempt::find_by_delivery(&state.pool, &delivery_id).await {
+            Ok(attempts) => ApiResponse::Ok(Some(Box::new(WebhookDeliveryWithAttempts(
+                delivery, attempts,
+            )))),
+            Err(e) => ApiError::InternalServerError(e.into()).into(),
+        }
+    }
+
+    /// Webhook the user is allowed to see.
+    async fn find_owned(state: &AppState, user: &User, id: &Id) -> Result<Webhook, ApiError> {
+        let webhook = Webhook::find(&state.pool, id)
+            .await
+            .map_err(|e| ApiError::InternalServerError(e.into()))?
+            .ok_or(ApiError::NotFound("Webhook not found"))?;
+        authorize(
+            user,
+            Action::ManageWebhooks,
+            Resource::User(*webhook.owner_id()),
+        )
+        .map_err(|_| ApiError::Forbidden)?;
+        Ok(webhook)
+    }
+}
@@ -1,21 +1,41 @@
-use crate::routes::routes;
+use crate::{
+    jobs::{
+        spawn_digest_job, spawn_event_listener, spawn_purge_job, spawn_task_job, spawn_webhook_job,
+    },
+    live::EventHub,
+    routes::routes,
+};
 use actix_web::web::Data;
 use actix_web::{dev, App, HttpServer};
 use commons::{
-    configuration::settings::Settings,
+    actor::{Actor, Role},
+    configuration::settings::{
+        AuthSettings, LiveSettings, ReviewSettings, Settings, WebhookSettings,
+    },
     id::{IdGenerator, StdIdGenerator},
-    time::SystemClock,
+    mail::mailer_from_settings,
+    time::{Clock, SystemClock},
+};
+use cqrs::{
+    command_bus::{bus::CommandBus, command::assign_role::AssignRoleCommandBuilder},
+    digest::DigestJob,
+    webhooks::WebhookDeliveryJob,
 };
-use cqrs::command_bus::bus::CommandBus;
 use sqlx::PgPool;
 use std::{net::TcpListener, sync::Arc};
-use storage::pool_from_settings;
+use storage::{model::user::User, pool_from_settings, query::user::QueryUser};
 
 #[derive(Clone)]
 pub struct AppState {
     pub command_bus: Arc<CommandBus>,
     pub ids: Arc<dyn IdGenerator>,
+    pub clock: Arc<dyn Clock>,
     pub pool: PgPool,
+    pub auth: AuthSettings,
+    pub review: ReviewSettings,
+    pub events: EventHub,
+    pub live: LiveSettings,
+    pub webhooks: WebhookSettings,
 }
 
 #[macro_export]
@@ -65,15 +85,50 @@ pub struct Server(dev::Server);
 impl Server {
     pub async fn from_settings(settings: &Settings) -> Result<Self, anyhow::Error> {
         let ids = Arc::new(StdIdGenerator);
+        let clock = Arc::new(SystemClock);
         let pool = pool_from_settings(settings).await?;
-        let state = AppState {
-            command_bus: Arc::new(CommandBus::new(
+        let command_bus = Arc::new(CommandBus::new(pool.clone(), clock.clone(), ids.clone()));
+        if let Some(username) = &settings.auth.admin_username {
+            promote_admin(&pool, &command_bus, username).await?;
+        }
+        spawn_purge_job(
+            command_bus.clone(),
+            clock.clone(),
+            settings.retention.clone(),
+        );
+        spawn_task_job(command_bus.clone(), settings.tasks.clone());
+        spawn_digest_job(
+            DigestJob::new(
                 pool.clone(),
-                Arc::new(SystemClock),
+                command_bus.clone(),
+                mailer_from_settings(&settings.mail)?,
+                clock.clone(),
+                settings.mail.public_url.clone(),
+            ),
+            settings.mail.clone(),
+        );
+        spawn_webhook_job(
+            WebhookDeliveryJob::new(
+                pool.clone(),
+                command_bus.clone(),
+                clock.clone(),
                 ids.clone(),
-            )),
-            ids: ids,
+                settings.webhooks.clone(),
+            ),
+            settings.webhooks.clone(),
+        );
+        let events = EventHub::new(settings.live.buffer);
+        spawn_event_listener(pool.clone(), events.clone());
+        let state = AppState {
+            command_bus,
+            ids,
+            clock,
             pool: pool.clone(),
+            auth: settings.auth.clone(),
+            review: settings.review.clone(),
+            events,
+            live: settings.live.clone(),
+            webhooks: settings.webhooks.clone(),
         };
 
         Ok(Self(
@@ -91,3 +146,25 @@ impl Server {
         TcpListener::bind(format!("{}:{}", settings.server.host, settings.server.port))
     }
 }
+
+/// Makes the configured user an admin. Users register through the API with the default role, so
+/// this is how the first admin is appointed; later admins can be assigned by that one.
+async fn promote_admin(
+    pool: &PgPool,
+    command_bus: &CommandBus,
+    username: &str,
+) -> Result<(), anyhow::Error> {
+    let username = username.trim().to_lowercase();
+    let Some(user) = User::find_by_username(pool, &username).await? else {
+        tracing::warn!("Admin [{username}] is not registered, restart once they have registered");
+        return Ok(());
+    };
+
+    let command = AssignRoleCommandBuilder::default()
+        .user_id(*user.id())
+        .role(Role::Admin)
+        .build()
+        .unwrap();
+    command_bus.execute(Actor::System, command).await?;
+    Ok(())
+}
@@ -0,0 +1,22 @@
+create table credentials(
+    user_id             uuid            not null,
+    login               varchar         not null,
+    password_hash       varchar         not null,
+    created_at          timestamp       not null,
+
+    constraint credentials_pk primary key (user_id),
+    constraint credentials_uk_login unique (login),
+    constraint credentials_fk_user foreign key (user_id) references users(id)
+);
+
+create table sessions(
+    id                  uuid            not null,
+    user_id             uuid            not null,
+    token_hash          varchar         not null,
+    created_at          timestamp       not null,
+    expires_at          timestamp       not null,
+
+    constraint sessions_pk primary key (id),
+    constraint sessions_uk_token_hash unique (token_hash),
+    constraint sessions_fk_user foreign key (user_id) references users(id)
+);
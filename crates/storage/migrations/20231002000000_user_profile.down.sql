@@ -0,0 +1,7 @@
+alter table users
+    drop constraint if exists users_uk_username,
+    drop column if exists username,
+    drop column if exists display_name,
+    drop column if exists bio,
+    drop column if exists avatar_url,
+    drop column if exists created_at;
@@ -0,0 +1,20 @@
+ALTER TYPE event_type ADD VALUE 'user_registered';
+ALTER TYPE event_type ADD VALUE 'profile_updated';
+ALTER TYPE command_type ADD VALUE 'register_user';
+ALTER TYPE command_type ADD VALUE 'update_profile';
+
+alter table users
+    add column username         varchar         null,
+    add column display_name     varchar         null,
+    add column bio              varchar         null,
+    add column avatar_url       varchar         null,
+    add column created_at       timestamp       null;
+
+update users set 
+    username = 'user_' || replace(id::text, '-', ''),
+    created_at = now() at time zone 'utc';
+
+alter table users
+    alter column username set not null,
+    alter column created_at set not null,
+    add constraint users_uk_username unique (username);
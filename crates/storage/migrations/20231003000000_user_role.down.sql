@@ -0,0 +1,4 @@
+alter table users
+    drop column if exists role;
+
+drop type if exists user_role;
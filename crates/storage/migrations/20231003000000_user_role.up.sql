@@ -0,0 +1,6 @@
+CREATE TYPE user_role AS ENUM ('user', 'moderator', 'admin');
+ALTER TYPE event_type ADD VALUE 'role_assigned';
+ALTER TYPE command_type ADD VALUE 'assign_role';
+
+alter table users
+    add column role             user_role       not null default 'user';
@@ -0,0 +1,6 @@
+drop table if exists fork_invites;
+
+alter table fragments
+    drop column if exists fork_policy;
+
+drop type if exists fork_policy;
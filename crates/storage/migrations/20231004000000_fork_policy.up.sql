@@ -0,0 +1,16 @@
+CREATE TYPE fork_policy AS ENUM ('anyone', 'followers', 'mutual_friends', 'invite_only', 'locked');
+ALTER TYPE event_type ADD VALUE 'fork_policy_changed';
+ALTER TYPE command_type ADD VALUE 'set_fork_policy';
+
+alter table fragments
+    add column fork_policy      fork_policy     not null default 'mutual_friends';
+
+create table fork_invites(
+    fragment_id         uuid            not null,
+    user_id             uuid            not null,
+    created_at          timestamp       not null,
+
+    constraint fork_invites_pk primary key (fragment_id, user_id),
+    constraint fork_invites_fk_fragment foreign key (fragment_id) references fragments(id),
+    constraint fork_invites_fk_user foreign key (user_id) references users(id)
+);
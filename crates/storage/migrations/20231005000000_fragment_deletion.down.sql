@@ -0,0 +1,4 @@
+drop index if exists fragments_idx_deleted_at;
+
+alter table fragments
+    drop column if exists deleted_at;
@@ -0,0 +1,11 @@
+ALTER TYPE event_type ADD VALUE 'fragment_deleted';
+ALTER TYPE event_type ADD VALUE 'fragment_restored';
+ALTER TYPE event_type ADD VALUE 'fragments_purged';
+ALTER TYPE command_type ADD VALUE 'delete_fragment';
+ALTER TYPE command_type ADD VALUE 'restore_fragment';
+ALTER TYPE command_type ADD VALUE 'purge_fragments';
+
+alter table fragments
+    add column deleted_at       timestamp       null;
+
+create index fragments_idx_deleted_at on fragments(deleted_at) where deleted_at is not null;
@@ -0,0 +1 @@
+drop table if exists fragment_revisions;
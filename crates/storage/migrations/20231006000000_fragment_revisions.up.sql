@@ -0,0 +1,17 @@
+ALTER TYPE event_type ADD VALUE 'fragment_reverted';
+ALTER TYPE command_type ADD VALUE 'revert_fragment';
+
+create table fragment_revisions(
+    fragment_id         uuid            not null,
+    number              integer         not null,
+    content             varchar         not null,
+    author_id           uuid            not null,
+    created_at          timestamp       not null,
+
+    constraint fragment_revisions_pk primary key (fragment_id, number),
+    constraint fragment_revisions_fk_fragment foreign key (fragment_id) references fragments(id),
+    constraint fragment_revisions_fk_author foreign key (author_id) references users(id)
+);
+
+insert into fragment_revisions (fragment_id, number, content, author_id, created_at)
+    select id, 1, content, author_id, last_modified_at from fragments;
@@ -0,0 +1,4 @@
+drop index if exists reviews_idx_fragment;
+
+ALTER TYPE fragment_state RENAME VALUE 'approved' TO 'aproved';
+ALTER TYPE fragment_state RENAME VALUE 'submitted' TO 'waiting_review';
@@ -0,0 +1,6 @@
+ALTER TYPE fragment_state RENAME VALUE 'waiting_review' TO 'submitted';
+ALTER TYPE fragment_state RENAME VALUE 'aproved' TO 'approved';
+ALTER TYPE event_type ADD VALUE 'fork_submitted';
+ALTER TYPE command_type ADD VALUE 'submit_fork';
+
+create index reviews_idx_fragment on reviews(fragment_id, created_at);
@@ -0,0 +1,2 @@
+drop table if exists review_suggestions;
+drop type if exists suggestion_status;
@@ -0,0 +1,25 @@
+CREATE TYPE suggestion_status AS ENUM ('pending', 'accepted', 'rejected');
+ALTER TYPE event_type ADD VALUE 'suggestion_accepted';
+ALTER TYPE event_type ADD VALUE 'suggestion_rejected';
+ALTER TYPE command_type ADD VALUE 'accept_suggestion';
+ALTER TYPE command_type ADD VALUE 'reject_suggestion';
+
+create table review_suggestions(
+    id                  uuid                not null,
+    review_id           uuid                not null,
+    fragment_id         uuid                not null,
+    range_start         integer             not null,
+    range_end           integer             not null,
+    original            varchar             not null,
+    replacement         varchar             not null,
+    status              suggestion_status   not null default 'pending',
+    created_at          timestamp           not null,
+    resolved_at         timestamp,
+
+    constraint review_suggestions_pk primary key (id),
+    constraint review_suggestions_fk_review foreign key (review_id) references reviews(id),
+    constraint review_suggestions_fk_fragment foreign key (fragment_id) references fragments(id),
+    constraint review_suggestions_range check (range_start >= 0 and range_start <= range_end)
+);
+
+create index review_suggestions_idx_fragment on review_suggestions(fragment_id, created_at);
@@ -0,0 +1,31 @@
+ALTER TYPE event_type ADD VALUE 'comment_created';
+ALTER TYPE event_type ADD VALUE 'comment_edited';
+ALTER TYPE event_type ADD VALUE 'comment_deleted';
+ALTER TYPE event_type ADD VALUE 'comment_moderated';
+ALTER TYPE command_type ADD VALUE 'create_comment';
+ALTER TYPE command_type ADD VALUE 'edit_comment';
+ALTER TYPE command_type ADD VALUE 'delete_comment';
+ALTER TYPE command_type ADD VALUE 'moderate_comment';
+
+create table comments(
+    id                  uuid            not null,
+    fragment_id         uuid            not null,
+    review_id           uuid            null,
+    parent_id           uuid            null,
+    author_id           uuid            not null,
+    body                varchar         not null,
+    created_at          timestamp       not null,
+    last_modified_at    timestamp       not null,
+    deleted_at          timestamp       null,
+    hidden_at           timestamp       null,
+    hidden_by           uuid            null,
+
+    constraint comments_pk primary key (id),
+    constraint comments_fk_fragment foreign key (fragment_id) references fragments(id),
+    constraint comments_fk_review foreign key (review_id) references reviews(id),
+    constraint comments_fk_parent foreign key (parent_id) references comments(id),
+    constraint comments_fk_author foreign key (author_id) references users(id),
+    constraint comments_fk_hidden_by foreign key (hidden_by) references users(id)
+);
+
+create index comments_idx_thread on comments(fragment_id, review_id, created_at);
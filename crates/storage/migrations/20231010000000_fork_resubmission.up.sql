@@ -0,0 +1,4 @@
+ALTER TYPE event_type ADD VALUE 'fork_withdrawn';
+ALTER TYPE event_type ADD VALUE 'fork_resubmitted';
+ALTER TYPE command_type ADD VALUE 'withdraw_fork';
+ALTER TYPE command_type ADD VALUE 'resubmit_fork';
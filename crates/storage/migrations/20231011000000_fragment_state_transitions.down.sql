@@ -0,0 +1,2 @@
+drop table if exists fragment_state_transitions;
+drop type if exists fragment_transition;
@@ -0,0 +1,25 @@
+create type fragment_transition as enum (
+    'submit',
+    'withdraw',
+    'resubmit',
+    'approve',
+    'reject',
+    'request_changes',
+    'publish'
+);
+
+create table fragment_state_transitions(
+    id                  bigserial               primary key,
+    fragment_id         uuid                    not null,
+    transition          fragment_transition     not null,
+    from_state          fragment_state          not null,
+    to_state            fragment_state          not null,
+    actor_id            uuid,
+    event_type          event_type              not null,
+    created_at          timestamp               not null,
+
+    constraint fragment_state_transitions_fk_fragment foreign key (fragment_id) references fragments(id),
+    constraint fragment_state_transitions_fk_actor foreign key (actor_id) references users(id)
+);
+
+create index fragment_state_transitions_idx_fragment on fragment_state_transitions(fragment_id, id);
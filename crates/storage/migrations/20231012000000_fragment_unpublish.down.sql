@@ -0,0 +1 @@
+-- Enum values can not be removed from a type.
@@ -0,0 +1,3 @@
+ALTER TYPE event_type ADD VALUE 'fragment_unpublished';
+ALTER TYPE command_type ADD VALUE 'unpublish_fragment';
+ALTER TYPE fragment_transition ADD VALUE 'unpublish';
@@ -0,0 +1,8 @@
+-- Enum values can not be removed from a type.
+drop index if exists tasks_idx_pending;
+alter table tasks drop column if exists failure;
+alter table tasks drop column if exists cancelled_at;
+alter table tasks rename column scheduled_at to scheduled_to;
+alter table tasks alter column actor_id type json using to_json(actor_id);
+alter table tasks alter column actor_type type varchar using actor_type::varchar;
+alter table tasks alter column command_type type varchar using command_type::varchar;
@@ -0,0 +1,12 @@
+ALTER TYPE event_type ADD VALUE 'publication_cancelled';
+ALTER TYPE command_type ADD VALUE 'cancel_publication';
+
+alter table tasks alter column command_type type command_type using command_type::command_type;
+alter table tasks alter column actor_type type actor_type using actor_type::actor_type;
+alter table tasks alter column actor_id drop not null;
+alter table tasks alter column actor_id type uuid using (actor_id #>> '{}')::uuid;
+alter table tasks rename column scheduled_to to scheduled_at;
+alter table tasks add column cancelled_at timestamp null;
+alter table tasks add column failure varchar null;
+
+create index tasks_idx_pending on tasks(scheduled_at) where completed_at is null and cancelled_at is null;
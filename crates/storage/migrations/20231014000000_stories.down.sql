@@ -0,0 +1,3 @@
+-- Enum values can not be removed from a type.
+drop table if exists stories;
+drop type if exists maturity_rating;
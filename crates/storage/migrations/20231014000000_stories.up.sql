@@ -0,0 +1,28 @@
+ALTER TYPE event_type ADD VALUE 'story_created';
+ALTER TYPE event_type ADD VALUE 'story_updated';
+ALTER TYPE command_type ADD VALUE 'create_story';
+ALTER TYPE command_type ADD VALUE 'update_story';
+
+create type maturity_rating as enum ('general', 'teen', 'mature');
+
+create table stories(
+    id                  uuid                not null,
+    fragment_id         uuid                not null,
+    author_id           uuid                not null,
+    title               varchar             not null,
+    synopsis            varchar             null,
+    genres              varchar[]           not null default '{}',
+    language            varchar             not null,
+    maturity            maturity_rating     not null default 'general',
+    cover_url           varchar             null,
+    created_at          timestamp           not null,
+    last_modified_at    timestamp           not null,
+
+    constraint stories_pk primary key (id),
+    constraint stories_uq_fragment unique (fragment_id),
+    constraint stories_fk_fragment foreign key (fragment_id) references fragments(id),
+    constraint stories_fk_author foreign key (author_id) references users(id)
+);
+
+create index stories_idx_genres on stories using gin (genres);
+create index stories_idx_language on stories(language);
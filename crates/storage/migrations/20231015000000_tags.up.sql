@@ -0,0 +1,17 @@
+ALTER TYPE event_type ADD VALUE 'tag_added';
+ALTER TYPE event_type ADD VALUE 'tag_removed';
+ALTER TYPE command_type ADD VALUE 'add_tag';
+ALTER TYPE command_type ADD VALUE 'remove_tag';
+
+create type tag_target as enum ('fragment', 'story');
+
+create table tags(
+    target          tag_target      not null,
+    target_id       uuid            not null,
+    tag             varchar         not null,
+    created_at      timestamp       not null,
+
+    constraint tags_pk primary key (target, target_id, tag)
+);
+
+create index tags_idx_tag on tags(tag);
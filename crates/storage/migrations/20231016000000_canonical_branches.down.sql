@@ -0,0 +1,2 @@
+-- Enum values can not be removed from a type.
+drop table if exists canonical_branches;
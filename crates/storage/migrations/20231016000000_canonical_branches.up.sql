@@ -0,0 +1,14 @@
+ALTER TYPE event_type ADD VALUE 'canonical_branch_set';
+ALTER TYPE command_type ADD VALUE 'set_canonical_branch';
+
+create table canonical_branches(
+    fragment_id     uuid            not null,
+    child_id        uuid            not null,
+    chosen_by       uuid            not null,
+    chosen_at       timestamp       not null,
+
+    constraint canonical_branches_pk primary key (fragment_id),
+    constraint canonical_branches_fk_fragment foreign key (fragment_id) references fragments(id),
+    constraint canonical_branches_fk_child foreign key (child_id) references fragments(id),
+    constraint canonical_branches_fk_chosen_by foreign key (chosen_by) references users(id)
+);
@@ -0,0 +1,5 @@
+-- Enum values can not be removed from a type.
+drop table if exists votes;
+drop table if exists polls;
+delete from canonical_branches where chosen_by is null;
+alter table canonical_branches alter column chosen_by set not null;
@@ -0,0 +1,37 @@
+ALTER TYPE event_type ADD VALUE 'poll_opened';
+ALTER TYPE event_type ADD VALUE 'vote_cast';
+ALTER TYPE event_type ADD VALUE 'poll_closed';
+ALTER TYPE command_type ADD VALUE 'open_poll';
+ALTER TYPE command_type ADD VALUE 'cast_vote';
+ALTER TYPE command_type ADD VALUE 'close_poll';
+
+-- Canonical branches won by a poll are not chosen by a user.
+alter table canonical_branches alter column chosen_by drop not null;
+
+create table polls(
+    id              uuid            not null,
+    fragment_id     uuid            not null,
+    created_by      uuid            not null,
+    closes_at       timestamp       not null,
+    created_at      timestamp       not null,
+    closed_at       timestamp       null,
+    winner_id       uuid            null,
+
+    constraint polls_pk primary key (id),
+    constraint polls_fk_fragment foreign key (fragment_id) references fragments(id),
+    constraint polls_fk_created_by foreign key (created_by) references users(id)
+);
+
+create unique index polls_uq_open_fragment on polls(fragment_id) where closed_at is null;
+
+create table votes(
+    poll_id         uuid            not null,
+    user_id         uuid            not null,
+    child_id        uuid            not null,
+    created_at      timestamp       not null,
+
+    constraint votes_pk primary key (poll_id, user_id),
+    constraint votes_fk_poll foreign key (poll_id) references polls(id),
+    constraint votes_fk_user foreign key (user_id) references users(id),
+    constraint votes_fk_child foreign key (child_id) references fragments(id)
+);
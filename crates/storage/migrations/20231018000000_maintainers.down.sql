@@ -0,0 +1,3 @@
+-- Enum values can not be removed from a type.
+drop table if exists maintainers;
+drop type if exists maintainer_state;
@@ -0,0 +1,24 @@
+ALTER TYPE event_type ADD VALUE 'maintainer_invited';
+ALTER TYPE event_type ADD VALUE 'maintainer_invitation_accepted';
+ALTER TYPE event_type ADD VALUE 'maintainer_invitation_declined';
+ALTER TYPE command_type ADD VALUE 'invite_maintainer';
+ALTER TYPE command_type ADD VALUE 'accept_maintainer_invitation';
+ALTER TYPE command_type ADD VALUE 'decline_maintainer_invitation';
+
+create type maintainer_state as enum ('invited', 'accepted', 'declined');
+
+create table maintainers(
+    root_id         uuid                not null,
+    user_id         uuid                not null,
+    invited_by      uuid                not null,
+    state           maintainer_state    not null default 'invited',
+    invited_at      timestamp           not null,
+    responded_at    timestamp           null,
+
+    constraint maintainers_pk primary key (root_id, user_id),
+    constraint maintainers_fk_root foreign key (root_id) references fragments(id),
+    constraint maintainers_fk_user foreign key (user_id) references users(id),
+    constraint maintainers_fk_invited_by foreign key (invited_by) references users(id)
+);
+
+create index maintainers_idx_user on maintainers(user_id);
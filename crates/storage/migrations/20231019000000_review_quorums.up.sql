@@ -0,0 +1,13 @@
+ALTER TYPE event_type ADD VALUE 'review_quorum_changed';
+ALTER TYPE command_type ADD VALUE 'set_review_quorum';
+
+create table review_quorums(
+    root_id     uuid        not null,
+    approvals   integer     not null,
+    veto        boolean     not null default false,
+    updated_at  timestamp   not null,
+
+    constraint review_quorums_pk primary key (root_id),
+    constraint review_quorums_fk_root foreign key (root_id) references fragments(id),
+    constraint review_quorums_approvals check (approvals > 0)
+);
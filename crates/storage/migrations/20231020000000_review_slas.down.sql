@@ -0,0 +1,3 @@
+-- Enum values can not be removed from a type.
+drop table if exists review_slas;
+drop type if exists review_sla_outcome;
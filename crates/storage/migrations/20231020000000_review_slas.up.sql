@@ -0,0 +1,19 @@
+ALTER TYPE event_type ADD VALUE 'review_sla_changed';
+ALTER TYPE event_type ADD VALUE 'fork_auto_rejected';
+ALTER TYPE event_type ADD VALUE 'fork_returned_to_draft';
+ALTER TYPE event_type ADD VALUE 'fork_escalated';
+ALTER TYPE command_type ADD VALUE 'set_review_sla';
+ALTER TYPE command_type ADD VALUE 'expire_fork';
+
+create type review_sla_outcome as enum ('reject', 'return_to_draft', 'escalate');
+
+create table review_slas(
+    root_id         uuid                not null,
+    window_hours    integer             not null,
+    outcome         review_sla_outcome  not null,
+    updated_at      timestamp           not null,
+
+    constraint review_slas_pk primary key (root_id),
+    constraint review_slas_fk_root foreign key (root_id) references fragments(id),
+    constraint review_slas_window check (window_hours > 0)
+);
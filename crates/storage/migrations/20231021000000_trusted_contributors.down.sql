@@ -0,0 +1,4 @@
+-- Enum values can not be removed from a type.
+drop table if exists trusted_contributors;
+delete from reviews where reviewer_id is null;
+alter table reviews alter column reviewer_id set not null;
@@ -0,0 +1,19 @@
+ALTER TYPE event_type ADD VALUE 'contributor_trusted';
+ALTER TYPE event_type ADD VALUE 'contributor_untrusted';
+ALTER TYPE command_type ADD VALUE 'trust_contributor';
+ALTER TYPE command_type ADD VALUE 'untrust_contributor';
+
+-- Automatic reviews are recorded without a reviewer.
+alter table reviews alter column reviewer_id drop not null;
+
+create table trusted_contributors(
+    author_id       uuid        not null,
+    contributor_id  uuid        not null,
+    auto_publish    boolean     not null default false,
+    created_at      timestamp   not null,
+
+    constraint trusted_contributors_pk primary key (author_id, contributor_id),
+    constraint trusted_contributors_fk_author foreign key (author_id) references users(id),
+    constraint trusted_contributors_fk_contributor foreign key (contributor_id) references users(id),
+    constraint trusted_contributors_not_self check (author_id <> contributor_id)
+);
@@ -0,0 +1,3 @@
+-- Enum values can not be removed from a type.
+drop table if exists notifications;
+drop type if exists notification_kind;
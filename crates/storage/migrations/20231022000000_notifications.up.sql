@@ -0,0 +1,29 @@
+ALTER TYPE event_type ADD VALUE 'notifications_read';
+ALTER TYPE command_type ADD VALUE 'mark_notifications_read';
+
+create type notification_kind as enum (
+    'fork_reviewed',
+    'fragment_forked',
+    'fork_submitted',
+    'fragment_liked',
+    'user_followed'
+);
+
+create table notifications(
+    id              uuid                not null,
+    user_id         uuid                not null,
+    kind            notification_kind   not null,
+    subject_id      uuid                not null,
+    actor_ids       uuid[]              not null,
+    created_at      timestamp           not null,
+    updated_at      timestamp           not null,
+    read_at         timestamp,
+
+    constraint notifications_pk primary key (id),
+    constraint notifications_fk_user foreign key (user_id) references users(id)
+);
+
+-- Similar unread notifications are grouped in a single row.
+create unique index notifications_unread_group on notifications(user_id, kind, subject_id)
+    where read_at is null;
+create index notifications_user_idx on notifications(user_id, updated_at desc);
@@ -0,0 +1,3 @@
+-- Enum values can not be removed from a type.
+drop table if exists notification_preferences;
+drop type if exists digest_frequency;
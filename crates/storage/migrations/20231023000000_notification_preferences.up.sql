@@ -0,0 +1,23 @@
+ALTER TYPE event_type ADD VALUE 'notification_preferences_changed';
+ALTER TYPE event_type ADD VALUE 'digest_sent';
+ALTER TYPE event_type ADD VALUE 'digest_unsubscribed';
+ALTER TYPE command_type ADD VALUE 'set_notification_preferences';
+ALTER TYPE command_type ADD VALUE 'record_digest';
+ALTER TYPE command_type ADD VALUE 'unsubscribe_digest';
+
+create type digest_frequency as enum ('never', 'daily', 'weekly');
+
+create table notification_preferences(
+    user_id             uuid                not null,
+    email               text,
+    digest              digest_frequency    not null default 'never',
+    muted_kinds         notification_kind[] not null default '{}',
+    unsubscribe_token   text                not null,
+    last_digest_at      timestamp,
+    updated_at          timestamp           not null,
+
+    constraint notification_preferences_pk primary key (user_id),
+    constraint notification_preferences_fk_user foreign key (user_id) references users(id),
+    constraint notification_preferences_token_uk unique (unsubscribe_token),
+    constraint notification_preferences_digest_email check (digest = 'never' or email is not null)
+);
@@ -0,0 +1,5 @@
+-- Enum values can not be removed from a type.
+drop table if exists webhook_attempts;
+drop table if exists webhook_deliveries;
+drop type if exists webhook_delivery_state;
+drop table if exists webhooks;
@@ -0,0 +1,64 @@
+ALTER TYPE event_type ADD VALUE 'webhook_created';
+ALTER TYPE event_type ADD VALUE 'webhook_deleted';
+ALTER TYPE event_type ADD VALUE 'webhook_enabled';
+ALTER TYPE event_type ADD VALUE 'webhook_disabled';
+ALTER TYPE command_type ADD VALUE 'create_webhook';
+ALTER TYPE command_type ADD VALUE 'delete_webhook';
+ALTER TYPE command_type ADD VALUE 'enable_webhook';
+ALTER TYPE command_type ADD VALUE 'disable_webhook';
+
+create table webhooks(
+    id                      uuid            not null,
+    owner_id                uuid            not null,
+    url                     text            not null,
+    secret                  text            not null,
+    -- An empty list subscribes to every event type.
+    event_types             event_type[]    not null default '{}',
+    story_id                uuid,
+    enabled                 boolean         not null default true,
+    consecutive_failures    integer         not null default 0,
+    created_at              timestamp       not null,
+    updated_at              timestamp       not null,
+
+    constraint webhooks_pk primary key (id),
+    constraint webhooks_fk_owner foreign key (owner_id) references users(id),
+    constraint webhooks_fk_story foreign key (story_id) references stories(id)
+);
+
+create index webhooks_owner_idx on webhooks(owner_id);
+
+create type webhook_delivery_state as enum ('pending', 'delivered', 'failed');
+
+create table webhook_deliveries(
+    id              uuid                    not null,
+    webhook_id      uuid                    not null,
+    event_id        uuid                    not null,
+    event_type      event_type              not null,
+    payload         jsonb                   not null,
+    state           webhook_delivery_state  not null default 'pending',
+    attempts        integer                 not null default 0,
+    next_attempt_at timestamp               not null,
+    created_at      timestamp               not null,
+    completed_at    timestamp,
+
+    constraint webhook_deliveries_pk primary key (id),
+    constraint webhook_deliveries_fk_webhook foreign key (webhook_id) references webhooks(id)
+);
+
+create index webhook_deliveries_due_idx on webhook_deliveries(next_attempt_at)
+    where state = 'pending';
+create index webhook_deliveries_webhook_idx on webhook_deliveries(webhook_id, created_at desc);
+
+create table webhook_attempts(
+    id              uuid        not null,
+    delivery_id     uuid        not null,
+    status_code     integer,
+    error           text,
+    attempted_at    timestamp   not null,
+
+    constraint webhook_attempts_pk primary key (id),
+    constraint webhook_attempts_fk_delivery foreign key (delivery_id)
+        references webhook_deliveries(id)
+);
+
+create index webhook_attempts_delivery_idx on webhook_attempts(delivery_id);
@@ -0,0 +1,4 @@
+-- Increasing position of every event, used by live streams to resume where they left off.
+alter table events add column position bigserial not null;
+
+create unique index events_position_idx on events(position);
@@ -0,0 +1,5 @@
+drop trigger if exists events_assign_position on events;
+drop function if exists assign_event_position();
+update events set position = nextval('events_position_seq') where position is null;
+alter table events alter column position set default nextval('events_position_seq');
+alter table events alter column position set not null;
@@ -0,0 +1,19 @@
+-- Positions are assigned when the saving transaction commits rather than when the event
+-- is inserted, one transaction at a time. A position is then only handed out once every
+-- lower one is visible, so readers resuming after a position never miss an event.
+alter table events alter column position drop default;
+alter table events alter column position drop not null;
+
+create function assign_event_position() returns trigger as $$
+begin
+    -- Held until the transaction ends, after its events are visible to others.
+    perform pg_advisory_xact_lock(hashtext('events_position'));
+    update events set position = nextval('events_position_seq') where id = new.id;
+    return null;
+end;
+$$ language plpgsql;
+
+create constraint trigger events_assign_position
+    after insert on events
+    deferrable initially deferred
+    for each row execute function assign_event_position();
@@ -0,0 +1 @@
+alter table fragments drop column if exists taken_down_at;
@@ -0,0 +1,4 @@
+-- Set when a moderator unpublishes someone else's fragment. Only a moderator may publish
+-- it again, which clears the mark.
+alter table fragments
+    add column taken_down_at    timestamp       null;
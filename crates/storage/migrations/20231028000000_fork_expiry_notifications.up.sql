@@ -0,0 +1,3 @@
+ALTER TYPE notification_kind ADD VALUE 'fork_auto_rejected';
+ALTER TYPE notification_kind ADD VALUE 'fork_returned_to_draft';
+ALTER TYPE notification_kind ADD VALUE 'fork_escalated';
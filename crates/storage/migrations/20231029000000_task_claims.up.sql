@@ -0,0 +1,3 @@
+-- Tasks are claimed while they run and only completed once their command finished. Claims
+-- of runners that stopped midway expire, so that their tasks run again.
+alter table tasks add column claimed_at timestamp null;
@@ -17,6 +17,16 @@ pub enum StorageError {
     Sqlx(#[from] sqlx::Error),
 }
 
+impl StorageError {
+    /// Whether the database refused the statement because it breaks `constraint`.
+    pub fn violates(&self, constraint: &str) -> bool {
+        match self {
+            Self::Sqlx(Error::Database(e)) => e.constraint() == Some(constraint),
+            _ => false,
+        }
+    }
+}
+
 pub trait Entity {
     type Id: PartialEq;
 
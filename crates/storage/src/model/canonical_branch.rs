@@ -0,0 +1,26 @@
+use commons::{id::Id, time::DateTime};
+use derive_builder::Builder;
+use derive_getters::Getters;
+use sqlx::FromRow;
+
+use crate::Entity;
+
+/// The child a fragment continues with by default, as chosen by the story maintainers.
+/// Readers follow canonical branches from the root to get the main story.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromRow, Builder, Getters)]
+#[builder(setter(into))]
+pub struct CanonicalBranch {
+    fragment_id: Id,
+    child_id: Id,
+    /// Missing when the branch won a poll.
+    chosen_by: Option<Id>,
+    chosen_at: DateTime,
+}
+
+impl Entity for CanonicalBranch {
+    type Id = Id;
+
+    fn id(&self) -> Self::Id {
+        self.fragment_id
+    }
+}
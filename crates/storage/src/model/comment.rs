@@ -0,0 +1,58 @@
+use commons::{id::Id, review, time::DateTime};
+use derive_builder::Builder;
+use derive_getters::Getters;
+use derive_setters::Setters;
+use sqlx::FromRow;
+
+use crate::Entity;
+
+/// Message in a discussion thread. Comments belong to a fragment, and to one of its reviews
+/// when `review_id` is set. Replies point to the comment they answer through `parent_id`.
+#[derive(Debug, Clone, PartialEq, Eq, FromRow, Builder, Getters, Setters)]
+#[builder(setter(into))]
+#[setters(prefix = "set_")]
+#[setters(into)]
+pub struct Comment {
+    id: Id,
+    fragment_id: Id,
+    #[builder(default)]
+    review_id: Option<Id>,
+    #[builder(default)]
+    parent_id: Option<Id>,
+    author_id: Id,
+    body: review::Comment,
+    created_at: DateTime,
+    last_modified_at: DateTime,
+
+    /// Deleted comments are kept so that their replies stay in the thread.
+    #[builder(default)]
+    deleted_at: Option<DateTime>,
+
+    /// Set when a moderator hides the comment.
+    #[builder(default)]
+    hidden_at: Option<DateTime>,
+    #[builder(default)]
+    hidden_by: Option<Id>,
+}
+
+impl Entity for Comment {
+    type Id = Id;
+
+    fn id(&self) -> Self::Id {
+        self.id
+    }
+}
+
+impl Comment {
+    pub fn is_author(&self, user_id: Id) -> bool {
+        self.author_id == user_id
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    pub fn is_hidden(&self) -> bool {
+        self.hidden_at.is_some()
+    }
+}
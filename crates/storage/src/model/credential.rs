@@ -0,0 +1,29 @@
+use commons::{auth::PasswordHash, id::Id, time::DateTime};
+use derive_builder::Builder;
+use derive_getters::Getters;
+use sqlx::FromRow;
+
+use crate::Entity;
+
+#[derive(Debug, Clone, PartialEq, Eq, FromRow, Builder, Getters)]
+#[builder(setter(into))]
+pub struct Credential {
+    user_id: Id,
+    login: String,
+    password_hash: PasswordHash,
+    created_at: DateTime,
+}
+
+impl Entity for Credential {
+    type Id = Id;
+
+    fn id(&self) -> Self::Id {
+        self.user_id
+    }
+}
+
+impl Credential {
+    pub fn verify(&self, password: &str) -> bool {
+        self.password_hash.verify(password)
+    }
+}
@@ -31,6 +31,11 @@ pub struct DbEvent {
     timestamp: DateTime,
     actor_type: ActorType,
     actor_id: Option<Id>,
+
+    /// Assigned by the database when the transaction saving the event commits, in commit
+    /// order.
+    #[builder(default)]
+    position: Option<i64>,
 }
 
 impl Entity for DbEvent {
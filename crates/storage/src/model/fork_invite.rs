@@ -0,0 +1,22 @@
+use commons::{id::Id, time::DateTime};
+use derive_builder::Builder;
+use derive_getters::Getters;
+use sqlx::FromRow;
+
+use crate::Entity;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromRow, Builder, Getters)]
+#[builder(setter(into))]
+pub struct ForkInvite {
+    fragment_id: Id,
+    user_id: Id,
+    created_at: DateTime,
+}
+
+impl Entity for ForkInvite {
+    type Id = (Id, Id);
+
+    fn id(&self) -> Self::Id {
+        (self.fragment_id, self.user_id)
+    }
+}
@@ -1,6 +1,6 @@
-use super::review::ReviewAction;
+use super::{review::ReviewAction, state_transition::StateTransition};
 use crate::Entity;
-use commons::{fragment::Content, id::Id, time::DateTime};
+use commons::{actor::Actor, events::EventType, fragment::Content, id::Id, time::DateTime};
 use derive_builder::Builder;
 use derive_getters::Getters;
 use derive_setters::Setters;
@@ -65,6 +65,18 @@ pub struct Fragment {
     created_at: DateTime,
 
     last_modified_at: DateTime,
+
+    #[builder(default)]
+    fork_policy: ForkPolicy,
+
+    /// Soft deletion mark. Deleted fragments hide their whole subtree until purged.
+    #[builder(default)]
+    deleted_at: Option<DateTime>,
+
+    /// Moderator takedown mark. Taken down fragments can only be published again by a
+    /// moderator.
+    #[builder(default)]
+    taken_down_at: Option<DateTime>,
 }
 
 impl Entity for Fragment {
@@ -80,15 +92,49 @@ impl Fragment {
     }
 
     pub fn is_publishable(&self) -> bool {
-        if self.is_fork() {
-            return self.is_approved();
-        }
-
-        self.is_draft()
+        self.can(Transition::Publish)
     }
 
     pub fn is_submittable(&self) -> bool {
-        self.is_fork() && (self.is_draft() || self.is_waiting_changes())
+        self.can(Transition::Submit)
+    }
+
+    /// Lifecycle rule allowing `transition` from the current state, if any.
+    pub fn rule(&self, transition: Transition) -> Option<&'static TransitionRule> {
+        LIFECYCLE.iter().find(|rule| {
+            rule.transition == transition
+                && rule.from == self.state
+                && rule.scope.matches(self.is_fork())
+        })
+    }
+
+    pub fn can(&self, transition: Transition) -> bool {
+        self.rule(transition).is_some()
+    }
+
+    /// Moves the fragment along its lifecycle as `role`, returning the updated fragment
+    /// together with the history entry to persist. Whether the actor really holds the role
+    /// is left to the caller's authorization.
+    pub fn transition(
+        self,
+        transition: Transition,
+        actor: Actor,
+        role: LifecycleRole,
+        at: DateTime,
+    ) -> Result<(Self, StateTransition), TransitionError> {
+        let rule = self.rule(transition).ok_or(TransitionError::InvalidState {
+            transition,
+            state: self.state,
+        })?;
+        if !role.satisfies(rule.role) {
+            return Err(TransitionError::Forbidden {
+                transition,
+                role: rule.role,
+            });
+        }
+        let record = StateTransition::new(self.id, rule, actor.id(), at);
+
+        Ok((self.set_state(rule.to).set_last_modified_at(at), record))
     }
 
     pub fn is_published(&self) -> bool {
@@ -115,10 +161,23 @@ impl Fragment {
         self.parent_id.is_none()
     }
 
+    /// Id of the story root this fragment belongs to.
+    pub fn root_id(&self) -> Id {
+        self.path.as_ref().first().copied().unwrap_or(self.id)
+    }
+
     pub const fn is_fork(&self) -> bool {
         self.parent_id.is_some()
     }
 
+    pub const fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    pub const fn is_taken_down(&self) -> bool {
+        self.taken_down_at.is_some()
+    }
+
     pub fn is_approved(&self) -> bool {
         self.state == FragmentState::Approved
     }
@@ -126,6 +185,7 @@ impl Fragment {
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, sqlx::Type, Copy, Default)]
 #[sqlx(type_name = "fragment_state", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
 pub enum FragmentState {
     #[default]
     Draft,
@@ -136,12 +196,219 @@ pub enum FragmentState {
     WaitingChanges,
 }
 
-impl From<ReviewAction> for FragmentState {
+/// Who may fork the fragments of a story. Only the root fragment policy is enforced.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, sqlx::Type, Copy, Default)]
+#[sqlx(type_name = "fork_policy", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ForkPolicy {
+    Anyone,
+    Followers,
+    #[default]
+    MutualFriends,
+    InviteOnly,
+    Locked,
+}
+
+/// Steps of the fragment lifecycle. See [`LIFECYCLE`] for the states each one connects.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, sqlx::Type, Copy)]
+#[sqlx(type_name = "fragment_transition", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum Transition {
+    Submit,
+    Withdraw,
+    Resubmit,
+    Approve,
+    Reject,
+    RequestChanges,
+    Publish,
+    Unpublish,
+}
+
+impl From<ReviewAction> for Transition {
     fn from(value: ReviewAction) -> Self {
         match value {
-            ReviewAction::Approve => Self::Approved,
-            ReviewAction::Reject => Self::Rejected,
-            ReviewAction::RequestChanges => Self::WaitingChanges,
+            ReviewAction::Approve => Self::Approve,
+            ReviewAction::Reject => Self::Reject,
+            ReviewAction::RequestChanges => Self::RequestChanges,
+        }
+    }
+}
+
+/// Relation to the fragment an actor performs a transition as. Rules name the one they
+/// require, moderators and the system may perform any transition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifecycleRole {
+    /// Author of the fragment itself.
+    Author,
+    /// Author of the parent fragment or story maintainer, reviewing a fork.
+    Reviewer,
+    /// Moderator overriding the author or the reviewers.
+    Moderator,
+    /// Automatic transitions, such as expiries and trusted contributor approvals.
+    System,
+}
+
+impl LifecycleRole {
+    /// Whether acting as this role is enough for a rule requiring `required`.
+    pub fn satisfies(self, required: Self) -> bool {
+        matches!(self, Self::Moderator | Self::System) || self == required
+    }
+}
+
+/// Kind of fragment a rule applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    Root,
+    Fork,
+}
+
+impl Scope {
+    const fn matches(self, is_fork: bool) -> bool {
+        match self {
+            Self::Root => !is_fork,
+            Self::Fork => is_fork,
         }
     }
 }
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct TransitionRule {
+    pub transition: Transition,
+    pub scope: Scope,
+    pub from: FragmentState,
+    pub to: FragmentState,
+    pub role: LifecycleRole,
+    pub event: EventType,
+}
+
+const fn rule(
+    transition: Transition,
+    scope: Scope,
+    from: FragmentState,
+    to: FragmentState,
+    role: LifecycleRole,
+    event: EventType,
+) -> TransitionRule {
+    TransitionRule {
+        transition,
+        scope,
+        from,
+        to,
+        role,
+        event,
+    }
+}
+
+/// Every state change a fragment may go through. Anything not listed here is rejected.
+pub const LIFECYCLE: &[TransitionRule] = {
+    use FragmentState::*;
+    use LifecycleRole::*;
+    use Scope::*;
+    use Transition::*;
+
+    &[
+        rule(
+            Publish,
+            Root,
+            Draft,
+            Published,
+            Author,
+            EventType::FragmentPublished,
+        ),
+        rule(
+            Publish,
+            Fork,
+            Approved,
+            Published,
+            Author,
+            EventType::FragmentPublished,
+        ),
+        rule(
+            Unpublish,
+            Root,
+            Published,
+            Draft,
+            Author,
+            EventType::FragmentUnpublished,
+        ),
+        rule(
+            Unpublish,
+            Fork,
+            Published,
+            Draft,
+            Author,
+            EventType::FragmentUnpublished,
+        ),
+        rule(
+            Submit,
+            Fork,
+            Draft,
+            Submitted,
+            Author,
+            EventType::ForkSubmitted,
+        ),
+        rule(
+            Submit,
+            Fork,
+            WaitingChanges,
+            Submitted,
+            Author,
+            EventType::ForkSubmitted,
+        ),
+        rule(
+            Withdraw,
+            Fork,
+            Submitted,
+            Draft,
+            Author,
+            EventType::ForkWithdrawn,
+        ),
+        rule(
+            Resubmit,
+            Fork,
+            Rejected,
+            Submitted,
+            Author,
+            EventType::ForkResubmitted,
+        ),
+        rule(
+            Approve,
+            Fork,
+            Submitted,
+            Approved,
+            Reviewer,
+            EventType::FragmentForkReviewed,
+        ),
+        rule(
+            Reject,
+            Fork,
+            Submitted,
+            Rejected,
+            Reviewer,
+            EventType::FragmentForkReviewed,
+        ),
+        rule(
+            RequestChanges,
+            Fork,
+            Submitted,
+            WaitingChanges,
+            Reviewer,
+            EventType::FragmentForkReviewed,
+        ),
+    ]
+};
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum TransitionError {
+    #[error("transition {transition:?} is not allowed from state {state:?}")]
+    InvalidState {
+        transition: Transition,
+        state: FragmentState,
+    },
+
+    #[error("transition {transition:?} requires the {role:?} role")]
+    Forbidden {
+        transition: Transition,
+        role: LifecycleRole,
+    },
+}
@@ -0,0 +1,63 @@
+use commons::{id::Id, time::DateTime};
+use derive_builder::Builder;
+use derive_getters::Getters;
+use derive_setters::Setters;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::Entity;
+
+/// Co-maintainer of a story, invited by the root author. Accepted maintainers share the
+/// review of forks anywhere in the story tree.
+#[derive(Debug, Builder, Clone, FromRow, Getters, Setters, PartialEq, Eq)]
+#[builder(setter(into))]
+#[setters(prefix = "set_")]
+#[setters(into)]
+pub struct Maintainer {
+    /// Root fragment of the story.
+    #[setters(skip)]
+    root_id: Id,
+
+    #[setters(skip)]
+    user_id: Id,
+
+    #[setters(skip)]
+    invited_by: Id,
+
+    #[builder(default)]
+    state: MaintainerState,
+
+    #[setters(skip)]
+    invited_at: DateTime,
+
+    #[builder(default)]
+    responded_at: Option<DateTime>,
+}
+
+impl Entity for Maintainer {
+    type Id = (Id, Id);
+
+    fn id(&self) -> Self::Id {
+        (self.root_id, self.user_id)
+    }
+}
+
+impl Maintainer {
+    pub fn is_invited(&self) -> bool {
+        self.state == MaintainerState::Invited
+    }
+
+    pub fn is_accepted(&self) -> bool {
+        self.state == MaintainerState::Accepted
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, sqlx::Type, Copy, Default)]
+#[sqlx(type_name = "maintainer_state", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum MaintainerState {
+    #[default]
+    Invited,
+    Accepted,
+    Declined,
+}
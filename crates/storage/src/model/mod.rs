@@ -1,7 +1,25 @@
+pub mod canonical_branch;
+pub mod comment;
+pub mod credential;
 pub mod event;
 pub mod follow;
+pub mod fork_invite;
 pub mod fragment;
 pub mod like;
+pub mod maintainer;
+pub mod notification;
+pub mod notification_preferences;
+pub mod poll;
 pub mod review;
+pub mod review_quorum;
+pub mod review_sla;
+pub mod revision;
+pub mod session;
+pub mod state_transition;
+pub mod story;
+pub mod suggestion;
+pub mod tag;
 pub mod task;
+pub mod trusted_contributor;
 pub mod user;
+pub mod webhook;
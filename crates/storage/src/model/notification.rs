@@ -0,0 +1,76 @@
+use commons::{id::Id, time::DateTime};
+use derive_builder::Builder;
+use derive_getters::Getters;
+use derive_setters::Setters;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::Entity;
+
+/// In-app notification of a user. Unread notifications of the same kind about the same
+/// subject are grouped, collecting everyone who triggered them.
+#[derive(Debug, Builder, Clone, FromRow, Getters, Setters, PartialEq, Eq)]
+#[builder(setter(into))]
+#[setters(prefix = "set_")]
+#[setters(into)]
+pub struct Notification {
+    #[setters(skip)]
+    id: Id,
+
+    /// Recipient.
+    #[setters(skip)]
+    user_id: Id,
+
+    #[setters(skip)]
+    kind: NotificationKind,
+
+    /// Fragment the notification is about, or the recipient for follows.
+    #[setters(skip)]
+    subject_id: Id,
+
+    /// Users who triggered the notification, oldest first.
+    #[setters(skip)]
+    actor_ids: Vec<Id>,
+
+    #[setters(skip)]
+    created_at: DateTime,
+
+    updated_at: DateTime,
+
+    #[builder(default)]
+    read_at: Option<DateTime>,
+}
+
+impl Entity for Notification {
+    type Id = Id;
+
+    fn id(&self) -> Self::Id {
+        self.id
+    }
+}
+
+impl Notification {
+    pub fn is_read(&self) -> bool {
+        self.read_at.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, sqlx::Type, Copy)]
+#[sqlx(type_name = "notification_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// A fork of the recipient was reviewed.
+    ForkReviewed,
+    /// A fragment of the recipient was forked.
+    FragmentForked,
+    /// A fork of a fragment of the recipient was submitted for review.
+    ForkSubmitted,
+    FragmentLiked,
+    UserFollowed,
+    /// A fork of the recipient was rejected for not being reviewed in time.
+    ForkAutoRejected,
+    /// A fork of the recipient was sent back to draft for not being reviewed in time.
+    ForkReturnedToDraft,
+    /// A fork the recipient reviews was not reviewed in time.
+    ForkEscalated,
+}
@@ -0,0 +1,96 @@
+use super::notification::NotificationKind;
+use chrono::Duration;
+use commons::{auth::UnsubscribeToken, id::Id, mail::EmailAddress, time::DateTime};
+use derive_builder::Builder;
+use derive_getters::Getters;
+use derive_setters::Setters;
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    postgres::{PgHasArrayType, PgTypeInfo},
+    FromRow,
+};
+
+use crate::Entity;
+
+/// How a user wants to hear about their notifications besides the in-app inbox.
+#[derive(Debug, Builder, Clone, FromRow, Getters, Setters, PartialEq, Eq)]
+#[builder(setter(into))]
+#[setters(prefix = "set_")]
+#[setters(into)]
+pub struct NotificationPreferences {
+    #[setters(skip)]
+    user_id: Id,
+
+    /// Where digests are sent, required unless digests are off.
+    #[builder(default)]
+    email: Option<EmailAddress>,
+
+    #[builder(default)]
+    digest: DigestFrequency,
+
+    /// Kinds of notifications the user opted out of, in the inbox and in digests.
+    #[builder(default)]
+    muted_kinds: Vec<NotificationKind>,
+
+    #[setters(skip)]
+    unsubscribe_token: UnsubscribeToken,
+
+    #[builder(default)]
+    last_digest_at: Option<DateTime>,
+
+    updated_at: DateTime,
+}
+
+impl Entity for NotificationPreferences {
+    type Id = Id;
+
+    fn id(&self) -> Self::Id {
+        self.user_id
+    }
+}
+
+impl NotificationPreferences {
+    /// Preferences of a user who never changed them.
+    pub fn default_for(user_id: Id, at: DateTime) -> Self {
+        Self {
+            user_id,
+            email: None,
+            digest: DigestFrequency::default(),
+            muted_kinds: Vec::new(),
+            unsubscribe_token: UnsubscribeToken::generate(),
+            last_digest_at: None,
+            updated_at: at,
+        }
+    }
+
+    pub fn is_muted(&self, kind: NotificationKind) -> bool {
+        self.muted_kinds.contains(&kind)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, sqlx::Type, Copy, Default)]
+#[sqlx(type_name = "digest_frequency", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum DigestFrequency {
+    #[default]
+    Never,
+    Daily,
+    Weekly,
+}
+
+impl DigestFrequency {
+    /// Time between two digests, if any are sent.
+    pub fn period(&self) -> Option<Duration> {
+        match self {
+            Self::Never => None,
+            Self::Daily => Some(Duration::days(1)),
+            Self::Weekly => Some(Duration::weeks(1)),
+        }
+    }
+}
+
+impl PgHasArrayType for NotificationKind {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_notification_kind")
+    }
+}
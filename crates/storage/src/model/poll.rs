@@ -0,0 +1,81 @@
+use commons::{id::Id, time::DateTime};
+use derive_builder::Builder;
+use derive_getters::Getters;
+use derive_setters::Setters;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::Entity;
+
+/// Readers vote among the published children of a fragment until `closes_at`. The child
+/// with the most votes becomes the canonical branch of the fragment.
+#[derive(Debug, Builder, Clone, FromRow, Getters, Setters, PartialEq, Eq)]
+#[builder(setter(into))]
+#[setters(prefix = "set_")]
+#[setters(into)]
+pub struct Poll {
+    #[setters(skip)]
+    id: Id,
+
+    #[setters(skip)]
+    fragment_id: Id,
+
+    #[setters(skip)]
+    created_by: Id,
+
+    #[setters(skip)]
+    closes_at: DateTime,
+
+    #[setters(skip)]
+    created_at: DateTime,
+
+    #[builder(default)]
+    closed_at: Option<DateTime>,
+
+    /// Missing until the poll is closed, or when nobody voted.
+    #[builder(default)]
+    winner_id: Option<Id>,
+}
+
+impl Entity for Poll {
+    type Id = Id;
+
+    fn id(&self) -> Self::Id {
+        self.id
+    }
+}
+
+impl Poll {
+    /// Whether votes are still accepted at `now`.
+    pub fn is_open(&self, now: &DateTime) -> bool {
+        self.closed_at.is_none() && now < &self.closes_at
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed_at.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromRow, Builder, Getters)]
+#[builder(setter(into))]
+pub struct Vote {
+    poll_id: Id,
+    user_id: Id,
+    child_id: Id,
+    created_at: DateTime,
+}
+
+impl Entity for Vote {
+    type Id = (Id, Id);
+
+    fn id(&self) -> Self::Id {
+        (self.poll_id, self.user_id)
+    }
+}
+
+/// Votes received by a child of the polled fragment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromRow, Getters, Serialize, Deserialize)]
+pub struct PollResult {
+    child_id: Id,
+    votes: i64,
+}
@@ -11,7 +11,8 @@ use crate::Entity;
 pub struct Review {
     id: Id,
     fragment_id: Id,
-    reviewer_id: Id,
+    /// Missing when the review was recorded automatically by the system.
+    reviewer_id: Option<Id>,
     action: ReviewAction,
     comment: Option<Comment>,
     created_at: DateTime,
@@ -25,8 +26,15 @@ impl Entity for Review {
     }
 }
 
+impl Review {
+    pub fn is_automatic(&self) -> bool {
+        self.reviewer_id.is_none()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, sqlx::Type, Copy)]
 #[sqlx(type_name = "review_action", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
 pub enum ReviewAction {
     Approve,
     Reject,
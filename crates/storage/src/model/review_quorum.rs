@@ -0,0 +1,75 @@
+use commons::{id::Id, time::DateTime};
+use derive_builder::Builder;
+use derive_getters::Getters;
+use derive_setters::Setters;
+use sqlx::FromRow;
+
+use crate::{
+    model::review::{Review, ReviewAction},
+    Entity,
+};
+
+/// Reviews a fork needs before leaving the `Submitted` state, configured per story.
+///
+/// Stories without a configured quorum use the single review one: the first review decides.
+#[derive(Debug, Builder, Clone, FromRow, Getters, Setters, PartialEq, Eq)]
+#[builder(setter(into))]
+#[setters(prefix = "set_")]
+#[setters(into)]
+pub struct ReviewQuorum {
+    /// Root fragment of the story.
+    #[setters(skip)]
+    root_id: Id,
+
+    /// Approvals from distinct reviewers needed to approve a fork.
+    #[builder(default = "1")]
+    approvals: i32,
+
+    /// Whether a single rejection rejects the fork, whatever the approvals.
+    #[builder(default)]
+    veto: bool,
+
+    updated_at: DateTime,
+}
+
+impl Entity for ReviewQuorum {
+    type Id = Id;
+
+    fn id(&self) -> Self::Id {
+        self.root_id
+    }
+}
+
+impl ReviewQuorum {
+    pub fn single(root_id: Id, at: DateTime) -> Self {
+        Self {
+            root_id,
+            approvals: 1,
+            veto: false,
+            updated_at: at,
+        }
+    }
+
+    /// Outcome of a review round, given the latest review of each reviewer. `None` while
+    /// the quorum is not reached.
+    ///
+    /// A change request always sends the fork back to its author. Without veto, rejections
+    /// need the same count as approvals.
+    pub fn evaluate(&self, reviews: &[Review]) -> Option<ReviewAction> {
+        let count = |action| reviews.iter().filter(|r| *r.action() == action).count();
+        let required = usize::try_from(self.approvals).unwrap_or(1);
+        let rejections = count(ReviewAction::Reject);
+
+        if count(ReviewAction::RequestChanges) > 0 {
+            Some(ReviewAction::RequestChanges)
+        } else if self.veto && rejections > 0 {
+            Some(ReviewAction::Reject)
+        } else if count(ReviewAction::Approve) >= required {
+            Some(ReviewAction::Approve)
+        } else if rejections >= required {
+            Some(ReviewAction::Reject)
+        } else {
+            None
+        }
+    }
+}
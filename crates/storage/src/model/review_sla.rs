@@ -0,0 +1,53 @@
+use chrono::Duration;
+use commons::{id::Id, time::DateTime};
+use derive_builder::Builder;
+use derive_getters::Getters;
+use derive_setters::Setters;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::Entity;
+
+/// Time a submitted fork of a story may wait for a review, and what happens once it is over.
+/// Stories without one let forks wait forever.
+#[derive(Debug, Builder, Clone, FromRow, Getters, Setters, PartialEq, Eq)]
+#[builder(setter(into))]
+#[setters(prefix = "set_")]
+#[setters(into)]
+pub struct ReviewSla {
+    /// Root fragment of the story.
+    #[setters(skip)]
+    root_id: Id,
+
+    window_hours: i32,
+
+    outcome: SlaOutcome,
+
+    updated_at: DateTime,
+}
+
+impl Entity for ReviewSla {
+    type Id = Id;
+
+    fn id(&self) -> Self::Id {
+        self.root_id
+    }
+}
+
+impl ReviewSla {
+    /// When a fork submitted at `submitted_at` expires.
+    pub fn deadline(&self, submitted_at: DateTime) -> DateTime {
+        submitted_at + Duration::hours(i64::from(self.window_hours))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, sqlx::Type, Copy)]
+#[sqlx(type_name = "review_sla_outcome", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SlaOutcome {
+    Reject,
+    /// Sends the fork back to draft, as if its author withdrew it.
+    ReturnToDraft,
+    /// Keeps the fork under review and calls the story maintainers in.
+    Escalate,
+}
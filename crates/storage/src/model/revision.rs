@@ -0,0 +1,44 @@
+use commons::{fragment::Content, id::Id, time::DateTime};
+use derive_builder::Builder;
+use derive_getters::Getters;
+use sqlx::FromRow;
+
+use crate::{model::fragment::Fragment, Entity};
+
+#[derive(Debug, Clone, PartialEq, Eq, FromRow, Builder, Getters)]
+#[builder(setter(into))]
+pub struct Revision {
+    fragment_id: Id,
+
+    /// Assigned by the database on save, starting at 1 for each fragment.
+    #[builder(default)]
+    number: i32,
+
+    content: Content,
+
+    /// User who wrote this revision, not necessarily the fragment author.
+    author_id: Id,
+
+    created_at: DateTime,
+}
+
+impl Entity for Revision {
+    type Id = (Id, i32);
+
+    fn id(&self) -> Self::Id {
+        (self.fragment_id, self.number)
+    }
+}
+
+impl Revision {
+    /// Snapshot of the current fragment content, written by `author_id`.
+    pub fn of(fragment: &Fragment, author_id: Id) -> Self {
+        Self {
+            fragment_id: *fragment.id(),
+            number: 0,
+            content: fragment.content().clone(),
+            author_id,
+            created_at: *fragment.last_modified_at(),
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use commons::{auth::TokenHash, id::Id, time::DateTime};
+use derive_builder::Builder;
+use derive_getters::Getters;
+use sqlx::FromRow;
+
+use crate::Entity;
+
+#[derive(Debug, Clone, PartialEq, Eq, FromRow, Builder, Getters)]
+#[builder(setter(into))]
+pub struct Session {
+    id: Id,
+    user_id: Id,
+    token_hash: TokenHash,
+    created_at: DateTime,
+    expires_at: DateTime,
+}
+
+impl Entity for Session {
+    type Id = Id;
+
+    fn id(&self) -> Self::Id {
+        self.id
+    }
+}
@@ -0,0 +1,53 @@
+use commons::{events::EventType, id::Id, time::DateTime};
+use derive_getters::Getters;
+use sqlx::FromRow;
+
+use crate::{
+    model::fragment::{FragmentState, Transition, TransitionRule},
+    Entity,
+};
+
+/// Audit entry for a lifecycle transition of a fragment.
+#[derive(Debug, Clone, PartialEq, Eq, FromRow, Getters)]
+pub struct StateTransition {
+    /// Assigned by the database on save.
+    id: i64,
+
+    fragment_id: Id,
+
+    transition: Transition,
+
+    from_state: FragmentState,
+
+    to_state: FragmentState,
+
+    /// Missing when the system performed the transition.
+    actor_id: Option<Id>,
+
+    event_type: EventType,
+
+    created_at: DateTime,
+}
+
+impl Entity for StateTransition {
+    type Id = i64;
+
+    fn id(&self) -> Self::Id {
+        self.id
+    }
+}
+
+impl StateTransition {
+    pub fn new(fragment_id: Id, rule: &TransitionRule, actor_id: Option<Id>, at: DateTime) -> Self {
+        Self {
+            id: 0,
+            fragment_id,
+            transition: rule.transition,
+            from_state: rule.from,
+            to_state: rule.to,
+            actor_id,
+            event_type: rule.event,
+            created_at: at,
+        }
+    }
+}
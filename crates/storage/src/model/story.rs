@@ -0,0 +1,80 @@
+use commons::{id::Id, time::DateTime};
+use derive_builder::Builder;
+use derive_getters::Getters;
+use derive_setters::Setters;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::Entity;
+
+/// Story metadata, bound to the root fragment the story starts with.
+#[derive(Debug, Builder, Clone, FromRow, Getters, Setters, PartialEq, Eq)]
+#[builder(setter(into))]
+#[setters(prefix = "set_")]
+#[setters(into)]
+pub struct Story {
+    #[setters(skip)]
+    id: Id,
+
+    /// Root fragment of the story.
+    #[setters(skip)]
+    fragment_id: Id,
+
+    #[setters(skip)]
+    author_id: Id,
+
+    title: String,
+
+    #[builder(default)]
+    synopsis: Option<String>,
+
+    /// Lowercase genre names.
+    #[builder(default)]
+    genres: Vec<String>,
+
+    /// ISO 639-1 language code.
+    language: String,
+
+    #[builder(default)]
+    maturity: MaturityRating,
+
+    #[builder(default)]
+    cover_url: Option<String>,
+
+    #[setters(skip)]
+    created_at: DateTime,
+
+    last_modified_at: DateTime,
+}
+
+impl Entity for Story {
+    type Id = Id;
+
+    fn id(&self) -> Self::Id {
+        self.id
+    }
+}
+
+impl Story {
+    pub fn is_author(&self, author: impl Into<Id>) -> bool {
+        self.author_id == author.into()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, sqlx::Type, Copy, Default)]
+#[sqlx(type_name = "maturity_rating", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum MaturityRating {
+    #[default]
+    General,
+    Teen,
+    Mature,
+}
+
+/// Criteria to list published stories. Absent criteria match every story.
+#[derive(Debug, Clone, Default, Builder, Getters)]
+#[builder(setter(into), default)]
+pub struct StoryFilter {
+    genre: Option<String>,
+    language: Option<String>,
+}
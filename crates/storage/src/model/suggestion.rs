@@ -0,0 +1,76 @@
+use commons::{fragment::Content, id::Id, time::DateTime};
+use derive_builder::Builder;
+use derive_getters::Getters;
+use derive_setters::Setters;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::Entity;
+
+/// Replacement proposed by a reviewer for a character range of the fork content.
+#[derive(Debug, Clone, PartialEq, Eq, FromRow, Builder, Getters, Setters)]
+#[builder(setter(into))]
+#[setters(prefix = "set_")]
+#[setters(into)]
+pub struct Suggestion {
+    id: Id,
+    review_id: Id,
+    fragment_id: Id,
+
+    /// Character offsets, `range_end` excluded.
+    range_start: i32,
+    range_end: i32,
+
+    /// Text covered by the range when the suggestion was made.
+    original: Content,
+    replacement: Content,
+
+    #[builder(default)]
+    status: SuggestionStatus,
+
+    created_at: DateTime,
+
+    #[builder(default)]
+    resolved_at: Option<DateTime>,
+}
+
+impl Entity for Suggestion {
+    type Id = Id;
+
+    fn id(&self) -> Self::Id {
+        self.id
+    }
+}
+
+impl Suggestion {
+    pub fn is_pending(&self) -> bool {
+        self.status == SuggestionStatus::Pending
+    }
+
+    /// Applies the replacement to `content`, or returns `None` when the range no longer
+    /// covers the original text.
+    pub fn apply(&self, content: &Content) -> Option<Content> {
+        let chars: Vec<char> = content.as_ref().chars().collect();
+        let start = usize::try_from(self.range_start).ok()?;
+        let end = usize::try_from(self.range_end).ok()?;
+        let covered: String = chars.get(start..end)?.iter().collect();
+        if covered != self.original.as_ref() {
+            return None;
+        }
+
+        let mut applied: String = chars[..start].iter().collect();
+        applied.push_str(self.replacement.as_ref());
+        applied.extend(&chars[end..]);
+        Some(Content::from(applied))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "suggestion_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestionStatus {
+    #[default]
+    Pending,
+    Accepted,
+    Rejected,
+}
@@ -0,0 +1,41 @@
+use commons::{id::Id, tag::Tag, time::DateTime};
+use derive_builder::Builder;
+use derive_getters::Getters;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::Entity;
+
+/// A tag put on a fragment or a story.
+#[derive(Debug, Clone, PartialEq, Eq, FromRow, Builder, Getters)]
+#[builder(setter(into))]
+pub struct Tagging {
+    target: TagTarget,
+    target_id: Id,
+    tag: Tag,
+    created_at: DateTime,
+}
+
+impl Entity for Tagging {
+    type Id = (TagTarget, Id, Tag);
+
+    fn id(&self) -> Self::Id {
+        (self.target, self.target_id, self.tag.clone())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, sqlx::Type, Copy)]
+#[sqlx(type_name = "tag_target", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TagTarget {
+    Fragment,
+    Story,
+}
+
+/// How many published fragments and stories carry a tag.
+#[derive(Debug, Clone, PartialEq, Eq, FromRow, Getters)]
+pub struct TagCount {
+    tag: Tag,
+    fragments: i64,
+    stories: i64,
+}
@@ -2,22 +2,42 @@ use ::serde::de::DeserializeOwned;
 use commons::{actor::ActorType, commands::CommandType, id::Id, time::DateTime};
 use derive_builder::Builder;
 use derive_getters::Getters;
+use derive_setters::Setters;
 use serde::Serialize;
 use serde_json::Value;
 use sqlx::{FromRow, Type};
 
 use crate::Entity;
 
-#[derive(Debug, FromRow, Getters, Builder)]
+/// Command scheduled by `CommandBus::dispatch`, run once `scheduled_at` is reached.
+#[derive(Debug, FromRow, Getters, Builder, Setters)]
+#[setters(prefix = "set_")]
+#[setters(into)]
 pub struct Task {
+    #[setters(skip)]
     id: Id,
+    #[setters(skip)]
     command_type: CommandType,
-    commnad_data: CommandData,
+    #[setters(skip)]
+    command_data: CommandData,
+    #[setters(skip)]
     actor_type: ActorType,
+    #[setters(skip)]
     actor_id: Option<Id>,
+    #[setters(skip)]
     created_at: DateTime,
+    #[setters(skip)]
     scheduled_at: DateTime,
+    /// When a runner last took the task, see `QueryTask::claim_due`.
+    #[builder(default)]
+    claimed_at: Option<DateTime>,
+    #[builder(default)]
     completed_at: Option<DateTime>,
+    #[builder(default)]
+    cancelled_at: Option<DateTime>,
+    /// Error of the last run, if it failed. Failed tasks are completed and not retried.
+    #[builder(default)]
+    failure: Option<String>,
 }
 
 impl Entity for Task {
@@ -28,6 +48,12 @@ impl Entity for Task {
     }
 }
 
+impl Task {
+    pub const fn is_pending(&self) -> bool {
+        self.completed_at.is_none() && self.cancelled_at.is_none()
+    }
+}
+
 #[derive(Debug, Type, Clone)]
 #[sqlx(transparent)]
 pub struct CommandData(Value);
@@ -0,0 +1,30 @@
+use commons::{id::Id, time::DateTime};
+use derive_builder::Builder;
+use derive_getters::Getters;
+use sqlx::FromRow;
+
+use crate::Entity;
+
+/// User whose forks are approved without review by the author of the parent fragment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromRow, Builder, Getters)]
+#[builder(setter(into))]
+pub struct TrustedContributor {
+    /// Author granting the trust.
+    author_id: Id,
+
+    contributor_id: Id,
+
+    /// Whether auto-approved forks are published right away.
+    #[builder(default)]
+    auto_publish: bool,
+
+    created_at: DateTime,
+}
+
+impl Entity for TrustedContributor {
+    type Id = (Id, Id);
+
+    fn id(&self) -> Self::Id {
+        (self.author_id, self.contributor_id)
+    }
+}
@@ -1,17 +1,40 @@
 use commons::{
-    actor::{Actor, ActorTrait},
+    actor::{Actor, ActorTrait, Role},
     id::Id,
+    time::DateTime,
 };
 use derive_builder::Builder;
 use derive_getters::Getters;
+use derive_setters::Setters;
 use sqlx::FromRow;
 
 use crate::Entity;
 
-#[derive(Debug, Builder, Clone, FromRow, Getters)]
+#[derive(Debug, Builder, Clone, FromRow, Getters, Setters, PartialEq, Eq)]
 #[builder(setter(into))]
+#[setters(prefix = "set_")]
+#[setters(into)]
 pub struct User {
+    #[setters(skip)]
     id: Id,
+
+    #[setters(skip)]
+    username: String,
+
+    #[builder(default)]
+    display_name: Option<String>,
+
+    #[builder(default)]
+    bio: Option<String>,
+
+    #[builder(default)]
+    avatar_url: Option<String>,
+
+    #[builder(default)]
+    role: Role,
+
+    #[setters(skip)]
+    created_at: DateTime,
 }
 
 impl Entity for User {
@@ -26,4 +49,8 @@ impl ActorTrait for User {
     fn actor(&self) -> Actor {
         Actor::User(self.id)
     }
+
+    fn role(&self) -> Role {
+        self.role
+    }
 }
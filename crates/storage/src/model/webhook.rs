@@ -0,0 +1,161 @@
+use commons::{auth::WebhookSecret, events::EventType, id::Id, time::DateTime};
+use derive_builder::Builder;
+use derive_getters::Getters;
+use derive_setters::Setters;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{FromRow, Type};
+
+use crate::Entity;
+
+/// HTTP endpoint called with the events a user subscribed to.
+#[derive(Debug, Builder, Clone, FromRow, Getters, Setters, PartialEq, Eq)]
+#[builder(setter(into))]
+#[setters(prefix = "set_")]
+#[setters(into)]
+pub struct Webhook {
+    #[setters(skip)]
+    id: Id,
+
+    #[setters(skip)]
+    owner_id: Id,
+
+    #[setters(skip)]
+    url: String,
+
+    /// Key of the HMAC signature sent with every delivery.
+    #[setters(skip)]
+    secret: WebhookSecret,
+
+    /// Event types the endpoint receives, all of them when empty.
+    #[builder(default)]
+    #[setters(skip)]
+    event_types: Vec<EventType>,
+
+    /// Story the events must belong to, if any.
+    #[builder(default)]
+    #[setters(skip)]
+    story_id: Option<Id>,
+
+    #[builder(default = "true")]
+    enabled: bool,
+
+    /// Failed attempts since the last successful delivery.
+    #[builder(default)]
+    consecutive_failures: i32,
+
+    #[setters(skip)]
+    created_at: DateTime,
+
+    updated_at: DateTime,
+}
+
+impl Entity for Webhook {
+    type Id = Id;
+
+    fn id(&self) -> Self::Id {
+        self.id
+    }
+}
+
+impl Webhook {
+    pub fn is_owner(&self, user: impl Into<Id>) -> bool {
+        self.owner_id == user.into()
+    }
+
+    /// Whether the endpoint wants events of the given type, regardless of their story.
+    pub fn subscribes_to(&self, event_type: EventType) -> bool {
+        self.event_types.is_empty() || self.event_types.contains(&event_type)
+    }
+}
+
+/// One event queued for a webhook, retried until delivered or out of attempts.
+#[derive(Debug, Builder, Clone, FromRow, Getters, Setters, PartialEq)]
+#[builder(setter(into))]
+#[setters(prefix = "set_")]
+#[setters(into)]
+pub struct WebhookDelivery {
+    #[setters(skip)]
+    id: Id,
+
+    #[setters(skip)]
+    webhook_id: Id,
+
+    #[setters(skip)]
+    event_id: Id,
+
+    #[setters(skip)]
+    event_type: EventType,
+
+    /// Body posted to the endpoint.
+    #[setters(skip)]
+    payload: Value,
+
+    #[builder(default)]
+    state: WebhookDeliveryState,
+
+    #[builder(default)]
+    attempts: i32,
+
+    next_attempt_at: DateTime,
+
+    #[setters(skip)]
+    created_at: DateTime,
+
+    #[builder(default)]
+    completed_at: Option<DateTime>,
+}
+
+impl Entity for WebhookDelivery {
+    type Id = Id;
+
+    fn id(&self) -> Self::Id {
+        self.id
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Type, Default)]
+#[sqlx(type_name = "webhook_delivery_state", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookDeliveryState {
+    #[default]
+    Pending,
+    Delivered,
+    Failed,
+}
+
+/// Outcome of one HTTP call made for a delivery.
+#[derive(Debug, Builder, Clone, FromRow, Getters, PartialEq, Eq)]
+#[builder(setter(into))]
+pub struct WebhookAttempt {
+    id: Id,
+
+    delivery_id: Id,
+
+    /// Status of the response, if one was received.
+    #[builder(default)]
+    status_code: Option<i32>,
+
+    /// Why the attempt failed, if it did.
+    #[builder(default)]
+    error: Option<String>,
+
+    attempted_at: DateTime,
+}
+
+impl Entity for WebhookAttempt {
+    type Id = Id;
+
+    fn id(&self) -> Self::Id {
+        self.id
+    }
+}
+
+impl WebhookAttempt {
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+            && self
+                .status_code
+                .is_some_and(|code| (200..300).contains(&code))
+    }
+}
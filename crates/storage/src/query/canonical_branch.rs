@@ -0,0 +1,117 @@
+use commons::id::Id;
+use sqlx::PgExecutor;
+
+use crate::{
+    model::{canonical_branch::CanonicalBranch, fragment::Fragment},
+    StorageError,
+};
+
+#[async_trait::async_trait]
+impl QueryCanonicalBranch for CanonicalBranch {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO canonical_branches (fragment_id, child_id, chosen_by, chosen_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (fragment_id) DO UPDATE SET
+                child_id = EXCLUDED.child_id,
+                chosen_by = EXCLUDED.chosen_by,
+                chosen_at = EXCLUDED.chosen_at
+            RETURNING *
+            "#,
+        )
+        .bind(self.fragment_id())
+        .bind(self.child_id())
+        .bind(self.chosen_by())
+        .bind(self.chosen_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn delete<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<bool, StorageError> {
+        Ok(
+            sqlx::query("DELETE FROM canonical_branches WHERE fragment_id = $1")
+                .bind(self.fragment_id())
+                .execute(exec)
+                .await
+                .map(|r| r.rows_affected() > 0)?,
+        )
+    }
+
+    async fn find<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Option<Self>, StorageError> {
+        Ok(
+            sqlx::query_as("SELECT * FROM canonical_branches WHERE fragment_id = $1")
+                .bind(fragment_id)
+                .fetch_optional(exec)
+                .await?,
+        )
+    }
+
+    async fn find_in_subtree<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Vec<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            SELECT b.* FROM canonical_branches b
+            JOIN fragments f ON f.id = b.fragment_id
+            WHERE f.id = $1 OR $1 = ANY(f.path)
+            "#,
+        )
+        .bind(fragment_id)
+        .fetch_all(exec)
+        .await?)
+    }
+
+    async fn path<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Vec<Fragment>, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            WITH RECURSIVE route AS (
+                SELECT f.*, 0 AS depth FROM fragments f WHERE f.id = $1
+                UNION ALL
+                SELECT c.*, r.depth + 1 FROM route r
+                JOIN canonical_branches b ON b.fragment_id = r.id
+                JOIN fragments c ON c.id = b.child_id
+                WHERE c.state = 'published' AND c.deleted_at IS NULL
+            )
+            SELECT * FROM route ORDER BY depth
+            "#,
+        )
+        .bind(fragment_id)
+        .fetch_all(exec)
+        .await?)
+    }
+}
+
+#[async_trait::async_trait]
+pub trait QueryCanonicalBranch {
+    /// Saves the canonical branch of a fragment, replacing the previous one.
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<CanonicalBranch, StorageError>;
+
+    async fn delete<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<bool, StorageError>;
+
+    async fn find<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Option<CanonicalBranch>, StorageError>;
+
+    /// Canonical branches chosen within the subtree of a fragment, the fragment included.
+    async fn find_in_subtree<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Vec<CanonicalBranch>, StorageError>;
+
+    /// Default route through the tree: the fragment followed by its canonical child, then
+    /// the canonical child of that child and so on. The route stops at the first fragment
+    /// without a canonical child, or whose canonical child is no longer published.
+    async fn path<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Vec<Fragment>, StorageError>;
+}
@@ -0,0 +1,107 @@
+use commons::id::Id;
+use sqlx::PgExecutor;
+
+use crate::{model::comment::Comment, StorageError};
+
+#[async_trait::async_trait]
+impl QueryComment for Comment {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO comments
+                (id, fragment_id, review_id, parent_id, author_id, body, created_at,
+                 last_modified_at, deleted_at, hidden_at, hidden_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING *
+            "#,
+        )
+        .bind(self.id())
+        .bind(self.fragment_id())
+        .bind(self.review_id())
+        .bind(self.parent_id())
+        .bind(self.author_id())
+        .bind(self.body())
+        .bind(self.created_at())
+        .bind(self.last_modified_at())
+        .bind(self.deleted_at())
+        .bind(self.hidden_at())
+        .bind(self.hidden_by())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn update<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            UPDATE comments
+            SET body = $2, last_modified_at = $3, deleted_at = $4, hidden_at = $5, hidden_by = $6
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(self.id())
+        .bind(self.body())
+        .bind(self.last_modified_at())
+        .bind(self.deleted_at())
+        .bind(self.hidden_at())
+        .bind(self.hidden_by())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn find<'e, E: PgExecutor<'e>>(exec: E, id: &Id) -> Result<Option<Self>, StorageError> {
+        Ok(sqlx::query_as("SELECT * FROM comments WHERE id = $1")
+            .bind(id)
+            .fetch_optional(exec)
+            .await?)
+    }
+
+    async fn find_by_fragment<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Vec<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            SELECT * FROM comments
+            WHERE fragment_id = $1 AND review_id IS NULL
+            ORDER BY created_at
+            "#,
+        )
+        .bind(fragment_id)
+        .fetch_all(exec)
+        .await?)
+    }
+
+    async fn find_by_review<'e, E: PgExecutor<'e>>(
+        exec: E,
+        review_id: &Id,
+    ) -> Result<Vec<Self>, StorageError> {
+        Ok(
+            sqlx::query_as("SELECT * FROM comments WHERE review_id = $1 ORDER BY created_at")
+                .bind(review_id)
+                .fetch_all(exec)
+                .await?,
+        )
+    }
+}
+
+#[async_trait::async_trait]
+pub trait QueryComment: Send {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Comment, StorageError>;
+
+    async fn update<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Comment, StorageError>;
+
+    async fn find<'e, E: PgExecutor<'e>>(exec: E, id: &Id)
+        -> Result<Option<Comment>, StorageError>;
+
+    /// Comments on the fragment itself, leaving out the review threads.
+    async fn find_by_fragment<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Vec<Comment>, StorageError>;
+
+    async fn find_by_review<'e, E: PgExecutor<'e>>(
+        exec: E,
+        review_id: &Id,
+    ) -> Result<Vec<Comment>, StorageError>;
+}
@@ -0,0 +1,42 @@
+use sqlx::PgExecutor;
+
+use crate::{model::credential::Credential, StorageError};
+
+#[async_trait::async_trait]
+impl QueryCredential for Credential {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO credentials (user_id, login, password_hash, created_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(self.user_id())
+        .bind(self.login())
+        .bind(self.password_hash())
+        .bind(self.created_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn find_by_login<'e, E: PgExecutor<'e>>(
+        exec: E,
+        login: &str,
+    ) -> Result<Option<Self>, StorageError> {
+        Ok(sqlx::query_as("SELECT * FROM credentials WHERE login = $1")
+            .bind(login)
+            .fetch_optional(exec)
+            .await?)
+    }
+}
+
+#[async_trait::async_trait]
+pub trait QueryCredential {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Credential, StorageError>;
+
+    async fn find_by_login<'e, E: PgExecutor<'e>>(
+        exec: E,
+        login: &str,
+    ) -> Result<Option<Credential>, StorageError>;
+}
@@ -1,14 +1,19 @@
+use commons::id::Id;
 use sqlx::PgExecutor;
 
 use crate::{model::event::DbEvent, StorageError};
 
+/// Channel notified with the id of every saved event. Postgres only delivers the
+/// notification once the transaction saving the event commits.
+pub const EVENTS_CHANNEL: &str = "events";
+
 #[async_trait::async_trait]
 impl QueryEvent for DbEvent {
     async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
         Ok(sqlx::query_as(
             r#"
-            INSERT INTO events (id, event_type, event_data, timestamp)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO events (id, event_type, event_data, timestamp, actor_type, actor_id)
+            VALUES ($1, $2, $3, $4, $5, $6)
             RETURNING *
             "#,
         )
@@ -16,10 +21,21 @@ impl QueryEvent for DbEvent {
         .bind(self.event_type())
         .bind(self.event_data())
         .bind(self.timestamp())
+        .bind(self.actor_type())
+        .bind(self.actor_id())
         .fetch_one(exec)
         .await?)
     }
 
+    async fn notify<'e, E: PgExecutor<'e>>(&self, exec: E) -> Result<(), StorageError> {
+        sqlx::query("SELECT pg_notify($1, $2)")
+            .bind(EVENTS_CHANNEL)
+            .bind(self.id().to_string())
+            .execute(exec)
+            .await?;
+        Ok(())
+    }
+
     async fn all<'e, E: PgExecutor<'e>>(exec: E) -> Result<Vec<Self>, StorageError> {
         Ok(sqlx::query_as(
             r#"
@@ -29,11 +45,59 @@ impl QueryEvent for DbEvent {
         .fetch_all(exec)
         .await?)
     }
+
+    async fn find<'e, E: PgExecutor<'e>>(exec: E, id: &Id) -> Result<Option<Self>, StorageError> {
+        Ok(sqlx::query_as("SELECT * FROM events WHERE id = $1")
+            .bind(id)
+            .fetch_optional(exec)
+            .await?)
+    }
+
+    async fn last_position<'e, E: PgExecutor<'e>>(exec: E) -> Result<Option<i64>, StorageError> {
+        Ok(sqlx::query_scalar("SELECT MAX(position) FROM events")
+            .fetch_one(exec)
+            .await?)
+    }
+
+    async fn find_after<'e, E: PgExecutor<'e>>(
+        exec: E,
+        position: i64,
+        limit: i64,
+    ) -> Result<Vec<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            SELECT * FROM events
+            WHERE position > $1
+            ORDER BY position
+            LIMIT $2
+            "#,
+        )
+        .bind(position)
+        .bind(limit)
+        .fetch_all(exec)
+        .await?)
+    }
 }
 
 #[async_trait::async_trait]
 pub trait QueryEvent {
     async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<DbEvent, StorageError>;
 
+    /// Announces the event on [`EVENTS_CHANNEL`].
+    async fn notify<'e, E: PgExecutor<'e>>(&self, exec: E) -> Result<(), StorageError>;
+
     async fn all<'e, E: PgExecutor<'e>>(exec: E) -> Result<Vec<DbEvent>, StorageError>;
+
+    async fn find<'e, E: PgExecutor<'e>>(exec: E, id: &Id)
+        -> Result<Option<DbEvent>, StorageError>;
+
+    /// Position of the last committed event.
+    async fn last_position<'e, E: PgExecutor<'e>>(exec: E) -> Result<Option<i64>, StorageError>;
+
+    /// Events committed after the one at `position`, in commit order.
+    async fn find_after<'e, E: PgExecutor<'e>>(
+        exec: E,
+        position: i64,
+        limit: i64,
+    ) -> Result<Vec<DbEvent>, StorageError>;
 }
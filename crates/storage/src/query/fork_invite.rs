@@ -0,0 +1,87 @@
+use commons::id::Id;
+use sqlx::PgExecutor;
+
+use crate::{model::fork_invite::ForkInvite, StorageError};
+
+#[async_trait::async_trait]
+impl QueryForkInvite for ForkInvite {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO fork_invites (fragment_id, user_id, created_at)
+            VALUES ($1, $2, $3)
+            RETURNING *
+            "#,
+        )
+        .bind(self.fragment_id())
+        .bind(self.user_id())
+        .bind(self.created_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn delete_all<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<u64, StorageError> {
+        Ok(
+            sqlx::query("DELETE FROM fork_invites WHERE fragment_id = $1")
+                .bind(fragment_id)
+                .execute(exec)
+                .await
+                .map(|r| r.rows_affected())?,
+        )
+    }
+
+    async fn find<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+        user_id: &Id,
+    ) -> Result<Option<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            SELECT *
+            FROM fork_invites
+            WHERE
+                fragment_id = $1 AND
+                user_id = $2"#,
+        )
+        .bind(fragment_id)
+        .bind(user_id)
+        .fetch_optional(exec)
+        .await?)
+    }
+
+    async fn find_by_fragment<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Vec<Self>, StorageError> {
+        Ok(
+            sqlx::query_as("SELECT * FROM fork_invites WHERE fragment_id = $1 ORDER BY created_at")
+                .bind(fragment_id)
+                .fetch_all(exec)
+                .await?,
+        )
+    }
+}
+
+#[async_trait::async_trait]
+pub trait QueryForkInvite: Send {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<ForkInvite, StorageError>;
+
+    async fn delete_all<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<u64, StorageError>;
+
+    async fn find<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+        user_id: &Id,
+    ) -> Result<Option<ForkInvite>, StorageError>;
+
+    async fn find_by_fragment<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Vec<ForkInvite>, StorageError>;
+}
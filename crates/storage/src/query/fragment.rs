@@ -1,4 +1,4 @@
-use commons::id::Id;
+use commons::{id::Id, time::DateTime};
 use sqlx::{query_as, PgExecutor};
 
 use crate::{
@@ -8,6 +8,14 @@ use crate::{
 
 use super::user::QueryUser;
 
+/// Fragments that are neither deleted nor below a deleted ancestor.
+pub(crate) const VISIBLE: &str = r#"
+    deleted_at IS NULL
+    AND NOT EXISTS (
+        SELECT 1 FROM fragments a
+        WHERE a.id = ANY(fragments.path) AND a.deleted_at IS NOT NULL
+    )"#;
+
 #[async_trait::async_trait]
 impl QueryFragment for Fragment {
     async fn author<'e, E: PgExecutor<'e>>(&self, exec: E) -> Result<User, StorageError> {
@@ -18,33 +26,102 @@ impl QueryFragment for Fragment {
         &self,
         exec: E,
     ) -> Result<Option<Self>, StorageError> {
+        query_as(&format!(
+            "SELECT * from fragments WHERE id = $1 AND {VISIBLE}"
+        ))
+        .bind(self.parent_id())
+        .fetch_optional(exec)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn root<'e, E: PgExecutor<'e>>(&self, exec: E) -> Result<Self, StorageError> {
+        if self.is_root() {
+            return Ok(self.clone());
+        }
         query_as("SELECT * from fragments WHERE id = $1")
-            .bind(self.parent_id())
-            .fetch_optional(exec)
+            .bind(self.root_id())
+            .fetch_one(exec)
             .await
             .map_err(Into::into)
     }
 
     async fn children<'e, E: PgExecutor<'e>>(&self, exec: E) -> Result<Vec<Self>, StorageError> {
-        query_as("SELECT * from fragments WHERE parent_id = $1")
-            .bind(self.id())
-            .fetch_all(exec)
-            .await
-            .map_err(Into::into)
+        query_as(&format!(
+            "SELECT * from fragments WHERE parent_id = $1 AND {VISIBLE}"
+        ))
+        .bind(self.id())
+        .fetch_all(exec)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn published_descendants<'e, E: PgExecutor<'e>>(
+        &self,
+        exec: E,
+    ) -> Result<Vec<Self>, StorageError> {
+        query_as(&format!(
+            r#"
+            SELECT * from fragments
+            WHERE $1 = ANY(path) AND state = 'published' AND {VISIBLE}
+            ORDER BY cardinality(path), created_at
+            "#
+        ))
+        .bind(self.id())
+        .fetch_all(exec)
+        .await
+        .map_err(Into::into)
     }
 
     async fn find<'e, E: PgExecutor<'e>>(exec: E, id: &Id) -> Result<Option<Self>, StorageError> {
-        query_as("SELECT * from fragments  WHERE id = $1")
+        query_as(&format!(
+            "SELECT * from fragments WHERE id = $1 AND {VISIBLE}"
+        ))
+        .bind(id)
+        .fetch_optional(exec)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn find_for_update<'e, E: PgExecutor<'e>>(
+        exec: E,
+        id: &Id,
+    ) -> Result<Option<Self>, StorageError> {
+        query_as(&format!(
+            "SELECT * from fragments WHERE id = $1 AND {VISIBLE} FOR NO KEY UPDATE"
+        ))
+        .bind(id)
+        .fetch_optional(exec)
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn find_with_deleted<'e, E: PgExecutor<'e>>(
+        exec: E,
+        id: &Id,
+    ) -> Result<Option<Self>, StorageError> {
+        query_as("SELECT * from fragments WHERE id = $1")
             .bind(id)
             .fetch_optional(exec)
             .await
             .map_err(Into::into)
     }
 
+    async fn find_deleted_before<'e, E: PgExecutor<'e>>(
+        exec: E,
+        before: &DateTime,
+    ) -> Result<Vec<Self>, StorageError> {
+        query_as("SELECT * from fragments WHERE deleted_at < $1 ORDER BY deleted_at")
+            .bind(before)
+            .fetch_all(exec)
+            .await
+            .map_err(Into::into)
+    }
+
     async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
         query_as(r#"
-            INSERT INTO fragments (id, author_id, content, state, parent_id, created_at, last_modified_at, path, _end) 
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) RETURNING *"#)
+            INSERT INTO fragments (id, author_id, content, state, parent_id, created_at, last_modified_at, path, _end, fork_policy) 
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) RETURNING *"#)
         .bind(self.id())
         .bind(self.author_id())
         .bind(self.content())
@@ -54,6 +131,7 @@ impl QueryFragment for Fragment {
         .bind(self.last_modified_at())
         .bind(self.path())
         .bind(self.end())
+        .bind(self.fork_policy())
         .fetch_one(exec).await
         .map_err(Into::into)
     }
@@ -66,7 +144,10 @@ impl QueryFragment for Fragment {
                 content = $2, 
                 state = $3, 
                 last_modified_at = $4,
-                _end = $5
+                _end = $5,
+                fork_policy = $6,
+                deleted_at = $7,
+                taken_down_at = $8
             WHERE id = $1 RETURNING *"#,
         )
         .bind(self.id())
@@ -74,10 +155,102 @@ impl QueryFragment for Fragment {
         .bind(self.state())
         .bind(self.last_modified_at())
         .bind(self.end())
+        .bind(self.fork_policy())
+        .bind(self.deleted_at())
+        .bind(self.taken_down_at())
         .fetch_one(exec)
         .await
         .map_err(Into::into)
     }
+
+    async fn purge<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Vec<Id>, StorageError> {
+        sqlx::query_scalar(
+            r#"
+            WITH tree AS (
+                SELECT id FROM fragments WHERE id = $1 OR $1 = ANY(path)
+            ),
+            purged_likes AS (
+                DELETE FROM likes WHERE fragment_id IN (SELECT id FROM tree)
+            ),
+            purged_comments AS (
+                DELETE FROM comments WHERE fragment_id IN (SELECT id FROM tree)
+            ),
+            purged_suggestions AS (
+                DELETE FROM review_suggestions WHERE fragment_id IN (SELECT id FROM tree)
+            ),
+            purged_reviews AS (
+                DELETE FROM reviews WHERE fragment_id IN (SELECT id FROM tree)
+            ),
+            purged_invites AS (
+                DELETE FROM fork_invites WHERE fragment_id IN (SELECT id FROM tree)
+            ),
+            purged_revisions AS (
+                DELETE FROM fragment_revisions WHERE fragment_id IN (SELECT id FROM tree)
+            ),
+            purged_canonical_branches AS (
+                DELETE FROM canonical_branches
+                WHERE fragment_id IN (SELECT id FROM tree) OR child_id IN (SELECT id FROM tree)
+            ),
+            purged_votes AS (
+                DELETE FROM votes
+                WHERE child_id IN (SELECT id FROM tree)
+                    OR poll_id IN (SELECT id FROM polls WHERE fragment_id IN (SELECT id FROM tree))
+            ),
+            purged_polls AS (
+                DELETE FROM polls WHERE fragment_id IN (SELECT id FROM tree)
+            ),
+            purged_slas AS (
+                DELETE FROM review_slas WHERE root_id IN (SELECT id FROM tree)
+            ),
+            purged_quorums AS (
+                DELETE FROM review_quorums WHERE root_id IN (SELECT id FROM tree)
+            ),
+            purged_maintainers AS (
+                DELETE FROM maintainers WHERE root_id IN (SELECT id FROM tree)
+            ),
+            purged_notifications AS (
+                DELETE FROM notifications WHERE subject_id IN (SELECT id FROM tree)
+            ),
+            purged_webhook_attempts AS (
+                DELETE FROM webhook_attempts WHERE delivery_id IN (
+                    SELECT d.id FROM webhook_deliveries d
+                    JOIN webhooks w ON w.id = d.webhook_id
+                    JOIN stories s ON s.id = w.story_id
+                    WHERE s.fragment_id IN (SELECT id FROM tree)
+                )
+            ),
+            purged_webhook_deliveries AS (
+                DELETE FROM webhook_deliveries WHERE webhook_id IN (
+                    SELECT w.id FROM webhooks w
+                    JOIN stories s ON s.id = w.story_id
+                    WHERE s.fragment_id IN (SELECT id FROM tree)
+                )
+            ),
+            purged_webhooks AS (
+                DELETE FROM webhooks WHERE story_id IN (
+                    SELECT id FROM stories WHERE fragment_id IN (SELECT id FROM tree)
+                )
+            ),
+            purged_transitions AS (
+                DELETE FROM fragment_state_transitions WHERE fragment_id IN (SELECT id FROM tree)
+            ),
+            purged_tags AS (
+                DELETE FROM tags
+                WHERE (target = 'fragment' AND target_id IN (SELECT id FROM tree))
+                    OR (target = 'story' AND target_id IN (
+                        SELECT id FROM stories WHERE fragment_id IN (SELECT id FROM tree)
+                    ))
+            ),
+            purged_stories AS (
+                DELETE FROM stories WHERE fragment_id IN (SELECT id FROM tree)
+            )
+            DELETE FROM fragments WHERE id IN (SELECT id FROM tree) RETURNING id"#,
+        )
+        .bind(self.id())
+        .fetch_all(exec)
+        .await
+        .map_err(Into::into)
+    }
 }
 
 #[async_trait::async_trait]
@@ -89,15 +262,44 @@ pub trait QueryFragment {
         exec: E,
     ) -> Result<Option<Fragment>, StorageError>;
 
+    async fn root<'e, E: PgExecutor<'e>>(&self, exec: E) -> Result<Fragment, StorageError>;
+
     async fn children<'e, E: PgExecutor<'e>>(&self, exec: E)
         -> Result<Vec<Fragment>, StorageError>;
 
+    /// Published, visible fragments below this one, level by level.
+    async fn published_descendants<'e, E: PgExecutor<'e>>(
+        &self,
+        exec: E,
+    ) -> Result<Vec<Fragment>, StorageError>;
+
     async fn find<'e, E: PgExecutor<'e>>(
         exec: E,
         id: &Id,
     ) -> Result<Option<Fragment>, StorageError>;
 
+    /// Same as `find`, but locks the fragment until the transaction ends so that concurrent
+    /// commands changing it run one after the other.
+    async fn find_for_update<'e, E: PgExecutor<'e>>(
+        exec: E,
+        id: &Id,
+    ) -> Result<Option<Fragment>, StorageError>;
+
+    /// Same as `find`, but also returns soft deleted fragments.
+    async fn find_with_deleted<'e, E: PgExecutor<'e>>(
+        exec: E,
+        id: &Id,
+    ) -> Result<Option<Fragment>, StorageError>;
+
+    async fn find_deleted_before<'e, E: PgExecutor<'e>>(
+        exec: E,
+        before: &DateTime,
+    ) -> Result<Vec<Fragment>, StorageError>;
+
     async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Fragment, StorageError>;
 
     async fn update<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Fragment, StorageError>;
+
+    /// Hard deletes the fragment, its subtree and everything attached to them.
+    async fn purge<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Vec<Id>, StorageError>;
 }
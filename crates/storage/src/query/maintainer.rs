@@ -0,0 +1,117 @@
+use commons::id::Id;
+use sqlx::PgExecutor;
+
+use crate::{model::maintainer::Maintainer, StorageError};
+
+#[async_trait::async_trait]
+impl QueryMaintainer for Maintainer {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO maintainers (root_id, user_id, invited_by, state, invited_at, responded_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (root_id, user_id) DO UPDATE SET
+                invited_by = EXCLUDED.invited_by,
+                state = EXCLUDED.state,
+                invited_at = EXCLUDED.invited_at,
+                responded_at = EXCLUDED.responded_at
+            RETURNING *
+            "#,
+        )
+        .bind(self.root_id())
+        .bind(self.user_id())
+        .bind(self.invited_by())
+        .bind(self.state())
+        .bind(self.invited_at())
+        .bind(self.responded_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn update<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            UPDATE maintainers SET state = $3, responded_at = $4
+            WHERE root_id = $1 AND user_id = $2
+            RETURNING *
+            "#,
+        )
+        .bind(self.root_id())
+        .bind(self.user_id())
+        .bind(self.state())
+        .bind(self.responded_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn find<'e, E: PgExecutor<'e>>(
+        exec: E,
+        root_id: &Id,
+        user_id: &Id,
+    ) -> Result<Option<Self>, StorageError> {
+        Ok(
+            sqlx::query_as("SELECT * FROM maintainers WHERE root_id = $1 AND user_id = $2")
+                .bind(root_id)
+                .bind(user_id)
+                .fetch_optional(exec)
+                .await?,
+        )
+    }
+
+    async fn find_by_root<'e, E: PgExecutor<'e>>(
+        exec: E,
+        root_id: &Id,
+    ) -> Result<Vec<Self>, StorageError> {
+        Ok(
+            sqlx::query_as("SELECT * FROM maintainers WHERE root_id = $1 ORDER BY invited_at")
+                .bind(root_id)
+                .fetch_all(exec)
+                .await?,
+        )
+    }
+
+    async fn is_maintainer<'e, E: PgExecutor<'e>>(
+        exec: E,
+        root_id: &Id,
+        user_id: &Id,
+    ) -> Result<bool, StorageError> {
+        Ok(sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM maintainers
+                WHERE root_id = $1 AND user_id = $2 AND state = 'accepted'
+            )
+            "#,
+        )
+        .bind(root_id)
+        .bind(user_id)
+        .fetch_one(exec)
+        .await?)
+    }
+}
+
+#[async_trait::async_trait]
+pub trait QueryMaintainer {
+    /// Saves an invitation, replacing a previous one for the same user.
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Maintainer, StorageError>;
+
+    async fn update<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Maintainer, StorageError>;
+
+    async fn find<'e, E: PgExecutor<'e>>(
+        exec: E,
+        root_id: &Id,
+        user_id: &Id,
+    ) -> Result<Option<Maintainer>, StorageError>;
+
+    async fn find_by_root<'e, E: PgExecutor<'e>>(
+        exec: E,
+        root_id: &Id,
+    ) -> Result<Vec<Maintainer>, StorageError>;
+
+    /// Whether the user accepted to co-maintain the story.
+    async fn is_maintainer<'e, E: PgExecutor<'e>>(
+        exec: E,
+        root_id: &Id,
+        user_id: &Id,
+    ) -> Result<bool, StorageError>;
+}
@@ -0,0 +1,187 @@
+use commons::{id::Id, time::DateTime};
+use sqlx::PgExecutor;
+
+use crate::{
+    model::notification::{Notification, NotificationKind},
+    StorageError,
+};
+
+#[async_trait::async_trait]
+impl QueryNotification for Notification {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO notifications
+                (id, user_id, kind, subject_id, actor_ids, created_at, updated_at, read_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *
+            "#,
+        )
+        .bind(self.id())
+        .bind(self.user_id())
+        .bind(self.kind())
+        .bind(self.subject_id())
+        .bind(self.actor_ids())
+        .bind(self.created_at())
+        .bind(self.updated_at())
+        .bind(self.read_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn save_grouped<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO notifications
+                (id, user_id, kind, subject_id, actor_ids, created_at, updated_at, read_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, NULL)
+            ON CONFLICT (user_id, kind, subject_id) WHERE read_at IS NULL DO UPDATE SET
+                actor_ids = notifications.actor_ids || ARRAY(
+                    SELECT actor_id FROM unnest(EXCLUDED.actor_ids) AS actor_id
+                    WHERE actor_id <> ALL(notifications.actor_ids)
+                ),
+                updated_at = EXCLUDED.updated_at
+            RETURNING *
+            "#,
+        )
+        .bind(self.id())
+        .bind(self.user_id())
+        .bind(self.kind())
+        .bind(self.subject_id())
+        .bind(self.actor_ids())
+        .bind(self.created_at())
+        .bind(self.updated_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn update<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            UPDATE notifications SET actor_ids = $2, updated_at = $3, read_at = $4
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(self.id())
+        .bind(self.actor_ids())
+        .bind(self.updated_at())
+        .bind(self.read_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn find<'e, E: PgExecutor<'e>>(exec: E, id: &Id) -> Result<Option<Self>, StorageError> {
+        Ok(sqlx::query_as("SELECT * FROM notifications WHERE id = $1")
+            .bind(id)
+            .fetch_optional(exec)
+            .await?)
+    }
+
+    async fn find_by_user<'e, E: PgExecutor<'e>>(
+        exec: E,
+        user_id: &Id,
+        unread_only: bool,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            SELECT * FROM notifications
+            WHERE user_id = $1 AND (NOT $2 OR read_at IS NULL)
+            ORDER BY updated_at DESC, id
+            LIMIT $3 OFFSET $4
+            "#,
+        )
+        .bind(user_id)
+        .bind(unread_only)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(exec)
+        .await?)
+    }
+
+    async fn find_for_digest<'e, E: PgExecutor<'e>>(
+        exec: E,
+        user_id: &Id,
+        since: &DateTime,
+        muted: &[NotificationKind],
+    ) -> Result<Vec<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            SELECT * FROM notifications
+            WHERE user_id = $1 AND read_at IS NULL AND updated_at > $2 AND kind <> ALL($3)
+            ORDER BY updated_at DESC, id
+            "#,
+        )
+        .bind(user_id)
+        .bind(since)
+        .bind(muted)
+        .fetch_all(exec)
+        .await?)
+    }
+
+    async fn mark_read<'e, E: PgExecutor<'e>>(
+        exec: E,
+        user_id: &Id,
+        notification_id: Option<&Id>,
+        at: &DateTime,
+    ) -> Result<u64, StorageError> {
+        Ok(sqlx::query(
+            r#"
+            UPDATE notifications SET read_at = $3
+            WHERE user_id = $1 AND ($2::uuid IS NULL OR id = $2) AND read_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(notification_id)
+        .bind(at)
+        .execute(exec)
+        .await
+        .map(|r| r.rows_affected())?)
+    }
+}
+
+#[async_trait::async_trait]
+pub trait QueryNotification {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Notification, StorageError>;
+
+    /// Saves the unread notification, or adds its actors to the unread one of the same
+    /// recipient, kind and subject when there is one. Safe against concurrent saves.
+    async fn save_grouped<'e, E: PgExecutor<'e>>(
+        self,
+        exec: E,
+    ) -> Result<Notification, StorageError>;
+
+    async fn update<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Notification, StorageError>;
+
+    async fn find<'e, E: PgExecutor<'e>>(
+        exec: E,
+        id: &Id,
+    ) -> Result<Option<Notification>, StorageError>;
+
+    /// Most recently updated first.
+    async fn find_by_user<'e, E: PgExecutor<'e>>(
+        exec: E,
+        user_id: &Id,
+        unread_only: bool,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Notification>, StorageError>;
+
+    /// Unread notifications updated since the last digest, leaving out muted kinds.
+    async fn find_for_digest<'e, E: PgExecutor<'e>>(
+        exec: E,
+        user_id: &Id,
+        since: &DateTime,
+        muted: &[NotificationKind],
+    ) -> Result<Vec<Notification>, StorageError>;
+
+    /// Marks one notification, or all of them, as read. Returns how many were unread.
+    async fn mark_read<'e, E: PgExecutor<'e>>(
+        exec: E,
+        user_id: &Id,
+        notification_id: Option<&Id>,
+        at: &DateTime,
+    ) -> Result<u64, StorageError>;
+}
@@ -0,0 +1,158 @@
+use commons::{auth::UnsubscribeToken, id::Id, time::DateTime};
+use sqlx::PgExecutor;
+
+use crate::{model::notification_preferences::NotificationPreferences, StorageError};
+
+#[async_trait::async_trait]
+impl QueryNotificationPreferences for NotificationPreferences {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO notification_preferences
+                (user_id, email, digest, muted_kinds, unsubscribe_token, last_digest_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (user_id) DO UPDATE SET
+                email = EXCLUDED.email,
+                digest = EXCLUDED.digest,
+                muted_kinds = EXCLUDED.muted_kinds,
+                last_digest_at = EXCLUDED.last_digest_at,
+                updated_at = EXCLUDED.updated_at
+            RETURNING *
+            "#,
+        )
+        .bind(self.user_id())
+        .bind(self.email())
+        .bind(self.digest())
+        .bind(self.muted_kinds())
+        .bind(self.unsubscribe_token())
+        .bind(self.last_digest_at())
+        .bind(self.updated_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn find<'e, E: PgExecutor<'e>>(
+        exec: E,
+        user_id: &Id,
+    ) -> Result<Option<Self>, StorageError> {
+        Ok(
+            sqlx::query_as("SELECT * FROM notification_preferences WHERE user_id = $1")
+                .bind(user_id)
+                .fetch_optional(exec)
+                .await?,
+        )
+    }
+
+    async fn find_by_token<'e, E: PgExecutor<'e>>(
+        exec: E,
+        token: &UnsubscribeToken,
+    ) -> Result<Option<Self>, StorageError> {
+        Ok(
+            sqlx::query_as("SELECT * FROM notification_preferences WHERE unsubscribe_token = $1")
+                .bind(token)
+                .fetch_optional(exec)
+                .await?,
+        )
+    }
+
+    async fn find_due_digests<'e, E: PgExecutor<'e>>(
+        exec: E,
+        now: &DateTime,
+    ) -> Result<Vec<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            SELECT * FROM notification_preferences
+            WHERE email IS NOT NULL AND (
+                (digest = 'daily' AND (last_digest_at IS NULL OR last_digest_at <= $1 - interval '1 day'))
+                OR (digest = 'weekly' AND (last_digest_at IS NULL OR last_digest_at <= $1 - interval '7 days'))
+            )
+            ORDER BY user_id
+            "#,
+        )
+        .bind(now)
+        .fetch_all(exec)
+        .await?)
+    }
+
+    async fn claim_digest<'e, E: PgExecutor<'e>>(
+        exec: E,
+        user_id: &Id,
+        last_digest_at: Option<&DateTime>,
+        now: &DateTime,
+    ) -> Result<bool, StorageError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE notification_preferences SET last_digest_at = $3
+            WHERE user_id = $1 AND last_digest_at IS NOT DISTINCT FROM $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(last_digest_at)
+        .bind(now)
+        .execute(exec)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn release_digest<'e, E: PgExecutor<'e>>(
+        exec: E,
+        user_id: &Id,
+        claimed_at: &DateTime,
+        last_digest_at: Option<&DateTime>,
+    ) -> Result<(), StorageError> {
+        sqlx::query(
+            r#"
+            UPDATE notification_preferences SET last_digest_at = $3
+            WHERE user_id = $1 AND last_digest_at = $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(claimed_at)
+        .bind(last_digest_at)
+        .execute(exec)
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+pub trait QueryNotificationPreferences {
+    /// Saves the preferences, keeping the unsubscribe token of existing ones.
+    async fn save<'e, E: PgExecutor<'e>>(
+        self,
+        exec: E,
+    ) -> Result<NotificationPreferences, StorageError>;
+
+    async fn find<'e, E: PgExecutor<'e>>(
+        exec: E,
+        user_id: &Id,
+    ) -> Result<Option<NotificationPreferences>, StorageError>;
+
+    async fn find_by_token<'e, E: PgExecutor<'e>>(
+        exec: E,
+        token: &UnsubscribeToken,
+    ) -> Result<Option<NotificationPreferences>, StorageError>;
+
+    /// Preferences of the users whose next digest is due at `now`.
+    async fn find_due_digests<'e, E: PgExecutor<'e>>(
+        exec: E,
+        now: &DateTime,
+    ) -> Result<Vec<NotificationPreferences>, StorageError>;
+
+    /// Claims the digest of the user by moving their last digest from `last_digest_at` to
+    /// `now`. Returns `false` when a concurrent run claimed it first.
+    async fn claim_digest<'e, E: PgExecutor<'e>>(
+        exec: E,
+        user_id: &Id,
+        last_digest_at: Option<&DateTime>,
+        now: &DateTime,
+    ) -> Result<bool, StorageError>;
+
+    /// Gives back a digest claimed at `claimed_at`, restoring the previous `last_digest_at`.
+    async fn release_digest<'e, E: PgExecutor<'e>>(
+        exec: E,
+        user_id: &Id,
+        claimed_at: &DateTime,
+        last_digest_at: Option<&DateTime>,
+    ) -> Result<(), StorageError>;
+}
@@ -0,0 +1,147 @@
+use commons::id::Id;
+use sqlx::PgExecutor;
+
+use crate::{
+    model::poll::{Poll, PollResult, Vote},
+    StorageError,
+};
+
+#[async_trait::async_trait]
+impl QueryPoll for Poll {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO polls (id, fragment_id, created_by, closes_at, created_at, closed_at, winner_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(self.id())
+        .bind(self.fragment_id())
+        .bind(self.created_by())
+        .bind(self.closes_at())
+        .bind(self.created_at())
+        .bind(self.closed_at())
+        .bind(self.winner_id())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn update<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            UPDATE polls SET closed_at = $2, winner_id = $3
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(self.id())
+        .bind(self.closed_at())
+        .bind(self.winner_id())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn find<'e, E: PgExecutor<'e>>(exec: E, id: &Id) -> Result<Option<Self>, StorageError> {
+        Ok(sqlx::query_as("SELECT * FROM polls WHERE id = $1")
+            .bind(id)
+            .fetch_optional(exec)
+            .await?)
+    }
+
+    async fn find_open_by_fragment<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Option<Self>, StorageError> {
+        Ok(
+            sqlx::query_as("SELECT * FROM polls WHERE fragment_id = $1 AND closed_at IS NULL")
+                .bind(fragment_id)
+                .fetch_optional(exec)
+                .await?,
+        )
+    }
+
+    async fn results<'e, E: PgExecutor<'e>>(
+        &self,
+        exec: E,
+    ) -> Result<Vec<PollResult>, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            SELECT v.child_id, COUNT(*) AS votes
+            FROM votes v
+            JOIN fragments f ON f.id = v.child_id
+            WHERE v.poll_id = $1 AND f.state = 'published' AND f.deleted_at IS NULL
+            GROUP BY v.child_id, f.created_at
+            ORDER BY votes DESC, f.created_at, v.child_id
+            "#,
+        )
+        .bind(self.id())
+        .fetch_all(exec)
+        .await?)
+    }
+}
+
+#[async_trait::async_trait]
+pub trait QueryPoll {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Poll, StorageError>;
+
+    async fn update<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Poll, StorageError>;
+
+    async fn find<'e, E: PgExecutor<'e>>(exec: E, id: &Id) -> Result<Option<Poll>, StorageError>;
+
+    async fn find_open_by_fragment<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Option<Poll>, StorageError>;
+
+    /// Votes per child, most voted first. Ties go to the oldest child. Votes for children
+    /// that are no longer published are left out.
+    async fn results<'e, E: PgExecutor<'e>>(
+        &self,
+        exec: E,
+    ) -> Result<Vec<PollResult>, StorageError>;
+}
+
+#[async_trait::async_trait]
+impl QueryVote for Vote {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO votes (poll_id, user_id, child_id, created_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(self.poll_id())
+        .bind(self.user_id())
+        .bind(self.child_id())
+        .bind(self.created_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn find<'e, E: PgExecutor<'e>>(
+        exec: E,
+        poll_id: &Id,
+        user_id: &Id,
+    ) -> Result<Option<Self>, StorageError> {
+        Ok(
+            sqlx::query_as("SELECT * FROM votes WHERE poll_id = $1 AND user_id = $2")
+                .bind(poll_id)
+                .bind(user_id)
+                .fetch_optional(exec)
+                .await?,
+        )
+    }
+}
+
+#[async_trait::async_trait]
+pub trait QueryVote {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Vote, StorageError>;
+
+    async fn find<'e, E: PgExecutor<'e>>(
+        exec: E,
+        poll_id: &Id,
+        user_id: &Id,
+    ) -> Result<Option<Vote>, StorageError>;
+}
@@ -1,15 +1,85 @@
+use commons::id::Id;
 use sqlx::PgExecutor;
 
 use crate::{model::review::Review, StorageError};
 
 #[async_trait::async_trait]
 impl QueryReview for Review {
-    async fn save<'e, E: PgExecutor<'e>>(self, _: E) -> Result<Self, StorageError> {
-        todo!()
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO reviews (id, fragment_id, reviewer_id, comment, created_at, action)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(self.id())
+        .bind(self.fragment_id())
+        .bind(self.reviewer_id())
+        .bind(self.comment())
+        .bind(self.created_at())
+        .bind(self.action())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn find<'e, E: PgExecutor<'e>>(exec: E, id: &Id) -> Result<Option<Self>, StorageError> {
+        Ok(sqlx::query_as("SELECT * FROM reviews WHERE id = $1")
+            .bind(id)
+            .fetch_optional(exec)
+            .await?)
+    }
+
+    async fn find_by_fragment<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Vec<Self>, StorageError> {
+        Ok(
+            sqlx::query_as("SELECT * FROM reviews WHERE fragment_id = $1 ORDER BY created_at")
+                .bind(fragment_id)
+                .fetch_all(exec)
+                .await?,
+        )
+    }
+
+    async fn find_current_round<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Vec<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            SELECT DISTINCT ON (reviewer_id) * FROM reviews
+            WHERE fragment_id = $1
+            AND created_at >= COALESCE(
+                (
+                    SELECT max(created_at) FROM fragment_state_transitions
+                    WHERE fragment_id = $1 AND to_state = 'submitted'
+                ),
+                '-infinity'
+            )
+            ORDER BY reviewer_id, created_at DESC
+            "#,
+        )
+        .bind(fragment_id)
+        .fetch_all(exec)
+        .await?)
     }
 }
 
 #[async_trait::async_trait]
 pub trait QueryReview {
-    async fn save<'e, E: PgExecutor<'e>>(self, _: E) -> Result<Review, StorageError>;
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Review, StorageError>;
+
+    async fn find<'e, E: PgExecutor<'e>>(exec: E, id: &Id) -> Result<Option<Review>, StorageError>;
+
+    async fn find_by_fragment<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Vec<Review>, StorageError>;
+
+    /// Latest review of each reviewer since the fork was last submitted.
+    async fn find_current_round<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Vec<Review>, StorageError>;
 }
@@ -0,0 +1,50 @@
+use commons::id::Id;
+use sqlx::PgExecutor;
+
+use crate::{model::review_quorum::ReviewQuorum, StorageError};
+
+#[async_trait::async_trait]
+impl QueryReviewQuorum for ReviewQuorum {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO review_quorums (root_id, approvals, veto, updated_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (root_id) DO UPDATE SET
+                approvals = EXCLUDED.approvals,
+                veto = EXCLUDED.veto,
+                updated_at = EXCLUDED.updated_at
+            RETURNING *
+            "#,
+        )
+        .bind(self.root_id())
+        .bind(self.approvals())
+        .bind(self.veto())
+        .bind(self.updated_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn find<'e, E: PgExecutor<'e>>(
+        exec: E,
+        root_id: &Id,
+    ) -> Result<Option<Self>, StorageError> {
+        Ok(
+            sqlx::query_as("SELECT * FROM review_quorums WHERE root_id = $1")
+                .bind(root_id)
+                .fetch_optional(exec)
+                .await?,
+        )
+    }
+}
+
+#[async_trait::async_trait]
+pub trait QueryReviewQuorum {
+    /// Saves the quorum, replacing the current one of the story.
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<ReviewQuorum, StorageError>;
+
+    async fn find<'e, E: PgExecutor<'e>>(
+        exec: E,
+        root_id: &Id,
+    ) -> Result<Option<ReviewQuorum>, StorageError>;
+}
@@ -0,0 +1,50 @@
+use commons::id::Id;
+use sqlx::PgExecutor;
+
+use crate::{model::review_sla::ReviewSla, StorageError};
+
+#[async_trait::async_trait]
+impl QueryReviewSla for ReviewSla {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO review_slas (root_id, window_hours, outcome, updated_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (root_id) DO UPDATE SET
+                window_hours = EXCLUDED.window_hours,
+                outcome = EXCLUDED.outcome,
+                updated_at = EXCLUDED.updated_at
+            RETURNING *
+            "#,
+        )
+        .bind(self.root_id())
+        .bind(self.window_hours())
+        .bind(self.outcome())
+        .bind(self.updated_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn find<'e, E: PgExecutor<'e>>(
+        exec: E,
+        root_id: &Id,
+    ) -> Result<Option<Self>, StorageError> {
+        Ok(
+            sqlx::query_as("SELECT * FROM review_slas WHERE root_id = $1")
+                .bind(root_id)
+                .fetch_optional(exec)
+                .await?,
+        )
+    }
+}
+
+#[async_trait::async_trait]
+pub trait QueryReviewSla {
+    /// Saves the SLA, replacing the current one of the story.
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<ReviewSla, StorageError>;
+
+    async fn find<'e, E: PgExecutor<'e>>(
+        exec: E,
+        root_id: &Id,
+    ) -> Result<Option<ReviewSla>, StorageError>;
+}
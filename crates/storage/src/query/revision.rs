@@ -0,0 +1,95 @@
+use commons::id::Id;
+use sqlx::PgExecutor;
+
+use crate::{model::revision::Revision, StorageError};
+
+#[async_trait::async_trait]
+impl QueryRevision for Revision {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO fragment_revisions (fragment_id, number, content, author_id, created_at)
+            SELECT $1, COALESCE(MAX(number), 0) + 1, $2, $3, $4
+            FROM fragment_revisions
+            WHERE fragment_id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(self.fragment_id())
+        .bind(self.content())
+        .bind(self.author_id())
+        .bind(self.created_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn find<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+        number: i32,
+    ) -> Result<Option<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            "SELECT * FROM fragment_revisions WHERE fragment_id = $1 AND number = $2",
+        )
+        .bind(fragment_id)
+        .bind(number)
+        .fetch_optional(exec)
+        .await?)
+    }
+
+    async fn find_by_fragment<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Vec<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            "SELECT * FROM fragment_revisions WHERE fragment_id = $1 ORDER BY number",
+        )
+        .bind(fragment_id)
+        .fetch_all(exec)
+        .await?)
+    }
+
+    async fn find_since_last_review<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Vec<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            SELECT * FROM fragment_revisions
+            WHERE fragment_id = $1 AND created_at > COALESCE(
+                (SELECT MAX(created_at) FROM reviews WHERE fragment_id = $1),
+                '-infinity'
+            )
+            ORDER BY number
+            "#,
+        )
+        .bind(fragment_id)
+        .fetch_all(exec)
+        .await?)
+    }
+}
+
+#[async_trait::async_trait]
+pub trait QueryRevision: Send {
+    /// Saves the revision under the next free number of its fragment. The fragment must be
+    /// locked with `find_for_update` first, concurrent saves would take the same number.
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Revision, StorageError>;
+
+    async fn find<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+        number: i32,
+    ) -> Result<Option<Revision>, StorageError>;
+
+    async fn find_by_fragment<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Vec<Revision>, StorageError>;
+
+    /// Revisions made after the last review of the fragment, all of them when it was
+    /// never reviewed.
+    async fn find_since_last_review<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Vec<Revision>, StorageError>;
+}
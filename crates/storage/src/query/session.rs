@@ -0,0 +1,65 @@
+use commons::{auth::TokenHash, time::DateTime};
+use sqlx::PgExecutor;
+
+use crate::{model::session::Session, StorageError};
+
+#[async_trait::async_trait]
+impl QuerySession for Session {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO sessions (id, user_id, token_hash, created_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+        )
+        .bind(self.id())
+        .bind(self.user_id())
+        .bind(self.token_hash())
+        .bind(self.created_at())
+        .bind(self.expires_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn delete<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<bool, StorageError> {
+        Ok(sqlx::query("DELETE FROM sessions WHERE id = $1")
+            .bind(self.id())
+            .execute(exec)
+            .await
+            .map(|r| r.rows_affected() > 0)?)
+    }
+
+    async fn find_active<'e, E: PgExecutor<'e>>(
+        exec: E,
+        token_hash: &TokenHash,
+        now: &DateTime,
+    ) -> Result<Option<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            SELECT * 
+            FROM sessions 
+            WHERE 
+                token_hash = $1 AND 
+                expires_at > $2
+            "#,
+        )
+        .bind(token_hash)
+        .bind(now)
+        .fetch_optional(exec)
+        .await?)
+    }
+}
+
+#[async_trait::async_trait]
+pub trait QuerySession {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Session, StorageError>;
+
+    async fn delete<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<bool, StorageError>;
+
+    async fn find_active<'e, E: PgExecutor<'e>>(
+        exec: E,
+        token_hash: &TokenHash,
+        now: &DateTime,
+    ) -> Result<Option<Session>, StorageError>;
+}
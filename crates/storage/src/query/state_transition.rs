@@ -0,0 +1,71 @@
+use commons::{id::Id, time::DateTime};
+use sqlx::PgExecutor;
+
+use crate::{model::state_transition::StateTransition, StorageError};
+
+#[async_trait::async_trait]
+impl QueryStateTransition for StateTransition {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO fragment_state_transitions
+                (fragment_id, transition, from_state, to_state, actor_id, event_type, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(self.fragment_id())
+        .bind(self.transition())
+        .bind(self.from_state())
+        .bind(self.to_state())
+        .bind(self.actor_id())
+        .bind(self.event_type())
+        .bind(self.created_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn find_by_fragment<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Vec<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            "SELECT * FROM fragment_state_transitions WHERE fragment_id = $1 ORDER BY id",
+        )
+        .bind(fragment_id)
+        .fetch_all(exec)
+        .await?)
+    }
+
+    async fn last_submitted_at<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Option<DateTime>, StorageError> {
+        Ok(sqlx::query_scalar(
+            r#"
+            SELECT max(created_at) FROM fragment_state_transitions
+            WHERE fragment_id = $1 AND to_state = 'submitted'
+            "#,
+        )
+        .bind(fragment_id)
+        .fetch_one(exec)
+        .await?)
+    }
+}
+
+#[async_trait::async_trait]
+pub trait QueryStateTransition: Send {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<StateTransition, StorageError>;
+
+    /// Lifecycle history of a fragment, oldest first.
+    async fn find_by_fragment<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Vec<StateTransition>, StorageError>;
+
+    /// When the fork was last submitted or resubmitted for review.
+    async fn last_submitted_at<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Option<DateTime>, StorageError>;
+}
@@ -0,0 +1,124 @@
+use commons::id::Id;
+use sqlx::PgExecutor;
+
+use crate::{
+    model::story::{Story, StoryFilter},
+    StorageError,
+};
+
+#[async_trait::async_trait]
+impl QueryStory for Story {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO stories (
+                id, fragment_id, author_id, title, synopsis, genres, language, maturity,
+                cover_url, created_at, last_modified_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING *
+            "#,
+        )
+        .bind(self.id())
+        .bind(self.fragment_id())
+        .bind(self.author_id())
+        .bind(self.title())
+        .bind(self.synopsis())
+        .bind(self.genres())
+        .bind(self.language())
+        .bind(self.maturity())
+        .bind(self.cover_url())
+        .bind(self.created_at())
+        .bind(self.last_modified_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn update<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            UPDATE stories SET
+                title = $2, synopsis = $3, genres = $4, language = $5, maturity = $6,
+                cover_url = $7, last_modified_at = $8
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(self.id())
+        .bind(self.title())
+        .bind(self.synopsis())
+        .bind(self.genres())
+        .bind(self.language())
+        .bind(self.maturity())
+        .bind(self.cover_url())
+        .bind(self.last_modified_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn find<'e, E: PgExecutor<'e>>(exec: E, id: &Id) -> Result<Option<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            SELECT s.* FROM stories s
+            JOIN fragments f ON f.id = s.fragment_id
+            WHERE s.id = $1 AND f.deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(exec)
+        .await?)
+    }
+
+    async fn find_by_fragment<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Option<Self>, StorageError> {
+        Ok(
+            sqlx::query_as("SELECT * FROM stories WHERE fragment_id = $1")
+                .bind(fragment_id)
+                .fetch_optional(exec)
+                .await?,
+        )
+    }
+
+    async fn find_published<'e, E: PgExecutor<'e>>(
+        exec: E,
+        filter: &StoryFilter,
+    ) -> Result<Vec<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            SELECT s.* FROM stories s
+            JOIN fragments f ON f.id = s.fragment_id
+            WHERE f.state = 'published' AND f.deleted_at IS NULL
+                AND ($1::varchar IS NULL OR $1 = ANY(s.genres))
+                AND ($2::varchar IS NULL OR s.language = $2)
+            ORDER BY s.created_at DESC
+            "#,
+        )
+        .bind(filter.genre())
+        .bind(filter.language())
+        .fetch_all(exec)
+        .await?)
+    }
+}
+
+#[async_trait::async_trait]
+pub trait QueryStory {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Story, StorageError>;
+
+    async fn update<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Story, StorageError>;
+
+    /// Finds a story, unless its root fragment is deleted.
+    async fn find<'e, E: PgExecutor<'e>>(exec: E, id: &Id) -> Result<Option<Story>, StorageError>;
+
+    async fn find_by_fragment<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Option<Story>, StorageError>;
+
+    /// Stories whose root fragment is published, newest first.
+    async fn find_published<'e, E: PgExecutor<'e>>(
+        exec: E,
+        filter: &StoryFilter,
+    ) -> Result<Vec<Story>, StorageError>;
+}
@@ -0,0 +1,111 @@
+use commons::id::Id;
+use sqlx::PgExecutor;
+
+use crate::{model::suggestion::Suggestion, StorageError};
+
+#[async_trait::async_trait]
+impl QuerySuggestion for Suggestion {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO review_suggestions
+                (id, review_id, fragment_id, range_start, range_end, original, replacement,
+                 status, created_at, resolved_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING *
+            "#,
+        )
+        .bind(self.id())
+        .bind(self.review_id())
+        .bind(self.fragment_id())
+        .bind(self.range_start())
+        .bind(self.range_end())
+        .bind(self.original())
+        .bind(self.replacement())
+        .bind(self.status())
+        .bind(self.created_at())
+        .bind(self.resolved_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn update<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            "UPDATE review_suggestions SET status = $2, resolved_at = $3 WHERE id = $1 RETURNING *",
+        )
+        .bind(self.id())
+        .bind(self.status())
+        .bind(self.resolved_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn shift_pending<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+        from: i32,
+        delta: i32,
+    ) -> Result<(), StorageError> {
+        sqlx::query(
+            r#"
+            UPDATE review_suggestions
+            SET range_start = range_start + $3, range_end = range_end + $3
+            WHERE fragment_id = $1 AND status = 'pending' AND range_start >= $2
+            "#,
+        )
+        .bind(fragment_id)
+        .bind(from)
+        .bind(delta)
+        .execute(exec)
+        .await?;
+        Ok(())
+    }
+
+    async fn find<'e, E: PgExecutor<'e>>(exec: E, id: &Id) -> Result<Option<Self>, StorageError> {
+        Ok(
+            sqlx::query_as("SELECT * FROM review_suggestions WHERE id = $1")
+                .bind(id)
+                .fetch_optional(exec)
+                .await?,
+        )
+    }
+
+    async fn find_by_fragment<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Vec<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            "SELECT * FROM review_suggestions WHERE fragment_id = $1 ORDER BY created_at, range_start",
+        )
+        .bind(fragment_id)
+        .fetch_all(exec)
+        .await?)
+    }
+}
+
+#[async_trait::async_trait]
+pub trait QuerySuggestion: Send {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Suggestion, StorageError>;
+
+    /// Only the status and resolution time of a suggestion can change.
+    async fn update<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Suggestion, StorageError>;
+
+    /// Moves the pending suggestions starting at or after `from` by `delta` characters,
+    /// keeping them anchored once an earlier part of the content has been replaced.
+    async fn shift_pending<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+        from: i32,
+        delta: i32,
+    ) -> Result<(), StorageError>;
+
+    async fn find<'e, E: PgExecutor<'e>>(
+        exec: E,
+        id: &Id,
+    ) -> Result<Option<Suggestion>, StorageError>;
+
+    async fn find_by_fragment<'e, E: PgExecutor<'e>>(
+        exec: E,
+        fragment_id: &Id,
+    ) -> Result<Vec<Suggestion>, StorageError>;
+}
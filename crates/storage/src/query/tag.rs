@@ -0,0 +1,134 @@
+use commons::{id::Id, tag::Tag};
+use sqlx::PgExecutor;
+
+use crate::{
+    model::{
+        fragment::Fragment,
+        tag::{TagCount, TagTarget, Tagging},
+    },
+    query::fragment::VISIBLE,
+    StorageError,
+};
+
+#[async_trait::async_trait]
+impl QueryTagging for Tagging {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO tags (target, target_id, tag, created_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(self.target())
+        .bind(self.target_id())
+        .bind(self.tag())
+        .bind(self.created_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn delete<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<bool, StorageError> {
+        Ok(sqlx::query(
+            r#"
+            DELETE FROM tags
+            WHERE target = $1 AND target_id = $2 AND tag = $3
+            "#,
+        )
+        .bind(self.target())
+        .bind(self.target_id())
+        .bind(self.tag())
+        .execute(exec)
+        .await
+        .map(|r| r.rows_affected() > 0)?)
+    }
+
+    async fn find_by_target<'e, E: PgExecutor<'e>>(
+        exec: E,
+        target: TagTarget,
+        target_id: &Id,
+    ) -> Result<Vec<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            SELECT * FROM tags
+            WHERE target = $1 AND target_id = $2
+            ORDER BY tag
+            "#,
+        )
+        .bind(target)
+        .bind(target_id)
+        .fetch_all(exec)
+        .await?)
+    }
+
+    async fn counts<'e, E: PgExecutor<'e>>(exec: E) -> Result<Vec<TagCount>, StorageError> {
+        Ok(sqlx::query_as(&format!(
+            r#"
+            SELECT tag, SUM(fragments)::bigint AS fragments, SUM(stories)::bigint AS stories
+            FROM (
+                SELECT t.tag, 1 AS fragments, 0 AS stories
+                FROM tags t JOIN fragments ON fragments.id = t.target_id
+                WHERE t.target = 'fragment' AND fragments.state = 'published' AND {VISIBLE}
+                UNION ALL
+                SELECT t.tag, 0 AS fragments, 1 AS stories
+                FROM tags t
+                JOIN stories s ON s.id = t.target_id
+                JOIN fragments f ON f.id = s.fragment_id
+                WHERE t.target = 'story' AND f.state = 'published' AND f.deleted_at IS NULL
+            ) tagged
+            GROUP BY tag
+            ORDER BY SUM(fragments) + SUM(stories) DESC, tag
+            "#
+        ))
+        .fetch_all(exec)
+        .await?)
+    }
+
+    async fn fragments_tagged<'e, E: PgExecutor<'e>>(
+        exec: E,
+        tag: &Tag,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Fragment>, StorageError> {
+        Ok(sqlx::query_as(&format!(
+            r#"
+            SELECT fragments.* FROM fragments
+            JOIN tags t ON t.target = 'fragment' AND t.target_id = fragments.id
+            WHERE t.tag = $1 AND fragments.state = 'published' AND {VISIBLE}
+            ORDER BY fragments.created_at DESC, fragments.id
+            LIMIT $2 OFFSET $3
+            "#
+        ))
+        .bind(tag)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(exec)
+        .await?)
+    }
+}
+
+#[async_trait::async_trait]
+pub trait QueryTagging {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Tagging, StorageError>;
+
+    async fn delete<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<bool, StorageError>;
+
+    /// Tags of a fragment or a story, in alphabetical order.
+    async fn find_by_target<'e, E: PgExecutor<'e>>(
+        exec: E,
+        target: TagTarget,
+        target_id: &Id,
+    ) -> Result<Vec<Tagging>, StorageError>;
+
+    /// Usage of every tag on published content, most used first. Tags only found on
+    /// unpublished or deleted content are left out.
+    async fn counts<'e, E: PgExecutor<'e>>(exec: E) -> Result<Vec<TagCount>, StorageError>;
+
+    /// Published, visible fragments carrying a tag, newest first.
+    async fn fragments_tagged<'e, E: PgExecutor<'e>>(
+        exec: E,
+        tag: &Tag,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Fragment>, StorageError>;
+}
@@ -1,6 +1,10 @@
+use commons::{commands::CommandType, id::Id, time::DateTime};
 use sqlx::PgExecutor;
 
-use crate::{model::task::Task, StorageError};
+use crate::{
+    model::task::{CommandData, Task},
+    StorageError,
+};
 
 #[async_trait::async_trait]
 impl QueryTask for Task {
@@ -9,11 +13,11 @@ impl QueryTask for Task {
             r#"
             INSERT INTO tasks 
             (id, command_type, command_data, actor_type, actor_id, created_at, scheduled_at) 
-            VALUES ( $1, $2, $3, $4, $5, $6, $7, $8 ) RETURNING *"#,
+            VALUES ( $1, $2, $3, $4, $5, $6, $7 ) RETURNING *"#,
         )
         .bind(self.id())
         .bind(self.command_type())
-        .bind(self.commnad_data())
+        .bind(self.command_data())
         .bind(self.actor_type())
         .bind(self.actor_id())
         .bind(self.created_at())
@@ -21,9 +25,90 @@ impl QueryTask for Task {
         .fetch_one(exec)
         .await?)
     }
+
+    async fn update<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            UPDATE tasks SET completed_at = $2, cancelled_at = $3, failure = $4
+            WHERE id = $1 RETURNING *"#,
+        )
+        .bind(self.id())
+        .bind(self.completed_at())
+        .bind(self.cancelled_at())
+        .bind(self.failure())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn find<'e, E: PgExecutor<'e>>(exec: E, id: &Id) -> Result<Option<Self>, StorageError> {
+        Ok(sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
+            .bind(id)
+            .fetch_optional(exec)
+            .await?)
+    }
+
+    async fn claim_due<'e, E: PgExecutor<'e>>(
+        exec: E,
+        now: &DateTime,
+    ) -> Result<Option<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            UPDATE tasks SET claimed_at = $1
+            WHERE id = (
+                SELECT id FROM tasks
+                WHERE scheduled_at <= $1 AND completed_at IS NULL AND cancelled_at IS NULL
+                    AND (claimed_at IS NULL OR claimed_at <= $1 - interval '10 minutes')
+                ORDER BY scheduled_at
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING *"#,
+        )
+        .bind(now)
+        .fetch_optional(exec)
+        .await?)
+    }
+
+    async fn find_pending<'e, E: PgExecutor<'e>>(
+        exec: E,
+        command_type: &CommandType,
+        data: &CommandData,
+    ) -> Result<Option<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            SELECT * FROM tasks
+            WHERE command_type = $1 AND command_data @> $2
+                AND completed_at IS NULL AND cancelled_at IS NULL
+            ORDER BY scheduled_at
+            LIMIT 1"#,
+        )
+        .bind(command_type)
+        .bind(data)
+        .fetch_optional(exec)
+        .await?)
+    }
 }
 
 #[async_trait::async_trait]
 pub trait QueryTask {
     async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Task, StorageError>;
+
+    async fn update<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Task, StorageError>;
+
+    async fn find<'e, E: PgExecutor<'e>>(exec: E, id: &Id) -> Result<Option<Task>, StorageError>;
+
+    /// Oldest pending task whose schedule is reached, claimed at `now` so that no other
+    /// runner picks it up. Tasks being claimed concurrently are skipped, and claims expire
+    /// after ten minutes so that tasks of runners that stopped midway run again.
+    async fn claim_due<'e, E: PgExecutor<'e>>(
+        exec: E,
+        now: &DateTime,
+    ) -> Result<Option<Task>, StorageError>;
+
+    /// First pending task of `command_type` whose data contains `data`.
+    async fn find_pending<'e, E: PgExecutor<'e>>(
+        exec: E,
+        command_type: &CommandType,
+        data: &CommandData,
+    ) -> Result<Option<Task>, StorageError>;
 }
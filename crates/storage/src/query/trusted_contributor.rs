@@ -0,0 +1,82 @@
+use commons::id::Id;
+use sqlx::PgExecutor;
+
+use crate::{model::trusted_contributor::TrustedContributor, StorageError};
+
+#[async_trait::async_trait]
+impl QueryTrustedContributor for TrustedContributor {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO trusted_contributors (author_id, contributor_id, auto_publish, created_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (author_id, contributor_id) DO UPDATE SET
+                auto_publish = EXCLUDED.auto_publish
+            RETURNING *
+            "#,
+        )
+        .bind(self.author_id())
+        .bind(self.contributor_id())
+        .bind(self.auto_publish())
+        .bind(self.created_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn delete<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<bool, StorageError> {
+        Ok(sqlx::query(
+            "DELETE FROM trusted_contributors WHERE author_id = $1 AND contributor_id = $2",
+        )
+        .bind(self.author_id())
+        .bind(self.contributor_id())
+        .execute(exec)
+        .await
+        .map(|r| r.rows_affected() > 0)?)
+    }
+
+    async fn find<'e, E: PgExecutor<'e>>(
+        exec: E,
+        author_id: &Id,
+        contributor_id: &Id,
+    ) -> Result<Option<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            "SELECT * FROM trusted_contributors WHERE author_id = $1 AND contributor_id = $2",
+        )
+        .bind(author_id)
+        .bind(contributor_id)
+        .fetch_optional(exec)
+        .await?)
+    }
+
+    async fn find_by_author<'e, E: PgExecutor<'e>>(
+        exec: E,
+        author_id: &Id,
+    ) -> Result<Vec<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            "SELECT * FROM trusted_contributors WHERE author_id = $1 ORDER BY created_at",
+        )
+        .bind(author_id)
+        .fetch_all(exec)
+        .await?)
+    }
+}
+
+#[async_trait::async_trait]
+pub trait QueryTrustedContributor {
+    /// Saves the entry, updating the publication preference if the user is already trusted.
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E)
+        -> Result<TrustedContributor, StorageError>;
+
+    async fn delete<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<bool, StorageError>;
+
+    async fn find<'e, E: PgExecutor<'e>>(
+        exec: E,
+        author_id: &Id,
+        contributor_id: &Id,
+    ) -> Result<Option<TrustedContributor>, StorageError>;
+
+    async fn find_by_author<'e, E: PgExecutor<'e>>(
+        exec: E,
+        author_id: &Id,
+    ) -> Result<Vec<TrustedContributor>, StorageError>;
+}
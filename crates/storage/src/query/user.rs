@@ -8,12 +8,41 @@ use super::follow::QueryFollow;
 #[async_trait::async_trait]
 impl QueryUser for User {
     async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
-        Ok(
-            sqlx::query_as("INSERT INTO users (id) VALUES ($1) RETURNING *")
-                .bind(self.id())
-                .fetch_one(exec)
-                .await?,
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO users (id, username, display_name, bio, avatar_url, role, created_at) 
+            VALUES ($1, $2, $3, $4, $5, $6, $7) 
+            RETURNING *"#,
         )
+        .bind(self.id())
+        .bind(self.username())
+        .bind(self.display_name())
+        .bind(self.bio())
+        .bind(self.avatar_url())
+        .bind(self.role())
+        .bind(self.created_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn update<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            UPDATE users 
+            SET 
+                display_name = $2, 
+                bio = $3, 
+                avatar_url = $4,
+                role = $5
+            WHERE id = $1 RETURNING *"#,
+        )
+        .bind(self.id())
+        .bind(self.display_name())
+        .bind(self.bio())
+        .bind(self.avatar_url())
+        .bind(self.role())
+        .fetch_one(exec)
+        .await?)
     }
 
     async fn find<'e, E: PgExecutor<'e>>(exec: E, id: &Id) -> Result<Option<Self>, StorageError> {
@@ -23,6 +52,16 @@ impl QueryUser for User {
             .await?)
     }
 
+    async fn find_by_username<'e, E: PgExecutor<'e>>(
+        exec: E,
+        username: &str,
+    ) -> Result<Option<Self>, StorageError> {
+        Ok(sqlx::query_as("SELECT * FROM users WHERE username = $1")
+            .bind(username)
+            .fetch_optional(exec)
+            .await?)
+    }
+
     async fn is_friend<'e, E: PgExecutor<'e>>(
         &self,
         exec: E,
@@ -36,8 +75,15 @@ impl QueryUser for User {
 pub trait QueryUser {
     async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<User, StorageError>;
 
+    async fn update<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<User, StorageError>;
+
     async fn find<'e, E: PgExecutor<'e>>(exec: E, id: &Id) -> Result<Option<User>, StorageError>;
 
+    async fn find_by_username<'e, E: PgExecutor<'e>>(
+        exec: E,
+        username: &str,
+    ) -> Result<Option<User>, StorageError>;
+
     async fn is_friend<'e, E: PgExecutor<'e>>(
         &self,
         exec: E,
@@ -0,0 +1,301 @@
+use commons::{events::EventType, id::Id, time::DateTime};
+use sqlx::PgExecutor;
+
+use crate::{
+    model::webhook::{Webhook, WebhookAttempt, WebhookDelivery},
+    StorageError,
+};
+
+#[async_trait::async_trait]
+impl QueryWebhook for Webhook {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO webhooks (id, owner_id, url, secret, event_types, story_id, enabled,
+                consecutive_failures, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING *
+            "#,
+        )
+        .bind(self.id())
+        .bind(self.owner_id())
+        .bind(self.url())
+        .bind(self.secret())
+        .bind(self.event_types())
+        .bind(self.story_id())
+        .bind(self.enabled())
+        .bind(self.consecutive_failures())
+        .bind(self.created_at())
+        .bind(self.updated_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn update<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            UPDATE webhooks SET enabled = $2, consecutive_failures = $3, updated_at = $4
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(self.id())
+        .bind(self.enabled())
+        .bind(self.consecutive_failures())
+        .bind(self.updated_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn delete<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<bool, StorageError> {
+        Ok(sqlx::query(
+            r#"
+            WITH deleted_attempts AS (
+                DELETE FROM webhook_attempts WHERE delivery_id IN (
+                    SELECT id FROM webhook_deliveries WHERE webhook_id = $1
+                )
+            ),
+            deleted_deliveries AS (
+                DELETE FROM webhook_deliveries WHERE webhook_id = $1
+            )
+            DELETE FROM webhooks WHERE id = $1
+            "#,
+        )
+        .bind(self.id())
+        .execute(exec)
+        .await
+        .map(|r| r.rows_affected() > 0)?)
+    }
+
+    async fn find<'e, E: PgExecutor<'e>>(exec: E, id: &Id) -> Result<Option<Self>, StorageError> {
+        Ok(sqlx::query_as("SELECT * FROM webhooks WHERE id = $1")
+            .bind(id)
+            .fetch_optional(exec)
+            .await?)
+    }
+
+    async fn find_by_owner<'e, E: PgExecutor<'e>>(
+        exec: E,
+        owner_id: &Id,
+    ) -> Result<Vec<Self>, StorageError> {
+        Ok(
+            sqlx::query_as("SELECT * FROM webhooks WHERE owner_id = $1 ORDER BY created_at, id")
+                .bind(owner_id)
+                .fetch_all(exec)
+                .await?,
+        )
+    }
+
+    async fn find_subscribed<'e, E: PgExecutor<'e>>(
+        exec: E,
+        event_type: &EventType,
+    ) -> Result<Vec<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            SELECT * FROM webhooks
+            WHERE enabled AND (cardinality(event_types) = 0 OR $1 = ANY(event_types))
+            ORDER BY id
+            "#,
+        )
+        .bind(event_type)
+        .fetch_all(exec)
+        .await?)
+    }
+}
+
+#[async_trait::async_trait]
+pub trait QueryWebhook {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Webhook, StorageError>;
+
+    /// Updates the state of the endpoint, its subscription can not change.
+    async fn update<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Webhook, StorageError>;
+
+    /// Deletes the webhook along with its deliveries.
+    async fn delete<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<bool, StorageError>;
+
+    async fn find<'e, E: PgExecutor<'e>>(exec: E, id: &Id)
+        -> Result<Option<Webhook>, StorageError>;
+
+    async fn find_by_owner<'e, E: PgExecutor<'e>>(
+        exec: E,
+        owner_id: &Id,
+    ) -> Result<Vec<Webhook>, StorageError>;
+
+    /// Enabled webhooks receiving events of the given type, whatever their story scope.
+    async fn find_subscribed<'e, E: PgExecutor<'e>>(
+        exec: E,
+        event_type: &EventType,
+    ) -> Result<Vec<Webhook>, StorageError>;
+}
+
+#[async_trait::async_trait]
+impl QueryWebhookDelivery for WebhookDelivery {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO webhook_deliveries (id, webhook_id, event_id, event_type, payload, state,
+                attempts, next_attempt_at, created_at, completed_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING *
+            "#,
+        )
+        .bind(self.id())
+        .bind(self.webhook_id())
+        .bind(self.event_id())
+        .bind(self.event_type())
+        .bind(self.payload())
+        .bind(self.state())
+        .bind(self.attempts())
+        .bind(self.next_attempt_at())
+        .bind(self.created_at())
+        .bind(self.completed_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn update<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            UPDATE webhook_deliveries
+            SET state = $2, attempts = $3, next_attempt_at = $4, completed_at = $5
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(self.id())
+        .bind(self.state())
+        .bind(self.attempts())
+        .bind(self.next_attempt_at())
+        .bind(self.completed_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn find<'e, E: PgExecutor<'e>>(exec: E, id: &Id) -> Result<Option<Self>, StorageError> {
+        Ok(
+            sqlx::query_as("SELECT * FROM webhook_deliveries WHERE id = $1")
+                .bind(id)
+                .fetch_optional(exec)
+                .await?,
+        )
+    }
+
+    async fn claim_due<'e, E: PgExecutor<'e>>(
+        exec: E,
+        now: &DateTime,
+        lease_until: &DateTime,
+    ) -> Result<Option<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            UPDATE webhook_deliveries SET next_attempt_at = $2
+            WHERE id = (
+                SELECT d.id FROM webhook_deliveries d
+                JOIN webhooks w ON w.id = d.webhook_id
+                WHERE d.state = 'pending' AND d.next_attempt_at <= $1 AND w.enabled
+                ORDER BY d.next_attempt_at, d.id
+                LIMIT 1
+                FOR UPDATE OF d SKIP LOCKED
+            )
+            RETURNING *
+            "#,
+        )
+        .bind(now)
+        .bind(lease_until)
+        .fetch_optional(exec)
+        .await?)
+    }
+
+    async fn find_by_webhook<'e, E: PgExecutor<'e>>(
+        exec: E,
+        webhook_id: &Id,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            SELECT * FROM webhook_deliveries
+            WHERE webhook_id = $1
+            ORDER BY created_at DESC, id
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(exec)
+        .await?)
+    }
+}
+
+#[async_trait::async_trait]
+pub trait QueryWebhookDelivery {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<WebhookDelivery, StorageError>;
+
+    async fn update<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<WebhookDelivery, StorageError>;
+
+    async fn find<'e, E: PgExecutor<'e>>(
+        exec: E,
+        id: &Id,
+    ) -> Result<Option<WebhookDelivery>, StorageError>;
+
+    /// Claims the oldest pending delivery of an enabled webhook whose next attempt is due,
+    /// by pushing its next attempt to `lease_until`. Concurrent callers never claim the
+    /// same delivery, and a delivery whose attempt is never recorded is due again once the
+    /// lease ends.
+    async fn claim_due<'e, E: PgExecutor<'e>>(
+        exec: E,
+        now: &DateTime,
+        lease_until: &DateTime,
+    ) -> Result<Option<WebhookDelivery>, StorageError>;
+
+    /// Deliveries of a webhook, most recent first.
+    async fn find_by_webhook<'e, E: PgExecutor<'e>>(
+        exec: E,
+        webhook_id: &Id,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<WebhookDelivery>, StorageError>;
+}
+
+#[async_trait::async_trait]
+impl QueryWebhookAttempt for WebhookAttempt {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<Self, StorageError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO webhook_attempts (id, delivery_id, status_code, error, attempted_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+        )
+        .bind(self.id())
+        .bind(self.delivery_id())
+        .bind(self.status_code())
+        .bind(self.error())
+        .bind(self.attempted_at())
+        .fetch_one(exec)
+        .await?)
+    }
+
+    async fn find_by_delivery<'e, E: PgExecutor<'e>>(
+        exec: E,
+        delivery_id: &Id,
+    ) -> Result<Vec<Self>, StorageError> {
+        Ok(sqlx::query_as(
+            "SELECT * FROM webhook_attempts WHERE delivery_id = $1 ORDER BY attempted_at, id",
+        )
+        .bind(delivery_id)
+        .fetch_all(exec)
+        .await?)
+    }
+}
+
+#[async_trait::async_trait]
+pub trait QueryWebhookAttempt {
+    async fn save<'e, E: PgExecutor<'e>>(self, exec: E) -> Result<WebhookAttempt, StorageError>;
+
+    /// Attempts made for a delivery, oldest first.
+    async fn find_by_delivery<'e, E: PgExecutor<'e>>(
+        exec: E,
+        delivery_id: &Id,
+    ) -> Result<Vec<WebhookAttempt>, StorageError>;
+}
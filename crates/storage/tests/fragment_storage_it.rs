@@ -9,8 +9,11 @@ use storage::{
 };
 
 async fn create_user(pool: &PgPool) -> User {
+    let id = Id::new();
     UserBuilder::default()
-        .id(Id::new())
+        .id(id)
+        .username(format!("user_{}", &id.to_string()[..18]))
+        .created_at(DateTime::now())
         .build()
         .unwrap()
         .save(pool)
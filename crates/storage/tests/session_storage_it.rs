@@ -0,0 +1,92 @@
+use chrono::Duration;
+use commons::{
+    auth::{PasswordHash, SessionToken},
+    id::Id,
+    time::DateTime,
+};
+use sqlx::PgPool;
+use storage::{
+    model::{
+        credential::{Credential, CredentialBuilder},
+        session::{Session, SessionBuilder},
+        user::{User, UserBuilder},
+    },
+    query::{credential::QueryCredential, session::QuerySession, user::QueryUser},
+};
+
+async fn create_user(pool: &PgPool) -> User {
+    let id = Id::new();
+    UserBuilder::default()
+        .id(id)
+        .username(format!("user_{}", &id.to_string()[..18]))
+        .created_at(DateTime::now())
+        .build()
+        .unwrap()
+        .save(pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn find_credential_by_login(pool: PgPool) {
+    let user = create_user(&pool).await;
+    let credential = CredentialBuilder::default()
+        .user_id(*user.id())
+        .login("john")
+        .password_hash(PasswordHash::generate("secret").unwrap())
+        .created_at(DateTime::now())
+        .build()
+        .unwrap()
+        .save(&pool)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        Credential::find_by_login(&pool, "john").await.unwrap(),
+        Some(credential.clone())
+    );
+    assert_eq!(
+        Credential::find_by_login(&pool, "jane").await.unwrap(),
+        None
+    );
+    assert!(credential.verify("secret"));
+}
+
+#[sqlx::test]
+async fn find_active_session(pool: PgPool) {
+    let user = create_user(&pool).await;
+    let token = SessionToken::generate();
+    let now = DateTime::now();
+    let session = SessionBuilder::default()
+        .id(Id::new())
+        .user_id(*user.id())
+        .token_hash(token.hash())
+        .created_at(now)
+        .expires_at(now + Duration::hours(1))
+        .build()
+        .unwrap()
+        .save(&pool)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        Session::find_active(&pool, &token.hash(), &now)
+            .await
+            .unwrap(),
+        Some(session.clone())
+    );
+    assert_eq!(
+        Session::find_active(&pool, &token.hash(), &(now + Duration::hours(2)))
+            .await
+            .unwrap(),
+        None
+    );
+
+    assert!(session.delete(&pool).await.unwrap());
+    assert_eq!(
+        Session::find_active(&pool, &token.hash(), &now)
+            .await
+            .unwrap(),
+        None
+    );
+}